@@ -0,0 +1,123 @@
+use mog_core::miracle_octad_generator::{
+    BinaryGolayCode, LinearCode, NearestCodewordsResult, Point, Vector,
+};
+use mog_core::permutation::Permutation;
+use mog_core::traits::Enumerated;
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Usage: mog-cli [--json] <command> <arguments>
+
+Points are numbered 0-23 as in the MOG.
+
+Commands:
+    complete-octad <5 points>        The unique octad containing 5 points
+    nearest-codeword <vector>        The nearest codeword(s) to a vector given as a list of points,
+                                     24 binary digits or 6 hex digits
+    is-automorphism <cycles>         Whether a permutation such as `(0 1 2)(3 4)` preserves the code";
+
+enum Output {
+    Text,
+    Json,
+}
+
+// A vector as a JSON list of its points
+fn json_points(vector: &Vector) -> String {
+    format!("[{}]", vector.to_point_list().replace(' ', ","))
+}
+
+// 6 hex digits are read as 24 binary digits, the most significant bit being point 0
+fn parse_vector(s: &str) -> Result<Vector, String> {
+    let s = s.trim();
+    let hex = s.strip_prefix("0x").unwrap_or(s);
+    if hex.len() == Point::N / 4
+        && let Ok(n) = u32::from_str_radix(hex, 16)
+    {
+        return Vector::parse(&format!("{:024b}", n));
+    }
+    Vector::parse(s)
+}
+
+fn run(command: &str, argument: &str, output: Output) -> Result<String, String> {
+    let mog = BinaryGolayCode::default();
+    match command {
+        "complete-octad" => {
+            let vector = Vector::parse(argument)?;
+            if vector.weight() != 5 {
+                return Err(format!("Expected 5 points but got {}", vector.weight()));
+            }
+            let octad = mog.complete_octad(&vector).unwrap();
+            Ok(match output {
+                Output::Text => octad.to_point_list(),
+                Output::Json => format!("{{\"octad\":{}}}", json_points(&octad)),
+            })
+        }
+        "nearest-codeword" => {
+            let vector = parse_vector(argument)?;
+            let nearest = mog.nearest_codeword(&vector);
+            let distance = nearest.distance();
+            let mut codewords = match nearest {
+                NearestCodewordsResult::Unique { codeword, .. } => vec![codeword],
+                NearestCodewordsResult::Six { codewords } => codewords.to_vec(),
+            };
+            codewords.sort();
+            Ok(match output {
+                Output::Text => {
+                    let mut out = format!("distance {}", distance);
+                    for codeword in &codewords {
+                        out += "\n";
+                        if codeword.weight() == 0 {
+                            out += "empty";
+                        } else {
+                            out += &codeword.to_point_list();
+                        }
+                    }
+                    out
+                }
+                Output::Json => format!(
+                    "{{\"distance\":{},\"codewords\":[{}]}}",
+                    distance,
+                    codewords
+                        .iter()
+                        .map(json_points)
+                        .collect::<Vec<_>>()
+                        .join(",")
+                ),
+            })
+        }
+        "is-automorphism" => {
+            let permutation = Permutation::<Point>::parse_cycles(argument)?;
+            let is_automorphism = mog.is_automorphism(&permutation);
+            Ok(match output {
+                Output::Text => is_automorphism.to_string(),
+                Output::Json => format!("{{\"automorphism\":{}}}", is_automorphism),
+            })
+        }
+        _ => Err(format!("Unknown command `{}`\n\n{}", command, USAGE)),
+    }
+}
+
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1).collect::<Vec<_>>();
+    let output = if let Some(i) = args.iter().position(|arg| arg == "--json") {
+        args.remove(i);
+        Output::Json
+    } else {
+        Output::Text
+    };
+    if args.is_empty() || args[0] == "--help" || args[0] == "-h" {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+    // The rest of the arguments are joined so points and cycles can be given unquoted
+    match run(&args[0], &args[1..].join(" "), output) {
+        Ok(out) => {
+            println!("{}", out);
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("{}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,97 @@
+// How long the queries the GUI makes every frame take
+use criterion::{Criterion, criterion_group, criterion_main};
+use mog_core::finite_field_4::Point as F4Point;
+use mog_core::hexacode;
+use mog_core::miracle_octad_generator::{
+    BinaryGolayCode, LinearCode, OrderedSextet, Point, Vector,
+};
+use mog_core::permutation::Permutation;
+use mog_core::traits::{Enumerated, Labelled};
+use std::hint::black_box;
+
+// A spread of vectors of every weight, the same on every run
+fn vectors() -> Vec<Vector> {
+    (0..=Point::N)
+        .map(|weight| {
+            Vector::from_points(
+                (0..weight).map(|i| Point::usize_to_point((7 * i + weight) % Point::N).unwrap()),
+            )
+        })
+        .collect()
+}
+
+fn foursomes() -> Vec<Vector> {
+    (0..Point::N)
+        .map(|start| {
+            Vector::from_points(
+                (0..4).map(|i| Point::usize_to_point((start + 5 * i) % Point::N).unwrap()),
+            )
+        })
+        .collect()
+}
+
+fn decoding(c: &mut Criterion) {
+    let mog = BinaryGolayCode::default();
+    let vectors = vectors();
+    c.bench_function("nearest_codeword", |b| {
+        b.iter(|| {
+            for vector in &vectors {
+                black_box(mog.nearest_codeword(black_box(vector)));
+            }
+        })
+    });
+    let foursomes = foursomes();
+    c.bench_function("complete_sextet", |b| {
+        b.iter(|| {
+            for foursome in &foursomes {
+                black_box(mog.complete_sextet(black_box(foursome)).unwrap());
+            }
+        })
+    });
+}
+
+fn labelling(c: &mut Criterion) {
+    let mog = BinaryGolayCode::default();
+    let foursomes = mog.complete_sextet(&foursomes()[0]).unwrap();
+    let sextet = OrderedSextet::from_foursomes(Labelled::from_fn(|h: hexacode::Point| {
+        foursomes[h.point_to_usize()].clone()
+    }));
+    let points = |t: usize| {
+        sextet
+            .foursome(hexacode::Point::usize_to_point(t).unwrap())
+            .points()
+            .collect::<Vec<_>>()
+    };
+    let (t0, t1, t2) = (points(0), points(1), points(2));
+    c.bench_function("complete_labelling", |b| {
+        b.iter(|| {
+            black_box(mog.complete_labelling(
+                black_box(sextet.clone()),
+                t0[0],
+                t1[0],
+                t1[1],
+                t2[0],
+                F4Point::Alpha,
+            ))
+        })
+    });
+
+    let labelling = mog.complete_labelling(sextet, t0[0], t1[0], t1[1], t2[0], F4Point::Alpha);
+    let automorphism = Permutation::from_fn(|p| Point {
+        col: *labelling.foursomes().get(p),
+        row: *labelling.labels().get(p),
+    });
+    let swap = Permutation::new_swap(
+        &Point::usize_to_point(0).unwrap(),
+        &Point::usize_to_point(1).unwrap(),
+    );
+    c.bench_function("is_automorphism", |b| {
+        b.iter(|| {
+            black_box(mog.is_automorphism(black_box(&automorphism)));
+            black_box(mog.is_automorphism(black_box(&swap)));
+        })
+    });
+}
+
+criterion_group!(benches, decoding, labelling);
+criterion_main!(benches);
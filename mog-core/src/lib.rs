@@ -0,0 +1,5269 @@
+#![allow(dead_code)]
+#![allow(clippy::result_unit_err)]
+
+pub mod permutation {
+    use super::traits::Enumerated;
+    use std::{
+        collections::{HashMap, HashSet},
+        hash::Hash,
+        ops::Mul,
+    };
+
+    #[derive(Debug, Clone)]
+    #[cfg_attr(
+        feature = "serde",
+        derive(serde::Serialize, serde::Deserialize),
+        serde(
+            into = "Vec<(T, T)>",
+            try_from = "Vec<(T, T)>",
+            bound(
+                serialize = "T: Clone + serde::Serialize",
+                deserialize = "T: Clone + serde::Deserialize<'de>"
+            )
+        )
+    )]
+    pub struct Permutation<T: PartialEq + Eq + Hash> {
+        perm: Vec<(T, T)>,
+        right: HashMap<T, T>,
+        left: HashMap<T, T>,
+    }
+
+    impl<T: PartialEq + Eq + Hash + Clone> Permutation<T> {
+        pub fn identity() -> Self {
+            Self {
+                perm: vec![],
+                right: HashMap::new(),
+                left: HashMap::new(),
+            }
+        }
+
+        fn from_perm_unchecked(perm: Vec<(T, T)>) -> Self {
+            let right = perm.iter().cloned().collect::<HashMap<T, T>>();
+            let left = perm
+                .iter()
+                .cloned()
+                .map(|(a, b)| (b, a))
+                .collect::<HashMap<T, T>>();
+            debug_assert_eq!(perm.len(), right.len());
+            debug_assert_eq!(perm.len(), left.len());
+            Self { perm, left, right }
+        }
+
+        pub fn map_injective_unchecked<S: PartialEq + Eq + Hash + Clone>(
+            self,
+            f: impl Fn(T) -> S,
+        ) -> Permutation<S> {
+            Permutation::from_perm_unchecked(
+                self.perm.into_iter().map(|(a, b)| (f(a), f(b))).collect(),
+            )
+        }
+
+        pub fn from_fn(f: impl Fn(T) -> T) -> Self
+        where
+            T: Enumerated,
+        {
+            let points = T::points().collect::<Vec<_>>();
+            let images = T::points().map(f).collect::<Vec<_>>();
+            debug_assert_eq!(points.len(), T::N);
+            debug_assert_eq!(images.len(), T::N);
+            assert_eq!(T::N, images.iter().collect::<HashSet<_>>().len());
+            Self::from_perm_unchecked(points.into_iter().zip(images).collect())
+        }
+
+        pub fn new_swap(t1: &T, t2: &T) -> Self {
+            if t1 == t2 {
+                Self::identity()
+            } else {
+                Self::from_perm_unchecked(vec![(t1.clone(), t2.clone()), (t2.clone(), t1.clone())])
+            }
+        }
+
+        pub fn new_cycle(ts: Vec<&T>) -> Self {
+            // Check the items are unique
+            let n = ts.len();
+            assert_eq!(ts.iter().collect::<HashSet<_>>().len(), n);
+            Self::from_perm_unchecked(
+                (0..n)
+                    .map(|i| (ts[i].clone(), ts[(i + 1) % n].clone()))
+                    .collect(),
+            )
+        }
+
+        pub fn inverse(self) -> Self {
+            Self {
+                perm: self.perm.into_iter().map(|(a, b)| (b, a)).collect(),
+                right: self.left,
+                left: self.right,
+            }
+        }
+
+        pub fn apply<'a>(&'a self, t: &'a T) -> &'a T {
+            self.right.get(t).unwrap_or(t)
+        }
+
+        pub fn apply_inverse<'a>(&'a self, t: &'a T) -> &'a T {
+            self.left.get(t).unwrap_or(t)
+        }
+
+        pub fn disjoint_cycles(&self) -> Vec<Vec<&T>> {
+            let mut cycles = vec![];
+            let mut used = HashSet::new();
+            for t in self.right.keys() {
+                if !used.contains(t) {
+                    let mut cycle = vec![];
+                    let mut s = t;
+                    loop {
+                        cycle.push(s);
+                        used.insert(s);
+                        s = self.right.get(s).unwrap();
+                        if s == t {
+                            break;
+                        }
+                    }
+                    if cycle.len() >= 2 {
+                        cycles.push(cycle);
+                    }
+                }
+            }
+            cycles
+        }
+
+        // The least common multiple of the cycle lengths
+        pub fn order(&self) -> usize {
+            fn gcd(a: usize, b: usize) -> usize {
+                if b == 0 { a } else { gcd(b, a % b) }
+            }
+            self.disjoint_cycles()
+                .into_iter()
+                .map(|cycle| cycle.len())
+                .fold(1, |order, n| order / gcd(order, n) * n)
+        }
+
+        // The permutation applied `k` times
+        pub fn pow(&self, k: usize) -> Self {
+            let mut power = Self::identity();
+            for _ in 0..k % self.order() {
+                power = &power * self;
+            }
+            power
+        }
+
+        // 1 for even permutations and -1 for odd permutations
+        pub fn sign(&self) -> isize {
+            let transpositions: usize = self
+                .disjoint_cycles()
+                .into_iter()
+                .map(|cycle| cycle.len() - 1)
+                .sum();
+            if transpositions.is_multiple_of(2) {
+                1
+            } else {
+                -1
+            }
+        }
+
+        pub fn fixed_points(&self) -> Vec<T>
+        where
+            T: Enumerated,
+        {
+            T::points().filter(|t| self.apply(t) == t).collect()
+        }
+
+        // The lengths of all the cycles including fixed points, largest first
+        pub fn cycle_type(&self) -> Vec<usize>
+        where
+            T: Enumerated,
+        {
+            let mut lengths = self
+                .disjoint_cycles()
+                .into_iter()
+                .map(|cycle| cycle.len())
+                .collect::<Vec<_>>();
+            lengths.extend(std::iter::repeat_n(1, self.fixed_points().len()));
+            lengths.sort_unstable_by(|a, b| b.cmp(a));
+            lengths
+        }
+    }
+
+    impl<T: PartialEq + Eq + Hash> Mul<&Permutation<T>> for &Permutation<T>
+    where
+        T: Clone,
+    {
+        type Output = Permutation<T>;
+
+        fn mul(self, other: &Permutation<T>) -> Self::Output {
+            Permutation::from_perm_unchecked(
+                self.right
+                    .keys()
+                    .collect::<HashSet<_>>()
+                    .union(&other.right.keys().collect::<HashSet<_>>())
+                    .map(|t| ((*t).clone(), other.apply(self.apply(t)).clone()))
+                    .filter(|(a, b)| a != b)
+                    .collect(),
+            )
+        }
+    }
+
+    // Points listed as fixed, as `from_fn` lists every point, make no difference
+    impl<T: PartialEq + Eq + Hash> PartialEq for Permutation<T> {
+        fn eq(&self, other: &Self) -> bool {
+            self.right
+                .keys()
+                .chain(other.right.keys())
+                .all(|t| self.right.get(t).unwrap_or(t) == other.right.get(t).unwrap_or(t))
+        }
+    }
+
+    impl<T: PartialEq + Eq + Hash> Eq for Permutation<T> {}
+
+    // The pairs (x, image of x)
+    impl<T: PartialEq + Eq + Hash> From<Permutation<T>> for Vec<(T, T)> {
+        fn from(permutation: Permutation<T>) -> Self {
+            permutation.perm
+        }
+    }
+
+    impl<T: PartialEq + Eq + Hash + Clone> TryFrom<Vec<(T, T)>> for Permutation<T> {
+        type Error = String;
+
+        fn try_from(perm: Vec<(T, T)>) -> Result<Self, Self::Error> {
+            let domain = perm.iter().map(|(a, _)| a).collect::<HashSet<_>>();
+            let image = perm.iter().map(|(_, b)| b).collect::<HashSet<_>>();
+            if domain.len() != perm.len() || domain != image {
+                return Err("Not a permutation".to_string());
+            }
+            Ok(Self::from_perm_unchecked(perm))
+        }
+    }
+}
+
+pub mod traits {
+    use super::permutation::Permutation;
+    use std::{borrow::Borrow, marker::PhantomData};
+
+    pub trait Enumerated: Sized {
+        const N: usize;
+        fn points() -> impl Iterator<Item = Self> {
+            (0..Self::N).map(|i| Self::usize_to_point(i).unwrap())
+        }
+        fn usize_to_point(i: usize) -> Result<Self, ()>;
+        fn point_to_usize(&self) -> usize;
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+    #[cfg_attr(
+        feature = "serde",
+        derive(serde::Serialize, serde::Deserialize),
+        serde(
+            into = "Vec<T>",
+            try_from = "Vec<T>",
+            bound(
+                serialize = "Point: Clone, T: Clone + serde::Serialize",
+                deserialize = "T: serde::Deserialize<'de>"
+            )
+        )
+    )]
+    pub struct Labelled<Point: Enumerated, T> {
+        _length: PhantomData<Point>,
+        components: Vec<T>, // legnth = Point::N
+    }
+
+    impl<Point: Enumerated, T> Labelled<Point, T> {
+        pub fn from_fn(components: impl Fn(Point) -> T) -> Self {
+            Self {
+                _length: PhantomData,
+                components: Point::points().map(components).collect(),
+            }
+        }
+
+        pub fn new_constant(t: impl Borrow<T>) -> Self
+        where
+            T: Clone,
+        {
+            Self {
+                _length: PhantomData,
+                components: (0..Point::N).map(|_| t.borrow().clone()).collect(),
+            }
+        }
+
+        pub fn get(&self, p: Point) -> &T {
+            &self.components[Point::point_to_usize(&p)]
+        }
+
+        pub fn get_mut(&mut self, p: Point) -> &mut T {
+            &mut self.components[Point::point_to_usize(&p)]
+        }
+
+        pub fn set(&mut self, p: Point, t: T) {
+            self.components[Point::point_to_usize(&p)] = t
+        }
+
+        pub fn set_all(&mut self, t: impl Borrow<T>)
+        where
+            T: Clone,
+        {
+            for p in Point::points() {
+                self.set(p, t.borrow().clone());
+            }
+        }
+
+        pub fn apply_fn<S>(&self, f: impl Fn(&T) -> S) -> Labelled<Point, S> {
+            Labelled {
+                _length: PhantomData,
+                components: self.components.iter().map(f).collect(),
+            }
+        }
+
+        pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+            self.components
+                .iter()
+                .enumerate()
+                .map(|(i, t)| (Point::usize_to_point(i).unwrap(), t))
+        }
+
+        pub fn permute(&self, permutation: impl Borrow<Permutation<Point>>) -> Self
+        where
+            Point: Clone + Eq + std::hash::Hash,
+            T: Clone,
+        {
+            Self {
+                _length: PhantomData,
+                components: (0..Point::N)
+                    .map(|i| {
+                        self.components[permutation
+                            .borrow()
+                            .apply_inverse(&Point::usize_to_point(i).unwrap())
+                            .point_to_usize()]
+                        .clone()
+                    })
+                    .collect(),
+            }
+        }
+    }
+
+    // The labels in the order of the points
+    impl<Point: Enumerated, T> From<Labelled<Point, T>> for Vec<T> {
+        fn from(labelled: Labelled<Point, T>) -> Self {
+            labelled.components
+        }
+    }
+
+    impl<Point: Enumerated, T> TryFrom<Vec<T>> for Labelled<Point, T> {
+        type Error = String;
+
+        fn try_from(components: Vec<T>) -> Result<Self, Self::Error> {
+            if components.len() != Point::N {
+                return Err(format!(
+                    "Expected {} labels but got {}",
+                    Point::N,
+                    components.len()
+                ));
+            }
+            Ok(Self {
+                _length: PhantomData,
+                components,
+            })
+        }
+    }
+}
+
+pub mod finite_field_4 {
+    use super::traits::Enumerated;
+    use std::ops::{Add, Mul};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub enum Point {
+        Zero,
+        One,
+        Alpha,
+        Beta,
+    }
+
+    impl Add<Self> for Point {
+        type Output = Self;
+
+        fn add(self, other: Self) -> Self::Output {
+            match (self, other) {
+                (x, Point::Zero) => x,
+                (Point::Zero, x) => x,
+                (Point::One, Point::One) => Point::Zero,
+                (Point::One, Point::Alpha) => Point::Beta,
+                (Point::One, Point::Beta) => Point::Alpha,
+                (Point::Alpha, Point::One) => Point::Beta,
+                (Point::Alpha, Point::Alpha) => Point::Zero,
+                (Point::Alpha, Point::Beta) => Point::One,
+                (Point::Beta, Point::One) => Point::Alpha,
+                (Point::Beta, Point::Alpha) => Point::One,
+                (Point::Beta, Point::Beta) => Point::Zero,
+            }
+        }
+    }
+
+    impl Mul<Self> for Point {
+        type Output = Self;
+
+        fn mul(self, other: Self) -> Self::Output {
+            match (self, other) {
+                (_, Point::Zero) => Point::Zero,
+                (Point::Zero, _) => Point::Zero,
+                (x, Point::One) => x,
+                (Point::One, x) => x,
+                (Point::Alpha, Point::Alpha) => Point::Beta,
+                (Point::Alpha, Point::Beta) => Point::One,
+                (Point::Beta, Point::Alpha) => Point::One,
+                (Point::Beta, Point::Beta) => Point::Alpha,
+            }
+        }
+    }
+
+    impl Point {
+        pub fn conjugate(self) -> Self {
+            match self {
+                Point::Zero => Point::Zero,
+                Point::One => Point::One,
+                Point::Alpha => Point::Beta,
+                Point::Beta => Point::Alpha,
+            }
+        }
+
+        pub fn inverse(self) -> Option<Self> {
+            match self {
+                Point::Zero => None,
+                Point::One => Some(Point::One),
+                Point::Alpha => Some(Point::Beta),
+                Point::Beta => Some(Point::Alpha),
+            }
+        }
+    }
+
+    impl Enumerated for Point {
+        const N: usize = 4;
+
+        fn usize_to_point(i: usize) -> Result<Self, ()> {
+            match i {
+                0 => Ok(Self::Zero),
+                1 => Ok(Self::One),
+                2 => Ok(Self::Alpha),
+                3 => Ok(Self::Beta),
+                _ => Err(()),
+            }
+        }
+
+        fn point_to_usize(&self) -> usize {
+            match self {
+                Point::Zero => 0,
+                Point::One => 1,
+                Point::Alpha => 2,
+                Point::Beta => 3,
+            }
+        }
+    }
+}
+
+pub mod finite_field_3 {
+    use super::traits::Enumerated;
+    use std::ops::{Add, Mul, Neg};
+
+    // Written 0, + and - as in the MINIMOG
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub enum Point {
+        Zero,
+        Plus,
+        Minus,
+    }
+
+    impl Add<Self> for Point {
+        type Output = Self;
+
+        fn add(self, other: Self) -> Self::Output {
+            Self::usize_to_point((self.point_to_usize() + other.point_to_usize()) % 3).unwrap()
+        }
+    }
+
+    impl Mul<Self> for Point {
+        type Output = Self;
+
+        fn mul(self, other: Self) -> Self::Output {
+            Self::usize_to_point((self.point_to_usize() * other.point_to_usize()) % 3).unwrap()
+        }
+    }
+
+    impl Neg for Point {
+        type Output = Self;
+
+        fn neg(self) -> Self::Output {
+            match self {
+                Point::Zero => Point::Zero,
+                Point::Plus => Point::Minus,
+                Point::Minus => Point::Plus,
+            }
+        }
+    }
+
+    impl Enumerated for Point {
+        const N: usize = 3;
+
+        fn usize_to_point(i: usize) -> Result<Self, ()> {
+            match i {
+                0 => Ok(Self::Zero),
+                1 => Ok(Self::Plus),
+                2 => Ok(Self::Minus),
+                _ => Err(()),
+            }
+        }
+
+        fn point_to_usize(&self) -> usize {
+            match self {
+                Point::Zero => 0,
+                Point::Plus => 1,
+                Point::Minus => 2,
+            }
+        }
+    }
+}
+
+pub mod hexacode {
+    use super::finite_field_4::Point as F4Point;
+    use super::traits::{Enumerated, Labelled};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Side {
+        Left,
+        Right,
+    }
+
+    impl Enumerated for Side {
+        // 0 1
+
+        const N: usize = 2;
+
+        fn usize_to_point(i: usize) -> Result<Self, ()> {
+            match i {
+                0 => Ok(Self::Left),
+                1 => Ok(Self::Right),
+                _ => Err(()),
+            }
+        }
+
+        fn point_to_usize(&self) -> usize {
+            match self {
+                Self::Left => 0,
+                Self::Right => 1,
+            }
+        }
+    }
+
+    impl Side {
+        pub fn flip(self) -> Self {
+            match self {
+                Self::Left => Self::Right,
+                Self::Right => Self::Left,
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Pair {
+        Left,
+        Middle,
+        Right,
+    }
+
+    impl Enumerated for Pair {
+        // 0 1 2
+
+        const N: usize = 3;
+
+        fn usize_to_point(i: usize) -> Result<Self, ()> {
+            match i {
+                0 => Ok(Self::Left),
+                1 => Ok(Self::Middle),
+                2 => Ok(Self::Right),
+                _ => Err(()),
+            }
+        }
+
+        fn point_to_usize(&self) -> usize {
+            match self {
+                Self::Left => 0,
+                Self::Middle => 1,
+                Self::Right => 2,
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct Point {
+        pub side: Side,
+        pub pair: Pair,
+    }
+
+    impl Enumerated for Point {
+        // 0 1  2 3  4 5
+
+        const N: usize = 6;
+
+        fn usize_to_point(i: usize) -> Result<Self, ()> {
+            if i < 6 {
+                Ok(Self {
+                    side: Side::usize_to_point(i % 2).unwrap(),
+                    pair: Pair::usize_to_point(i / 2).unwrap(),
+                })
+            } else {
+                Err(())
+            }
+        }
+
+        fn point_to_usize(&self) -> usize {
+            self.side.point_to_usize() + 2 * self.pair.point_to_usize()
+        }
+    }
+
+    pub type Vector = Labelled<Point, F4Point>;
+
+    impl Vector {
+        fn component(&self, p: Point) -> F4Point {
+            *self.get(p)
+        }
+
+        // The hexacodeword (a, b, c, f(1), f(ω), f(ω̄)) where f(x) = ax^2 + bx + c
+        pub fn from_polynomial(a: F4Point, b: F4Point, c: F4Point) -> Self {
+            let f = |x: F4Point| a * x * x + b * x + c;
+            let components = [
+                a,
+                b,
+                c,
+                f(F4Point::One),
+                f(F4Point::Alpha),
+                f(F4Point::Beta),
+            ];
+            Self::from_fn(|p| components[p.point_to_usize()])
+        }
+
+        pub fn weight(&self) -> usize {
+            self.iter().filter(|(_, x)| **x != F4Point::Zero).count()
+        }
+
+        pub fn distance(&self, other: &Self) -> usize {
+            Point::points()
+                .filter(|p| self.component(*p) != other.component(*p))
+                .count()
+        }
+    }
+
+    /*
+    The hexacode is the 3 dimensional code over F4 of length 6 consisting of the words (a, b, c, f(1), f(ω), f(ω̄)) for f(x) = ax^2 + bx + c
+    Its minimum weight is 4 so any 3 coordinates determine a hexacodeword and it corrects 1 error
+    The scores of the columns of a Golay codeword in the MOG form a hexacodeword
+    */
+    pub fn codewords() -> Vec<Vector> {
+        let mut codewords = vec![];
+        for a in F4Point::points() {
+            for b in F4Point::points() {
+                for c in F4Point::points() {
+                    codewords.push(Vector::from_polynomial(a, b, c));
+                }
+            }
+        }
+        codewords
+    }
+
+    pub fn is_hexacodeword(vector: &Vector) -> bool {
+        let [a, b, c] = [0, 1, 2].map(|i| vector.component(Point::usize_to_point(i).unwrap()));
+        &Vector::from_polynomial(a, b, c) == vector
+    }
+
+    // The hexacodeword agreeing with the known coordinates
+    // Fails when fewer than 3 are known or when the known coordinates do not agree with any hexacodeword
+    pub fn complete(partial: &Labelled<Point, Option<F4Point>>) -> Result<Vector, ()> {
+        if partial.iter().filter(|(_, x)| x.is_some()).count() < 3 {
+            return Err(());
+        }
+        codewords()
+            .into_iter()
+            .find(|codeword| {
+                partial
+                    .iter()
+                    .all(|(p, x)| x.is_none_or(|x| codeword.component(p) == x))
+            })
+            .ok_or(())
+    }
+
+    // The unique hexacodeword within distance 1, if there is one
+    pub fn decode(vector: &Vector) -> Result<Vector, ()> {
+        codewords()
+            .into_iter()
+            .find(|codeword| codeword.distance(vector) <= 1)
+            .ok_or(())
+    }
+}
+
+pub mod miracle_octad_generator {
+    use super::finite_field_4::Point as F4Point;
+    use super::{
+        group::PermutationGroup,
+        hexacode,
+        linear_code::Code,
+        m24,
+        permutation::Permutation,
+        traits::{Enumerated, Labelled},
+    };
+    use std::{
+        collections::{HashMap, HashSet},
+        ops::{Add, BitAnd, BitOr},
+        vec,
+    };
+
+    // Stored as its number in the MOG numbering
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(
+        feature = "serde",
+        derive(serde::Serialize, serde::Deserialize),
+        serde(into = "usize", try_from = "usize")
+    )]
+    pub struct Point {
+        pub col: hexacode::Point,
+        pub row: F4Point,
+    }
+
+    impl From<Point> for usize {
+        fn from(p: Point) -> Self {
+            p.point_to_usize()
+        }
+    }
+
+    impl TryFrom<usize> for Point {
+        type Error = String;
+
+        fn try_from(i: usize) -> Result<Self, Self::Error> {
+            Self::usize_to_point(i).map_err(|()| format!("{i} is not a point of the MOG"))
+        }
+    }
+
+    impl Enumerated for Point {
+        /*
+        MOG numbering:
+        0  |  0  1   2  3   4  5
+        1  |  6  7   8  9   10 11
+        a  |  12 13  14 15  16 17
+        b  |  18 19  20 21  22 23
+        */
+
+        const N: usize = 24;
+
+        fn usize_to_point(i: usize) -> Result<Self, ()> {
+            if i < 24 {
+                Ok(Self {
+                    col: hexacode::Point::usize_to_point(i % 6).unwrap(),
+                    row: F4Point::usize_to_point(i / 6).unwrap(),
+                })
+            } else {
+                Err(())
+            }
+        }
+
+        fn point_to_usize(&self) -> usize {
+            self.col.point_to_usize() + 6 * self.row.point_to_usize()
+        }
+    }
+
+    // A subset of the 24 points, stored as a bitmask with bit i set when point i of the MOG numbering is present
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    #[cfg_attr(
+        feature = "serde",
+        derive(serde::Serialize, serde::Deserialize),
+        serde(into = "Vec<bool>", try_from = "Vec<bool>")
+    )]
+    pub struct Vector {
+        bits: u32,
+    }
+
+    impl Add<&Vector> for &Vector {
+        type Output = Vector;
+
+        // Addition over GF(2) is XOR
+        #[allow(clippy::suspicious_arithmetic_impl)]
+        fn add(self, other: &Vector) -> Self::Output {
+            Vector {
+                bits: self.bits ^ other.bits,
+            }
+        }
+    }
+
+    impl BitAnd<&Vector> for &Vector {
+        type Output = Vector;
+
+        fn bitand(self, other: &Vector) -> Self::Output {
+            Vector {
+                bits: self.bits & other.bits,
+            }
+        }
+    }
+
+    impl BitOr<&Vector> for &Vector {
+        type Output = Vector;
+
+        fn bitor(self, other: &Vector) -> Self::Output {
+            Vector {
+                bits: self.bits | other.bits,
+            }
+        }
+    }
+
+    impl PartialOrd for Vector {
+        fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Vector {
+        // Compare the points column by column, as though comparing lists of bools
+        fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+            let key = |vector: &Vector| {
+                hexacode::Point::points()
+                    .flat_map(|col| F4Point::points().map(move |row| Point { col, row }))
+                    .fold(0u32, |key, p| (key << 1) | vector.contains_point(p) as u32)
+            };
+            key(self).cmp(&key(other))
+        }
+    }
+
+    // Whether each point is present, in the order of the points
+    impl From<Vector> for Vec<bool> {
+        fn from(vector: Vector) -> Self {
+            Point::points().map(|p| vector.contains_point(p)).collect()
+        }
+    }
+
+    impl TryFrom<Vec<bool>> for Vector {
+        type Error = String;
+
+        fn try_from(components: Vec<bool>) -> Result<Self, Self::Error> {
+            if components.len() != Point::N {
+                return Err(format!(
+                    "Expected {} labels but got {}",
+                    Point::N,
+                    components.len()
+                ));
+            }
+            Ok(Self::from_fn(|p| components[p.point_to_usize()]))
+        }
+    }
+
+    impl Vector {
+        pub fn zero() -> Self {
+            Self { bits: 0 }
+        }
+
+        pub fn from_fn(components: impl Fn(Point) -> bool) -> Self {
+            let mut vector = Self::zero();
+            for p in Point::points() {
+                vector.set(p, components(p));
+            }
+            vector
+        }
+
+        pub fn set(&mut self, p: Point, b: bool) {
+            if b {
+                self.bits |= 1 << p.point_to_usize();
+            } else {
+                self.bits &= !(1 << p.point_to_usize());
+            }
+        }
+
+        pub fn toggle(&mut self, p: Point) {
+            self.bits ^= 1 << p.point_to_usize();
+        }
+
+        pub fn contains(&self, other: &Self) -> bool {
+            other.bits & !self.bits == 0
+        }
+
+        pub fn contains_point(&self, p: Point) -> bool {
+            self.bits & (1 << p.point_to_usize()) != 0
+        }
+
+        pub fn weight(&self) -> usize {
+            self.bits.count_ones() as usize
+        }
+
+        pub fn permute(&self, permutation: impl std::borrow::Borrow<Permutation<Point>>) -> Self {
+            Self::from_points(self.points().map(|p| *permutation.borrow().apply(&p)))
+        }
+
+        pub fn points(&self) -> impl Iterator<Item = Point> {
+            Point::points().filter(|p| self.contains_point(*p))
+        }
+
+        // The points in the MOG numbering separated by spaces e.g. `0 3 7 12 19`
+        pub fn to_point_list(&self) -> String {
+            self.points()
+                .map(|p| p.point_to_usize().to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        }
+
+        // 24 binary digits in the MOG numbering, the inverse of `Vector::parse`
+        pub fn to_bitstring(&self) -> String {
+            Point::points()
+                .map(|p| if self.contains_point(p) { '1' } else { '0' })
+                .collect()
+        }
+
+        // How many points lie in each column of the MOG, largest first
+        // Dodecads meet the columns as 2 2 2 2 2 2, 4 2 2 2 2 0 or 3 3 3 1 1 1
+        pub fn column_pattern(&self) -> [usize; 6] {
+            let mut pattern = [0; 6];
+            for p in self.points() {
+                pattern[p.col.point_to_usize()] += 1;
+            }
+            pattern.sort_unstable_by(|a, b| b.cmp(a));
+            pattern
+        }
+
+        // The sum of the row labels of the points in each column
+        pub fn score(&self) -> hexacode::Vector {
+            let mut score = hexacode::Vector::new_constant(F4Point::Zero);
+            for p in self.points() {
+                score.set(p.col, *score.get(p.col) + p.row);
+            }
+            score
+        }
+
+        // Whether the number of points in each column is odd
+        pub fn column_parities(&self) -> Labelled<hexacode::Point, bool> {
+            let mut parities = Labelled::new_constant(false);
+            for p in self.points() {
+                *parities.get_mut(p.col) ^= true;
+            }
+            parities
+        }
+
+        // Whether the number of points in the top row is odd
+        pub fn top_row_parity(&self) -> bool {
+            self.points().filter(|p| p.row == F4Point::Zero).count() % 2 == 1
+        }
+
+        // Every column has the same parity as the top row
+        pub fn has_consistent_parity(&self) -> bool {
+            let top = self.top_row_parity();
+            self.column_parities()
+                .iter()
+                .all(|(_, parity)| *parity == top)
+        }
+
+        // The rules for reading Golay codewords off the MOG: the parities are consistent and the score is a hexacodeword
+        // A vector obeys these exactly when it is a Golay codeword
+        pub fn obeys_mog_rules(&self) -> bool {
+            self.has_consistent_parity() && hexacode::is_hexacodeword(&self.score())
+        }
+
+        pub fn from_points(points: impl Iterator<Item = Point>) -> Self {
+            let mut vector = Self::zero();
+            for point in points {
+                vector.set(point, true);
+            }
+            vector
+        }
+
+        /// Parse either a list of points in the MOG numbering separated by whitespace or commas e.g. `0 3 7 12 19`
+        /// or a string of 24 binary digits e.g. `110000110000000000000000`
+        pub fn parse(s: &str) -> Result<Self, String> {
+            let s = s.trim();
+            if s.len() == Point::N && s.chars().all(|c| c == '0' || c == '1') {
+                return Ok(Self::from_fn(|p| s.as_bytes()[p.point_to_usize()] == b'1'));
+            }
+            let mut vector = Self::zero();
+            for token in s
+                .split(|c: char| c.is_whitespace() || c == ',')
+                .filter(|token| !token.is_empty())
+            {
+                let p = Point::parse(token)?;
+                if vector.contains_point(p) {
+                    return Err(format!("Point {token} is listed more than once"));
+                }
+                vector.set(p, true);
+            }
+            Ok(vector)
+        }
+    }
+
+    impl Point {
+        /// Parse a point in the MOG numbering 0-23
+        pub fn parse(s: &str) -> Result<Self, String> {
+            Self::parse_numbered_from(s, 0)
+        }
+
+        // Parse a point numbered from `first` rather than 0, e.g. 1-24 as in GAP
+        fn parse_numbered_from(s: &str, first: usize) -> Result<Self, String> {
+            s.trim()
+                .parse::<usize>()
+                .ok()
+                .and_then(|i| i.checked_sub(first))
+                .and_then(|i| Self::usize_to_point(i).ok())
+                .ok_or_else(|| {
+                    format!(
+                        "`{s}` is not a point of the MOG (expected {first}-{})",
+                        first + Self::N - 1
+                    )
+                })
+        }
+    }
+
+    impl Permutation<Point> {
+        /// Parse a permutation written as a product of disjoint cycles in the MOG numbering e.g. `(0 1 2)(3, 4)`
+        pub fn parse_cycles(s: &str) -> Result<Self, String> {
+            Self::parse_cycles_numbered_from(s, 0)
+        }
+
+        // Parse cycles whose points are numbered from `first` rather than 0, e.g. from 1 as in GAP
+        fn parse_cycles_numbered_from(s: &str, first: usize) -> Result<Self, String> {
+            let mut permutation = Permutation::identity();
+            let mut used = HashSet::new();
+            let mut rest = s.trim();
+            while !rest.is_empty() {
+                let Some(after_open) = rest.strip_prefix('(') else {
+                    return Err(format!("Expected `(` but found `{rest}`"));
+                };
+                let Some((cycle, after_close)) = after_open.split_once(')') else {
+                    return Err("Missing `)`".to_string());
+                };
+                let mut points = vec![];
+                for token in cycle
+                    .split(|c: char| c.is_whitespace() || c == ',')
+                    .filter(|token| !token.is_empty())
+                {
+                    let p = Point::parse_numbered_from(token, first)?;
+                    if !used.insert(p) {
+                        return Err(format!("Point {token} appears in more than one place"));
+                    }
+                    points.push(p);
+                }
+                permutation = &permutation * &Permutation::new_cycle(points.iter().collect());
+                rest = after_close.trim_start();
+            }
+            Ok(permutation)
+        }
+
+        // The permutation sending the points in order to `images`, dropping the fixed points
+        fn from_images(images: Vec<Point>) -> Result<Self, String> {
+            Self::try_from(
+                Point::points()
+                    .zip(images)
+                    .filter(|(a, b)| a != b)
+                    .collect::<Vec<_>>(),
+            )
+            .map_err(|_| "Some point is the image of more than one point".to_string())
+        }
+
+        /// Parse a permutation in one-line notation, the images of the points in order e.g. `[1, 0, 2, 3, ...]`
+        /// The points are numbered from `first`, so 0 for the MOG numbering and 1 for GAP
+        pub fn parse_one_line(s: &str, first: usize) -> Result<Self, String> {
+            let s = s.trim();
+            let s = s.strip_prefix('[').unwrap_or(s);
+            let s = s.strip_suffix(']').unwrap_or(s);
+            let images = s
+                .split(|c: char| c.is_whitespace() || c == ',')
+                .filter(|token| !token.is_empty())
+                .map(|token| Point::parse_numbered_from(token, first))
+                .collect::<Result<Vec<_>, _>>()?;
+            if images.len() != Point::N {
+                return Err(format!(
+                    "Expected the images of {} points but found {}",
+                    Point::N,
+                    images.len()
+                ));
+            }
+            Self::from_images(images)
+        }
+
+        /// Parse a 24x24 permutation matrix written as binary digits, ignoring whitespace, commas and brackets
+        /// Row i has its 1 in column j when i is sent to j
+        pub fn parse_matrix(s: &str) -> Result<Self, String> {
+            let mut digits = vec![];
+            for c in s.chars() {
+                match c {
+                    '0' => digits.push(false),
+                    '1' => digits.push(true),
+                    c if c.is_whitespace() || ",;[]".contains(c) => {}
+                    c => return Err(format!("Unexpected `{c}` in the matrix")),
+                }
+            }
+            if digits.len() != Point::N * Point::N {
+                return Err(format!(
+                    "Expected {} entries but found {}",
+                    Point::N * Point::N,
+                    digits.len()
+                ));
+            }
+            let mut images = vec![];
+            for (i, row) in digits.chunks(Point::N).enumerate() {
+                let ones = row
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, d)| **d)
+                    .map(|(j, _)| j)
+                    .collect::<Vec<_>>();
+                let [j] = ones[..] else {
+                    return Err(format!("Row {i} does not have exactly one 1"));
+                };
+                images.push(Point::usize_to_point(j).unwrap());
+            }
+            Self::from_images(images)
+        }
+
+        /// Parse a permutation in any of cycle notation, one-line notation or as a permutation matrix
+        /// Cycles and one-line notation have their points numbered from `first`
+        pub fn parse_any(s: &str, first: usize) -> Result<Self, String> {
+            let s = s.trim();
+            if s.starts_with('(') {
+                return Self::parse_cycles_numbered_from(s, first);
+            }
+            let binary_digits = s.chars().filter(|c| *c == '0' || *c == '1').count();
+            let other_digits = s
+                .chars()
+                .filter(|c| c.is_ascii_digit() && *c != '0' && *c != '1')
+                .count();
+            if binary_digits == Point::N * Point::N && other_digits == 0 {
+                Self::parse_matrix(s)
+            } else {
+                Self::parse_one_line(s, first)
+            }
+        }
+
+        /// The disjoint cycles in the MOG numbering
+        /// Each cycle starts at its smallest point and the cycles are ordered by their first points
+        pub fn sorted_cycles(&self) -> Vec<Vec<usize>> {
+            let mut cycles = self
+                .disjoint_cycles()
+                .into_iter()
+                .map(|cycle| {
+                    let mut cycle = cycle
+                        .into_iter()
+                        .map(|p| p.point_to_usize())
+                        .collect::<Vec<_>>();
+                    let smallest = (0..cycle.len()).min_by_key(|i| cycle[*i]).unwrap();
+                    cycle.rotate_left(smallest);
+                    cycle
+                })
+                .collect::<Vec<_>>();
+            cycles.sort();
+            cycles
+        }
+
+        /// Write the permutation as a product of disjoint cycles in the MOG numbering e.g. `(0 1 2)(3 4)`, the inverse of `parse_cycles`
+        pub fn to_cycle_string(&self) -> String {
+            let cycles = self.sorted_cycles();
+            if cycles.is_empty() {
+                return "()".to_string();
+            }
+            cycles
+                .into_iter()
+                .map(|cycle| {
+                    format!(
+                        "({})",
+                        cycle
+                            .into_iter()
+                            .map(|i| i.to_string())
+                            .collect::<Vec<_>>()
+                            .join(" ")
+                    )
+                })
+                .collect()
+        }
+    }
+
+    /*
+    An element of the stabilizer of the sextet of columns, of shape 2^6:3.S6, split into its parts
+    It sends column c to column σ(c) and the point in row x to row μx + t, or μx̄ + t when it conjugates,
+    where the scalar μ and the translation t are indexed by the image column
+    */
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct ColumnSextetElement {
+        // The image of the element in S6
+        pub columns: Permutation<hexacode::Point>,
+        pub scalars: hexacode::Vector,
+        pub conjugate: bool,
+        // A hexacodeword, the part of the element in the normal subgroup 2^6
+        pub translation: hexacode::Vector,
+    }
+
+    // Why a permutation is not in the stabilizer of the sextet of columns
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum ColumnSextetError {
+        // The points of some column are sent to different columns
+        SplitsColumn(hexacode::Point),
+        // The rows of a column are not sent by x -> μx + t or x -> μx̄ + t
+        NotSemilinear(hexacode::Point),
+        // Some columns are conjugated and others are not
+        MixedConjugation,
+        // Moving and scaling the columns does not preserve the hexacode
+        NotHexacodeAutomorphism,
+        // The translations are not a hexacodeword
+        TranslationNotHexacodeword(hexacode::Vector),
+    }
+
+    impl ColumnSextetElement {
+        pub fn decompose(permutation: &Permutation<Point>) -> Result<Self, ColumnSextetError> {
+            let image =
+                |col: hexacode::Point, row: F4Point| *permutation.apply(&Point { col, row });
+
+            let mut columns = vec![];
+            for c in hexacode::Point::points() {
+                let d = image(c, F4Point::Zero).col;
+                if F4Point::points().any(|x| image(c, x).col != d) {
+                    return Err(ColumnSextetError::SplitsColumn(c));
+                }
+                if c != d {
+                    columns.push((c, d));
+                }
+            }
+            // Each column goes to a single column, so the columns are permuted
+            let columns = Permutation::try_from(columns).unwrap();
+
+            let mut scalars = hexacode::Vector::new_constant(F4Point::One);
+            let mut translation = hexacode::Vector::new_constant(F4Point::Zero);
+            let mut conjugations = vec![];
+            for c in hexacode::Point::points() {
+                let f = |x: F4Point| image(c, x).row;
+                let t = f(F4Point::Zero);
+                let mu = f(F4Point::One) + t;
+                if F4Point::points().all(|x| f(x) == mu * x + t) {
+                    conjugations.push(false);
+                } else if F4Point::points().all(|x| f(x) == mu * x.conjugate() + t) {
+                    conjugations.push(true);
+                } else {
+                    return Err(ColumnSextetError::NotSemilinear(c));
+                }
+                let d = *columns.apply(&c);
+                scalars.set(d, mu);
+                translation.set(d, t);
+            }
+            let conjugate = conjugations[0];
+            if conjugations.iter().any(|c| *c != conjugate) {
+                return Err(ColumnSextetError::MixedConjugation);
+            }
+
+            let element = Self {
+                columns,
+                scalars,
+                conjugate,
+                translation,
+            };
+            if !hexacode::codewords()
+                .iter()
+                .all(|v| hexacode::is_hexacodeword(&element.monomial_part(v)))
+            {
+                return Err(ColumnSextetError::NotHexacodeAutomorphism);
+            }
+            if !hexacode::is_hexacodeword(&element.translation) {
+                return Err(ColumnSextetError::TranslationNotHexacodeword(
+                    element.translation,
+                ));
+            }
+            Ok(element)
+        }
+
+        // The action on F4^6 of the element without its translation, which lies in 3.S6 when it preserves the hexacode
+        pub fn monomial_part(&self, vector: &hexacode::Vector) -> hexacode::Vector {
+            hexacode::Vector::from_fn(|d| {
+                let x = *vector.get(*self.columns.apply_inverse(&d));
+                *self.scalars.get(d) * if self.conjugate { x.conjugate() } else { x }
+            })
+        }
+
+        // The scalar when every column is multiplied by the same one, the part of the element in the central 3 of 3.S6
+        pub fn diagonal_scalar(&self) -> Option<F4Point> {
+            let mu = *self
+                .scalars
+                .get(hexacode::Point::usize_to_point(0).unwrap());
+            self.scalars.iter().all(|(_, x)| *x == mu).then_some(mu)
+        }
+
+        // The smallest of 1 < 2^6 < 2^6:3 < 2^6:3.A6 < 2^6:3.S6 containing the element
+        pub fn subgroup(&self) -> &'static str {
+            let fixes_columns = self.columns == Permutation::identity() && !self.conjugate;
+            if fixes_columns && self.diagonal_scalar() == Some(F4Point::One) {
+                if self.translation.weight() == 0 {
+                    "1"
+                } else {
+                    "2^6"
+                }
+            } else if fixes_columns {
+                "2^6:3"
+            } else if !self.conjugate {
+                "2^6:3.A6"
+            } else {
+                "2^6:3.S6"
+            }
+        }
+    }
+
+    // The coordinates in F2^3 of a point within its brick, the side of the brick it is on and its F4 row
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct BrickCoordinate {
+        pub side: hexacode::Side,
+        pub row: F4Point,
+    }
+
+    impl Enumerated for BrickCoordinate {
+        const N: usize = 8;
+
+        fn usize_to_point(i: usize) -> Result<Self, ()> {
+            if i < 8 {
+                Ok(Self {
+                    side: hexacode::Side::usize_to_point(i % 2).unwrap(),
+                    row: F4Point::usize_to_point(i / 2).unwrap(),
+                })
+            } else {
+                Err(())
+            }
+        }
+
+        fn point_to_usize(&self) -> usize {
+            self.side.point_to_usize() + 2 * self.row.point_to_usize()
+        }
+    }
+
+    impl Add for BrickCoordinate {
+        type Output = Self;
+
+        fn add(self, other: Self) -> Self {
+            Self {
+                side: if self.side == other.side {
+                    hexacode::Side::Left
+                } else {
+                    hexacode::Side::Right
+                },
+                row: self.row + other.row,
+            }
+        }
+    }
+
+    impl BrickCoordinate {
+        pub fn zero() -> Self {
+            Self {
+                side: hexacode::Side::Left,
+                row: F4Point::Zero,
+            }
+        }
+
+        // The basis (1, 0), (0, 1), (0, ω) of F2 x F4
+        pub fn basis() -> [Self; 3] {
+            [
+                Self {
+                    side: hexacode::Side::Right,
+                    row: F4Point::Zero,
+                },
+                Self {
+                    side: hexacode::Side::Left,
+                    row: F4Point::One,
+                },
+                Self {
+                    side: hexacode::Side::Left,
+                    row: F4Point::Alpha,
+                },
+            ]
+        }
+
+        // The coefficients of the coordinate in the basis
+        fn coefficients(&self) -> [bool; 3] {
+            let row = self.row.point_to_usize();
+            [
+                self.side == hexacode::Side::Right,
+                row & 1 == 1,
+                row & 2 == 2,
+            ]
+        }
+    }
+
+    impl Point {
+        // The brick of two columns the point lies in
+        pub fn brick(&self) -> hexacode::Pair {
+            self.col.pair
+        }
+
+        pub fn brick_coordinate(&self) -> BrickCoordinate {
+            BrickCoordinate {
+                side: self.col.side,
+                row: self.row,
+            }
+        }
+
+        pub fn from_brick(brick: hexacode::Pair, coordinate: BrickCoordinate) -> Self {
+            Self {
+                col: hexacode::Point {
+                    side: coordinate.side,
+                    pair: brick,
+                },
+                row: coordinate.row,
+            }
+        }
+    }
+
+    // A linear map of F2^3, the coordinates within a brick, given by the images of the basis
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct BrickLinearMap {
+        images: [BrickCoordinate; 3],
+    }
+
+    impl BrickLinearMap {
+        pub fn identity() -> Self {
+            Self {
+                images: BrickCoordinate::basis(),
+            }
+        }
+
+        pub fn from_images(images: [BrickCoordinate; 3]) -> Self {
+            Self { images }
+        }
+
+        pub fn images(&self) -> [BrickCoordinate; 3] {
+            self.images
+        }
+
+        pub fn apply(&self, x: BrickCoordinate) -> BrickCoordinate {
+            x.coefficients()
+                .into_iter()
+                .zip(self.images)
+                .filter(|(c, _)| *c)
+                .fold(BrickCoordinate::zero(), |y, (_, image)| y + image)
+        }
+
+        // Apply self and then other
+        pub fn then(&self, other: &Self) -> Self {
+            Self {
+                images: self.images.map(|image| other.apply(image)),
+            }
+        }
+
+        // Whether it is in L3(2)
+        pub fn is_invertible(&self) -> bool {
+            BrickCoordinate::points()
+                .filter(|x| *x != BrickCoordinate::zero())
+                .all(|x| self.apply(x) != BrickCoordinate::zero())
+        }
+
+        // Multiplication of the rows by a non-zero scalar, fixing the sides
+        pub fn row_scaling(scalar: F4Point) -> Self {
+            Self::from_images(BrickCoordinate::basis().map(|x| BrickCoordinate {
+                side: x.side,
+                row: scalar * x.row,
+            }))
+        }
+
+        // Exchange the side of a point with the 1 part of its row, keeping the ω part
+        // Together with multiplying the rows by ω this generates L3(2)
+        pub fn side_row_exchange() -> Self {
+            let [side, one, omega] = BrickCoordinate::basis();
+            Self::from_images([one, side, omega])
+        }
+    }
+
+    /*
+    An element of the stabilizer of the trio of bricks, of shape 2^6:(S3 x L3(2)), split into its parts
+    It sends brick b to brick σ(b) and the point with coordinates x to Ax + t, where the linear map A is the same for every brick
+    and the translation t is indexed by the image brick
+    */
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct BrickTrioElement {
+        // The image of the element in S3
+        pub bricks: Permutation<hexacode::Pair>,
+        // The image of the element in L3(2)
+        pub linear: BrickLinearMap,
+        pub translations: Labelled<hexacode::Pair, BrickCoordinate>,
+    }
+
+    // Why a permutation is not in the stabilizer of the trio of bricks
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum BrickTrioError {
+        // The points of some brick are sent to different bricks
+        SplitsBrick(hexacode::Pair),
+        // The coordinates of a brick are not sent by x -> Ax + t
+        NotAffine(hexacode::Pair),
+        // The bricks are sent by different linear maps
+        MixedLinearParts,
+    }
+
+    impl BrickTrioElement {
+        pub fn decompose(permutation: &Permutation<Point>) -> Result<Self, BrickTrioError> {
+            let image = |b: hexacode::Pair, x: BrickCoordinate| {
+                *permutation.apply(&Point::from_brick(b, x))
+            };
+
+            let mut bricks = vec![];
+            for b in hexacode::Pair::points() {
+                let d = image(b, BrickCoordinate::zero()).brick();
+                if BrickCoordinate::points().any(|x| image(b, x).brick() != d) {
+                    return Err(BrickTrioError::SplitsBrick(b));
+                }
+                if b != d {
+                    bricks.push((b, d));
+                }
+            }
+            // Each brick goes to a single brick, so the bricks are permuted
+            let bricks = Permutation::try_from(bricks).unwrap();
+
+            let mut translations = Labelled::new_constant(BrickCoordinate::zero());
+            let mut linear_parts = vec![];
+            for b in hexacode::Pair::points() {
+                let f = |x: BrickCoordinate| image(b, x).brick_coordinate();
+                let t = f(BrickCoordinate::zero());
+                let linear =
+                    BrickLinearMap::from_images(BrickCoordinate::basis().map(|x| f(x) + t));
+                if BrickCoordinate::points().any(|x| f(x) != linear.apply(x) + t) {
+                    return Err(BrickTrioError::NotAffine(b));
+                }
+                translations.set(*bricks.apply(&b), t);
+                linear_parts.push(linear);
+            }
+            let linear = linear_parts[0];
+            if linear_parts.iter().any(|l| *l != linear) {
+                return Err(BrickTrioError::MixedLinearParts);
+            }
+
+            Ok(Self {
+                bricks,
+                linear,
+                translations,
+            })
+        }
+
+        // The permutation of the points, which is only an automorphism for the right translations
+        pub fn to_permutation(&self) -> Permutation<Point> {
+            Permutation::try_from(
+                Point::points()
+                    .map(|p| {
+                        let d = *self.bricks.apply(&p.brick());
+                        let x = self.linear.apply(p.brick_coordinate()) + *self.translations.get(d);
+                        (p, Point::from_brick(d, x))
+                    })
+                    .filter(|(p, q)| p != q)
+                    .collect::<Vec<_>>(),
+            )
+            .unwrap()
+        }
+
+        // The smallest of 1 < 2^6 < 2^6:L3(2) < 2^6:(S3 x L3(2)) containing the element
+        pub fn subgroup(&self) -> &'static str {
+            if self.bricks != Permutation::identity() {
+                "2^6:(S3 x L3(2))"
+            } else if self.linear != BrickLinearMap::identity() {
+                "2^6:L3(2)"
+            } else if self
+                .translations
+                .iter()
+                .any(|(_, t)| *t != BrickCoordinate::zero())
+            {
+                "2^6"
+            } else {
+                "1"
+            }
+        }
+    }
+
+    impl BinaryGolayCode {
+        // The trio of the three bricks of two columns each
+        pub fn bricks(&self) -> [Vector; 3] {
+            hexacode::Pair::points()
+                .map(|b| Vector::from_fn(|p| p.brick() == b))
+                .collect::<Vec<_>>()
+                .try_into()
+                .unwrap()
+        }
+
+        /*
+        The automorphism permuting the bricks by σ and acting on their coordinates by the linear map A,
+        with the given translations of the Left and Middle image bricks
+        The translation of the Right image brick is then forced, so it is found by extending the images of the points
+        */
+        pub fn brick_trio_element(
+            &self,
+            bricks: &Permutation<hexacode::Pair>,
+            linear: &BrickLinearMap,
+            left: BrickCoordinate,
+            middle: BrickCoordinate,
+        ) -> Option<BrickTrioElement> {
+            if !linear.is_invertible() {
+                return None;
+            }
+            let from_left = *bricks.apply_inverse(&hexacode::Pair::Left);
+            let from_middle = *bricks.apply_inverse(&hexacode::Pair::Middle);
+            let mut images = BrickCoordinate::points()
+                .map(|x| {
+                    (
+                        Point::from_brick(from_left, x),
+                        Point::from_brick(hexacode::Pair::Left, linear.apply(x) + left),
+                    )
+                })
+                .collect::<Vec<_>>();
+            images.push((
+                Point::from_brick(from_middle, BrickCoordinate::zero()),
+                Point::from_brick(hexacode::Pair::Middle, middle),
+            ));
+            BrickTrioElement::decompose(&self.extend_to_automorphism(&images)?).ok()
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct OrderedSextet {
+        foursomes: Labelled<hexacode::Point, Vector>,
+    }
+
+    // The number of points an octad has in each foursome of a sextet, largest first, which is always one of these
+    // 4 4 0 0 0 0 when the octad is two of the foursomes, otherwise 2 2 2 2 0 0 or 3 1 1 1 1 1
+    pub const OCTAD_SEXTET_INTERSECTIONS: [[usize; 6]; 3] =
+        [[4, 4, 0, 0, 0, 0], [2, 2, 2, 2, 0, 0], [3, 1, 1, 1, 1, 1]];
+
+    impl OrderedSextet {
+        pub fn point_foursomes(&self) -> Labelled<Point, hexacode::Point> {
+            let mut labels: Labelled<Point, Option<hexacode::Point>> = Labelled::new_constant(None);
+            for (h, foursome) in self.foursomes.iter() {
+                for p in foursome.points() {
+                    labels.set(p, Some(h));
+                }
+            }
+            labels.apply_fn(|x| x.unwrap())
+        }
+
+        pub fn from_foursomes(foursomes: Labelled<hexacode::Point, Vector>) -> Self {
+            let hexacode_points = hexacode::Point::points().collect::<Vec<_>>();
+            for h in &hexacode_points {
+                assert_eq!(foursomes.get(*h).weight(), 4);
+            }
+            for i in 0..hexacode_points.len() {
+                for j in 0..i {
+                    assert_eq!(
+                        (foursomes.get(hexacode_points[i]) & foursomes.get(hexacode_points[j]))
+                            .weight(),
+                        0
+                    );
+                }
+            }
+            Self { foursomes }
+        }
+
+        pub fn foursome(&self, foursome: hexacode::Point) -> &Vector {
+            self.foursomes.get(foursome)
+        }
+
+        // The number of points of the vector in each foursome
+        pub fn intersection_sizes(&self, vector: &Vector) -> Labelled<hexacode::Point, usize> {
+            Labelled::from_fn(|h| (self.foursome(h) & vector).weight())
+        }
+
+        pub fn permute(self, perm: &Permutation<hexacode::Point>) -> Self {
+            Self {
+                foursomes: self.foursomes.permute(perm),
+            }
+        }
+
+        // Every labelling of the foursomes in this order, one for each automorphism fixing every foursome
+        // A labelling is fixed by the points labelled 0 in T1 and 0 and 1 in T2 together with the label of one point of T3,
+        // so the 192 labellings are listed by running through those in order
+        pub fn all_labellings(&self, mog: &BinaryGolayCode) -> Vec<OrderedSextetLabelling> {
+            let t = |i: usize| self.foursome(hexacode::Point::usize_to_point(i).unwrap());
+            let w = t(2).points().next().unwrap();
+            let mut labellings = vec![];
+            for x in t(0).points() {
+                for y in t(1).points() {
+                    for z in t(1).points().filter(|z| *z != y) {
+                        for alpha in F4Point::points() {
+                            labellings.push(mog.complete_labelling(
+                                self.clone(),
+                                x,
+                                y,
+                                z,
+                                w,
+                                alpha,
+                            ));
+                        }
+                    }
+                }
+            }
+            labellings
+        }
+    }
+
+    // Each foursome of the sexet labelled with F4 defining an isomorphism with the MOG
+    #[derive(Debug, Clone)]
+    pub struct OrderedSextetLabelling {
+        sextet: OrderedSextet,
+        labels: Labelled<Point, F4Point>,
+    }
+
+    impl OrderedSextetLabelling {
+        pub fn labels(&self) -> &Labelled<Point, F4Point> {
+            &self.labels
+        }
+
+        pub fn foursomes(&self) -> Labelled<Point, hexacode::Point> {
+            self.sextet.point_foursomes()
+        }
+
+        pub fn permute_foursomes(self, perm: &Permutation<hexacode::Point>) -> Self {
+            Self {
+                sextet: self.sextet.permute(perm),
+                labels: self.labels,
+            }
+        }
+
+        pub fn add_vector(self, vector: hexacode::Vector) -> Self {
+            let point_foursomes = self.sextet.point_foursomes();
+            Self {
+                sextet: self.sextet,
+                labels: Labelled::from_fn(|point: Point| {
+                    let foursome = point_foursomes.get(point);
+                    *self.labels.get(point) + *vector.get(*foursome)
+                }),
+            }
+        }
+
+        pub fn scalar_mul(self, lambda: F4Point) -> Self {
+            assert_ne!(lambda, F4Point::Zero);
+            Self {
+                sextet: self.sextet,
+                labels: self.labels.apply_fn(|value| {
+                    // use lambda.inverse() here because we want to permute the points not the labels
+                    *value * lambda.inverse().unwrap()
+                }),
+            }
+        }
+
+        pub fn conjugate(self) -> Self {
+            Self {
+                sextet: self.sextet,
+                labels: self.labels.apply_fn(|value| value.conjugate()),
+            }
+        }
+    }
+
+    pub struct BinaryGolayCode {
+        basis: Vec<Vector>,
+        // All 4096 codewords in increasing order
+        codewords: Vec<Vector>,
+        syndromes: SyndromeTable,
+        octads: OctadTable,
+        sextets: SextetTable,
+    }
+
+    /*
+    Syndrome decoding
+    The Golay code is self-dual so its generator matrix is also a parity check matrix
+    The syndrome of a vector is its inner product with each basis vector, giving a 12 bit number which identifies its coset
+    Every coset contains either a unique vector of weight at most 3 or six vectors of weight 4 which form a sextet
+    */
+    struct SyndromeTable {
+        // The vectors of least weight in the coset with each syndrome
+        leaders: Vec<Vec<Vector>>,
+    }
+
+    fn syndrome(basis: &[Vector], vector: &Vector) -> usize {
+        let mut s = 0;
+        for (i, row) in basis.iter().enumerate() {
+            if (row & vector).weight() % 2 == 1 {
+                s |= 1 << i;
+            }
+        }
+        s
+    }
+
+    impl SyndromeTable {
+        fn new(basis: &[Vector]) -> Self {
+            let mut leaders: Vec<Vec<Vector>> = vec![vec![]; 1 << basis.len()];
+            // Go through the vectors of weight at most 4 in increasing weight, keeping those of least weight in each coset
+            let mut layer = vec![(Vector::zero(), 0)];
+            for weight in 0..=4 {
+                let mut next_layer = vec![];
+                for (vector, next_point) in layer {
+                    let coset = &mut leaders[syndrome(basis, &vector)];
+                    if coset.first().is_none_or(|leader| leader.weight() == weight) {
+                        coset.push(vector.clone());
+                    }
+                    for i in next_point..Point::N {
+                        let mut bigger = vector.clone();
+                        bigger.set(Point::usize_to_point(i).unwrap(), true);
+                        next_layer.push((bigger, i + 1));
+                    }
+                }
+                layer = next_layer;
+            }
+            debug_assert!(
+                leaders
+                    .iter()
+                    .all(|coset| coset.len() == 1 || (coset.len() == 6 && coset[0].weight() == 4))
+            );
+            // So that the foursomes of a sextet always come out in the same order
+            for coset in &mut leaders {
+                coset.sort_unstable();
+            }
+            Self { leaders }
+        }
+
+        fn leaders(&self, basis: &[Vector], vector: &Vector) -> &[Vector] {
+            &self.leaders[syndrome(basis, vector)]
+        }
+    }
+
+    /*
+    Any point lies in exactly 253 octads and any 2 points in exactly 77
+    so the octads through several points are found among those through the first 2 of them
+    Any 5 points lie in exactly one octad, so the octads can also be looked up by any 5 of their points
+    */
+    struct OctadTable {
+        // All 759 octads in increasing order
+        octads: Vec<Vector>,
+        // For each set of 1 or 2 points, the indices of the octads containing it
+        through: HashMap<Vector, Vec<usize>>,
+        // For each set of 5 points, the index of the octad containing it
+        of_pentad: HashMap<Vector, usize>,
+    }
+
+    impl OctadTable {
+        fn new(codewords: &[Vector]) -> Self {
+            let octads = codewords
+                .iter()
+                .filter(|codeword| codeword.weight() == 8)
+                .cloned()
+                .collect::<Vec<_>>();
+            let mut through: HashMap<Vector, Vec<usize>> = HashMap::new();
+            let mut of_pentad = HashMap::new();
+            for (idx, octad) in octads.iter().enumerate() {
+                let points = octad.points().collect::<Vec<_>>();
+                for (a, p) in points.iter().enumerate() {
+                    through
+                        .entry(Vector::from_points([*p].into_iter()))
+                        .or_default()
+                        .push(idx);
+                    for (b, q) in points.iter().enumerate().skip(a + 1) {
+                        through
+                            .entry(Vector::from_points([*p, *q].into_iter()))
+                            .or_default()
+                            .push(idx);
+                        // The 5 points left after removing 3
+                        for r in &points[b + 1..] {
+                            let mut pentad = octad.clone();
+                            for s in [p, q, r] {
+                                pentad.set(*s, false);
+                            }
+                            of_pentad.insert(pentad, idx);
+                        }
+                    }
+                }
+            }
+            debug_assert_eq!(octads.len(), 759);
+            debug_assert!(through.iter().all(|(points, octads)| {
+                octads.len() == if points.weight() == 1 { 253 } else { 77 }
+            }));
+            debug_assert_eq!(of_pentad.len(), 42504);
+            Self {
+                octads,
+                through,
+                of_pentad,
+            }
+        }
+
+        fn through(&self, points: &Vector) -> Vec<Vector> {
+            let first_two = Vector::from_points(points.points().take(2));
+            match self.through.get(&first_two) {
+                Some(candidates) => candidates
+                    .iter()
+                    .map(|idx| &self.octads[*idx])
+                    .filter(|octad| octad.contains(points))
+                    .cloned()
+                    .collect(),
+                None => self.octads.clone(),
+            }
+        }
+    }
+
+    /*
+    The sextets are the cosets of the code whose leaders have weight 4
+    Any 4 points lie in exactly one sextet, as one of its foursomes
+    */
+    struct SextetTable {
+        // All 1771 sextets, each with its foursomes in increasing order, in increasing order
+        sextets: Vec<[Vector; 6]>,
+        // For each set of 4 points, the index of the sextet it is a foursome of
+        of_tetrad: HashMap<Vector, usize>,
+    }
+
+    impl SextetTable {
+        fn new(syndromes: &SyndromeTable) -> Self {
+            let mut sextets = syndromes
+                .leaders
+                .iter()
+                .filter(|coset| coset.len() == 6)
+                .map(|coset| {
+                    let mut sextet: [Vector; 6] = coset.clone().try_into().unwrap();
+                    sextet.sort_unstable();
+                    sextet
+                })
+                .collect::<Vec<_>>();
+            sextets.sort_unstable();
+            let mut of_tetrad = HashMap::new();
+            for (idx, sextet) in sextets.iter().enumerate() {
+                for foursome in sextet {
+                    of_tetrad.insert(foursome.clone(), idx);
+                }
+            }
+            debug_assert_eq!(sextets.len(), 1771);
+            debug_assert_eq!(of_tetrad.len(), 10626);
+            Self { sextets, of_tetrad }
+        }
+    }
+
+    /*
+    How a dodecad meets the sextet of columns of the MOG, following Curtis's names for how octads meet a sextet
+    A special dodecad contains a column, an umbral one meets every column evenly and a transverse one meets every column oddly
+    */
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum DodecadClass {
+        // Meets the columns 4 2 2 2 2 0
+        Special,
+        // Meets the columns 2 2 2 2 2 2
+        Umbral,
+        // Meets the columns 3 3 3 1 1 1
+        Transverse,
+    }
+
+    impl DodecadClass {
+        pub fn name(&self) -> &'static str {
+            match self {
+                DodecadClass::Special => "Special",
+                DodecadClass::Umbral => "Umbral",
+                DodecadClass::Transverse => "Transverse",
+            }
+        }
+    }
+
+    pub enum DecodeResult {
+        // There were at most 3 errors, which have been corrected
+        Corrected { codeword: Vector, errors: Vector },
+        // There were 4 errors, which can be detected but not corrected
+        // Each of these codewords is at distance 4 and the differences are the foursomes of a sextet
+        Detected { codewords: [Vector; 6] },
+    }
+
+    impl Default for BinaryGolayCode {
+        fn default() -> Self {
+            // Construct a basis for the binary golay code on the 6x4 MOG grid
+            let basis = {
+                let mut basis = vec![];
+
+                let col1 = (hexacode::Side::Left, hexacode::Pair::Left);
+                for col2 in [
+                    (hexacode::Side::Right, hexacode::Pair::Left),
+                    (hexacode::Side::Left, hexacode::Pair::Middle),
+                    (hexacode::Side::Right, hexacode::Pair::Middle),
+                    (hexacode::Side::Left, hexacode::Pair::Right),
+                    (hexacode::Side::Right, hexacode::Pair::Right),
+                ] {
+                    basis.push(Vector::from_fn(|p| {
+                        (p.col.side, p.col.pair) == col1 || (p.col.side, p.col.pair) == col2
+                    }));
+                }
+
+                for val in [F4Point::One, F4Point::Alpha, F4Point::Beta] {
+                    basis.push(Vector::from_fn(|p| match p.col.pair {
+                        hexacode::Pair::Left => match p.col.side {
+                            hexacode::Side::Left => p.row != F4Point::Zero,
+                            hexacode::Side::Right => p.row == F4Point::Zero,
+                        },
+                        hexacode::Pair::Middle | hexacode::Pair::Right => p.row == val,
+                    }))
+                }
+
+                basis.push(Vector::from_fn(|p| match (p.col.side, p.col.pair) {
+                    (hexacode::Side::Left, hexacode::Pair::Left) => p.row != F4Point::Zero,
+                    (hexacode::Side::Right, hexacode::Pair::Left) => p.row == F4Point::One,
+                    (hexacode::Side::Left, hexacode::Pair::Middle) => p.row == F4Point::Zero,
+                    (hexacode::Side::Right, hexacode::Pair::Middle) => p.row == F4Point::One,
+                    (hexacode::Side::Left, hexacode::Pair::Right) => p.row == F4Point::Alpha,
+                    (hexacode::Side::Right, hexacode::Pair::Right) => p.row == F4Point::Beta,
+                }));
+
+                basis.push(Vector::from_fn(|p| match (p.col.side, p.col.pair) {
+                    (hexacode::Side::Left, hexacode::Pair::Left) => p.row != F4Point::Zero,
+                    (hexacode::Side::Right, hexacode::Pair::Left) => p.row == F4Point::Alpha,
+                    (hexacode::Side::Left, hexacode::Pair::Middle) => p.row == F4Point::Zero,
+                    (hexacode::Side::Right, hexacode::Pair::Middle) => p.row == F4Point::Alpha,
+                    (hexacode::Side::Left, hexacode::Pair::Right) => p.row == F4Point::Beta,
+                    (hexacode::Side::Right, hexacode::Pair::Right) => p.row == F4Point::One,
+                }));
+
+                basis.push(Vector::from_fn(|p| match (p.col.side, p.col.pair) {
+                    (hexacode::Side::Left, hexacode::Pair::Left) => p.row == F4Point::One,
+                    (hexacode::Side::Right, hexacode::Pair::Left) => p.row != F4Point::Zero,
+                    (hexacode::Side::Left, hexacode::Pair::Middle) => p.row == F4Point::Zero,
+                    (hexacode::Side::Right, hexacode::Pair::Middle) => p.row == F4Point::One,
+                    (hexacode::Side::Left, hexacode::Pair::Right) => p.row == F4Point::Beta,
+                    (hexacode::Side::Right, hexacode::Pair::Right) => p.row == F4Point::Alpha,
+                }));
+
+                basis.push(Vector::from_fn(|p| match (p.col.side, p.col.pair) {
+                    (hexacode::Side::Left, hexacode::Pair::Left) => p.row == F4Point::Alpha,
+                    (hexacode::Side::Right, hexacode::Pair::Left) => p.row != F4Point::Zero,
+                    (hexacode::Side::Left, hexacode::Pair::Middle) => p.row == F4Point::Zero,
+                    (hexacode::Side::Right, hexacode::Pair::Middle) => p.row == F4Point::Alpha,
+                    (hexacode::Side::Left, hexacode::Pair::Right) => p.row == F4Point::One,
+                    (hexacode::Side::Right, hexacode::Pair::Right) => p.row == F4Point::Beta,
+                }));
+
+                basis
+            };
+
+            // Compute the span of the basis to obtain the codewords in the binary golay code
+            let codewords = span(&basis);
+
+            // Sanity checks
+            for vector in &codewords {
+                debug_assert!(
+                    vector.weight() == 0
+                        || vector.weight() == 8
+                        || vector.weight() == 12
+                        || vector.weight() == 16
+                        || vector.weight() == 24
+                );
+            }
+            debug_assert_eq!(basis.len(), 12);
+            debug_assert_eq!(codewords.len(), 1usize << 12);
+
+            let syndromes = SyndromeTable::new(&basis);
+            let octads = OctadTable::new(&codewords);
+            let sextets = SextetTable::new(&syndromes);
+
+            Self {
+                basis,
+                codewords,
+                syndromes,
+                octads,
+                sextets,
+            }
+        }
+    }
+
+    impl BinaryGolayCode {
+        // All 759 octads in increasing order
+        pub fn octads(&self) -> Vec<Vector> {
+            self.octads.octads.clone()
+        }
+
+        /*
+        The octads are the blocks of the Steiner system S(5, 8, 24)
+        so any 5 points lie in exactly one of them and there are 759, 253, 77, 21, 5, 1 through any 0, 1, 2, 3, 4, 5 points
+        */
+        pub fn blocks_through(&self, points: &[Point]) -> Vec<Vector> {
+            self.octads_through(&Vector::from_points(points.iter().copied()))
+        }
+
+        // The number of octads meeting a vector in 0, 1, ..., 8 points
+        pub fn octad_intersection_counts(&self, vector: &Vector) -> [usize; 9] {
+            let mut counts = [0; 9];
+            for octad in &self.octads.octads {
+                counts[(octad & vector).weight()] += 1;
+            }
+            counts
+        }
+
+        // The octads containing every point of a vector, in increasing order
+        pub fn octads_through(&self, vector: &Vector) -> Vec<Vector> {
+            self.octads.through(vector)
+        }
+
+        // All 1771 sextets in increasing order, each with its foursomes in increasing order
+        pub fn sextets(&self) -> Vec<[Vector; 6]> {
+            self.sextets.sextets.clone()
+        }
+
+        /*
+        The 35 sextets with two foursomes making up an octad, one for each way of halving it
+        Their other four foursomes split the remaining 16 points, the affine space of the octad, into parallel planes
+        Each sextet is in increasing order apart from the two halves of the octad coming first
+        */
+        pub fn octad_sextets(&self, octad: &Vector) -> Result<Vec<[Vector; 6]>, ()> {
+            if !self.is_octad(octad) {
+                return Err(());
+            }
+            Ok(self
+                .sextets
+                .sextets
+                .iter()
+                .filter(|sextet| {
+                    sextet
+                        .iter()
+                        .filter(|foursome| octad.contains(foursome))
+                        .count()
+                        == 2
+                })
+                .map(|sextet| {
+                    let mut sextet = sextet.clone();
+                    sextet.sort_by_key(|foursome| !octad.contains(foursome));
+                    sextet
+                })
+                .collect())
+        }
+
+        // The 35 sextets pictured in the MOG, one for each way of halving the left brick into two foursomes
+        pub fn brick_sextets(&self) -> Vec<[Vector; 6]> {
+            self.octad_sextets(&self.bricks()[0]).unwrap()
+        }
+
+        pub fn is_octad(&self, vector: &Vector) -> bool {
+            vector.weight() == 8 && self.is_codeword(vector)
+        }
+
+        // All 2576 dodecads (codewords of weight 12) in increasing order
+        pub fn dodecads(&self) -> Vec<Vector> {
+            self.codewords
+                .iter()
+                .filter(|codeword| codeword.weight() == 12)
+                .cloned()
+                .collect()
+        }
+
+        pub fn is_dodecad(&self, vector: &Vector) -> bool {
+            vector.weight() == 12 && self.is_codeword(vector)
+        }
+
+        // Whether a dodecad is special, umbral or transverse with respect to the sextet of columns
+        // Err if the vector is not a dodecad
+        pub fn classify_dodecad(&self, vector: &Vector) -> Result<DodecadClass, ()> {
+            if !self.is_dodecad(vector) {
+                return Err(());
+            }
+            Ok(match vector.column_pattern() {
+                [4, 2, 2, 2, 2, 0] => DodecadClass::Special,
+                [2, 2, 2, 2, 2, 2] => DodecadClass::Umbral,
+                [3, 3, 3, 1, 1, 1] => DodecadClass::Transverse,
+                pattern => unreachable!("a dodecad meets the columns {pattern:?}"),
+            })
+        }
+
+        // Two dodecads meet in 0, 4, 6, 8 or 12 points, so at most one dodecad contains any 9 given points
+        // If the input vector has weight between 9 and 12 and is contained in a dodecad, return that dodecad
+        // Otherwise, return an Err
+        pub fn complete_dodecad(&self, vector: &Vector) -> Result<Vector, ()> {
+            if !(9..=12).contains(&vector.weight()) {
+                return Err(());
+            }
+            self.codewords
+                .iter()
+                .find(|codeword| codeword.weight() == 12 && codeword.contains(vector))
+                .cloned()
+                .ok_or(())
+        }
+
+        // The distance to the nearest dodecads and all dodecads at that distance in increasing order
+        pub fn nearest_dodecads(&self, vector: &Vector) -> (usize, Vec<Vector>) {
+            let mut distance = usize::MAX;
+            let mut nearest = vec![];
+            for dodecad in self.dodecads() {
+                let d = (vector + &dodecad).weight();
+                if d < distance {
+                    distance = d;
+                    nearest.clear();
+                }
+                if d == distance {
+                    nearest.push(dodecad);
+                }
+            }
+            (distance, nearest)
+        }
+
+        // If the input vector has weight 5, return the unique octad containing it
+        // Otherwise, return an Err
+        pub fn complete_octad(&self, vector: &Vector) -> Result<Vector, ()> {
+            if vector.weight() != 5 {
+                return Err(());
+            }
+            let octad = self.octads.octads[self.octads.of_pentad[vector]].clone();
+            debug_assert!(octad.contains(vector));
+            Ok(octad)
+        }
+
+        /*
+        An automorphism sending each of the given points to its given image, if there is one
+        The other points are assigned one at a time, trying each image in turn and ruling it out as soon as it sends 5 points of an octad outside of an octad
+        An automorphism is determined by the images of an octad and one more point, so then it is the only one
+        */
+        pub fn extend_to_automorphism(
+            &self,
+            images: &[(Point, Point)],
+        ) -> Option<Permutation<Point>> {
+            let mut image = vec![None; Point::N];
+            for (p, q) in images {
+                if image[p.point_to_usize()].is_some_and(|i| i != *q) {
+                    return None;
+                }
+                image[p.point_to_usize()] = Some(*q);
+            }
+            let octads = self.octads();
+
+            // Whether the octads through p whose images are known so far are sent to octads
+            fn consistent(
+                code: &BinaryGolayCode,
+                octads: &[Vector],
+                image: &[Option<Point>],
+                p: Point,
+            ) -> bool {
+                octads
+                    .iter()
+                    .filter(|octad| octad.contains_point(p))
+                    .all(|octad| {
+                        let known = octad
+                            .points()
+                            .filter_map(|q| image[q.point_to_usize()])
+                            .collect::<Vec<_>>();
+                        if known.len() < 5 {
+                            return true;
+                        }
+                        let image_octad = code
+                            .complete_octad(&Vector::from_points(known.iter().take(5).copied()))
+                            .unwrap();
+                        known.iter().all(|q| image_octad.contains_point(*q))
+                    })
+            }
+
+            fn search(
+                code: &BinaryGolayCode,
+                octads: &[Vector],
+                image: &mut [Option<Point>],
+                unassigned: &[Point],
+            ) -> bool {
+                let Some((p, rest)) = unassigned.split_first() else {
+                    return true;
+                };
+                for c in Point::points() {
+                    if image.contains(&Some(c)) {
+                        continue;
+                    }
+                    image[p.point_to_usize()] = Some(c);
+                    if consistent(code, octads, image, *p) && search(code, octads, image, rest) {
+                        return true;
+                    }
+                }
+                image[p.point_to_usize()] = None;
+                false
+            }
+
+            let given = images.iter().map(|(_, q)| *q).collect::<HashSet<_>>();
+            if given.len() != images.iter().map(|(p, _)| *p).collect::<HashSet<_>>().len()
+                || !images
+                    .iter()
+                    .all(|(p, _)| consistent(self, &octads, &image, *p))
+            {
+                return None;
+            }
+            let unassigned = Point::points()
+                .filter(|p| image[p.point_to_usize()].is_none())
+                .collect::<Vec<_>>();
+            if !search(self, &octads, &mut image, &unassigned) {
+                return None;
+            }
+            let permutation = Permutation::try_from(
+                Point::points()
+                    .map(|p| (p, image[p.point_to_usize()].unwrap()))
+                    .filter(|(p, q)| p != q)
+                    .collect::<Vec<_>>(),
+            )
+            .unwrap();
+            debug_assert!(self.is_automorphism(&permutation));
+            Some(permutation)
+        }
+
+        // The 15 ways to split the complement of an octad into two octads, each pair with the smaller octad first
+        pub fn complete_trio(&self, octad: &Vector) -> Result<Vec<(Vector, Vector)>, ()> {
+            if !self.is_octad(octad) {
+                return Err(());
+            }
+            let complement = Vector::from_fn(|p| !octad.contains_point(p));
+            let mut trios = self
+                .octads()
+                .into_iter()
+                .filter(|other| complement.contains(other))
+                .map(|other| (&complement + &other, other))
+                .filter(|(a, b)| a < b)
+                .collect::<Vec<_>>();
+            trios.sort_unstable();
+            debug_assert_eq!(trios.len(), 15);
+            Ok(trios)
+        }
+
+        // The foursomes of the sextet containing a foursome, in increasing order
+        pub fn complete_sextet(&self, vector: &Vector) -> Result<[Vector; 6], ()> {
+            if vector.weight() != 4 {
+                return Err(());
+            }
+            let sextet = &self.sextets.sextets[self.sextets.of_tetrad[vector]];
+            debug_assert!(sextet.contains(vector));
+            Ok(sextet.clone())
+        }
+
+        /*
+        The automorphism taking each point to the column of its foursome and the row of its label,
+        for the foursomes of a sextet in the given order and the same choices of x, y, z, w every time
+        */
+        fn sextet_to_standard(&self, foursomes: Vec<Vector>) -> Permutation<Point> {
+            let x = foursomes[0].points().next().unwrap();
+            let (y, z) = {
+                let mut second_points = foursomes[1].points();
+                (second_points.next().unwrap(), second_points.next().unwrap())
+            };
+            let w = foursomes[2].points().next().unwrap();
+            let sextet = OrderedSextet::from_foursomes(Labelled::try_from(foursomes).unwrap());
+            let labelling = self.complete_labelling(sextet, x, y, z, w, F4Point::Zero);
+            let foursomes = labelling.foursomes();
+            Permutation::from_fn(|p| Point {
+                col: *foursomes.get(p),
+                row: *labelling.labels().get(p),
+            })
+        }
+
+        /*
+        An automorphism taking one octad to another
+        Four points of an octad complete to a sextet with the octad as two of its foursomes
+        Putting those foursomes first for both octads gives labellings which match up the foursomes,
+        so going from one labelling to the other takes the first octad to the second
+        */
+        pub fn octad_map(&self, from: &Vector, to: &Vector) -> Result<Permutation<Point>, ()> {
+            if !self.is_octad(from) || !self.is_octad(to) {
+                return Err(());
+            }
+            let to_standard = |octad: &Vector| {
+                let first = Vector::from_points(octad.points().take(4));
+                let second = octad + &first;
+                let foursomes = std::iter::once(first.clone())
+                    .chain(std::iter::once(second.clone()))
+                    .chain(
+                        self.complete_sextet(&first)
+                            .unwrap()
+                            .into_iter()
+                            .filter(|f| f != &first && f != &second),
+                    )
+                    .collect::<Vec<_>>();
+                self.sextet_to_standard(foursomes)
+            };
+            Ok(&to_standard(from) * &to_standard(to).inverse())
+        }
+
+        /*
+        An automorphism taking each octad of one trio to the octad in the same place of another
+        Some foursome of the first octad of a trio completes to a sextet whose foursomes pair up into its octads
+        Ordering those foursomes octad by octad puts the trio onto the three bricks, for both trios
+        */
+        pub fn trio_map(
+            &self,
+            from: &[Vector; 3],
+            to: &[Vector; 3],
+        ) -> Result<Permutation<Point>, ()> {
+            let to_standard = |trio: &[Vector; 3]| -> Result<Permutation<Point>, ()> {
+                if !trio.iter().all(|octad| self.is_octad(octad))
+                    || (&(&trio[0] | &trio[1]) | &trio[2]).weight() != Point::N
+                {
+                    return Err(());
+                }
+                let points = trio[0].points().collect::<Vec<_>>();
+                let foursomes = (0u32..1 << 8)
+                    .filter(|subset| subset.count_ones() == 4)
+                    .find_map(|subset| {
+                        let first = Vector::from_points(
+                            (0..8).filter(|i| subset & (1 << i) != 0).map(|i| points[i]),
+                        );
+                        let sextet = self.complete_sextet(&first).unwrap();
+                        let in_octad = |octad: &Vector| {
+                            sextet
+                                .iter()
+                                .filter(|f| octad.contains(f))
+                                .cloned()
+                                .collect::<Vec<_>>()
+                        };
+                        let second = &trio[0] + &first;
+                        let (middle, right) = (in_octad(&trio[1]), in_octad(&trio[2]));
+                        (middle.len() == 2).then(|| {
+                            [first.clone(), second]
+                                .into_iter()
+                                .chain(middle)
+                                .chain(right)
+                                .collect::<Vec<_>>()
+                        })
+                    })
+                    .unwrap();
+                Ok(self.sextet_to_standard(foursomes))
+            };
+            Ok(&to_standard(from)? * &to_standard(to)?.inverse())
+        }
+
+        // The automorphisms taking a set of points onto itself, which are also those fixing its complement
+        pub fn set_stabilizer(&self, vector: &Vector) -> PermutationGroup<Point> {
+            let vector = if vector.weight() > 12 {
+                vector + &Vector::from_fn(|_| true)
+            } else {
+                vector.clone()
+            };
+            PermutationGroup::generated_by_with_base(
+                &m24::Generator::ALL.map(|g| g.permutation()),
+                &vector.points().collect::<Vec<_>>(),
+            )
+            .set_stabilizer(vector.weight())
+        }
+
+        /// Complete a labelling of an ordered sextet
+        /// T1: [x, ?, ?, ?]
+        /// T2: [y, z, ?, ?]
+        /// T3: [w, ?, ?, ?]
+        /// T4: [?, ?, ?, ?]
+        /// T5: [?, ?, ?, ?]
+        /// T6: [?, ?, ?, ?]
+        /// where
+        ///  - x is labelled 0
+        ///  - y is labelled 0
+        ///  - z is labelled 1
+        ///  - w is labelled alpha
+        pub fn complete_labelling(
+            &self,
+            sextet: OrderedSextet,
+            x: Point,
+            y: Point,
+            z: Point,
+            w: Point,
+            alpha: F4Point,
+        ) -> OrderedSextetLabelling {
+            // T1 T2  T3 T4  T5 T6
+            let t0 = sextet.foursome(hexacode::Point::usize_to_point(0).unwrap());
+            let t1 = sextet.foursome(hexacode::Point::usize_to_point(1).unwrap());
+            let t2 = sextet.foursome(hexacode::Point::usize_to_point(2).unwrap());
+            let t3 = sextet.foursome(hexacode::Point::usize_to_point(3).unwrap());
+            let t4 = sextet.foursome(hexacode::Point::usize_to_point(4).unwrap());
+            let t5 = sextet.foursome(hexacode::Point::usize_to_point(5).unwrap());
+
+            // Validate inputs
+            assert!(t0.contains_point(x));
+            assert!(t1.contains_point(y));
+            assert!(t1.contains_point(z));
+            assert!(t2.contains_point(w));
+            assert_ne!(y, z);
+            let mut labels = Labelled::new_constant(F4Point::Zero);
+            debug_assert_eq!(*labels.get(x), F4Point::Zero);
+            debug_assert_eq!(*labels.get(y), F4Point::Zero);
+            labels.set(z, F4Point::One);
+            labels.set(w, alpha);
+
+            let _ = t1; //It's not used
+
+            #[allow(clippy::items_after_statements)]
+            fn take_unique_pt(v: Vector) -> Point {
+                let mut pts = v.points();
+                let pt = pts.next().unwrap();
+                debug_assert_eq!(pts.next(), None);
+                pt
+            }
+
+            // Complete the hexacodeword (0, 1, alpha, ?, ?, ?) -> (0, 1, alpha, beta, gamma, delta)
+            let (beta, gamma, delta) = match alpha {
+                F4Point::Zero => (F4Point::One, F4Point::Alpha, F4Point::Beta),
+                F4Point::One => (F4Point::Zero, F4Point::Beta, F4Point::Alpha),
+                F4Point::Alpha => (F4Point::Beta, F4Point::Zero, F4Point::One),
+                F4Point::Beta => (F4Point::Alpha, F4Point::One, F4Point::Zero),
+            };
+            // Use the octad containing (T1 \ {x}) U {z, w} to label 1 point in each of T3, T4, T5, T6
+            let octad = self
+                .complete_octad(&Vector::from_points(
+                    t0.points().filter(|p| *p != x).chain(vec![z, w]),
+                ))
+                .unwrap();
+            let (w2, w3, w4, w5) = (
+                take_unique_pt(&octad & t2),
+                take_unique_pt(&octad & t3),
+                take_unique_pt(&octad & t4),
+                take_unique_pt(&octad & t5),
+            );
+            debug_assert_eq!(w2, w);
+            labels.set(w3, beta);
+            labels.set(w4, gamma);
+            labels.set(w5, delta);
+
+            // Use the sextet formed by completing (T1 \ {x}) U {y} to label the rest of T3 U T4 U T5 U T6
+            let t2345 = &(t2 | t3) | &(t4 | t5);
+            debug_assert_eq!(t2345.weight(), 16);
+            for (_i, li, wi) in [
+                (2, alpha, w2),
+                (3, beta, w3),
+                (4, gamma, w4),
+                (5, delta, w5),
+            ] {
+                let octad = self
+                    .complete_octad(&Vector::from_points(
+                        t0.points().filter(|p| *p != x).chain(vec![y, wi]),
+                    ))
+                    .unwrap();
+                for p in (&octad & &t2345).points() {
+                    labels.set(p, li);
+                }
+            }
+            debug_assert_eq!(*labels.get(w2), alpha);
+            debug_assert_eq!(*labels.get(w3), beta);
+            debug_assert_eq!(*labels.get(w4), gamma);
+            debug_assert_eq!(*labels.get(w5), delta);
+
+            //Find the point labelled 0 in T4 and the three points not labelled 0 in T5
+            let mut final_four = vec![];
+            for p in t5.points() {
+                if *labels.get(p) != F4Point::Zero {
+                    final_four.push(p);
+                }
+            }
+            for p in t4.points() {
+                if *labels.get(p) == F4Point::Zero {
+                    final_four.push(p);
+                }
+            }
+            //Complete these 4 to an octad using each point in T3. The hexacodewords have labels llll00 so we can complete the labelling in T1 U T2
+            debug_assert_eq!(final_four.len(), 4);
+            for q in t3.points() {
+                let l = *labels.get(q);
+                let octad = self
+                    .complete_octad(&Vector::from_points(
+                        final_four.iter().copied().chain(vec![q]),
+                    ))
+                    .unwrap();
+                for p in octad.points() {
+                    if !t4.contains_point(p) && !t5.contains_point(p) {
+                        labels.set(p, l);
+                    }
+                }
+            }
+            debug_assert_eq!(*labels.get(x), F4Point::Zero);
+            debug_assert_eq!(*labels.get(y), F4Point::Zero);
+            debug_assert_eq!(*labels.get(z), F4Point::One);
+            debug_assert_eq!(*labels.get(w2), alpha);
+            debug_assert_eq!(*labels.get(w3), beta);
+            debug_assert_eq!(*labels.get(w4), gamma);
+            debug_assert_eq!(*labels.get(w5), delta);
+
+            #[cfg(debug_assertions)]
+            {
+                for t in [t0, t1, t2, t3, t4, t5] {
+                    let mut zero_count: usize = 0;
+                    let mut one_count: usize = 0;
+                    let mut alpha_count: usize = 0;
+                    let mut beta_count: usize = 0;
+                    for p in t.points() {
+                        match labels.get(p) {
+                            F4Point::Zero => zero_count += 1,
+                            F4Point::One => one_count += 1,
+                            F4Point::Alpha => alpha_count += 1,
+                            F4Point::Beta => beta_count += 1,
+                        }
+                    }
+                    assert_eq!(zero_count, 1);
+                    assert_eq!(one_count, 1);
+                    assert_eq!(alpha_count, 1);
+                    assert_eq!(beta_count, 1);
+                }
+            }
+
+            OrderedSextetLabelling { sextet, labels }
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    pub enum NearestCodewordsResult {
+        Unique { codeword: Vector, distance: usize },
+        Six { codewords: [Vector; 6] },
+    }
+
+    impl NearestCodewordsResult {
+        pub fn distance(&self) -> usize {
+            match self {
+                NearestCodewordsResult::Unique { distance, .. } => *distance,
+                NearestCodewordsResult::Six { .. } => 4,
+            }
+        }
+    }
+
+    impl BinaryGolayCode {
+        // Correct up to 3 errors using the syndrome of the vector
+        pub fn decode(&self, vector: &Vector) -> DecodeResult {
+            let leaders = self.syndromes.leaders(&self.basis, vector);
+            if let [errors] = leaders {
+                DecodeResult::Corrected {
+                    codeword: vector + errors,
+                    errors: errors.clone(),
+                }
+            } else {
+                let mut codewords: [Vector; 6] = std::array::from_fn(|i| vector + &leaders[i]);
+                codewords.sort_unstable();
+                DecodeResult::Detected { codewords }
+            }
+        }
+
+        // The codeword whose coordinates in the basis are the 12 information bits, bit i for row i
+        pub fn encode(&self, information: usize) -> Vector {
+            let mut codeword = Vector::zero();
+            for (i, row) in self.basis.iter().enumerate() {
+                if information & (1 << i) != 0 {
+                    codeword = &codeword + row;
+                }
+            }
+            codeword
+        }
+
+        // The information bits of a codeword, undoing `encode`
+        pub fn information(&self, codeword: &Vector) -> Result<usize, ()> {
+            if !self.is_codeword(codeword) {
+                return Err(());
+            }
+            /*
+            Row reduce the basis, keeping which basis rows make up each reduced row
+            Then clearing each pivot of the codeword in turn leaves zero, and the rows used make it up
+            */
+            let mut rows = self
+                .basis
+                .iter()
+                .enumerate()
+                .map(|(i, row)| (row.clone(), 1usize << i))
+                .collect::<Vec<_>>();
+            let mut pivots = vec![];
+            for p in Point::points() {
+                let r = pivots.len();
+                let Some(i) = (r..rows.len()).find(|i| rows[*i].0.contains_point(p)) else {
+                    continue;
+                };
+                rows.swap(r, i);
+                for j in r + 1..rows.len() {
+                    if rows[j].0.contains_point(p) {
+                        rows[j] = (&rows[j].0 + &rows[r].0, rows[j].1 ^ rows[r].1);
+                    }
+                }
+                pivots.push(p);
+            }
+            let mut remainder = codeword.clone();
+            let mut information = 0;
+            for ((row, used), p) in rows.iter().zip(pivots) {
+                if remainder.contains_point(p) {
+                    remainder = &remainder + row;
+                    information ^= used;
+                }
+            }
+            Ok(information)
+        }
+
+        // Set the bit at the deleted point so the weight is even, which takes a codeword of the perfect code back to the Golay code
+        pub fn extend_punctured(punctured: Point, vector: &Vector) -> Vector {
+            let mut extended = vector.clone();
+            extended.set(punctured, false);
+            if extended.weight() % 2 == 1 {
+                extended.set(punctured, true);
+            }
+            extended
+        }
+
+        /*
+        Deleting a point from every codeword gives the perfect binary Golay code, a [23, 12, 7] code whose balls of radius 3 fill the space
+        Decode a word of it, ignoring the bit of `vector` at the deleted point, by restoring that bit as the parity bit
+        If there were 1 or 3 errors the parity bit is wrong too, so with 3 the coset has a sextet of leaders and exactly one contains the deleted point
+        Returns the nearest codeword and the errors, both without the deleted point
+        */
+        pub fn decode_punctured(&self, punctured: Point, vector: &Vector) -> (Vector, Vector) {
+            let extended = Self::extend_punctured(punctured, vector);
+            let leaders = self.coset_leaders(&extended);
+            let mut errors = if let [leader] = leaders {
+                leader.clone()
+            } else {
+                leaders
+                    .iter()
+                    .find(|leader| leader.contains_point(punctured))
+                    .unwrap()
+                    .clone()
+            };
+            let mut codeword = &extended + &errors;
+            codeword.set(punctured, false);
+            errors.set(punctured, false);
+            (codeword, errors)
+        }
+
+        // The 12 bit number identifying the coset of the code containing the vector, which is 0 for codewords
+        pub fn syndrome(&self, vector: &Vector) -> usize {
+            syndrome(&self.basis, vector)
+        }
+
+        // The vectors of least weight in the coset of the vector, either one of weight at most 3 or the six foursomes of a sextet
+        pub fn coset_leaders(&self, vector: &Vector) -> &[Vector] {
+            self.syndromes.leaders(&self.basis, vector)
+        }
+
+        // The number of vectors of each weight from 0 to 24 in the coset of the vector
+        pub fn coset_weight_distribution(&self, vector: &Vector) -> Vec<usize> {
+            let mut distribution = vec![0; Point::N + 1];
+            for codeword in &self.codewords {
+                distribution[(vector + codeword).weight()] += 1;
+            }
+            distribution
+        }
+
+        pub fn nearest_codeword(&self, vector: &Vector) -> NearestCodewordsResult {
+            match self.decode(vector) {
+                DecodeResult::Corrected { codeword, errors } => NearestCodewordsResult::Unique {
+                    codeword,
+                    distance: errors.weight(),
+                },
+                DecodeResult::Detected { codewords } => NearestCodewordsResult::Six { codewords },
+            }
+        }
+    }
+
+    #[cfg(feature = "rand")]
+    impl BinaryGolayCode {
+        // A uniformly random element of M24
+        pub fn random_automorphism(&self, rng: &mut impl rand::Rng) -> Permutation<Point> {
+            self.random_automorphism_preserving(rng, &[Vector::from_fn(|_| true)])
+        }
+
+        // A uniformly random element of the stabilizer of an octad, of shape 2^4:A8
+        pub fn random_octad_stabilizer(
+            &self,
+            octad: &Vector,
+            rng: &mut impl rand::Rng,
+        ) -> Result<Permutation<Point>, ()> {
+            if !self.is_octad(octad) {
+                return Err(());
+            }
+            Ok(self.random_automorphism_preserving(rng, &[octad.clone(), self.complement(octad)]))
+        }
+
+        // A uniformly random element of the stabilizer of the sextet containing a foursome, of shape 2^6:3.S6
+        pub fn random_sextet_stabilizer(
+            &self,
+            foursome: &Vector,
+            rng: &mut impl rand::Rng,
+        ) -> Result<Permutation<Point>, ()> {
+            let sextet = self.complete_sextet(foursome)?;
+            Ok(self.random_automorphism_preserving(rng, &sextet))
+        }
+
+        // A uniformly random element of the stabilizer of a trio of disjoint octads, of shape 2^6:(S3 x L3(2))
+        pub fn random_trio_stabilizer(
+            &self,
+            trio: &[Vector; 3],
+            rng: &mut impl rand::Rng,
+        ) -> Result<Permutation<Point>, ()> {
+            if !trio.iter().all(|octad| self.is_octad(octad))
+                || (&(&trio[0] | &trio[1]) | &trio[2]).weight() != Point::N
+            {
+                return Err(());
+            }
+            Ok(self.random_automorphism_preserving(rng, trio))
+        }
+
+        // A uniformly random element of the stabilizer of a pair of points, of shape M22:2
+        pub fn random_duad_stabilizer(
+            &self,
+            duad: &Vector,
+            rng: &mut impl rand::Rng,
+        ) -> Result<Permutation<Point>, ()> {
+            if duad.weight() != 2 {
+                return Err(());
+            }
+            Ok(self.random_automorphism_preserving(rng, &[duad.clone(), self.complement(duad)]))
+        }
+
+        // A uniformly random element fixing each of one or two points, in M23 or M22
+        pub fn random_point_stabilizer(
+            &self,
+            points: &Vector,
+            rng: &mut impl rand::Rng,
+        ) -> Result<Permutation<Point>, ()> {
+            match points.weight() {
+                1 => Ok(self.random_automorphism_preserving(
+                    rng,
+                    &[points.clone(), self.complement(points)],
+                )),
+                2 => loop {
+                    // Half of M22:2 swaps the two points, so try again until both are fixed
+                    let permutation = self.random_duad_stabilizer(points, rng)?;
+                    if points.points().all(|p| *permutation.apply(&p) == p) {
+                        return Ok(permutation);
+                    }
+                },
+                _ => Err(()),
+            }
+        }
+
+        fn complement(&self, vector: &Vector) -> Vector {
+            Vector::from_fn(|p| !vector.contains_point(p))
+        }
+
+        /*
+        A uniformly random automorphism which sends each of the given blocks, which partition the points, to a block of the same size
+
+        Choose the images of the points in order, each uniformly among those which can be extended to such an automorphism
+        Any 5 points lie in a unique octad, so an image can be ruled out as soon as it sends 5 points of an octad outside of an octad
+        This is uniform because every partial assignment which extends does so in the same number of ways
+        */
+        fn random_automorphism_preserving(
+            &self,
+            rng: &mut impl rand::Rng,
+            blocks: &[Vector],
+        ) -> Permutation<Point> {
+            use rand::seq::SliceRandom;
+
+            let block_of = |p: Point| blocks.iter().position(|b| b.contains_point(p)).unwrap();
+            let octads = self.octads();
+
+            fn extend(
+                code: &BinaryGolayCode,
+                rng: &mut impl rand::Rng,
+                blocks: &[Vector],
+                block_of: &impl Fn(Point) -> usize,
+                octads: &[Vector],
+                images: &mut Vec<Point>,
+            ) -> bool {
+                let k = images.len();
+                let Ok(p) = Point::usize_to_point(k) else {
+                    return true;
+                };
+                let assigned = Vector::from_points(Point::points().take(k + 1));
+                let mut candidates = Point::points()
+                    .filter(|c| !images.contains(c))
+                    .collect::<Vec<_>>();
+                candidates.shuffle(rng);
+                for c in candidates {
+                    // The same points must share blocks before and after
+                    if blocks[block_of(p)].weight() != blocks[block_of(c)].weight()
+                        || Point::points().take(k).zip(images.iter()).any(|(q, d)| {
+                            (block_of(p) == block_of(q)) != (block_of(c) == block_of(*d))
+                        })
+                    {
+                        continue;
+                    }
+                    images.push(c);
+                    let image = |q: Point| images[q.point_to_usize()];
+                    let octads_preserved = octads.iter().all(|octad| {
+                        if !octad.contains_point(p) {
+                            return true;
+                        }
+                        let known = octad & &assigned;
+                        if known.weight() < 5 {
+                            return true;
+                        }
+                        let image_octad = code
+                            .complete_octad(&Vector::from_points(known.points().take(5).map(image)))
+                            .unwrap();
+                        known.points().all(|q| image_octad.contains_point(image(q)))
+                    });
+                    if octads_preserved && extend(code, rng, blocks, block_of, octads, images) {
+                        return true;
+                    }
+                    images.pop();
+                }
+                false
+            }
+
+            let mut images = vec![];
+            assert!(extend(self, rng, blocks, &block_of, &octads, &mut images));
+            Permutation::from_fn(|p: Point| images[p.point_to_usize()])
+        }
+    }
+
+    impl Code for BinaryGolayCode {
+        type Word = Vector;
+
+        fn length(&self) -> usize {
+            Point::N
+        }
+
+        fn basis(&self) -> &[Vector] {
+            &self.basis
+        }
+
+        fn is_codeword(&self, vector: &Vector) -> bool {
+            self.syndrome(vector) == 0
+        }
+
+        fn codewords(&self) -> &[Vector] {
+            &self.codewords
+        }
+    }
+
+    impl LinearCode for BinaryGolayCode {}
+
+    // A binary linear code of length 24 on the MOG points
+    pub trait LinearCode: Code<Word = Vector> {
+        // Whether the code is its own dual, which needs dimension 12 and any two rows of the generator matrix to meet evenly
+        fn is_self_dual(&self) -> bool {
+            2 * self.dimension() == Point::N
+                && self.basis().iter().all(|a| {
+                    self.basis()
+                        .iter()
+                        .all(|b| (a & b).weight().is_multiple_of(2))
+                })
+        }
+
+        fn is_automorphism(&self, permutation: &Permutation<Point>) -> bool {
+            self.basis()
+                .iter()
+                .all(|b| self.is_codeword(&b.permute(permutation)))
+        }
+
+        // A codeword of least weight whose image is not a codeword, which is an octad for the Golay code, or None for an automorphism
+        fn automorphism_witness(&self, permutation: &Permutation<Point>) -> Option<Vector> {
+            let mut codewords = self.codewords().to_vec();
+            codewords.sort_by_key(|codeword| codeword.weight());
+            codewords
+                .into_iter()
+                .filter(|codeword| codeword.weight() != 0)
+                .find(|codeword| !self.is_codeword(&codeword.permute(permutation)))
+        }
+    }
+
+    // The outcome of checking a list of permutations against a code, with lines numbered from 1
+    #[derive(Debug, Clone, Default)]
+    pub struct AutomorphismReport {
+        pub automorphisms: usize,
+        // The permutations which are not automorphisms
+        pub failures: Vec<(usize, Permutation<Point>)>,
+        // The lines which could not be read as permutations, and why
+        pub parse_errors: Vec<(usize, String)>,
+    }
+
+    impl AutomorphismReport {
+        pub fn permutations(&self) -> usize {
+            self.automorphisms + self.failures.len()
+        }
+    }
+
+    /// Check each permutation of a list against `is_automorphism`, e.g. generators for a subgroup of M24 found elsewhere
+    /// There is one permutation per line in cycle or one-line notation with points numbered from `first`
+    /// Blank lines and lines starting with `#` are skipped
+    pub fn verify_automorphisms(
+        code: &dyn LinearCode,
+        text: &str,
+        first: usize,
+    ) -> AutomorphismReport {
+        let mut report = AutomorphismReport::default();
+        for (line_idx, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match Permutation::parse_any(line, first) {
+                Ok(permutation) if code.is_automorphism(&permutation) => report.automorphisms += 1,
+                Ok(permutation) => report.failures.push((line_idx + 1, permutation)),
+                Err(err) => report.parse_errors.push((line_idx + 1, err)),
+            }
+        }
+        report
+    }
+
+    // Every sum of basis vectors, in increasing order without repeats
+    fn span(basis: &[Vector]) -> Vec<Vector> {
+        let mut codewords = vec![];
+        for b in 0usize..(1 << basis.len()) {
+            let mut codeword = Vector::zero();
+            #[allow(clippy::needless_range_loop)]
+            for i in 0..basis.len() {
+                if b & (1usize << i) != 0 {
+                    codeword = &codeword + &basis[i];
+                }
+            }
+            codewords.push(codeword);
+        }
+        codewords.sort_unstable();
+        codewords.dedup();
+        codewords
+    }
+
+    // A [24, 12] binary code given by a user supplied generator matrix
+    #[derive(Debug)]
+    pub struct BinaryCode {
+        basis: Vec<Vector>,
+        // All codewords in increasing order
+        codewords: Vec<Vector>,
+    }
+
+    impl BinaryCode {
+        pub fn from_generator_matrix(rows: Vec<Vector>) -> Result<Self, String> {
+            if rows.len() != 12 {
+                return Err(format!(
+                    "A generator matrix needs 12 rows but {} were given",
+                    rows.len()
+                ));
+            }
+            let codewords = span(&rows);
+            if codewords.len() != 1usize << rows.len() {
+                return Err("The rows of the generator matrix are not linearly independent".into());
+            }
+            Ok(Self {
+                basis: rows,
+                codewords,
+            })
+        }
+
+        /// Parse a generator matrix with one row of 24 binary digits per line
+        /// Spaces within a row, blank lines and lines starting with `#` are ignored
+        pub fn parse_generator_matrix(text: &str) -> Result<Self, String> {
+            let mut rows = vec![];
+            for (line_idx, line) in text.lines().enumerate() {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with('#') {
+                    continue;
+                }
+                let digits = line
+                    .chars()
+                    .filter(|c| !c.is_whitespace() && *c != ',')
+                    .collect::<String>();
+                if digits.len() != Point::N || !digits.chars().all(|c| c == '0' || c == '1') {
+                    return Err(format!(
+                        "line {}: expected a row of 24 binary digits",
+                        line_idx + 1
+                    ));
+                }
+                rows.push(Vector::parse(&digits)?);
+            }
+            Self::from_generator_matrix(rows)
+        }
+    }
+
+    impl Code for BinaryCode {
+        type Word = Vector;
+
+        fn length(&self) -> usize {
+            Point::N
+        }
+
+        fn basis(&self) -> &[Vector] {
+            &self.basis
+        }
+
+        fn is_codeword(&self, vector: &Vector) -> bool {
+            self.codewords.binary_search(vector).is_ok()
+        }
+
+        fn codewords(&self) -> &[Vector] {
+            &self.codewords
+        }
+    }
+
+    impl LinearCode for BinaryCode {}
+}
+
+pub mod m24 {
+    use super::{
+        group::PermutationGroup,
+        linear_code::Code,
+        miracle_octad_generator::{BinaryGolayCode, LinearCode, Point, Vector},
+        permutation::Permutation,
+        psl2_23,
+        traits::Enumerated,
+    };
+
+    pub const M24_ORDER: u128 = 244823040;
+
+    /*
+    Conjugacy classes of M24
+    Most classes are determined by their cycle type on the 24 points
+    The exceptions are the pairs 7AB, 14AB, 15AB, 21AB and 23AB where each element is in the other class from its inverse
+    The ATLAS only tells these apart by irrational character values, so which class of each pair is called A is a convention
+    Here 7A, 15A and 23A are the classes of the reference elements below
+    and 14A and 21A are the classes whose elements have a power in 7A
+    */
+    const REFERENCE_7A: &str = "(0 16 10 8 19 15 22)(2 13 3 6 20 7 21)(4 11 18 5 14 9 17)";
+    const REFERENCE_15A: &str = "(0 14 12 6 21 8 17 3 15 10 16 5 23 7 2)(1 11 9 18 22)(13 19 20)";
+    const REFERENCE_23A: &str = "(0 6 16 1 17 14 8 11 13 5 2 21 20 10 3 22 15 23 12 19 7 18 4)";
+
+    // The ATLAS name of the conjugacy class of an automorphism of the Golay code e.g. `2A`, `12B`
+    pub fn conjugacy_class(
+        code: &BinaryGolayCode,
+        permutation: &Permutation<Point>,
+    ) -> Result<&'static str, ()> {
+        if !code.is_automorphism(permutation) {
+            return Err(());
+        }
+        let reference = |s: &str| Permutation::parse_cycles(s).unwrap();
+        let in_class_a = |reference_a: &str, element: &Permutation<Point>| {
+            are_conjugate(code, element, &reference(reference_a))
+        };
+        let power = |n: usize| {
+            let mut result = Permutation::identity();
+            for _ in 0..n {
+                result = &result * permutation;
+            }
+            result
+        };
+        let pick = |a: bool, names: (&'static str, &'static str)| if a { names.0 } else { names.1 };
+        Ok(match permutation.cycle_type().as_slice() {
+            [1, ..] => "1A",
+            [2, 2, 2, 2, 2, 2, 2, 2, 1, 1, 1, 1, 1, 1, 1, 1] => "2A",
+            [2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2] => "2B",
+            [3, 3, 3, 3, 3, 3, 1, 1, 1, 1, 1, 1] => "3A",
+            [3, 3, 3, 3, 3, 3, 3, 3] => "3B",
+            [4, 4, 4, 4, 2, 2, 2, 2] => "4A",
+            [4, 4, 4, 4, 2, 2, 1, 1, 1, 1] => "4B",
+            [4, 4, 4, 4, 4, 4] => "4C",
+            [5, 5, 5, 5, 1, 1, 1, 1] => "5A",
+            [6, 6, 3, 3, 2, 2, 1, 1] => "6A",
+            [6, 6, 6, 6] => "6B",
+            [7, 7, 7, 1, 1, 1] => pick(in_class_a(REFERENCE_7A, permutation), ("7A", "7B")),
+            [8, 8, 4, 2, 1, 1] => "8A",
+            [10, 10, 2, 2] => "10A",
+            [11, 11, 1, 1] => "11A",
+            [12, 6, 4, 2] => "12A",
+            [12, 12] => "12B",
+            [14, 7, 2, 1] => pick(in_class_a(REFERENCE_7A, &power(2)), ("14A", "14B")),
+            [15, 5, 3, 1] => pick(in_class_a(REFERENCE_15A, permutation), ("15A", "15B")),
+            [21, 3] => pick(in_class_a(REFERENCE_7A, &power(3)), ("21A", "21B")),
+            [23, 1] => pick(in_class_a(REFERENCE_23A, permutation), ("23A", "23B")),
+            _ => unreachable!(),
+        })
+    }
+
+    // Whether there is an automorphism h of the code with h x h^-1 = y
+    // Such an h sends each cycle of x to a cycle of y of the same length, so try every way of matching up the cycles
+    pub fn are_conjugate(
+        code: &BinaryGolayCode,
+        x: &Permutation<Point>,
+        y: &Permutation<Point>,
+    ) -> bool {
+        // The cycles including fixed points, as lists of points in the MOG numbering
+        let cycles = |permutation: &Permutation<Point>| {
+            let mut cycles = permutation
+                .disjoint_cycles()
+                .into_iter()
+                .map(|cycle| cycle.into_iter().map(|p| p.point_to_usize()).collect())
+                .collect::<Vec<Vec<usize>>>();
+            cycles.extend(
+                permutation
+                    .fixed_points()
+                    .into_iter()
+                    .map(|p| vec![p.point_to_usize()]),
+            );
+            cycles
+        };
+        let (x_cycles, y_cycles) = (cycles(x), cycles(y));
+        if x.cycle_type() != y.cycle_type() {
+            return false;
+        }
+
+        fn search(
+            code: &BinaryGolayCode,
+            x_cycles: &[Vec<usize>],
+            y_cycles: &[Vec<usize>],
+            used: &mut Vec<bool>,
+            h: &mut [usize; 24],
+        ) -> bool {
+            let Some(x_cycle) = x_cycles.first() else {
+                let h = h.map(|i| Point::usize_to_point(i).unwrap());
+                return code.basis().iter().all(|b| {
+                    code.is_codeword(&Vector::from_points(
+                        b.points().map(|p| h[p.point_to_usize()]),
+                    ))
+                });
+            };
+            let n = x_cycle.len();
+            for (j, y_cycle) in y_cycles.iter().enumerate() {
+                if used[j] || y_cycle.len() != n {
+                    continue;
+                }
+                used[j] = true;
+                for offset in 0..n {
+                    for k in 0..n {
+                        h[x_cycle[k]] = y_cycle[(k + offset) % n];
+                    }
+                    if search(code, &x_cycles[1..], y_cycles, used, h) {
+                        return true;
+                    }
+                }
+                used[j] = false;
+            }
+            false
+        }
+
+        search(
+            code,
+            &x_cycles,
+            &y_cycles,
+            &mut vec![false; y_cycles.len()],
+            &mut [0; 24],
+        )
+    }
+
+    /*
+    Writing automorphisms as words in a fixed set of generators
+    The generators are Conway's α: x -> x+1, β: x -> 2x, γ: x -> -1/x generating PSL(2, 23) on the labels of `psl2_23`
+    together with δ: x -> x^3/9 for x a nonzero square and x -> 9x^3 for x a non-square, fixing 0 and ∞
+    */
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Generator {
+        Alpha,
+        Beta,
+        Gamma,
+        Delta,
+    }
+
+    impl Generator {
+        pub const ALL: [Generator; 4] = [
+            Generator::Alpha,
+            Generator::Beta,
+            Generator::Gamma,
+            Generator::Delta,
+        ];
+
+        pub fn name(&self) -> &'static str {
+            match self {
+                Generator::Alpha => "α",
+                Generator::Beta => "β",
+                Generator::Gamma => "γ",
+                Generator::Delta => "δ",
+            }
+        }
+
+        pub fn order(&self) -> usize {
+            match self {
+                Generator::Alpha => 23,
+                Generator::Beta => 11,
+                Generator::Gamma => 2,
+                Generator::Delta => 5,
+            }
+        }
+
+        pub fn permutation(&self) -> Permutation<Point> {
+            match self {
+                Generator::Alpha => psl2_23::translation(),
+                Generator::Beta => psl2_23::doubling(),
+                Generator::Gamma => psl2_23::negative_reciprocal(),
+                Generator::Delta => {
+                    let cube = |x: usize| x * x % 23 * x % 23;
+                    // 9 * 18 = 1 mod 23
+                    psl2_23::from_label_map(|x| match x {
+                        0 | psl2_23::INFINITY => x,
+                        x if (1..23).any(|y| y * y % 23 == x) => 18 * cube(x) % 23,
+                        x => 9 * cube(x) % 23,
+                    })
+                }
+            }
+        }
+    }
+
+    /*
+    An automorphism of the code taking one set of points onto another, or why there is none
+    M24 is 5-transitive so one always exists for sets of up to 5 points, and otherwise some invariants are compared first
+    The search runs through the stabilizer chain of a base starting with the points of the smaller of the set and its complement
+    */
+    pub fn set_map(
+        code: &BinaryGolayCode,
+        from: &Vector,
+        to: &Vector,
+    ) -> Result<Permutation<Point>, &'static str> {
+        if from.weight() != to.weight() {
+            return Err("The sets are of different sizes");
+        }
+        if code.is_codeword(from) != code.is_codeword(to) {
+            return Err("One set is a codeword and the other is not");
+        }
+        if code.coset_leaders(from)[0].weight() != code.coset_leaders(to)[0].weight() {
+            return Err("The sets are at different distances from the code");
+        }
+        let octads_in = |set: &Vector| {
+            code.octads()
+                .iter()
+                .filter(|octad| &(*octad & set) == *octad)
+                .count()
+        };
+        if octads_in(from) != octads_in(to) {
+            return Err("The sets contain different numbers of octads");
+        }
+
+        // A map between the complements is a map between the sets
+        let (from, to) = if from.weight() > 12 {
+            let all = Vector::from_fn(|_| true);
+            (from + &all, to + &all)
+        } else {
+            (from.clone(), to.clone())
+        };
+        PermutationGroup::generated_by_with_base(
+            &Generator::ALL.map(|g| g.permutation()),
+            &from.points().collect::<Vec<_>>(),
+        )
+        .set_map(&to.points().collect::<Vec<_>>())
+        .ok_or("No automorphism takes one set to the other")
+    }
+
+    // A generator or its inverse, with words composed left to right like `Permutation`
+    type Word = Vec<(Generator, bool)>;
+    // A word together with the images of the points under it
+    type Entry = (Word, Vec<usize>);
+
+    fn inverse_word(word: &Word) -> Word {
+        word.iter()
+            .rev()
+            .map(|(g, inverse)| (*g, !inverse))
+            .collect()
+    }
+
+    // Words longer than this are thrown away while filling in the table
+    const MAX_WORD_LENGTH: usize = 48;
+
+    /*
+    Minkwitz's table of short words for a stabilizer chain of M24
+    Entry j at level i is a word for an element fixing the first i base points and taking base point i to point j
+    Words are found by sifting short products of the generators and then products of the entries found, keeping the shortest
+    */
+    pub struct FactorizationTable {
+        base: Vec<usize>,
+        generators: Vec<Vec<usize>>,
+        entries: Vec<Vec<Option<Entry>>>,
+    }
+
+    impl Default for FactorizationTable {
+        fn default() -> Self {
+            let generators = Generator::ALL
+                .iter()
+                .map(|g| {
+                    let permutation = g.permutation();
+                    Point::points()
+                        .map(|p| permutation.apply(&p).point_to_usize())
+                        .collect()
+                })
+                .collect::<Vec<Vec<usize>>>();
+            let base = PermutationGroup::generated_by(&Generator::ALL.map(|g| g.permutation()))
+                .base()
+                .into_iter()
+                .map(|p| p.point_to_usize())
+                .collect::<Vec<_>>();
+            let mut entries = vec![vec![None; 24]; base.len()];
+            for (i, b) in base.iter().enumerate() {
+                entries[i][*b] = Some((vec![], (0..24).collect()));
+            }
+            let mut table = Self {
+                base,
+                generators,
+                entries,
+            };
+
+            // Every word up to a few letters
+            let letters = Generator::ALL
+                .iter()
+                .flat_map(|g| [(*g, false), (*g, true)])
+                .collect::<Vec<_>>();
+            let mut words: Vec<Word> = vec![vec![]];
+            for _ in 0..5 {
+                words = words
+                    .iter()
+                    .flat_map(|word| {
+                        letters
+                            .iter()
+                            .filter(|letter| word.last() != Some(&(letter.0, !letter.1)))
+                            .map(|letter| {
+                                let mut word = word.clone();
+                                word.push(*letter);
+                                word
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                    .collect();
+                for word in &words {
+                    let images = table.word_images(word);
+                    table.sift(word.clone(), images);
+                }
+            }
+
+            // Then products of pairs of entries until every entry is filled
+            // Stop early if a pass fills nothing new, leaving `factorize` to fail on the missing entries
+            while !table.is_complete() {
+                let filled = table.filled();
+                let found = table
+                    .entries
+                    .iter()
+                    .flatten()
+                    .flatten()
+                    .cloned()
+                    .collect::<Vec<_>>();
+                for (a, a_images) in &found {
+                    for (b, b_images) in &found {
+                        if a.len() + b.len() <= MAX_WORD_LENGTH {
+                            let mut word = a.clone();
+                            word.extend(b);
+                            table.sift(word, compose(a_images, b_images));
+                        }
+                    }
+                }
+                if table.filled() == filled {
+                    break;
+                }
+            }
+            table
+        }
+    }
+
+    fn compose(a: &[usize], b: &[usize]) -> Vec<usize> {
+        a.iter().map(|i| b[*i]).collect()
+    }
+
+    fn invert(a: &[usize]) -> Vec<usize> {
+        let mut inverse = vec![0; a.len()];
+        for (i, j) in a.iter().enumerate() {
+            inverse[*j] = i;
+        }
+        inverse
+    }
+
+    impl FactorizationTable {
+        fn word_images(&self, word: &Word) -> Vec<usize> {
+            word.iter().fold((0..24).collect(), |images, (g, inverse)| {
+                let generator = &self.generators[*g as usize];
+                if *inverse {
+                    compose(&images, &invert(generator))
+                } else {
+                    compose(&images, generator)
+                }
+            })
+        }
+
+        fn filled(&self) -> usize {
+            self.entries.iter().flatten().flatten().count()
+        }
+
+        fn is_complete(&self) -> bool {
+            self.entries
+                .iter()
+                .map(|level| level.iter().filter(|entry| entry.is_some()).count() as u128)
+                .product::<u128>()
+                == M24_ORDER
+        }
+
+        // Keep the word wherever it is shorter than the entry it meets, carrying on with what is left of the longer one
+        fn sift(&mut self, mut word: Word, mut images: Vec<usize>) {
+            for i in 0..self.base.len() {
+                let j = images[self.base[i]];
+                let entry = &mut self.entries[i][j];
+                match entry {
+                    None => {
+                        *entry = Some((word, images));
+                        return;
+                    }
+                    Some((entry_word, entry_images)) => {
+                        if word.len() < entry_word.len() {
+                            std::mem::swap(&mut word, entry_word);
+                            std::mem::swap(&mut images, entry_images);
+                        }
+                        word.extend(inverse_word(entry_word));
+                        images = compose(&images, &invert(entry_images));
+                        if word.len() > MAX_WORD_LENGTH {
+                            return;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // Write an automorphism of the code as a word in the generators, composed left to right like `Permutation`
+    // Inverses are written as powers, so e.g. α^-1 is 22 copies of α
+    pub fn factorize(
+        table: &FactorizationTable,
+        permutation: &Permutation<Point>,
+    ) -> Result<Vec<Generator>, ()> {
+        let mut images = Point::points()
+            .map(|p| permutation.apply(&p).point_to_usize())
+            .collect::<Vec<_>>();
+        let mut factors = vec![];
+        for i in 0..table.base.len() {
+            let Some((word, entry_images)) = &table.entries[i][images[table.base[i]]] else {
+                return Err(());
+            };
+            images = compose(&images, &invert(entry_images));
+            factors.push(word);
+        }
+        if images.iter().enumerate().any(|(i, j)| i != *j) {
+            return Err(());
+        }
+
+        // The permutation is the last entry used followed by the others in turn back to the first
+        // Cancel each letter against its inverse, then write inverses as powers
+        let mut letters: Word = vec![];
+        for letter in factors.into_iter().rev().flatten() {
+            if letters.last() == Some(&(letter.0, !letter.1)) {
+                letters.pop();
+            } else {
+                letters.push(*letter);
+            }
+        }
+        let mut word = vec![];
+        for (g, inverse) in letters {
+            let copies = if inverse { g.order() - 1 } else { 1 };
+            word.extend(std::iter::repeat_n(g, copies));
+        }
+        Ok(reduce(&word))
+    }
+
+    // Take runs of the same generator modulo its order
+    fn reduce(word: &[Generator]) -> Vec<Generator> {
+        let mut runs: Vec<(Generator, usize)> = vec![];
+        for g in word {
+            match runs.last_mut() {
+                Some((h, count)) if h == g => *count += 1,
+                _ => runs.push((*g, 1)),
+            }
+            let (g, count) = *runs.last().unwrap();
+            if count % g.order() == 0 {
+                runs.pop();
+            }
+        }
+        runs.into_iter()
+            .flat_map(|(g, count)| std::iter::repeat_n(g, count))
+            .collect()
+    }
+
+    // e.g. `α^-1 β γ δ^2`, with runs written as powers and the longer half of each power written as a negative power
+    pub fn word_string(word: &[Generator]) -> String {
+        let mut runs: Vec<(Generator, usize)> = vec![];
+        for g in word {
+            match runs.last_mut() {
+                Some((h, count)) if h == g => *count += 1,
+                _ => runs.push((*g, 1)),
+            }
+        }
+        if runs.is_empty() {
+            return "1".to_string();
+        }
+        runs.into_iter()
+            .map(|(g, count)| {
+                let order = g.order();
+                let power = if 2 * count > order {
+                    -((order - count) as isize)
+                } else {
+                    count as isize
+                };
+                match power {
+                    1 => g.name().to_string(),
+                    power => format!("{}^{power}", g.name()),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+pub mod psl2_23 {
+    use super::{miracle_octad_generator::Point, permutation::Permutation, traits::Enumerated};
+
+    // The points of the MOG labelled by the projective line over F23, with 23 standing for ∞
+    // PSL(2, 23) acts on the labels by Möbius transformations, and is a subgroup of M24 acting this way
+    // These are the labels the generators below and `m24::Generator` are written in
+    pub const INFINITY: usize = 23;
+
+    const LABELS: [usize; 24] = [
+        23, 0, 11, 1, 22, 2, //
+        9, 5, 13, 21, 12, 7, //
+        6, 15, 16, 14, 8, 17, //
+        3, 19, 4, 20, 18, 10, //
+    ];
+
+    pub fn label(point: Point) -> usize {
+        LABELS[point.point_to_usize()]
+    }
+
+    pub fn point(label: usize) -> Point {
+        let i = LABELS.iter().position(|l| *l == label).unwrap();
+        Point::usize_to_point(i).unwrap()
+    }
+
+    // e.g. `∞`, `0`, `22`
+    pub fn label_string(point: Point) -> String {
+        label_text(label(point))
+    }
+
+    fn label_text(label: usize) -> String {
+        match label {
+            INFINITY => "∞".to_string(),
+            x => x.to_string(),
+        }
+    }
+
+    /*
+    Curtis's labelling of the MOG by the projective line, as printed in Conway and Sloane, SPLAG chapter 11
+        0  ∞ |  1 11 |  2 22
+       19  3 | 20  4 | 10 18
+       15  6 | 14 16 | 17  8
+        5  9 | 21 13 |  7 12
+    The same Möbius transformations act on it by automorphisms, but it is a different labelling from the one above
+    */
+    pub const CURTIS_LABELS: [usize; 24] = [
+        0, 23, 1, 11, 2, 22, //
+        19, 3, 20, 4, 10, 18, //
+        15, 6, 14, 16, 17, 8, //
+        5, 9, 21, 13, 7, 12, //
+    ];
+
+    pub fn curtis_label_string(point: Point) -> String {
+        label_text(CURTIS_LABELS[point.point_to_usize()])
+    }
+
+    fn inverse(x: usize) -> usize {
+        (1..23).find(|y| x * y % 23 == 1).unwrap()
+    }
+
+    // The permutation of the MOG acting on the labels by `f`
+    pub(super) fn from_label_map(f: impl Fn(usize) -> usize) -> Permutation<Point> {
+        Permutation::try_from(
+            Point::points()
+                .map(|p| (p, point(f(label(p)))))
+                .filter(|(p, q)| p != q)
+                .collect::<Vec<_>>(),
+        )
+        .unwrap()
+    }
+
+    // x -> x+1, of order 23
+    pub fn translation() -> Permutation<Point> {
+        from_label_map(|x| if x == INFINITY { x } else { (x + 1) % 23 })
+    }
+
+    // x -> 2x, of order 11 since 2 is a square mod 23
+    pub fn doubling() -> Permutation<Point> {
+        from_label_map(|x| if x == INFINITY { x } else { 2 * x % 23 })
+    }
+
+    // x -> -1/x, an involution swapping 0 and ∞
+    pub fn negative_reciprocal() -> Permutation<Point> {
+        from_label_map(|x| match x {
+            INFINITY => 0,
+            0 => INFINITY,
+            x => 23 - inverse(x),
+        })
+    }
+}
+
+pub mod leech {
+    use super::{
+        linear_code::Code,
+        miracle_octad_generator::{BinaryGolayCode, Point, Vector as CodeVector},
+        traits::{Enumerated, Labelled},
+    };
+
+    /*
+    A vector of the Leech lattice in the coordinates of Conway, which are √8 times the usual ones so that they are integers
+    x is in the lattice when its coordinates are all congruent to m mod 2, they sum to 4m mod 8,
+    and for each residue the coordinates congruent to it mod 4 lie on a Golay codeword
+    */
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Vector {
+        coordinates: Labelled<Point, i32>,
+    }
+
+    impl Vector {
+        pub fn from_fn(coordinates: impl Fn(Point) -> i32) -> Self {
+            Self {
+                coordinates: Labelled::from_fn(coordinates),
+            }
+        }
+
+        pub fn coordinate(&self, p: Point) -> i32 {
+            *self.coordinates.get(p)
+        }
+
+        // ±4 on two points, a type 2 vector of shape (4^2 0^22)
+        pub fn from_pair(
+            p: Point,
+            q: Point,
+            p_negative: bool,
+            q_negative: bool,
+        ) -> Result<Self, ()> {
+            if p == q {
+                return Err(());
+            }
+            let sign = |negative: bool| if negative { -4 } else { 4 };
+            Ok(Self::from_fn(|r| {
+                if r == p {
+                    sign(p_negative)
+                } else if r == q {
+                    sign(q_negative)
+                } else {
+                    0
+                }
+            }))
+        }
+
+        // ±2 on an octad or a dodecad, negative on an even number of its points
+        // Octads give type 2 vectors of shape (2^8 0^16) and dodecads type 3 vectors of shape (2^12 0^12)
+        pub fn from_codeword(
+            code: &BinaryGolayCode,
+            codeword: &CodeVector,
+            negative: &CodeVector,
+        ) -> Result<Self, ()> {
+            if !(code.is_octad(codeword) || code.is_dodecad(codeword))
+                || !codeword.contains(negative)
+                || !negative.weight().is_multiple_of(2)
+            {
+                return Err(());
+            }
+            Ok(Self::from_fn(|p| {
+                if negative.contains_point(p) {
+                    -2
+                } else if codeword.contains_point(p) {
+                    2
+                } else {
+                    0
+                }
+            }))
+        }
+
+        /*
+        ±1 everywhere, positive exactly on a codeword, with an odd number of points changed by ∓4 so they become ∓3
+        One changed point gives a type 2 vector of shape (3 1^23) and three give a type 3 vector of shape (3^3 1^21)
+        The weight 24 word with one changed point gives (-3 1^23)
+        */
+        pub fn from_odd_codeword(
+            code: &BinaryGolayCode,
+            codeword: &CodeVector,
+            changed: &CodeVector,
+        ) -> Result<Self, ()> {
+            if !code.is_codeword(codeword) || changed.weight().is_multiple_of(2) {
+                return Err(());
+            }
+            Ok(Self::from_fn(|p| {
+                let x = if codeword.contains_point(p) { 1 } else { -1 };
+                if changed.contains_point(p) { -3 * x } else { x }
+            }))
+        }
+
+        pub fn inner_product(&self, other: &Self) -> i32 {
+            Point::points()
+                .map(|p| self.coordinate(p) * other.coordinate(p))
+                .sum::<i32>()
+                / 8
+        }
+
+        // The norm in the usual scaling, where the minimal vectors have norm 4
+        pub fn norm(&self) -> i32 {
+            self.inner_product(self)
+        }
+
+        // Half the norm, so the minimal vectors have type 2
+        pub fn vector_type(&self) -> i32 {
+            self.norm() / 2
+        }
+
+        pub fn is_in_lattice(&self, code: &BinaryGolayCode) -> bool {
+            let m = self
+                .coordinate(Point::usize_to_point(0).unwrap())
+                .rem_euclid(2);
+            if Point::points().any(|p| self.coordinate(p).rem_euclid(2) != m) {
+                return false;
+            }
+            let sum = Point::points().map(|p| self.coordinate(p)).sum::<i32>();
+            if sum.rem_euclid(8) != 4 * m {
+                return false;
+            }
+            (0..4).all(|residue| {
+                code.is_codeword(&CodeVector::from_fn(|p| {
+                    self.coordinate(p).rem_euclid(4) == residue
+                }))
+            })
+        }
+
+        // The absolute values of the coordinates with their multiplicities, e.g. `(2^8 0^16)`
+        pub fn shape(&self) -> String {
+            let mut values = Point::points()
+                .map(|p| self.coordinate(p).abs())
+                .collect::<Vec<_>>();
+            values.sort_unstable_by(|a, b| b.cmp(a));
+            let mut parts = vec![];
+            for value in values.iter().copied() {
+                match parts.last_mut() {
+                    Some((v, count)) if *v == value => *count += 1,
+                    _ => parts.push((value, 1)),
+                }
+            }
+            format!(
+                "({})",
+                parts
+                    .into_iter()
+                    .map(|(value, count)| if count == 1 {
+                        value.to_string()
+                    } else {
+                        format!("{value}^{count}")
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            )
+        }
+    }
+
+    // The Leech lattice vectors of type 2 or 3 and one shape coming from a Golay codeword, with an example
+    #[derive(Debug, Clone)]
+    pub struct VectorClass {
+        pub count: usize,
+        pub example: Vector,
+    }
+
+    /*
+    The vectors of type 2 and 3 whose coordinates are built on a codeword
+    The even vectors are ±2 on an octad or a dodecad, or ±4 on two points for the zero word,
+    and every codeword gives odd vectors by taking its points to be the +1 coordinates
+    Over all codewords there are 196560 vectors of type 2 and 16773120 of type 3
+    */
+    pub fn vectors_from_codeword(
+        code: &BinaryGolayCode,
+        codeword: &CodeVector,
+    ) -> Vec<VectorClass> {
+        if !code.is_codeword(codeword) {
+            return vec![];
+        }
+        let points = codeword.points().collect::<Vec<_>>();
+        let off = Point::points()
+            .filter(|p| !codeword.contains_point(*p))
+            .collect::<Vec<_>>();
+        let n = Point::N;
+        let mut classes = vec![];
+
+        match codeword.weight() {
+            0 => classes.push(VectorClass {
+                count: 4 * n * (n - 1) / 2,
+                example: Vector::from_pair(off[0], off[1], false, false).unwrap(),
+            }),
+            8 => {
+                classes.push(VectorClass {
+                    count: 1 << 7,
+                    example: Vector::from_codeword(code, codeword, &CodeVector::zero()).unwrap(),
+                });
+                // A type 3 vector of shape (4 2^8 0^15), with ±4 on one of the other 16 points
+                let mut example =
+                    Vector::from_codeword(code, codeword, &CodeVector::zero()).unwrap();
+                example.coordinates.set(off[0], 4);
+                example.coordinates.set(points[0], -2);
+                classes.push(VectorClass {
+                    count: off.len() << 8,
+                    example,
+                });
+            }
+            12 => classes.push(VectorClass {
+                count: 1 << 11,
+                example: Vector::from_codeword(code, codeword, &CodeVector::zero()).unwrap(),
+            }),
+            _ => {}
+        }
+
+        let first = Point::usize_to_point(0).unwrap();
+        classes.push(VectorClass {
+            count: n,
+            example: Vector::from_odd_codeword(
+                code,
+                codeword,
+                &CodeVector::from_points([first].into_iter()),
+            )
+            .unwrap(),
+        });
+        classes.push(VectorClass {
+            count: n * (n - 1) * (n - 2) / 6,
+            example: Vector::from_odd_codeword(
+                code,
+                codeword,
+                &CodeVector::from_points(Point::points().take(3)),
+            )
+            .unwrap(),
+        });
+        // A type 3 vector of shape (5 1^23), with one coordinate changed by ±4 the other way
+        let mut example = Vector::from_fn(|p| if codeword.contains_point(p) { 1 } else { -1 });
+        let x = example.coordinate(first);
+        example.coordinates.set(first, 5 * x);
+        classes.push(VectorClass { count: n, example });
+
+        classes
+    }
+}
+
+pub mod linear_code {
+    use super::miracle_octad_generator::{self, LinearCode, Point};
+    use super::traits::Enumerated;
+    use std::collections::HashSet;
+    use std::ops::{Add, BitAnd};
+
+    // Codes are enumerated in full, so the dimension is kept small enough to do that every frame
+    pub const MAX_LENGTH: usize = 64;
+    pub const MAX_DIMENSION: usize = 16;
+
+    // A binary word, a subset of the points of a code
+    pub trait Word: Clone {
+        fn weight(&self) -> usize;
+
+        // The number of points where the words differ
+        fn distance(&self, other: &Self) -> usize;
+    }
+
+    // A binary linear code of any length
+    // `LinearCode` extends this with what only makes sense on the 24 points of the MOG
+    pub trait Code {
+        type Word: Word;
+
+        fn length(&self) -> usize;
+
+        // The rows of a generator matrix
+        fn basis(&self) -> &[Self::Word];
+
+        fn is_codeword(&self, vector: &Self::Word) -> bool;
+
+        // All codewords in increasing order
+        fn codewords(&self) -> &[Self::Word];
+
+        fn dimension(&self) -> usize {
+            self.basis().len()
+        }
+
+        fn minimum_distance(&self) -> usize {
+            self.codewords()
+                .iter()
+                .map(|codeword| codeword.weight())
+                .filter(|w| *w != 0)
+                .min()
+                .unwrap_or(0)
+        }
+
+        // The number of codewords of each weight from 0 to the length, which is 1, 759, 2576, 759, 1 at 0, 8, 12, 16, 24 for the Golay code
+        fn weight_distribution(&self) -> Vec<usize> {
+            let mut distribution = vec![0; self.length() + 1];
+            for codeword in self.codewords() {
+                distribution[codeword.weight()] += 1;
+            }
+            distribution
+        }
+
+        // The distance to the nearest codewords and all codewords at that distance
+        fn nearest_codewords(&self, vector: &Self::Word) -> (usize, Vec<Self::Word>) {
+            let mut distance = usize::MAX;
+            let mut nearest = vec![];
+            for codeword in self.codewords() {
+                let d = vector.distance(codeword);
+                if d < distance {
+                    distance = d;
+                    nearest.clear();
+                }
+                if d == distance {
+                    nearest.push(codeword.clone());
+                }
+            }
+            (distance, nearest)
+        }
+    }
+
+    impl Word for miracle_octad_generator::Vector {
+        fn weight(&self) -> usize {
+            self.weight()
+        }
+
+        fn distance(&self, other: &Self) -> usize {
+            (self + other).weight()
+        }
+    }
+
+    // A word of a code of length at most 64, with bit i set when point i is in the support
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+    pub struct Vector {
+        bits: u64,
+    }
+
+    impl Vector {
+        pub fn zero() -> Self {
+            Self { bits: 0 }
+        }
+
+        pub fn from_points(points: impl Iterator<Item = usize>) -> Self {
+            let mut vector = Self::zero();
+            for i in points {
+                vector.set(i, true);
+            }
+            vector
+        }
+
+        pub fn contains_point(&self, i: usize) -> bool {
+            self.bits >> i & 1 == 1
+        }
+
+        pub fn set(&mut self, i: usize, value: bool) {
+            if value {
+                self.bits |= 1 << i;
+            } else {
+                self.bits &= !(1 << i);
+            }
+        }
+
+        pub fn toggle(&mut self, i: usize) {
+            self.bits ^= 1 << i;
+        }
+
+        pub fn weight(&self) -> usize {
+            self.bits.count_ones() as usize
+        }
+
+        pub fn points(&self) -> impl Iterator<Item = usize> {
+            let bits = self.bits;
+            (0..MAX_LENGTH).filter(move |i| bits >> i & 1 == 1)
+        }
+
+        // e.g. `{0, 3, 7}`
+        pub fn to_point_list(&self) -> String {
+            format!(
+                "{{{}}}",
+                self.points()
+                    .map(|i| i.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        }
+    }
+
+    impl BitAnd for Vector {
+        type Output = Self;
+
+        fn bitand(self, other: Self) -> Self {
+            Self {
+                bits: self.bits & other.bits,
+            }
+        }
+    }
+
+    impl Add for Vector {
+        type Output = Self;
+
+        // Addition over GF(2) is XOR
+        #[allow(clippy::suspicious_arithmetic_impl)]
+        fn add(self, other: Self) -> Self {
+            Self {
+                bits: self.bits ^ other.bits,
+            }
+        }
+    }
+
+    impl Word for Vector {
+        fn weight(&self) -> usize {
+            self.weight()
+        }
+
+        fn distance(&self, other: &Self) -> usize {
+            (*self + *other).weight()
+        }
+    }
+
+    // A binary linear code of any length given by a k x n generator matrix, such as a shortened Golay code or a Reed-Muller code
+    #[derive(Debug)]
+    pub struct BinaryLinearCode {
+        length: usize,
+        basis: Vec<Vector>,
+        // All codewords in increasing order
+        codewords: Vec<Vector>,
+        codeword_set: HashSet<Vector>,
+    }
+
+    impl BinaryLinearCode {
+        pub fn from_generator_matrix(rows: Vec<Vec<bool>>) -> Result<Self, String> {
+            let Some(length) = rows.first().map(|row| row.len()) else {
+                return Err("A generator matrix needs at least one row".into());
+            };
+            if length == 0 || length > MAX_LENGTH {
+                return Err(format!(
+                    "Rows must have between 1 and {MAX_LENGTH} digits but the first has {length}"
+                ));
+            }
+            if let Some(i) = rows.iter().position(|row| row.len() != length) {
+                return Err(format!(
+                    "Row {} has {} digits but the first has {length}",
+                    i + 1,
+                    rows[i].len()
+                ));
+            }
+            if rows.len() > MAX_DIMENSION {
+                return Err(format!(
+                    "At most {MAX_DIMENSION} rows are supported but {} were given",
+                    rows.len()
+                ));
+            }
+            let basis = rows
+                .iter()
+                .map(|row| Vector::from_points((0..length).filter(|i| row[*i])))
+                .collect::<Vec<_>>();
+
+            // All sums of subsets of the basis
+            let mut codeword_set = HashSet::new();
+            for b in 0usize..(1 << basis.len()) {
+                codeword_set.insert(
+                    (0..basis.len())
+                        .filter(|i| b >> i & 1 == 1)
+                        .fold(Vector::zero(), |codeword, i| codeword + basis[i]),
+                );
+            }
+            if codeword_set.len() != 1 << basis.len() {
+                return Err("The rows of the generator matrix are not linearly independent".into());
+            }
+            let mut codewords = codeword_set.iter().copied().collect::<Vec<_>>();
+            codewords.sort_unstable();
+
+            Ok(Self {
+                length,
+                basis,
+                codewords,
+                codeword_set,
+            })
+        }
+
+        /// Parse a generator matrix with one row of binary digits per line, as plain text or CSV
+        /// Spaces, commas and semicolons within a row, blank lines and lines starting with `#` are ignored
+        pub fn parse_generator_matrix(text: &str) -> Result<Self, String> {
+            let mut rows = vec![];
+            for (line_idx, line) in text.lines().enumerate() {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with('#') {
+                    continue;
+                }
+                let digits = line
+                    .chars()
+                    .filter(|c| !c.is_whitespace() && *c != ',' && *c != ';')
+                    .collect::<String>();
+                if !digits.chars().all(|c| c == '0' || c == '1') {
+                    return Err(format!(
+                        "line {}: expected a row of binary digits",
+                        line_idx + 1
+                    ));
+                }
+                rows.push(digits.chars().map(|c| c == '1').collect());
+            }
+            Self::from_generator_matrix(rows)
+        }
+
+        // The code on the 24 points of the MOG, numbered as usual
+        pub fn from_mog_code(code: &dyn LinearCode) -> Result<Self, String> {
+            Self::from_generator_matrix(
+                code.basis()
+                    .iter()
+                    .map(|row| Point::points().map(|p| row.contains_point(p)).collect())
+                    .collect(),
+            )
+        }
+
+        /*
+        The generator matrix in reduced row echelon form
+        It is systematic on the information set, the pivot columns, where it is the identity
+        */
+        fn row_reduce(&self) -> (Vec<Vector>, Vec<usize>) {
+            let mut rows = self.basis.clone();
+            let mut pivots = vec![];
+            for col in 0..self.length {
+                let r = pivots.len();
+                let Some(i) = (r..rows.len()).find(|i| rows[*i].contains_point(col)) else {
+                    continue;
+                };
+                rows.swap(r, i);
+                for j in 0..rows.len() {
+                    if j != r && rows[j].contains_point(col) {
+                        rows[j] = rows[j] + rows[r];
+                    }
+                }
+                pivots.push(col);
+            }
+            (rows, pivots)
+        }
+
+        pub fn generator_matrix(&self) -> Vec<Vector> {
+            self.row_reduce().0
+        }
+
+        // The columns where the systematic generator matrix is the identity
+        pub fn information_set(&self) -> Vec<usize> {
+            self.row_reduce().1
+        }
+
+        /*
+        A generator matrix of the dual code, with a row for each column outside the information set
+        The row for column j has a 1 there and in the pivot column of each row of the systematic generator matrix with a 1 in column j
+        */
+        pub fn parity_check_matrix(&self) -> Vec<Vector> {
+            let (rows, pivots) = self.row_reduce();
+            (0..self.length)
+                .filter(|j| !pivots.contains(j))
+                .map(|j| {
+                    let mut row = Vector::zero();
+                    row.set(j, true);
+                    for (r, pivot) in rows.iter().zip(&pivots) {
+                        if r.contains_point(j) {
+                            row.set(*pivot, true);
+                        }
+                    }
+                    row
+                })
+                .collect()
+        }
+
+        // The code of all vectors meeting every codeword evenly, of dimension n - k
+        pub fn dual(&self) -> Result<Self, String> {
+            Self::from_generator_matrix(
+                self.parity_check_matrix()
+                    .into_iter()
+                    .map(|row| (0..self.length).map(|i| row.contains_point(i)).collect())
+                    .collect(),
+            )
+        }
+
+        // The parity of the vector against each row of the parity check matrix, which is zero exactly for codewords
+        pub fn syndrome(&self, vector: &Vector) -> Vector {
+            syndrome(&self.parity_check_matrix(), vector)
+        }
+    }
+
+    // The syndrome against a parity check matrix worked out already
+    pub fn syndrome(parity_check_matrix: &[Vector], vector: &Vector) -> Vector {
+        Vector::from_points(
+            parity_check_matrix
+                .iter()
+                .enumerate()
+                .filter(|(_, row)| (*vector & **row).weight() % 2 == 1)
+                .map(|(i, _)| i),
+        )
+    }
+
+    impl Code for BinaryLinearCode {
+        type Word = Vector;
+
+        fn length(&self) -> usize {
+            self.length
+        }
+
+        fn basis(&self) -> &[Vector] {
+            &self.basis
+        }
+
+        fn is_codeword(&self, vector: &Vector) -> bool {
+            self.codeword_set.contains(vector)
+        }
+
+        fn codewords(&self) -> &[Vector] {
+            &self.codewords
+        }
+    }
+}
+
+pub mod minimog {
+    use super::finite_field_3::Point as F3Point;
+    use super::{
+        permutation::Permutation,
+        traits::{Enumerated, Labelled},
+    };
+    use std::collections::HashSet;
+
+    // A point of the MINIMOG, Conway's 4x3 arrangement of the 12 points on which M12 acts
+    // Stored as its number in the MINIMOG numbering
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct Point {
+        pub col: usize,
+        pub row: F3Point,
+    }
+
+    impl Enumerated for Point {
+        /*
+        MINIMOG numbering:
+        0  |  0  1  2  3
+        +  |  4  5  6  7
+        -  |  8  9  10 11
+        */
+
+        const N: usize = 12;
+
+        fn usize_to_point(i: usize) -> Result<Self, ()> {
+            if i < 12 {
+                Ok(Self {
+                    col: i % 4,
+                    row: F3Point::usize_to_point(i / 4).unwrap(),
+                })
+            } else {
+                Err(())
+            }
+        }
+
+        fn point_to_usize(&self) -> usize {
+            self.col + 4 * self.row.point_to_usize()
+        }
+    }
+
+    // The 9 words of the tetracode, the [4, 2, 3] code over F3 playing the part of the hexacode
+    // They are (a, b, b+a, b-a), e.g. `0 + + +` and `+ 0 + -`
+    pub fn tetracode() -> Vec<[F3Point; 4]> {
+        let mut words = vec![];
+        for a in F3Point::points() {
+            for b in F3Point::points() {
+                words.push([a, b, b + a, b + -a]);
+            }
+        }
+        words
+    }
+
+    // A subset of the 12 points, stored as a bitmask with bit i set when point i of the MINIMOG numbering is present
+    #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+    pub struct Vector {
+        bits: u16,
+    }
+
+    impl Vector {
+        pub fn zero() -> Self {
+            Self { bits: 0 }
+        }
+
+        pub fn from_fn(components: impl Fn(Point) -> bool) -> Self {
+            let mut vector = Self::zero();
+            for p in Point::points() {
+                vector.set(p, components(p));
+            }
+            vector
+        }
+
+        pub fn from_points(points: impl Iterator<Item = Point>) -> Self {
+            let mut vector = Self::zero();
+            for point in points {
+                vector.set(point, true);
+            }
+            vector
+        }
+
+        pub fn set(&mut self, p: Point, b: bool) {
+            if b {
+                self.bits |= 1 << p.point_to_usize();
+            } else {
+                self.bits &= !(1 << p.point_to_usize());
+            }
+        }
+
+        pub fn toggle(&mut self, p: Point) {
+            self.bits ^= 1 << p.point_to_usize();
+        }
+
+        pub fn contains(&self, other: &Self) -> bool {
+            other.bits & !self.bits == 0
+        }
+
+        pub fn contains_point(&self, p: Point) -> bool {
+            self.bits & (1 << p.point_to_usize()) != 0
+        }
+
+        pub fn weight(&self) -> usize {
+            self.bits.count_ones() as usize
+        }
+
+        pub fn points(&self) -> impl Iterator<Item = Point> {
+            Point::points().filter(|p| self.contains_point(*p))
+        }
+
+        pub fn permute(&self, permutation: &Permutation<Point>) -> Self {
+            Self::from_points(self.points().map(|p| *permutation.apply(&p)))
+        }
+
+        // The points in the MINIMOG numbering separated by spaces e.g. `0 3 7 8 9 11`
+        pub fn to_point_list(&self) -> String {
+            self.points()
+                .map(|p| p.point_to_usize().to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        }
+    }
+
+    // The extended ternary Golay code, a [12, 6, 6] code over F3
+    // Its 132 hexads, the supports of the codewords of weight 6, are the blocks of the Steiner system S(5, 6, 12)
+    pub struct TernaryGolayCode {
+        codewords: Vec<Labelled<Point, F3Point>>,
+        hexads: HashSet<Vector>,
+    }
+
+    impl Default for TernaryGolayCode {
+        fn default() -> Self {
+            // Spanned by the sums of a column and a tet, the four points in the rows given by a tetracodeword
+            let mut basis = vec![];
+            for col in 0..4 {
+                for word in tetracode() {
+                    basis.push(Labelled::from_fn(|p: Point| {
+                        let in_col = if p.col == col {
+                            F3Point::Plus
+                        } else {
+                            F3Point::Zero
+                        };
+                        let in_tet = if word[p.col] == p.row {
+                            F3Point::Plus
+                        } else {
+                            F3Point::Zero
+                        };
+                        in_col + in_tet
+                    }));
+                }
+            }
+
+            let mut codewords = HashSet::new();
+            codewords.insert(vec![F3Point::Zero; Point::N]);
+            for b in basis {
+                let b = Vec::from(b);
+                for codeword in codewords.clone() {
+                    for lambda in [F3Point::Plus, F3Point::Minus] {
+                        codewords.insert(
+                            codeword
+                                .iter()
+                                .zip(&b)
+                                .map(|(x, y)| *x + lambda * *y)
+                                .collect(),
+                        );
+                    }
+                }
+            }
+            debug_assert_eq!(codewords.len(), 729);
+
+            let codewords = codewords
+                .into_iter()
+                .map(|codeword| Labelled::try_from(codeword).unwrap())
+                .collect::<Vec<_>>();
+            let hexads = codewords
+                .iter()
+                .map(|codeword: &Labelled<Point, F3Point>| {
+                    Vector::from_fn(|p| *codeword.get(p) != F3Point::Zero)
+                })
+                .filter(|support| support.weight() == 6)
+                .collect::<HashSet<_>>();
+            debug_assert_eq!(hexads.len(), 132);
+
+            Self { codewords, hexads }
+        }
+    }
+
+    impl TernaryGolayCode {
+        pub fn codewords(&self) -> &[Labelled<Point, F3Point>] {
+            &self.codewords
+        }
+
+        // All 132 hexads in increasing order
+        pub fn hexads(&self) -> Vec<Vector> {
+            let mut hexads = self.hexads.iter().cloned().collect::<Vec<_>>();
+            hexads.sort_unstable();
+            hexads
+        }
+
+        pub fn is_hexad(&self, vector: &Vector) -> bool {
+            self.hexads.contains(vector)
+        }
+
+        // The hexads containing all of the given points
+        pub fn blocks_through(&self, points: &Vector) -> Vec<Vector> {
+            self.hexads()
+                .into_iter()
+                .filter(|hexad| hexad.contains(points))
+                .collect()
+        }
+
+        // Any 5 points lie in exactly one hexad
+        pub fn complete_hexad(&self, vector: &Vector) -> Result<Vector, ()> {
+            if vector.weight() != 5 {
+                return Err(());
+            }
+            let [hexad] = &self.blocks_through(vector)[..] else {
+                unreachable!()
+            };
+            Ok(hexad.clone())
+        }
+
+        // Whether a permutation of the points sends hexads to hexads, i.e. lies in M12
+        pub fn is_automorphism(&self, permutation: &Permutation<Point>) -> bool {
+            self.hexads
+                .iter()
+                .all(|hexad| self.hexads.contains(&hexad.permute(permutation)))
+        }
+    }
+}
+
+pub mod group {
+    use super::{permutation::Permutation, traits::Enumerated};
+    use std::{hash::Hash, marker::PhantomData};
+
+    // The images of the points by index, composed left to right like `Permutation`
+    type Images = Vec<usize>;
+
+    fn compose(a: &Images, b: &Images) -> Images {
+        a.iter().map(|i| b[*i]).collect()
+    }
+
+    fn invert(a: &Images) -> Images {
+        let mut inverse = vec![0; a.len()];
+        for (i, j) in a.iter().enumerate() {
+            inverse[*j] = i;
+        }
+        inverse
+    }
+
+    fn is_identity(a: &Images) -> bool {
+        a.iter().enumerate().all(|(i, j)| i == *j)
+    }
+
+    // The points a point can be taken to by the generators, in increasing order
+    fn orbit(generators: &[Images], x: usize) -> Vec<usize> {
+        let mut orbit = vec![x];
+        let mut i = 0;
+        while i < orbit.len() {
+            for s in generators {
+                if !orbit.contains(&s[orbit[i]]) {
+                    orbit.push(s[orbit[i]]);
+                }
+            }
+            i += 1;
+        }
+        orbit.sort_unstable();
+        orbit
+    }
+
+    /*
+    A group of permutations stored by a base and strong generating set, found with the Schreier-Sims algorithm
+    Level i holds the orbit of the i-th base point under the subgroup fixing the earlier base points
+    together with, for each point of the orbit, an element taking the base point there
+    */
+    #[derive(Debug, Clone)]
+    pub struct PermutationGroup<T: Enumerated> {
+        _points: PhantomData<T>,
+        base: Vec<usize>,
+        strong_generators: Vec<Images>,
+        transversals: Vec<Vec<Option<Images>>>,
+    }
+
+    impl<T: Enumerated + PartialEq + Eq + Hash + Clone> PermutationGroup<T> {
+        fn images(permutation: &Permutation<T>) -> Images {
+            T::points()
+                .map(|t| permutation.apply(&t).point_to_usize())
+                .collect()
+        }
+
+        fn permutation(images: &Images) -> Permutation<T> {
+            Permutation::from_fn(|t: T| T::usize_to_point(images[t.point_to_usize()]).unwrap())
+        }
+
+        pub fn generated_by(generators: &[Permutation<T>]) -> Self {
+            Self::generated_by_images(generators.iter().map(Self::images).collect(), vec![])
+        }
+
+        // The group with a base starting with the given points, which need not be moved by the group
+        pub fn generated_by_with_base(generators: &[Permutation<T>], base: &[T]) -> Self {
+            Self::generated_by_images(
+                generators.iter().map(Self::images).collect(),
+                base.iter().map(|b| b.point_to_usize()).collect(),
+            )
+        }
+
+        fn generated_by_images(generators: Vec<Images>, base: Vec<usize>) -> Self {
+            let mut group = Self {
+                _points: PhantomData,
+                base,
+                strong_generators: vec![],
+                transversals: vec![],
+            };
+            for generator in generators {
+                if !is_identity(&generator) {
+                    if !group.base.iter().any(|b| generator[*b] != *b) {
+                        group
+                            .base
+                            .push((0..T::N).find(|i| generator[*i] != *i).unwrap());
+                    }
+                    group.strong_generators.push(generator);
+                }
+            }
+            group.transversals = vec![vec![]; group.base.len()];
+
+            // Work up from the last level, checking that every Schreier generator sifts through the levels above
+            // Whenever one does not, its residue is a new strong generator and we resume from the level where it got stuck
+            let mut level = group.base.len();
+            while level > 0 {
+                let i = level - 1;
+                group.compute_transversal(i);
+                let mut new_generator = None;
+                'schreier: for x in 0..T::N {
+                    let Some(u_x) = group.transversals[i][x].clone() else {
+                        continue;
+                    };
+                    for s in group.level_generators(i) {
+                        let u_sx = group.transversals[i][s[x]].as_ref().unwrap();
+                        let schreier = compose(&compose(&u_x, &s), &invert(u_sx));
+                        let (residue, j) = group.sift(schreier, i + 1);
+                        if !is_identity(&residue) {
+                            new_generator = Some((residue, j));
+                            break 'schreier;
+                        }
+                    }
+                }
+                match new_generator {
+                    Some((residue, j)) => {
+                        if j == group.base.len() {
+                            group
+                                .base
+                                .push((0..T::N).find(|p| residue[*p] != *p).unwrap());
+                            group.transversals.push(vec![]);
+                        }
+                        group.strong_generators.push(residue);
+                        level = j + 1;
+                    }
+                    None => level -= 1,
+                }
+            }
+            group
+        }
+
+        // The strong generators fixing the first `i` base points
+        fn level_generators(&self, i: usize) -> Vec<Images> {
+            self.strong_generators
+                .iter()
+                .filter(|s| self.base[..i].iter().all(|b| s[*b] == *b))
+                .cloned()
+                .collect()
+        }
+
+        fn compute_transversal(&mut self, i: usize) {
+            let generators = self.level_generators(i);
+            let mut transversal = vec![None; T::N];
+            transversal[self.base[i]] = Some((0..T::N).collect::<Images>());
+            let mut queue = vec![self.base[i]];
+            while let Some(x) = queue.pop() {
+                for s in &generators {
+                    if transversal[s[x]].is_none() {
+                        transversal[s[x]] = Some(compose(transversal[x].as_ref().unwrap(), s));
+                        queue.push(s[x]);
+                    }
+                }
+            }
+            self.transversals[i] = transversal;
+        }
+
+        // Divide out by transversal elements from level `start` onwards
+        // Returns what is left and the level it could not get past, which is the number of levels if it got through
+        fn sift(&self, mut g: Images, start: usize) -> (Images, usize) {
+            for i in start..self.base.len() {
+                match &self.transversals[i][g[self.base[i]]] {
+                    Some(u) => g = compose(&g, &invert(u)),
+                    None => return (g, i),
+                }
+            }
+            (g, self.base.len())
+        }
+
+        // Points whose images pin down each element of the group
+        pub fn base(&self) -> Vec<T> {
+            self.base
+                .iter()
+                .map(|b| T::usize_to_point(*b).unwrap())
+                .collect()
+        }
+
+        pub fn order(&self) -> u128 {
+            self.transversals
+                .iter()
+                .map(|transversal| transversal.iter().filter(|u| u.is_some()).count() as u128)
+                .product()
+        }
+
+        pub fn contains(&self, permutation: &Permutation<T>) -> bool {
+            is_identity(&self.sift(Self::images(permutation), 0).0)
+        }
+
+        /*
+        An element taking the first `to.len()` base points onto the set `to`, or None if there is none
+        Every element is u_{m-1} ... u_1 u_0 with u_i from the transversal of level i, which sends base point i to
+        u_0(u_1(... u_i(b_i))), so the u_i are chosen level by level keeping the images of the base points inside the set
+        */
+        pub fn set_map(&self, to: &[T]) -> Option<Permutation<T>> {
+            let to = to.iter().map(|t| t.point_to_usize()).collect::<Vec<_>>();
+            if to.len() > self.base.len() {
+                return None;
+            }
+            self.search_set_map(&to, 0, &(0..T::N).collect())
+                .map(|images| Self::permutation(&images))
+        }
+
+        // Carry on `set_map` from a level, given u_{level-1} ... u_0 so far
+        fn search_set_map(&self, to: &[usize], level: usize, product: &Images) -> Option<Images> {
+            if level == to.len() {
+                return Some(product.clone());
+            }
+            for u in self.transversals[level].iter().flatten() {
+                let image = product[u[self.base[level]]];
+                if to.contains(&image)
+                    && let Some(found) = self.search_set_map(to, level + 1, &compose(u, product))
+                {
+                    return Some(found);
+                }
+            }
+            None
+        }
+
+        /*
+        The elements taking the first `size` base points onto themselves as a set
+        Working back from the last of these points, the generators found so far generate the elements fixing the points
+        before the current one, so only images of the current point outside their orbit need an element searched for
+        */
+        pub fn set_stabilizer(&self, size: usize) -> Self {
+            let set = &self.base[..size.min(self.base.len())];
+            let mut generators = self.level_generators(set.len());
+            for level in (0..set.len()).rev() {
+                let mut reached = orbit(&generators, set[level]);
+                for x in set {
+                    if reached.contains(x) {
+                        continue;
+                    }
+                    if let Some(u) = &self.transversals[level][*x]
+                        && let Some(g) = self.search_set_map(set, level + 1, u)
+                    {
+                        generators.push(g);
+                        reached = orbit(&generators, set[level]);
+                    }
+                }
+            }
+            Self::generated_by_images(generators, vec![])
+        }
+
+        // The orbits of the points, each in increasing order, in order of their smallest points
+        pub fn orbits(&self) -> Vec<Vec<T>> {
+            let mut orbits: Vec<Vec<usize>> = vec![];
+            for x in 0..T::N {
+                if !orbits.iter().any(|orbit| orbit.contains(&x)) {
+                    orbits.push(orbit(&self.strong_generators, x));
+                }
+            }
+            orbits
+                .into_iter()
+                .map(|orbit| {
+                    orbit
+                        .into_iter()
+                        .map(|x| T::usize_to_point(x).unwrap())
+                        .collect()
+                })
+                .collect()
+        }
+
+        pub fn is_subgroup_of(&self, other: &Self) -> bool {
+            self.strong_generators
+                .iter()
+                .all(|s| is_identity(&other.sift(s.clone(), 0).0))
+        }
+
+        // One element from each right coset Hg of the subgroup H
+        // This compares every pair of cosets so is only suitable for subgroups of small index
+        pub fn coset_representatives(&self, subgroup: &Self) -> Result<Vec<Permutation<T>>, ()> {
+            if !subgroup.is_subgroup_of(self) {
+                return Err(());
+            }
+            let generators = self.level_generators(0);
+            let mut representatives: Vec<Images> = vec![(0..T::N).collect()];
+            let mut i = 0;
+            while i < representatives.len() {
+                for s in &generators {
+                    let g = compose(&representatives[i], s);
+                    let g_inverse = invert(&g);
+                    // Hg = Hr exactly when r g^-1 is in H
+                    if !representatives
+                        .iter()
+                        .any(|r| is_identity(&subgroup.sift(compose(r, &g_inverse), 0).0))
+                    {
+                        representatives.push(g);
+                    }
+                }
+                i += 1;
+            }
+            Ok(representatives.iter().map(Self::permutation).collect())
+        }
+    }
+}
+
+pub mod presets {
+    use super::finite_field_4::Point as F4Point;
+    use super::hexacode;
+    use super::miracle_octad_generator::{Point, Vector};
+    use super::traits::{Enumerated, Labelled};
+
+    // A well known labelling of the MOG, given as the foursome and label of each point
+    pub struct Preset {
+        pub name: &'static str,
+        pub description: &'static str,
+        // The foursome of each point, row by row
+        foursomes: [[usize; 6]; 4],
+        // The label of each point, row by row, with 0 1 2 3 standing for 0 1 ω ω̄
+        labels: [[usize; 6]; 4],
+    }
+
+    // Labellings from the literature, offered by the sextet labelling editor
+    pub const PRESETS: [Preset; 2] = [
+        Preset {
+            name: "Curtis's standard labelling",
+            description: "The columns in order, with the rows labelled 0 1 ω ω̄ from the top. The hexacode is read off the MOG with this labelling",
+            foursomes: [[0, 1, 2, 3, 4, 5]; 4],
+            labels: [[0; 6], [1; 6], [2; 6], [3; 6]],
+        },
+        Preset {
+            name: "Turyn labelling",
+            description: "The six 2x2 squares of the bricks, top before bottom. They halve the three octads the Turyn construction glues together, the bricks",
+            foursomes: [
+                [0, 0, 2, 2, 4, 4],
+                [0, 0, 2, 2, 4, 4],
+                [1, 1, 3, 3, 5, 5],
+                [1, 1, 3, 3, 5, 5],
+            ],
+            labels: [
+                [0, 1, 0, 1, 3, 2],
+                [3, 2, 3, 2, 0, 1],
+                [0, 1, 0, 1, 3, 2],
+                [3, 2, 3, 2, 0, 1],
+            ],
+        },
+    ];
+
+    impl Preset {
+        fn entry(table: &[[usize; 6]; 4], p: Point) -> usize {
+            let i = p.point_to_usize();
+            table[i / 6][i % 6]
+        }
+
+        // The foursomes in order
+        pub fn foursomes(&self) -> Labelled<hexacode::Point, Vector> {
+            Labelled::from_fn(|h: hexacode::Point| {
+                Vector::from_points(
+                    Point::points()
+                        .filter(|p| Self::entry(&self.foursomes, *p) == h.point_to_usize()),
+                )
+            })
+        }
+
+        pub fn labels(&self) -> Labelled<Point, F4Point> {
+            Labelled::from_fn(|p| F4Point::usize_to_point(Self::entry(&self.labels, p)).unwrap())
+        }
+    }
+}
+
+pub mod script {
+    use super::finite_field_4::Point as F4Point;
+    use super::miracle_octad_generator::{Point, Vector};
+    use super::permutation::Permutation;
+    use super::traits::{Enumerated, Labelled};
+    use std::fmt;
+
+    // A line of a script, acting on what one screen edits
+    pub trait Replay: fmt::Display + Sized {
+        type State: Clone + PartialEq;
+
+        // The commands a line can start with, as a reminder when writing a script
+        const COMMANDS: &'static str;
+        // A few lines to show in an empty script
+        const EXAMPLE: &'static str;
+
+        // None for blank lines and comments
+        fn parse(line: &str) -> Result<Option<Self>, String>;
+
+        fn run(&self, state: &mut Self::State);
+
+        // Actions taking one state to another
+        fn between(before: &Self::State, after: &Self::State) -> Vec<Self>;
+    }
+
+    // Split a line which is not blank or a comment into its command and the rest
+    fn command(line: &str) -> Option<(&str, &str)> {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+        Some(line.split_once(' ').unwrap_or((line, "")))
+    }
+
+    /*
+    A readable record of edits to a selection and a permutation, one action per line, which can be replayed
+        toggle 7
+        select 0 1 2 3 4 5 6 7
+        swap (3 12)
+        permutation (0 1)(2 3)
+        apply
+    Blank lines and lines starting with # are skipped
+    */
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum Action {
+        Toggle(Point),
+        Select(Vector),
+        // Move points along the cycles, then by the permutation as it was
+        Swap(Permutation<Point>),
+        Permutation(Permutation<Point>),
+        // Move the selection by the permutation
+        Apply,
+    }
+
+    impl fmt::Display for Action {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Action::Toggle(p) => write!(f, "toggle {}", p.point_to_usize()),
+                Action::Select(vector) if vector.weight() == 0 => write!(f, "select"),
+                Action::Select(vector) => write!(f, "select {}", vector.to_point_list()),
+                Action::Swap(cycles) => write!(f, "swap {}", cycles.to_cycle_string()),
+                Action::Permutation(permutation) => {
+                    write!(f, "permutation {}", permutation.to_cycle_string())
+                }
+                Action::Apply => write!(f, "apply"),
+            }
+        }
+    }
+
+    impl Replay for Action {
+        type State = (Vector, Permutation<Point>);
+
+        const COMMANDS: &'static str = "toggle, select, swap, permutation or apply";
+        const EXAMPLE: &'static str = "toggle 7\nswap (3 12)\napply";
+
+        fn parse(line: &str) -> Result<Option<Self>, String> {
+            let Some((command, rest)) = command(line) else {
+                return Ok(None);
+            };
+            Ok(Some(match command {
+                "toggle" => Action::Toggle(Point::parse(rest)?),
+                "select" => Action::Select(Vector::parse(rest)?),
+                "swap" => Action::Swap(Permutation::parse_cycles(rest)?),
+                "permutation" => Action::Permutation(Permutation::parse_cycles(rest)?),
+                "apply" if rest.trim().is_empty() => Action::Apply,
+                _ => return Err(format!("`{}` is not an action", line.trim())),
+            }))
+        }
+
+        fn run(&self, (selection, permutation): &mut Self::State) {
+            match self {
+                Action::Toggle(p) => selection.toggle(*p),
+                Action::Select(vector) => *selection = vector.clone(),
+                Action::Swap(cycles) => *permutation = cycles * &*permutation,
+                Action::Permutation(new) => *permutation = new.clone(),
+                Action::Apply => *selection = selection.permute(&*permutation),
+            }
+        }
+
+        /*
+        The permutation comes first
+        A change of permutation by a single cycle, as made by dragging, is a swap
+        and a change of selection is a toggle of one point or the selection moved by the new permutation if it is either
+        */
+        fn between(before: &Self::State, after: &Self::State) -> Vec<Self> {
+            let mut actions = vec![];
+            if before.1 != after.1 {
+                let cycles = &after.1 * &before.1.clone().inverse();
+                actions.push(if cycles.disjoint_cycles().len() == 1 {
+                    Action::Swap(cycles)
+                } else {
+                    Action::Permutation(after.1.clone())
+                });
+            }
+            if before.0 != after.0 {
+                let changed = &before.0 + &after.0;
+                actions.push(if changed.weight() == 1 {
+                    Action::Toggle(changed.points().next().unwrap())
+                } else if before.0.permute(&after.1) == after.0 {
+                    Action::Apply
+                } else {
+                    Action::Select(after.0.clone())
+                });
+            }
+            actions
+        }
+    }
+
+    /*
+    Edits to the labelling of a sextet, in the same form
+        order 0 2 1 3 4 5
+        label 20 ω
+        unlabel 20
+    The order lists the foursomes of the sextet, as they are numbered when it is opened, from first to last
+    Labels are written 0, 1, ω and ω̄, or w and W
+    */
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum LabelAction {
+        Order(Vec<usize>),
+        Label(Point, F4Point),
+        Unlabel(Point),
+    }
+
+    fn label_text(label: F4Point) -> &'static str {
+        match label {
+            F4Point::Zero => "0",
+            F4Point::One => "1",
+            F4Point::Alpha => "ω",
+            F4Point::Beta => "ω̄",
+        }
+    }
+
+    fn parse_label(s: &str) -> Result<F4Point, String> {
+        match s.trim() {
+            "0" => Ok(F4Point::Zero),
+            "1" => Ok(F4Point::One),
+            "ω" | "w" => Ok(F4Point::Alpha),
+            "ω̄" | "W" => Ok(F4Point::Beta),
+            s => Err(format!("`{s}` is not a label (expected 0, 1, ω or ω̄)")),
+        }
+    }
+
+    fn parse_order(s: &str) -> Result<Vec<usize>, String> {
+        let order = s
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|token| !token.is_empty())
+            .map(|token| {
+                token
+                    .parse::<usize>()
+                    .map_err(|_| format!("`{token}` is not a foursome (expected 0-5)"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut sorted = order.clone();
+        sorted.sort_unstable();
+        if sorted != (0..6).collect::<Vec<_>>() {
+            return Err("An order lists each foursome 0-5 once".to_string());
+        }
+        Ok(order)
+    }
+
+    impl fmt::Display for LabelAction {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                LabelAction::Order(order) => write!(
+                    f,
+                    "order {}",
+                    order
+                        .iter()
+                        .map(|i| i.to_string())
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                ),
+                LabelAction::Label(p, label) => {
+                    write!(f, "label {} {}", p.point_to_usize(), label_text(*label))
+                }
+                LabelAction::Unlabel(p) => write!(f, "unlabel {}", p.point_to_usize()),
+            }
+        }
+    }
+
+    impl Replay for LabelAction {
+        // The order of the foursomes and the label of each point, if it has one
+        type State = (Vec<usize>, Labelled<Point, Option<F4Point>>);
+
+        const COMMANDS: &'static str = "order, label or unlabel";
+        const EXAMPLE: &'static str = "order 0 2 1 3 4 5\nlabel 20 ω\nunlabel 20";
+
+        fn parse(line: &str) -> Result<Option<Self>, String> {
+            let Some((command, rest)) = command(line) else {
+                return Ok(None);
+            };
+            Ok(Some(match command {
+                "order" => LabelAction::Order(parse_order(rest)?),
+                "label" => {
+                    let (p, label) = rest
+                        .trim()
+                        .split_once(' ')
+                        .ok_or_else(|| format!("`{}` needs a point and a label", line.trim()))?;
+                    LabelAction::Label(Point::parse(p)?, parse_label(label)?)
+                }
+                "unlabel" => LabelAction::Unlabel(Point::parse(rest)?),
+                _ => return Err(format!("`{}` is not an action", line.trim())),
+            }))
+        }
+
+        fn run(&self, (order, labelling): &mut Self::State) {
+            match self {
+                LabelAction::Order(new) => *order = new.clone(),
+                LabelAction::Label(p, label) => labelling.set(*p, Some(*label)),
+                LabelAction::Unlabel(p) => labelling.set(*p, None),
+            }
+        }
+
+        // The order comes first, then the label of each point which changed
+        fn between(before: &Self::State, after: &Self::State) -> Vec<Self> {
+            let mut actions = vec![];
+            if before.0 != after.0 {
+                actions.push(LabelAction::Order(after.0.clone()));
+            }
+            for p in Point::points() {
+                if before.1.get(p) != after.1.get(p) {
+                    actions.push(match after.1.get(p) {
+                        Some(label) => LabelAction::Label(p, *label),
+                        None => LabelAction::Unlabel(p),
+                    });
+                }
+            }
+            actions
+        }
+    }
+
+    // Parse every line of a script, giving the line number of the first which is not an action
+    pub fn parse<A: Replay>(script: &str) -> Result<Vec<A>, String> {
+        script
+            .lines()
+            .enumerate()
+            .filter_map(|(i, line)| {
+                A::parse(line)
+                    .map_err(|err| format!("Line {}: {err}", i + 1))
+                    .transpose()
+            })
+            .collect()
+    }
+}
+
+#[cfg(feature = "rhai")]
+pub mod console {
+    use super::miracle_octad_generator::{BinaryGolayCode, LinearCode, Point, Vector};
+    use super::permutation::Permutation;
+    use super::traits::Enumerated;
+    use rhai::{Array, Dynamic, Engine, EvalAltResult, INT};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    /*
+    Limits on what one run of a script can use, so a runaway loop stops within a fraction of a second
+    and a string or array doubled in a loop stops long before it fills memory
+    */
+    pub const MAX_OPERATIONS: u64 = 1_000_000;
+    pub const MAX_STRING_SIZE: usize = 100_000;
+    pub const MAX_ARRAY_SIZE: usize = 10_000;
+    pub const MAX_MAP_SIZE: usize = 10_000;
+
+    // The functions a script can call, with points numbered 0 to 23 and permutations written in cycle notation
+    pub const FUNCTIONS: [&str; 13] = [
+        "selection()",
+        "select([points])",
+        "toggle(point)",
+        "clear()",
+        "weight()",
+        "is_octad()",
+        "complete_octad()",
+        "permutation()",
+        "set_permutation(\"(cycles)\")",
+        "apply()",
+        "apply(\"(cycles)\")",
+        "is_automorphism()",
+        "is_automorphism(\"(cycles)\")",
+    ];
+
+    type State = Rc<RefCell<(Vector, Permutation<Point>)>>;
+
+    fn point(i: INT) -> Result<Point, Box<EvalAltResult>> {
+        usize::try_from(i)
+            .ok()
+            .and_then(|i| Point::usize_to_point(i).ok())
+            .ok_or_else(|| format!("{i} is not a point, which are numbered 0 to 23").into())
+    }
+
+    fn points(array: Array) -> Result<Vector, Box<EvalAltResult>> {
+        let mut vector = Vector::zero();
+        for item in array {
+            let i = item
+                .as_int()
+                .map_err(|_| format!("Expected a point number but found {item}"))?;
+            vector.set(point(i)?, true);
+        }
+        Ok(vector)
+    }
+
+    fn to_array(vector: &Vector) -> Array {
+        vector
+            .points()
+            .map(|p| Dynamic::from(p.point_to_usize() as INT))
+            .collect()
+    }
+
+    fn permutation(cycles: &str) -> Result<Permutation<Point>, Box<EvalAltResult>> {
+        Ok(Permutation::parse_cycles(cycles)?)
+    }
+
+    // The functions a script can call, each reading or changing the selection and permutation
+    fn engine(
+        mog: &'static BinaryGolayCode,
+        state: &State,
+        output: &Rc<RefCell<String>>,
+    ) -> Engine {
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+        engine.set_max_string_size(MAX_STRING_SIZE);
+        engine.set_max_array_size(MAX_ARRAY_SIZE);
+        engine.set_max_map_size(MAX_MAP_SIZE);
+
+        let out = output.clone();
+        engine.on_print(move |s| {
+            let mut out = out.borrow_mut();
+            if out.len() < MAX_STRING_SIZE {
+                out.push_str(s);
+                out.push('\n');
+            }
+        });
+
+        let s = state.clone();
+        engine.register_fn("selection", move || to_array(&s.borrow().0));
+        let s = state.clone();
+        engine.register_fn("select", move |array: Array| {
+            s.borrow_mut().0 = points(array)?;
+            Ok::<_, Box<EvalAltResult>>(())
+        });
+        let s = state.clone();
+        engine.register_fn("toggle", move |i: INT| {
+            s.borrow_mut().0.toggle(point(i)?);
+            Ok::<_, Box<EvalAltResult>>(())
+        });
+        let s = state.clone();
+        engine.register_fn("clear", move || s.borrow_mut().0 = Vector::zero());
+        let s = state.clone();
+        engine.register_fn("weight", move || s.borrow().0.weight() as INT);
+        let s = state.clone();
+        engine.register_fn("is_octad", move || mog.is_octad(&s.borrow().0));
+        let s = state.clone();
+        engine.register_fn("complete_octad", move || {
+            let octad = mog
+                .complete_octad(&s.borrow().0)
+                .map_err(|()| "Select 5 points to complete an octad")?;
+            s.borrow_mut().0 = octad;
+            Ok::<_, Box<EvalAltResult>>(())
+        });
+
+        let s = state.clone();
+        engine.register_fn("permutation", move || s.borrow().1.to_cycle_string());
+        let s = state.clone();
+        engine.register_fn("set_permutation", move |cycles: &str| {
+            s.borrow_mut().1 = permutation(cycles)?;
+            Ok::<_, Box<EvalAltResult>>(())
+        });
+        // Move the selection by the permutation, or by the one given
+        let s = state.clone();
+        engine.register_fn("apply", move || {
+            let mut state = s.borrow_mut();
+            let (selected, permutation) = &mut *state;
+            *selected = selected.permute(&*permutation);
+        });
+        let s = state.clone();
+        engine.register_fn("apply", move |cycles: &str| {
+            let permutation = permutation(cycles)?;
+            let selected = &mut s.borrow_mut().0;
+            *selected = selected.permute(permutation);
+            Ok::<_, Box<EvalAltResult>>(())
+        });
+        let s = state.clone();
+        engine.register_fn("is_automorphism", move || {
+            mog.is_automorphism(&s.borrow().1)
+        });
+        engine.register_fn("is_automorphism", move |cycles: &str| {
+            Ok::<_, Box<EvalAltResult>>(mog.is_automorphism(&permutation(cycles)?))
+        });
+
+        engine
+    }
+
+    /*
+    Run a rhai script against a selection and permutation, returning them as the script left them
+    and what it printed, followed by its value or the error which stopped it
+    Changes made by the script before any error are kept
+    */
+    pub fn run(
+        mog: &'static BinaryGolayCode,
+        script: &str,
+        state: (Vector, Permutation<Point>),
+    ) -> ((Vector, Permutation<Point>), String) {
+        let state = Rc::new(RefCell::new(state));
+        let output = Rc::new(RefCell::new(String::new()));
+        let result = engine(mog, &state, &output).eval::<Dynamic>(script);
+        let mut output = output.take();
+        match result {
+            Ok(value) if value.is_unit() => {}
+            Ok(value) => output.push_str(&format!("{value}\n")),
+            Err(err) => output.push_str(&format!("Error: {err}\n")),
+        }
+        let state = state.borrow().clone();
+        (state, output)
+    }
+}
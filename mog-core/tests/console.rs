@@ -0,0 +1,67 @@
+// Scripts run through the console, checking the functions it offers and the limits on a run
+use mog_core::console;
+use mog_core::miracle_octad_generator::{BinaryGolayCode, Point, Vector};
+use mog_core::permutation::Permutation;
+use std::sync::OnceLock;
+
+fn mog() -> &'static BinaryGolayCode {
+    static MOG: OnceLock<BinaryGolayCode> = OnceLock::new();
+    MOG.get_or_init(BinaryGolayCode::default)
+}
+
+fn run(script: &str) -> ((Vector, Permutation<Point>), String) {
+    console::run(mog(), script, (Vector::zero(), Permutation::identity()))
+}
+
+#[test]
+fn completes_an_octad_and_prints() {
+    let ((selected, permutation), output) = run("select([0, 1, 2, 3, 4]);
+        complete_octad();
+        set_permutation(\"(0 1)(2 3)\");
+        print(weight());
+        is_octad()");
+    assert!(mog().is_octad(&selected));
+    assert_eq!(
+        permutation,
+        Permutation::parse_cycles("(0 1)(2 3)").unwrap()
+    );
+    assert_eq!(output, "8\ntrue\n");
+}
+
+#[test]
+fn applies_permutations_to_the_selection() {
+    let ((selected, _), _) =
+        run("toggle(0); set_permutation(\"(0 5)\"); apply(); apply(\"(5 7)\")");
+    assert_eq!(selected, Vector::parse("7").unwrap());
+}
+
+#[test]
+fn keeps_changes_made_before_an_error() {
+    let ((selected, _), output) = run("toggle(3); toggle(24); toggle(4)");
+    assert_eq!(selected, Vector::parse("3").unwrap());
+    assert!(output.starts_with("Error: "), "{output}");
+}
+
+#[test]
+fn stops_a_runaway_loop() {
+    let (_, output) = run("loop { toggle(0); }");
+    assert!(output.starts_with("Error: "), "{output}");
+}
+
+#[test]
+fn stops_a_string_doubling_loop() {
+    let (_, output) = run("let a = \"mog\"; loop { a += a; }");
+    assert!(output.starts_with("Error: "), "{output}");
+}
+
+#[test]
+fn stops_an_array_doubling_loop() {
+    let (_, output) = run("let a = [0]; loop { a += a; }");
+    assert!(output.starts_with("Error: "), "{output}");
+}
+
+#[test]
+fn stops_a_map_growing_loop() {
+    let (_, output) = run("let m = #{}; let i = 0; loop { m[`${i}`] = i; i += 1; }");
+    assert!(output.starts_with("Error: "), "{output}");
+}
@@ -0,0 +1,566 @@
+// Properties of the Golay code, the MOG labellings and permutations, checked on random inputs
+use mog_core::finite_field_4::Point as F4Point;
+use mog_core::hexacode;
+use mog_core::linear_code::Code;
+use mog_core::m24::{self, FactorizationTable, M24_ORDER};
+use mog_core::miracle_octad_generator::{
+    BinaryGolayCode, DecodeResult, DodecadClass, LinearCode, OCTAD_SEXTET_INTERSECTIONS,
+    OrderedSextet, OrderedSextetLabelling, Point, Vector,
+};
+use mog_core::permutation::Permutation;
+use mog_core::script::{self, Action, LabelAction, Replay};
+use mog_core::traits::{Enumerated, Labelled};
+use quickcheck::{Arbitrary, Gen};
+use quickcheck_macros::quickcheck;
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+fn mog() -> &'static BinaryGolayCode {
+    static MOG: OnceLock<BinaryGolayCode> = OnceLock::new();
+    MOG.get_or_init(BinaryGolayCode::default)
+}
+
+// 0..n in a random order
+fn shuffled(g: &mut Gen, n: usize) -> Vec<usize> {
+    let mut items = (0..n).collect::<Vec<_>>();
+    for i in (1..n).rev() {
+        items.swap(i, usize::arbitrary(g) % (i + 1));
+    }
+    items
+}
+
+#[derive(Debug, Clone)]
+struct AnyCodeword(Vector);
+
+impl Arbitrary for AnyCodeword {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let codewords = mog().codewords();
+        Self(codewords[usize::arbitrary(g) % codewords.len()].clone())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct AnyPermutation(Permutation<Point>);
+
+impl Arbitrary for AnyPermutation {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let images = shuffled(g, Point::N);
+        Self(Permutation::from_fn(|p: Point| {
+            Point::usize_to_point(images[p.point_to_usize()]).unwrap()
+        }))
+    }
+}
+
+// The inputs to `complete_labelling`: a sextet with its foursomes in some order, and the points to label
+#[derive(Debug, Clone)]
+struct LabellingChoice {
+    sextet: OrderedSextet,
+    x: Point,
+    y: Point,
+    z: Point,
+    w: Point,
+    alpha: F4Point,
+}
+
+impl Arbitrary for LabellingChoice {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let points = shuffled(g, Point::N);
+        let foursome = Vector::from_points(
+            points[..4]
+                .iter()
+                .map(|i| Point::usize_to_point(*i).unwrap()),
+        );
+        let foursomes = mog().complete_sextet(&foursome).unwrap();
+        let ordering = shuffled(g, 6);
+        let sextet = OrderedSextet::from_foursomes(Labelled::from_fn(|h: hexacode::Point| {
+            foursomes[ordering[h.point_to_usize()]].clone()
+        }));
+        let mut pick = |t: usize, n: usize| {
+            let foursome = sextet.foursome(hexacode::Point::usize_to_point(t).unwrap());
+            let points = foursome.points().collect::<Vec<_>>();
+            let order = shuffled(g, 4);
+            order[..n].iter().map(|i| points[*i]).collect::<Vec<_>>()
+        };
+        let x = pick(0, 1)[0];
+        let yz = pick(1, 2);
+        let w = pick(2, 1)[0];
+        let alpha = *g
+            .choose(&[F4Point::Zero, F4Point::One, F4Point::Alpha, F4Point::Beta])
+            .unwrap();
+        Self {
+            sextet,
+            x,
+            y: yz[0],
+            z: yz[1],
+            w,
+            alpha,
+        }
+    }
+}
+
+impl LabellingChoice {
+    fn complete(&self) -> OrderedSextetLabelling {
+        mog().complete_labelling(
+            self.sextet.clone(),
+            self.x,
+            self.y,
+            self.z,
+            self.w,
+            self.alpha,
+        )
+    }
+}
+
+// The permutation taking the standard labelling of the MOG to a labelling
+fn labelling_permutation(labelling: &OrderedSextetLabelling) -> Permutation<Point> {
+    Permutation::from_fn(|p| Point {
+        col: *labelling.foursomes().get(p),
+        row: *labelling.labels().get(p),
+    })
+}
+
+#[quickcheck]
+fn codewords_are_closed_under_addition(a: AnyCodeword, b: AnyCodeword) -> bool {
+    mog().is_codeword(&(&a.0 + &b.0))
+}
+
+// The decoder recovers the information bits after at most 3 errors
+#[quickcheck]
+fn decode_corrects_up_to_3_errors(information: u16, errors: Vec<u8>) -> bool {
+    let information = information as usize % (1 << 12);
+    let errors = Vector::from_points(
+        errors
+            .iter()
+            .take(3)
+            .map(|i| Point::usize_to_point(*i as usize % Point::N).unwrap()),
+    );
+    let codeword = mog().encode(information);
+    match mog().decode(&(&codeword + &errors)) {
+        DecodeResult::Corrected {
+            codeword: decoded,
+            errors: found,
+        } => {
+            decoded == codeword && found == errors && mog().information(&decoded) == Ok(information)
+        }
+        DecodeResult::Detected { .. } => false,
+    }
+}
+
+// Deleting a point gives a perfect code, so any 3 errors among the other 23 points are corrected
+#[quickcheck]
+fn decode_punctured_corrects_up_to_3_errors(
+    information: u16,
+    punctured: u8,
+    errors: Vec<u8>,
+    bit: bool,
+) -> bool {
+    let punctured = Point::usize_to_point(punctured as usize % Point::N).unwrap();
+    let mut errors = Vector::from_points(
+        errors
+            .iter()
+            .take(3)
+            .map(|i| Point::usize_to_point(*i as usize % Point::N).unwrap()),
+    );
+    errors.set(punctured, false);
+    let mut codeword = mog().encode(information as usize % (1 << 12));
+    codeword.set(punctured, false);
+    // Whatever is at the deleted point is ignored
+    let mut received = &codeword + &errors;
+    received.set(punctured, bit);
+    mog().decode_punctured(punctured, &received) == (codeword, errors)
+}
+
+#[test]
+fn weight_distribution() {
+    let mut expected = vec![0; Point::N + 1];
+    expected[0] = 1;
+    expected[8] = 759;
+    expected[12] = 2576;
+    expected[16] = 759;
+    expected[24] = 1;
+    assert_eq!(mog().weight_distribution(), expected);
+}
+
+#[quickcheck]
+fn complete_labelling_gives_an_automorphism(choice: LabellingChoice) -> bool {
+    let labelling = choice.complete();
+    mog().is_automorphism(&labelling_permutation(&labelling))
+}
+
+#[quickcheck]
+fn complete_labelling_keeps_the_given_labels(choice: LabellingChoice) -> bool {
+    let labelling = choice.complete();
+    let labels = labelling.labels();
+    *labels.get(choice.x) == F4Point::Zero
+        && *labels.get(choice.y) == F4Point::Zero
+        && *labels.get(choice.z) == F4Point::One
+        && *labels.get(choice.w) == choice.alpha
+}
+
+#[quickcheck]
+fn composition_is_associative(a: AnyPermutation, b: AnyPermutation, c: AnyPermutation) -> bool {
+    &(&a.0 * &b.0) * &c.0 == &a.0 * &(&b.0 * &c.0)
+}
+
+// Whether a permutation takes every codeword to a codeword, the slow way
+fn preserves_every_codeword(permutation: &Permutation<Point>) -> bool {
+    mog()
+        .codewords()
+        .iter()
+        .all(|codeword| mog().is_codeword(&codeword.permute(permutation)))
+}
+
+#[quickcheck]
+fn is_automorphism_agrees_for_any_permutation(permutation: AnyPermutation) -> bool {
+    mog().is_automorphism(&permutation.0) == preserves_every_codeword(&permutation.0)
+}
+
+// Random permutations are almost never automorphisms, so also check some which are
+// together with automorphisms followed by a transposition, which never are
+#[quickcheck]
+fn is_automorphism_agrees_for_automorphisms(
+    first: LabellingChoice,
+    second: LabellingChoice,
+    swap: (usize, usize),
+) -> bool {
+    let automorphism = &labelling_permutation(&first.complete()).inverse()
+        * &labelling_permutation(&second.complete());
+    let swapped = &automorphism
+        * &Permutation::new_swap(
+            &Point::usize_to_point(swap.0 % Point::N).unwrap(),
+            &Point::usize_to_point(swap.1 % Point::N).unwrap(),
+        );
+    mog().is_automorphism(&automorphism) == preserves_every_codeword(&automorphism)
+        && mog().is_automorphism(&swapped) == preserves_every_codeword(&swapped)
+}
+
+#[quickcheck]
+fn automorphism_witness_is_an_octad_sent_outside_the_code(permutation: AnyPermutation) -> bool {
+    match mog().automorphism_witness(&permutation.0) {
+        Some(octad) => mog().is_octad(&octad) && !mog().is_codeword(&octad.permute(&permutation.0)),
+        None => mog().is_automorphism(&permutation.0),
+    }
+}
+
+// Every point is in 253 octads, so counting pairs of a point of the vector and an octad through it two ways
+#[quickcheck]
+fn octad_intersection_counts_add_up(permutation: AnyPermutation, k: usize) -> bool {
+    let vector = Vector::from_points(
+        Point::points()
+            .take(k % 25)
+            .map(|p| *permutation.0.apply(&p)),
+    );
+    let counts = mog().octad_intersection_counts(&vector);
+    counts.iter().sum::<usize>() == 759
+        && counts.iter().enumerate().map(|(i, n)| i * n).sum::<usize>() == 253 * vector.weight()
+}
+
+fn factorization_table() -> &'static FactorizationTable {
+    static TABLE: OnceLock<FactorizationTable> = OnceLock::new();
+    TABLE.get_or_init(FactorizationTable::default)
+}
+
+#[test]
+fn generators_are_automorphisms_of_their_order() {
+    for g in m24::Generator::ALL {
+        assert!(mog().is_automorphism(&g.permutation()));
+        assert_eq!(g.permutation().order(), g.order());
+    }
+}
+
+// The printed table is checked by the Möbius transformations generating PSL(2, 23) being automorphisms on it
+#[test]
+fn curtis_labels_carry_psl2_23() {
+    use mog_core::psl2_23::{CURTIS_LABELS, INFINITY};
+    let on_labels = |f: &dyn Fn(usize) -> usize| {
+        let point = |label: usize| {
+            Point::usize_to_point(CURTIS_LABELS.iter().position(|l| *l == label).unwrap()).unwrap()
+        };
+        Permutation::from_fn(|p: Point| point(f(CURTIS_LABELS[p.point_to_usize()])))
+    };
+    let inverse = |x: usize| (1..23).find(|y| x * y % 23 == 1).unwrap();
+    for permutation in [
+        on_labels(&|x| if x == INFINITY { x } else { (x + 1) % 23 }),
+        on_labels(&|x| if x == INFINITY { x } else { 2 * x % 23 }),
+        on_labels(&|x| match x {
+            INFINITY => 0,
+            0 => INFINITY,
+            x => 23 - inverse(x),
+        }),
+    ] {
+        assert!(mog().is_automorphism(&permutation));
+    }
+}
+
+#[quickcheck]
+fn factorize_gives_a_word_for_the_automorphism(
+    first: LabellingChoice,
+    second: LabellingChoice,
+) -> bool {
+    let automorphism = &labelling_permutation(&first.complete()).inverse()
+        * &labelling_permutation(&second.complete());
+    let word = m24::factorize(factorization_table(), &automorphism).unwrap();
+    word.iter().fold(Permutation::identity(), |product, g| {
+        &product * &g.permutation()
+    }) == automorphism
+}
+
+#[quickcheck]
+fn factorize_rejects_non_automorphisms(permutation: AnyPermutation) -> bool {
+    m24::factorize(factorization_table(), &permutation.0).is_ok()
+        == mog().is_automorphism(&permutation.0)
+}
+
+// Listing the labellings is slow, so only one sextet is checked
+#[test]
+fn all_labellings_are_distinct_automorphisms() {
+    let choice = LabellingChoice::arbitrary(&mut Gen::new(10));
+    let labellings = choice.sextet.all_labellings(mog());
+    let mut labels = labellings
+        .iter()
+        .map(|labelling| Vec::from(labelling.labels().clone()))
+        .collect::<Vec<_>>();
+    labels.sort_by_key(|labels| {
+        labels
+            .iter()
+            .map(|x| x.point_to_usize())
+            .collect::<Vec<_>>()
+    });
+    labels.dedup();
+    assert_eq!(labels.len(), 192);
+    assert!(
+        labellings
+            .iter()
+            .all(|labelling| mog().is_automorphism(&labelling_permutation(labelling)))
+    );
+}
+
+#[test]
+fn octads_meet_sextets_in_the_known_patterns() {
+    for sextet in mog().sextets().iter().step_by(50) {
+        let sextet = OrderedSextet::from_foursomes(Labelled::try_from(sextet.to_vec()).unwrap());
+        for octad in mog().octads() {
+            let mut sizes = Vec::from(sextet.intersection_sizes(&octad));
+            sizes.sort_unstable_by(|a, b| b.cmp(a));
+            assert!(
+                OCTAD_SEXTET_INTERSECTIONS
+                    .iter()
+                    .any(|pattern| sizes == pattern)
+            );
+        }
+    }
+}
+
+#[quickcheck]
+fn octad_map_takes_one_octad_to_the_other(from: usize, to: usize) -> bool {
+    let octads = mog().octads();
+    let (from, to) = (&octads[from % octads.len()], &octads[to % octads.len()]);
+    let map = mog().octad_map(from, to).unwrap();
+    mog().is_automorphism(&map) && &from.permute(&map) == to
+}
+
+#[quickcheck]
+fn trio_map_takes_the_bricks_to_any_trio(octad: usize, trio: usize) -> bool {
+    let octads = mog().octads();
+    let octad = &octads[octad % octads.len()];
+    let trios = mog().complete_trio(octad).unwrap();
+    let (a, b) = &trios[trio % trios.len()];
+    let to = [octad.clone(), a.clone(), b.clone()];
+    let map = mog().trio_map(&mog().bricks(), &to).unwrap();
+    mog().is_automorphism(&map)
+        && mog()
+            .bricks()
+            .iter()
+            .zip(&to)
+            .all(|(brick, octad)| &brick.permute(&map) == octad)
+}
+
+// Up to 5 points there is always a map, and for more the map found must be right
+#[quickcheck]
+fn set_map_takes_one_set_to_the_other(from: AnyPermutation, to: AnyPermutation, k: usize) -> bool {
+    let k = k % 9;
+    let set = |permutation: &AnyPermutation| {
+        Vector::from_points(Point::points().take(k).map(|p| *permutation.0.apply(&p)))
+    };
+    let (from, to) = (set(&from), set(&to));
+    match m24::set_map(mog(), &from, &to) {
+        Ok(map) => mog().is_automorphism(&map) && from.permute(&map) == to,
+        Err(_) => k > 5,
+    }
+}
+
+// M24 is transitive on sets of up to 5 points, so their stabilizers have the index of the number of such sets
+#[test]
+fn set_stabilizer_orders() {
+    let mut sets = 1;
+    for k in 0..=5 {
+        let set = Vector::from_points(Point::points().take(k));
+        assert_eq!(mog().set_stabilizer(&set).order(), M24_ORDER / sets);
+        sets = sets * (24 - k as u128) / (k as u128 + 1);
+    }
+    assert_eq!(mog().set_stabilizer(&mog().octads()[0]).order(), 322560);
+    assert_eq!(mog().set_stabilizer(&mog().dodecads()[0]).order(), 95040);
+}
+
+// There are 35 ways to halve an octad, and each completes to a different sextet
+#[test]
+fn brick_sextets_halve_the_left_brick() {
+    let brick = &mog().bricks()[0];
+    let halves = mog()
+        .brick_sextets()
+        .iter()
+        .map(|sextet| {
+            let mut halves = sextet
+                .iter()
+                .filter(|foursome| brick.contains(foursome))
+                .cloned()
+                .collect::<Vec<_>>();
+            assert_eq!(&(&halves[0] + &halves[1]), brick);
+            halves.sort_unstable();
+            halves
+        })
+        .collect::<HashSet<_>>();
+    assert_eq!(halves.len(), 35);
+}
+
+#[quickcheck]
+fn octad_sextets_split_the_other_16_points(i: usize) -> bool {
+    let octads = mog().octads();
+    let octad = &octads[i % octads.len()];
+    let sextets = mog().octad_sextets(octad).unwrap();
+    sextets.len() == 35
+        && sextets.iter().all(|sextet| {
+            &(&sextet[0] + &sextet[1]) == octad
+                && sextet[2..]
+                    .iter()
+                    .all(|foursome| (foursome & octad).weight() == 0)
+        })
+}
+
+#[test]
+fn turyn_labelling_halves_the_bricks() {
+    let preset = mog_core::presets::PRESETS
+        .iter()
+        .find(|preset| preset.name == "Turyn labelling")
+        .unwrap();
+    let foursomes = preset.foursomes();
+    for brick in mog().bricks() {
+        assert_eq!(
+            foursomes.iter().filter(|(_, f)| brick.contains(f)).count(),
+            2
+        );
+    }
+}
+
+#[test]
+fn presets_are_labellings() {
+    for preset in &mog_core::presets::PRESETS {
+        let sextet = OrderedSextet::from_foursomes(preset.foursomes());
+        let foursome = sextet.foursome(hexacode::Point::usize_to_point(0).unwrap());
+        let completed = mog().complete_sextet(foursome).unwrap();
+        assert!(
+            hexacode::Point::points().all(|h| completed.contains(sextet.foursome(h))),
+            "{}",
+            preset.name
+        );
+        let labels = preset.labels();
+        let foursomes = sextet.point_foursomes();
+        let permutation = Permutation::from_fn(|p| Point {
+            col: *foursomes.get(p),
+            row: *labels.get(p),
+        });
+        assert!(mog().is_automorphism(&permutation), "{}", preset.name);
+    }
+}
+
+// Replaying the actions recorded between two states gets from one to the other, also after writing them out and reading them back
+#[quickcheck]
+fn script_replays_the_recorded_actions(
+    bits: u32,
+    p: AnyPermutation,
+    q: AnyPermutation,
+    swap: (u8, u8),
+    change: u8,
+) -> bool {
+    let before = (
+        Vector::from_fn(|p| bits & (1 << p.point_to_usize()) != 0),
+        p.0,
+    );
+    let (x, y) = (
+        Point::usize_to_point(swap.0 as usize % Point::N).unwrap(),
+        Point::usize_to_point(swap.1 as usize % Point::N).unwrap(),
+    );
+    let permutation = match change % 3 {
+        0 => before.1.clone(),
+        1 => &Permutation::new_swap(&x, &y) * &before.1,
+        _ => q.0,
+    };
+    let mut selection = before.0.clone();
+    match change % 4 {
+        0 => {}
+        1 => selection.toggle(x),
+        2 => selection = selection.permute(&permutation),
+        _ => selection = Vector::from_fn(|p| bits & (1 << (p.point_to_usize() + 4)) != 0),
+    }
+    let after = (selection, permutation);
+
+    let actions = Action::between(&before, &after);
+    let written = actions
+        .iter()
+        .map(|action| action.to_string())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let mut state = before;
+    for action in script::parse::<Action>(&written).unwrap() {
+        action.run(&mut state);
+    }
+    script::parse::<Action>(&written).unwrap() == actions && state == after
+}
+
+// The same for edits to the labelling of a sextet
+#[quickcheck]
+fn label_script_replays_the_recorded_actions(labels: Vec<(u8, u8)>, reorder: bool) -> bool {
+    let before = (
+        (0..6).collect::<Vec<_>>(),
+        Labelled::<Point, Option<F4Point>>::new_constant(None),
+    );
+    let mut after = before.clone();
+    if reorder {
+        after.0 = shuffled(&mut Gen::new(6), 6);
+    }
+    // Labels numbered 4 are cleared again
+    for (p, label) in labels {
+        after.1.set(
+            Point::usize_to_point(p as usize % Point::N).unwrap(),
+            F4Point::usize_to_point(label as usize % 5).ok(),
+        );
+    }
+
+    let actions = LabelAction::between(&before, &after);
+    let written = actions
+        .iter()
+        .map(|action| action.to_string())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let mut state = before;
+    for action in script::parse::<LabelAction>(&written).unwrap() {
+        action.run(&mut state);
+    }
+    script::parse::<LabelAction>(&written).unwrap() == actions && state == after
+}
+
+// Relative to the sextet of columns there are 6 * 120 special dodecads, one for each column and dodecad through it
+#[test]
+fn dodecad_classes() {
+    let count = |class| {
+        mog()
+            .dodecads()
+            .iter()
+            .filter(|dodecad| mog().classify_dodecad(dodecad) == Ok(class))
+            .count()
+    };
+    assert_eq!(count(DodecadClass::Special), 720);
+    assert_eq!(count(DodecadClass::Umbral), 576);
+    assert_eq!(count(DodecadClass::Transverse), 1280);
+    assert!(mog().classify_dodecad(&mog().octads()[0]).is_err());
+}
@@ -0,0 +1,112 @@
+// The random automorphisms are automorphisms, and those preserving a structure send its blocks to blocks
+use mog_core::miracle_octad_generator::{BinaryGolayCode, LinearCode, Point, Vector};
+use mog_core::permutation::Permutation;
+use mog_core::traits::Enumerated;
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+use std::sync::OnceLock;
+
+// Each search takes up to a second in a debug build, so each property is checked for a few seeds
+const SEEDS: u64 = 4;
+
+fn mog() -> &'static BinaryGolayCode {
+    static MOG: OnceLock<BinaryGolayCode> = OnceLock::new();
+    MOG.get_or_init(BinaryGolayCode::default)
+}
+
+// n distinct points chosen by the random number generator
+fn points(rng: &mut SmallRng, n: usize) -> Vector {
+    let mut vector = Vector::zero();
+    while vector.weight() < n {
+        vector.set(
+            Point::usize_to_point(rng.random_range(0..Point::N)).unwrap(),
+            true,
+        );
+    }
+    vector
+}
+
+fn octad(rng: &mut SmallRng) -> Vector {
+    let octads = mog().octads();
+    octads[rng.random_range(0..octads.len())].clone()
+}
+
+// Whether the permutation is an automorphism sending each block to one of the blocks
+fn preserves(permutation: &Permutation<Point>, blocks: &[Vector]) -> bool {
+    mog().is_automorphism(permutation)
+        && blocks
+            .iter()
+            .all(|block| blocks.contains(&block.permute(permutation)))
+}
+
+#[test]
+fn random_automorphism_is_an_automorphism() {
+    for seed in 0..SEEDS {
+        let rng = &mut SmallRng::seed_from_u64(seed);
+        assert!(mog().is_automorphism(&mog().random_automorphism(rng)));
+    }
+}
+
+#[test]
+fn random_octad_stabilizer_fixes_the_octad() {
+    for seed in 0..SEEDS {
+        let rng = &mut SmallRng::seed_from_u64(seed);
+        let octad = octad(rng);
+        let permutation = mog().random_octad_stabilizer(&octad, rng).unwrap();
+        assert!(preserves(&permutation, &[octad]));
+    }
+}
+
+#[test]
+fn random_sextet_stabilizer_permutes_the_foursomes() {
+    for seed in 0..SEEDS {
+        let rng = &mut SmallRng::seed_from_u64(seed);
+        let foursome = points(rng, 4);
+        let sextet = mog().complete_sextet(&foursome).unwrap();
+        let permutation = mog().random_sextet_stabilizer(&foursome, rng).unwrap();
+        assert!(preserves(&permutation, &sextet));
+    }
+}
+
+#[test]
+fn random_trio_stabilizer_permutes_the_octads() {
+    for seed in 0..SEEDS {
+        let rng = &mut SmallRng::seed_from_u64(seed);
+        let octad = octad(rng);
+        let trios = mog().complete_trio(&octad).unwrap();
+        let (a, b) = trios[rng.random_range(0..trios.len())].clone();
+        let trio = [octad, a, b];
+        let permutation = mog().random_trio_stabilizer(&trio, rng).unwrap();
+        assert!(preserves(&permutation, &trio));
+    }
+}
+
+#[test]
+fn random_duad_stabilizer_fixes_the_pair() {
+    for seed in 0..SEEDS {
+        let rng = &mut SmallRng::seed_from_u64(seed);
+        let duad = points(rng, 2);
+        let permutation = mog().random_duad_stabilizer(&duad, rng).unwrap();
+        assert!(preserves(&permutation, &[duad]));
+    }
+}
+
+#[test]
+fn random_point_stabilizer_fixes_each_point() {
+    for seed in 0..SEEDS {
+        let rng = &mut SmallRng::seed_from_u64(seed);
+        let fixed = points(rng, 1 + seed as usize % 2);
+        let permutation = mog().random_point_stabilizer(&fixed, rng).unwrap();
+        assert!(mog().is_automorphism(&permutation));
+        assert!(fixed.points().all(|p| *permutation.apply(&p) == p));
+    }
+}
+
+// Listing fixed points, as `from_fn` does, does not change which permutation it is
+#[test]
+fn random_automorphism_equals_itself_without_fixed_points() {
+    for seed in 0..SEEDS {
+        let permutation = mog().random_automorphism(&mut SmallRng::seed_from_u64(seed));
+        assert_eq!(permutation, &permutation * &Permutation::identity());
+    }
+}
@@ -1,4 +1,5 @@
-pub mod logic;
+// The maths lives in its own crate so it can be used without the GUI
+pub use mog_core as logic;
 mod ui;
 
 /// We derive Deserialize/Serialize so we can persist app state on shutdown.
@@ -6,27 +7,149 @@ mod ui;
 #[serde(default)] // if we add new fields, give them default values when deserializing old state
 // Add #[serde(skip)] to fields to opt-out of serialization of a field
 pub struct MyApp {
-    #[serde(skip)]
-    state: Box<dyn AppState>,
+    // The tabs, each with its open screen and the screens it was opened from
+    workspaces: ui::workspaces::Workspaces,
+
+    // The saved form of the open screen from before there were tabs, only read when loading
+    session: Option<ui::session::Session>,
 
     // pixels per point i.e. zoom level
     ppp: f32,
+
+    // How the MOG is drawn in every screen
+    layout: ui::layout::MogLayout,
+
+    // Labels drawn on the points in every screen
+    coordinate_labels: ui::coordinates::CoordinateLabels,
+
+    // Colours and other preferences, changed in the settings window
+    settings: ui::settings::Settings,
+    show_settings: bool,
+
+    // Saved screens, listed in a drawer on the right
+    bookmarks: ui::bookmarks::Bookmarks,
+    show_bookmarks: bool,
+
+    // Show the weight enumerator and other facts about the Golay code
+    show_code_statistics: bool,
+
+    // Show frame times and cache statistics
+    show_perf_hud: bool,
+
+    // The width in pixels of exported images
+    png_width: u32,
+
+    // Where the last image was saved, or why it could not be
+    #[serde(skip)]
+    png_export_message: Option<Result<String, String>>,
+
+    // Why the last dropped or opened file could not be opened
+    #[serde(skip)]
+    file_drop_error: Option<String>,
+
+    // Where the session was last saved, or why it could not be
+    #[serde(skip)]
+    session_save_message: Option<Result<String, String>>,
+
+    #[serde(skip)]
+    code_import: ui::code_import::CodeImportWindow,
+
+    #[serde(skip)]
+    share_string: ui::share_string::ShareStringWindow,
+
+    #[serde(skip)]
+    command_palette: ui::command_palette::CommandPalette,
+
+    #[serde(skip)]
+    tutorial: ui::tutorial::Tutorial,
+}
+
+// Where a screen asks to go at the end of a frame
+pub enum Navigate {
+    // Open another screen on top of this one
+    Open(Box<dyn AppState>),
+    // Go back to the screen this one was opened from
+    Back,
+    // Go back, handing the screen this one was opened from the points it asked for
+    Pick(logic::miracle_octad_generator::Vector),
+    // Go back, handing the screen this one was opened from a permutation to select
+    PickPermutation(logic::permutation::Permutation<logic::miracle_octad_generator::Point>),
 }
 
 pub trait AppState {
-    fn update(
+    fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) -> Option<Navigate>;
+
+    // The name of the screen in the breadcrumb bar
+    fn title(&self) -> String;
+
+    // The screen this one was opened from, for when it is reopened on its own e.g. from a saved session
+    fn parent(&self) -> Option<Box<dyn AppState>> {
+        None
+    }
+
+    // Screens which open the point toggle screen with a `ui::selection_target::SelectionTarget` get the picked points here
+    fn picked(&mut self, _vector: logic::miracle_octad_generator::Vector) {}
+
+    // Screens which open others that make a permutation get it here
+    fn picked_permutation(
         &mut self,
-        ctx: &egui::Context,
-        frame: &mut eframe::Frame,
-    ) -> Option<Box<dyn AppState>>;
+        _permutation: logic::permutation::Permutation<logic::miracle_octad_generator::Point>,
+    ) {
+    }
+
+    // Screens with a selection or permutation that a bookmark's can replace override these, returning whether they took it
+    fn apply_selection(&mut self, _vector: &logic::miracle_octad_generator::Vector) -> bool {
+        false
+    }
+
+    fn apply_permutation(
+        &mut self,
+        _permutation: &logic::permutation::Permutation<logic::miracle_octad_generator::Point>,
+    ) -> bool {
+        false
+    }
+
+    // Screens with edits worth undoing keep a `ui::history::History` of snapshots and override these
+    fn can_undo(&self) -> bool {
+        false
+    }
+
+    fn can_redo(&self) -> bool {
+        false
+    }
+
+    fn undo(&mut self) {}
+
+    fn redo(&mut self) {}
+
+    // What to save so the screen can be reopened when the app is next started
+    fn session(&self) -> Option<ui::session::Session> {
+        None
+    }
 }
 
 impl Default for MyApp {
     fn default() -> Self {
         Self {
-            state: Box::new(ui::point_toggle::State::default()),
-            // state: Box::new(ui::permutation_selection::State::default()),
+            workspaces: Default::default(),
+            session: None,
             ppp: 2.5,
+            layout: Default::default(),
+            coordinate_labels: Default::default(),
+            settings: Default::default(),
+            show_settings: false,
+            bookmarks: Default::default(),
+            show_bookmarks: false,
+            show_code_statistics: false,
+            show_perf_hud: false,
+            png_width: 2000,
+            png_export_message: None,
+            file_drop_error: None,
+            session_save_message: None,
+            code_import: Default::default(),
+            share_string: Default::default(),
+            command_palette: Default::default(),
+            tutorial: Default::default(),
         }
     }
 }
@@ -40,11 +163,17 @@ impl MyApp {
 
         // Load previous app state (if any).
         // Note that you must enable the `persistence` feature for this to work.
-        let app: Self = if let Some(storage) = cc.storage {
+        let mut app: Self = if let Some(storage) = cc.storage {
             eframe::get_value(storage, eframe::APP_KEY).unwrap_or_default()
         } else {
             Default::default()
         };
+        let session = app.session.take();
+        if !app.workspaces.restore()
+            && let Some(state) = session.and_then(|session| session.restore())
+        {
+            *app.workspaces.navigation_mut() = ui::navigation::Navigation::new(state);
+        }
         app
     }
 }
@@ -74,11 +203,13 @@ fn setup_custom_fonts(ctx: &egui::Context) {
 impl eframe::App for MyApp {
     /// Called by the framework to save state before shutdown.
     fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        self.workspaces.save();
         eframe::set_value(storage, eframe::APP_KEY, self);
+        self.workspaces.forget_saved();
     }
 
     fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
-        // Allow changing the zoom with ctrl + scroll
+        // Allow changing the zoom with ctrl + scroll, or by pinching on a touch screen
         ctx.set_pixels_per_point(self.ppp);
 
         ctx.input(|input| {
@@ -89,18 +220,289 @@ impl eframe::App for MyApp {
                 new_scale = new_scale.clamp(0.2, 12.0);
                 self.ppp = new_scale;
             }
+            if let Some(touch) = input.multi_touch() {
+                self.ppp = (self.ppp * touch.zoom_delta).clamp(0.2, 12.0);
+            }
         });
 
+        ui::perf_hud::begin_frame(ctx);
+        ui::command_palette::begin_frame(ctx);
+        self.tutorial.begin_frame(ctx);
+
         // Global Settings
+        let mut menu_state = None;
         egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
             egui::MenuBar::new().ui(ui, |ui| {
                 egui::widgets::global_theme_preference_buttons(ui);
+                #[cfg(not(target_arch = "wasm32"))]
+                ui.menu_button("File", |ui| {
+                    if ui.button("Open…").clicked() {
+                        match ui::session_file::open() {
+                            Some(Ok(new_state)) => menu_state = Some(new_state),
+                            Some(Err(err)) => self.file_drop_error = Some(err),
+                            None => {}
+                        }
+                    }
+                    if ui.button("Save…").clicked() {
+                        self.session_save_message =
+                            ui::session_file::save(self.workspaces.navigation().current());
+                    }
+                });
+                ui.menu_button("Edit", |ui| {
+                    if ui
+                        .add_enabled(
+                            self.workspaces.navigation().current().can_undo(),
+                            egui::Button::new("Undo"),
+                        )
+                        .clicked()
+                    {
+                        self.workspaces.navigation_mut().current_mut().undo();
+                    }
+                    if ui
+                        .add_enabled(
+                            self.workspaces.navigation().current().can_redo(),
+                            egui::Button::new("Redo"),
+                        )
+                        .clicked()
+                    {
+                        self.workspaces.navigation_mut().current_mut().redo();
+                    }
+                    ui.separator();
+                    self.share_string
+                        .menu(ui, self.workspaces.navigation().current());
+                });
+                ui.menu_button("Layout", |ui| self.layout.menu(ui));
+                ui.menu_button("View", |ui| {
+                    self.coordinate_labels.menu(ui);
+                    ui.separator();
+                    ui.checkbox(&mut self.show_bookmarks, "Bookmarks");
+                    ui.checkbox(&mut self.settings.panels.hidden, "Hide the controls");
+                    ui.menu_button("Panes", |ui| self.settings.panels.menu(ui));
+                    ui.checkbox(&mut self.show_settings, "Settings");
+                    ui.separator();
+                    if ui
+                        .add(
+                            egui::Button::new("Commands…")
+                                .shortcut_text(ctx.format_shortcut(&ui::command_palette::OPEN)),
+                        )
+                        .clicked()
+                    {
+                        self.command_palette.open();
+                    }
+                });
+                ui.menu_button("Code", |ui| {
+                    menu_state = self.code_import.menu(ui);
+                    ui.checkbox(&mut self.show_code_statistics, "Statistics");
+                });
+                ui.menu_button("Export", |ui| {
+                    ui::table_export::export_menu(ui);
+                    if ui.button("Copy as SVG").clicked() {
+                        ui::svg_export::request_copy(ui.ctx());
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    ui.horizontal(|ui| {
+                        if ui.button("Export PNG").clicked() {
+                            ui::png_export::request_export(ui.ctx(), self.png_width);
+                        }
+                        ui.add(
+                            egui::DragValue::new(&mut self.png_width)
+                                .range(100..=10000)
+                                .suffix(" px wide"),
+                        );
+                    });
+                });
+                ui.menu_button("Debug", |ui| {
+                    ui.checkbox(&mut self.show_perf_hud, "Performance HUD");
+                });
+                ui.menu_button("Help", |ui| {
+                    if ui.button("Tutorial").clicked() {
+                        self.tutorial.open();
+                    }
+                });
             });
+            if self.workspaces.bar(ui) {
+                ctx.request_discard("Changed State");
+            }
+            if self.workspaces.navigation_mut().bar(ui) {
+                ctx.request_discard("Changed State");
+            }
         });
+        if let Some(new_state) = menu_state
+            .or_else(|| self.code_import.show(ctx))
+            .or_else(|| self.share_string.show(ctx))
+        {
+            self.workspaces.navigation_mut().open(new_state);
+        }
+        self.layout.set(ctx);
+        self.coordinate_labels.set(ctx);
+        self.settings.panels.show_button(ctx);
+        if ui::command_palette::offer(ctx, "Hide the controls", None) {
+            self.settings.panels.hidden = !self.settings.panels.hidden;
+        }
+        self.settings.set(ctx);
+
+        // Open files dropped onto the window
+        ui::file_drop::preview_files_being_dropped(ctx);
+        match ui::file_drop::take_dropped_file(ctx) {
+            Some(Ok(new_state)) => {
+                self.workspaces.navigation_mut().open(new_state);
+                self.file_drop_error = None;
+            }
+            Some(Err(err)) => {
+                self.file_drop_error = Some(err);
+            }
+            None => {}
+        }
+        if let Some(err) = &self.file_drop_error {
+            let mut open = true;
+            egui::Window::new("Could not open file")
+                .collapsible(false)
+                .resizable(false)
+                .open(&mut open)
+                .show(ctx, |ui| {
+                    ui.label(err);
+                });
+            if !open {
+                self.file_drop_error = None;
+            }
+        }
+
+        // Undo and redo, unless a text box wants the shortcuts for itself
+        if !ctx.wants_keyboard_input() {
+            let redo = ctx.input_mut(|i| {
+                i.consume_shortcut(&egui::KeyboardShortcut::new(
+                    egui::Modifiers::COMMAND | egui::Modifiers::SHIFT,
+                    egui::Key::Z,
+                )) || i.consume_shortcut(&egui::KeyboardShortcut::new(
+                    egui::Modifiers::COMMAND,
+                    egui::Key::Y,
+                ))
+            });
+            let undo = ctx.input_mut(|i| {
+                i.consume_shortcut(&egui::KeyboardShortcut::new(
+                    egui::Modifiers::COMMAND,
+                    egui::Key::Z,
+                ))
+            });
+            if redo {
+                self.workspaces.navigation_mut().current_mut().redo();
+            } else if undo {
+                self.workspaces.navigation_mut().current_mut().undo();
+            }
+
+            // Back and forward through the screens
+            let (back, forward) = ctx.input_mut(|i| {
+                (
+                    i.consume_shortcut(&ui::navigation::BACK),
+                    i.consume_shortcut(&ui::navigation::FORWARD),
+                )
+            });
+            if back {
+                self.workspaces.navigation_mut().go_back();
+            } else if forward {
+                self.workspaces.navigation_mut().go_forward();
+            }
+        }
 
-        if let Some(new_state) = self.state.update(ctx, frame) {
-            self.state = new_state;
+        // Menu items which can also be run from the command palette
+        if ui::command_palette::offer(ctx, "Undo", None) {
+            self.workspaces.navigation_mut().current_mut().undo();
+        }
+        if ui::command_palette::offer(ctx, "Redo", None) {
+            self.workspaces.navigation_mut().current_mut().redo();
+        }
+        if ui::command_palette::offer(ctx, "Copy as SVG", None) {
+            ui::svg_export::request_copy(ctx);
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        if ui::command_palette::offer(ctx, "Export PNG", None) {
+            ui::png_export::request_export(ctx, self.png_width);
+        }
+        if ui::command_palette::offer(ctx, "Settings", None) {
+            self.show_settings = true;
+        }
+        if ui::command_palette::offer(ctx, "Bookmarks", None) {
+            self.show_bookmarks = !self.show_bookmarks;
+        }
+        if ui::command_palette::offer(ctx, "Code statistics", None) {
+            self.show_code_statistics = true;
+        }
+        if ui::command_palette::offer(ctx, "Tutorial", None) {
+            self.tutorial.open();
+        }
+
+        if self.show_bookmarks
+            && let Some(new_state) = self
+                .bookmarks
+                .show(ctx, self.workspaces.navigation_mut().current_mut())
+        {
+            self.workspaces.navigation_mut().open(new_state);
+        }
+
+        if let Some(navigate) = self
+            .workspaces
+            .navigation_mut()
+            .current_mut()
+            .update(ctx, frame)
+        {
+            self.workspaces.navigation_mut().navigate(navigate);
             ctx.request_discard("Changed State");
         }
+        // Keep any panes the screen moved or hid from their headers
+        self.settings.panels = ui::panels::PanelLayout::get(ctx);
+        ui::svg_export::end_frame(ctx);
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(message) = ui::png_export::end_frame(ctx) {
+            self.png_export_message = Some(message);
+        }
+        if let Some(message) = &self.session_save_message {
+            let mut open = true;
+            egui::Window::new("Save Session")
+                .collapsible(false)
+                .resizable(false)
+                .open(&mut open)
+                .show(ctx, |ui| match message {
+                    Ok(path) => {
+                        ui.label(format!("Saved to {path}"));
+                    }
+                    Err(err) => {
+                        ui.colored_label(ui.visuals().error_fg_color, err);
+                    }
+                });
+            if !open {
+                self.session_save_message = None;
+            }
+        }
+        if let Some(message) = &self.png_export_message {
+            let mut open = true;
+            egui::Window::new("Export PNG")
+                .collapsible(false)
+                .resizable(false)
+                .open(&mut open)
+                .show(ctx, |ui| match message {
+                    Ok(path) => {
+                        ui.label(format!("Saved to {path}"));
+                    }
+                    Err(err) => {
+                        ui.colored_label(ui.visuals().error_fg_color, err);
+                    }
+                });
+            if !open {
+                self.png_export_message = None;
+            }
+        }
+
+        if self.show_settings {
+            self.settings.show(ctx, &mut self.show_settings);
+        }
+        if self.show_code_statistics {
+            ui::code_statistics::show(ctx, &mut self.show_code_statistics);
+        }
+        if self.show_perf_hud {
+            ui::perf_hud::show(ctx, &mut self.show_perf_hud);
+        }
+        self.tutorial
+            .show(ctx, || self.workspaces.navigation().current().session());
+        self.command_palette.show(ctx);
     }
 }
@@ -0,0 +1,168 @@
+use crate::app::logic::script::{self, Replay};
+use eframe::egui::{Button, Context, RichText, ScrollArea, TextEdit, Window};
+
+// The edits made on a screen, written out as a script which can be edited and played back
+#[derive(Clone)]
+pub struct ActionLogWindow<A: Replay> {
+    open: bool,
+    // Edits are only recorded while the window is open
+    recording: bool,
+    script: String,
+    // The state the last recorded action left, so the next can be found by comparing
+    last: Option<A::State>,
+    // The index of the line to run next when stepping through the script
+    next_line: usize,
+    error: Option<String>,
+    // The number of lines and the first line which is not an action, kept until the script changes
+    checked: Option<(usize, Option<String>)>,
+}
+
+impl<A: Replay> Default for ActionLogWindow<A> {
+    fn default() -> Self {
+        Self {
+            open: false,
+            recording: true,
+            script: String::new(),
+            last: None,
+            next_line: 0,
+            error: None,
+            checked: None,
+        }
+    }
+}
+
+impl<A: Replay> ActionLogWindow<A> {
+    pub fn open(&mut self) {
+        self.open = true;
+    }
+
+    // Call once a frame, after everything which can change the state
+    pub fn record(&mut self, state: impl FnOnce() -> A::State) {
+        if !(self.open && self.recording) {
+            // Edits made meanwhile are not compared against when recording starts again
+            self.last = None;
+            return;
+        }
+        let state = state();
+        if let Some(last) = &self.last {
+            for action in A::between(last, &state) {
+                self.script.push_str(&format!("{action}\n"));
+                self.checked = None;
+            }
+        }
+        self.last = Some(state);
+    }
+
+    // Run the actions from the next line on, stopping after the first when `all` is false
+    fn run(&mut self, mut state: A::State, all: bool) -> A::State {
+        self.error = None;
+        let lines = self.script.lines().collect::<Vec<_>>();
+        while self.next_line < lines.len() {
+            let i = self.next_line;
+            match A::parse(lines[i]) {
+                Ok(None) => self.next_line += 1,
+                Ok(Some(action)) => {
+                    action.run(&mut state);
+                    self.next_line += 1;
+                    if !all {
+                        break;
+                    }
+                }
+                Err(err) => {
+                    self.error = Some(format!("Line {}: {err}", i + 1));
+                    break;
+                }
+            }
+        }
+        // Replayed actions are already in the script
+        self.last = Some(state.clone());
+        state
+    }
+
+    // Returns the state after any actions replayed
+    pub fn show(&mut self, ctx: &Context, state: impl Fn() -> A::State) -> Option<A::State> {
+        if !self.open {
+            return None;
+        }
+        let mut replayed = None;
+        let mut open = self.open;
+        Window::new("Action Log").open(&mut open).show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut self.recording, "Record")
+                    .on_hover_text("Edits are recorded while this window is open");
+                if ui.button("Clear").clicked() {
+                    self.script.clear();
+                    self.checked = None;
+                    self.next_line = 0;
+                    self.error = None;
+                }
+                if ui.button("Copy").clicked() {
+                    ui.ctx().copy_text(self.script.clone());
+                }
+            });
+            ui.label(format!("One action per line: {}", A::COMMANDS));
+            ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                if ui
+                    .add(
+                        TextEdit::multiline(&mut self.script)
+                            .code_editor()
+                            .desired_rows(10)
+                            .hint_text(A::EXAMPLE),
+                    )
+                    .changed()
+                {
+                    self.checked = None;
+                    self.error = None;
+                }
+            });
+
+            let (lines, warning) = self
+                .checked
+                .get_or_insert_with(|| {
+                    (
+                        self.script.lines().count(),
+                        script::parse::<A>(&self.script).err(),
+                    )
+                })
+                .clone();
+
+            ui.heading("Replay");
+            self.next_line = self.next_line.min(lines);
+            ui.horizontal(|ui| {
+                if ui
+                    .button("Restart")
+                    .on_hover_text("Go back to the first line")
+                    .clicked()
+                {
+                    self.next_line = 0;
+                    self.error = None;
+                }
+                if ui
+                    .add_enabled(self.next_line < lines, Button::new("Step"))
+                    .clicked()
+                {
+                    replayed = Some(self.run(state(), false));
+                }
+                if ui
+                    .add_enabled(self.next_line < lines, Button::new("Run all"))
+                    .clicked()
+                {
+                    replayed = Some(self.run(state(), true));
+                }
+            });
+            match self.script.lines().nth(self.next_line) {
+                Some(line) => ui.label(
+                    RichText::new(format!("Next, line {}: {line}", self.next_line + 1)).monospace(),
+                ),
+                None => ui.label("At the end of the script"),
+            };
+            if let Some(err) = &self.error {
+                ui.colored_label(ui.visuals().error_fg_color, err);
+            } else if let Some(warning) = warning {
+                ui.colored_label(ui.visuals().warn_fg_color, warning);
+            }
+        });
+        self.open = open;
+        replayed
+    }
+}
@@ -0,0 +1,48 @@
+use eframe::egui::{Color32, Context, Id};
+
+// How long hover highlights take to fade in and out, in seconds
+pub const HOVER_FADE: f32 = 0.15;
+
+// How long points take to move to their images when a permutation is applied, in seconds
+pub const APPLY_DURATION: f32 = 0.5;
+
+// Move the value stored under `id` towards `target`, covering a distance of 1 every `duration` seconds
+// The first call for an id jumps straight to the target
+// Uses the frame time rather than counting frames so animations run at the same speed at any repaint rate
+pub fn animate(ctx: &Context, id: Id, target: f32, duration: f32) -> f32 {
+    let dt = ctx.input(|i| i.stable_dt);
+    let value = ctx.data_mut(|data| {
+        let value = data.get_temp_mut_or(id, target);
+        *value = if duration <= 0.0 {
+            target
+        } else {
+            let step = dt / duration;
+            if (target - *value).abs() <= step {
+                target
+            } else {
+                *value + step * (target - *value).signum()
+            }
+        };
+        *value
+    });
+    if value != target {
+        ctx.request_repaint();
+    }
+    value
+}
+
+// A value which goes from 0 to 1 over `duration` seconds while `on` is true and back again once it is false
+pub fn fade(ctx: &Context, id: Id, on: bool, duration: f32) -> f32 {
+    animate(ctx, id, if on { 1.0 } else { 0.0 }, duration)
+}
+
+// Blend from `from` at t = 0 to `to` at t = 1
+pub fn lerp_colour(from: Color32, to: Color32, t: f32) -> Color32 {
+    from.lerp_to_gamma(to, t.clamp(0.0, 1.0))
+}
+
+// Start and end slowly, for t from 0 to 1
+pub fn ease_in_out(t: f32) -> f32 {
+    let t = t.clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
@@ -0,0 +1,159 @@
+use crate::app::AppState;
+use crate::app::logic::miracle_octad_generator::{Point, Vector};
+use crate::app::logic::permutation::Permutation;
+use crate::app::ui::session::Session;
+use eframe::egui::{Button, Context, ScrollArea, SidePanel, TextEdit};
+
+// A screen saved under a name so it can be reopened later
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Bookmark {
+    pub name: String,
+    pub session: Session,
+}
+
+impl Bookmark {
+    // The points saved, which can be selected on another screen
+    pub fn selection(&self) -> &Vector {
+        match &self.session {
+            Session::PointToggle(session) => &session.selected_points,
+            Session::SextetLabelling(session) => &session.foursome,
+        }
+    }
+
+    // The permutation saved, if it is not the identity
+    pub fn permutation(&self) -> Option<&Permutation<Point>> {
+        match &self.session {
+            Session::PointToggle(session) => Some(&session.selected_permutation),
+            Session::SextetLabelling(_) => None,
+        }
+        .filter(|permutation| **permutation != Permutation::identity())
+    }
+
+    // A short description of what is saved, e.g. `8 points, (0 1)(2 3)`
+    pub fn description(&self) -> String {
+        match &self.session {
+            Session::PointToggle(session) => {
+                let mut description = format!("{} points", session.selected_points.weight());
+                if session.selected_permutation != Permutation::identity() {
+                    description += ", ";
+                    description += &session.selected_permutation.to_cycle_string();
+                }
+                if session.code.is_some() {
+                    description += ", imported code";
+                }
+                description
+            }
+            Session::SextetLabelling(session) => format!(
+                "Sextet labelling, {} of 24 points labelled",
+                session
+                    .labelling
+                    .iter()
+                    .filter(|(_, x)| x.is_some())
+                    .count()
+            ),
+        }
+    }
+}
+
+// The list of bookmarks, persisted along with the rest of the app
+#[derive(Debug, Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Bookmarks {
+    bookmarks: Vec<Bookmark>,
+    #[serde(skip)]
+    new_name: String,
+    // Why the last bookmark could not be applied to the open screen
+    #[serde(skip)]
+    apply_error: Option<String>,
+}
+
+impl Bookmarks {
+    // A drawer on the right listing the bookmarks, which must be shown before the screen's central panel
+    // Bookmarks are opened as new screens, or their selection or permutation is applied to the open one
+    pub fn show(&mut self, ctx: &Context, state: &mut dyn AppState) -> Option<Box<dyn AppState>> {
+        SidePanel::right("bookmarks")
+            .min_width(200.0)
+            .show(ctx, |ui| {
+                ui.heading("Bookmarks");
+
+                // Save the current screen
+                let session = state.session();
+                ui.horizontal(|ui| {
+                    ui.add(
+                        TextEdit::singleline(&mut self.new_name)
+                            .hint_text("Name")
+                            .desired_width(120.0),
+                    );
+                    if ui
+                        .add_enabled(
+                            session.is_some() && !self.new_name.trim().is_empty(),
+                            Button::new("Add"),
+                        )
+                        .on_disabled_hover_text(if session.is_none() {
+                            "This screen can't be bookmarked"
+                        } else {
+                            "Enter a name"
+                        })
+                        .clicked()
+                    {
+                        self.bookmarks.push(Bookmark {
+                            name: self.new_name.trim().to_string(),
+                            session: session.unwrap(),
+                        });
+                        self.new_name.clear();
+                    }
+                });
+
+                if self.bookmarks.is_empty() {
+                    ui.label("No bookmarks yet");
+                }
+
+                let mut new_state = None;
+                let mut remove = None;
+                ScrollArea::vertical().show(ui, |ui| {
+                    for (i, bookmark) in self.bookmarks.iter().enumerate() {
+                        ui.horizontal(|ui| {
+                            if ui
+                                .button(&bookmark.name)
+                                .on_hover_text(bookmark.description())
+                                .clicked()
+                            {
+                                // None if the bookmark no longer makes sense
+                                new_state = bookmark.session.clone().restore();
+                            }
+                            if ui
+                                .small_button("Select")
+                                .on_hover_text("Select its points on this screen")
+                                .clicked()
+                            {
+                                self.apply_error = (!state.apply_selection(bookmark.selection()))
+                                    .then(|| "This screen has no selection to replace".to_string());
+                            }
+                            if let Some(permutation) = bookmark.permutation()
+                                && ui
+                                    .small_button("Permutation")
+                                    .on_hover_text("Use its permutation on this screen")
+                                    .clicked()
+                            {
+                                self.apply_error =
+                                    (!state.apply_permutation(permutation)).then(|| {
+                                        "This screen has no permutation to replace".to_string()
+                                    });
+                            }
+                            if ui.small_button("🗑").on_hover_text("Remove").clicked() {
+                                remove = Some(i);
+                            }
+                        });
+                    }
+                });
+                if let Some(error) = &self.apply_error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+                if let Some(i) = remove {
+                    self.bookmarks.remove(i);
+                }
+                new_state
+            })
+            .inner
+    }
+}
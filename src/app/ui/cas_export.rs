@@ -0,0 +1,179 @@
+use crate::app::logic::miracle_octad_generator::{Point, Vector};
+use crate::app::logic::permutation::Permutation;
+use crate::app::logic::traits::Enumerated;
+use eframe::egui::Ui;
+
+// Computer algebra systems the selection can be exported to, to continue computing with it there
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CasFormat {
+    Gap,
+    // GAP with permutations given by the list of images rather than cycles
+    GapPermList,
+    Magma,
+}
+
+impl CasFormat {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CasFormat::Gap => "GAP",
+            CasFormat::GapPermList => "GAP PermList",
+            CasFormat::Magma => "Magma",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            CasFormat::Gap | CasFormat::GapPermList => "g",
+            CasFormat::Magma => "m",
+        }
+    }
+}
+
+// Something drawn on the MOG, written as an assignment in the syntax of a computer algebra system
+// GAP and Magma both permute the points 1 to 24, so every point is one more than in the MOG numbering
+pub enum CasObject {
+    Set(Vector),
+    Octad(Vector),
+    Sextet(Vec<Vector>),
+    Permutation(Permutation<Point>),
+}
+
+fn point_list(vector: &Vector) -> String {
+    vector
+        .points()
+        .map(|p| (p.point_to_usize() + 1).to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn cycles(permutation: &Permutation<Point>) -> String {
+    permutation
+        .sorted_cycles()
+        .into_iter()
+        .map(|cycle| {
+            format!(
+                "({})",
+                cycle
+                    .into_iter()
+                    .map(|i| (i + 1).to_string())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            )
+        })
+        .collect()
+}
+
+fn images(permutation: &Permutation<Point>) -> String {
+    Point::points()
+        .map(|p| (permutation.apply(&p).point_to_usize() + 1).to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl CasObject {
+    fn variable(&self) -> &'static str {
+        match self {
+            CasObject::Set(_) => "set",
+            CasObject::Octad(_) => "octad",
+            CasObject::Sextet(_) => "sextet",
+            CasObject::Permutation(_) => "perm",
+        }
+    }
+
+    fn value(&self, format: CasFormat) -> String {
+        let is_identity = |permutation: &Permutation<Point>| permutation.sorted_cycles().is_empty();
+        match (self, format) {
+            (CasObject::Set(vector) | CasObject::Octad(vector), CasFormat::Magma) => {
+                format!("{{{}}}", point_list(vector))
+            }
+            (CasObject::Set(vector) | CasObject::Octad(vector), _) => {
+                format!("[{}]", point_list(vector))
+            }
+            (CasObject::Sextet(tetrads), CasFormat::Magma) => format!(
+                "{{{}}}",
+                tetrads
+                    .iter()
+                    .map(|tetrad| format!("{{{}}}", point_list(tetrad)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            (CasObject::Sextet(tetrads), _) => format!(
+                "[{}]",
+                tetrads
+                    .iter()
+                    .map(|tetrad| format!("[{}]", point_list(tetrad)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            (CasObject::Permutation(permutation), CasFormat::Gap) => {
+                if is_identity(permutation) {
+                    "()".to_string()
+                } else {
+                    cycles(permutation)
+                }
+            }
+            (CasObject::Permutation(permutation), CasFormat::GapPermList) => {
+                format!("PermList([{}])", images(permutation))
+            }
+            (CasObject::Permutation(permutation), CasFormat::Magma) => {
+                if is_identity(permutation) {
+                    "Id(Sym(24))".to_string()
+                } else {
+                    format!("Sym(24)!{}", cycles(permutation))
+                }
+            }
+        }
+    }
+
+    pub fn export(&self, format: CasFormat) -> String {
+        format!("{} := {};\n", self.variable(), self.value(format))
+    }
+
+    // Ask where to write the export, suggesting a file named after the variable
+    // Returns the path it was saved to, or None if the dialog was cancelled
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save(&self, format: CasFormat) -> Option<Result<String, String>> {
+        let path = rfd::FileDialog::new()
+            .add_filter(format.name(), &[format.extension()])
+            .set_file_name(format!("{}.{}", self.variable(), format.extension()))
+            .save_file()?;
+        Some(
+            std::fs::write(&path, self.export(format))
+                .map(|()| path.display().to_string())
+                .map_err(|err| format!("Could not write {}: {err}", path.display())),
+        )
+    }
+}
+
+// A row of buttons, one per format, copying the object produced by `object` to the clipboard
+// Natively there is also a button per format saving it to a file, and the outcome of saving is returned
+pub fn export_buttons(
+    ui: &mut Ui,
+    label: &str,
+    formats: &[CasFormat],
+    object: impl Fn() -> CasObject,
+) -> Option<Result<String, String>> {
+    #[cfg_attr(target_arch = "wasm32", allow(unused_mut))]
+    let mut saved = None;
+    ui.horizontal(|ui| {
+        ui.label(label);
+        for format in formats {
+            if ui
+                .button(format.name())
+                .on_hover_text("Copy to the clipboard")
+                .clicked()
+            {
+                ui.ctx().copy_text(object().export(*format));
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            if ui
+                .small_button("💾")
+                .on_hover_text(format!("Save as {}", format.name()))
+                .clicked()
+            {
+                saved = object().save(*format);
+            }
+        }
+    });
+    saved
+}
@@ -0,0 +1,74 @@
+use crate::app::AppState;
+use crate::app::logic::linear_code::BinaryLinearCode;
+use crate::app::logic::miracle_octad_generator::BinaryCode;
+use eframe::egui::{Context, TextEdit, Ui, Window};
+use std::sync::Arc;
+
+// A window for pasting the generator matrix of a [24, 12] code to use in place of the Golay code
+// Matrices of other sizes open a simpler screen for codes of any length
+#[derive(Default)]
+pub struct CodeImportWindow {
+    open: bool,
+    text: String,
+    error: Option<String>,
+}
+
+impl CodeImportWindow {
+    // Menu entries for choosing which code the screens operate on
+    pub fn menu(&mut self, ui: &mut Ui) -> Option<Box<dyn AppState>> {
+        if ui.button("Import Generator Matrix").clicked() {
+            self.open = true;
+        }
+        if ui.button("Golay Code").clicked() {
+            return Some(Box::new(super::point_toggle::State::default()));
+        }
+        None
+    }
+
+    pub fn show(&mut self, ctx: &Context) -> Option<Box<dyn AppState>> {
+        let mut open = self.open;
+        let new_state = Window::new("Import Generator Matrix")
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.label("Paste 12 rows of 24 binary digits, one row per line.");
+                ui.label("Other sizes, up to 16 rows of 64 digits, open without the MOG.");
+                ui.add(
+                    TextEdit::multiline(&mut self.text)
+                        .code_editor()
+                        .desired_rows(12),
+                );
+                if let Some(error) = &self.error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+                if ui.button("Load").clicked() {
+                    match parse_code(&self.text) {
+                        Ok(state) => {
+                            self.error = None;
+                            self.open = false;
+                            return Some(state);
+                        }
+                        Err(error) => {
+                            self.error = Some(error);
+                        }
+                    }
+                }
+                None
+            })
+            .and_then(|response| response.inner.flatten());
+        self.open &= open;
+        new_state
+    }
+}
+
+pub fn open_code(code: BinaryCode) -> Box<dyn AppState> {
+    Box::new(super::point_toggle::State::default().with_code(Some(Arc::new(code))))
+}
+
+// A [24, 12] code is used in place of the Golay code on the MOG, and any other code gets its own screen
+pub fn parse_code(text: &str) -> Result<Box<dyn AppState>, String> {
+    if let Ok(code) = BinaryCode::parse_generator_matrix(text) {
+        return Ok(open_code(code));
+    }
+    let code = BinaryLinearCode::parse_generator_matrix(text)?;
+    Ok(Box::new(super::linear_code_view::State::new(code)))
+}
@@ -0,0 +1,97 @@
+use crate::app::logic::linear_code::{self, BinaryLinearCode, Code, Vector};
+use eframe::egui::{RichText, Ui};
+
+fn row_text(row: &Vector, length: usize) -> RichText {
+    RichText::new(
+        (0..length)
+            .map(|i| if row.contains_point(i) { '1' } else { '0' })
+            .collect::<String>(),
+    )
+    .monospace()
+}
+
+// What the dual code turned out to be
+#[derive(Debug, Clone, Copy)]
+enum Dual {
+    Itself,
+    // Its dimension and minimum distance
+    Other(usize, usize),
+    TooLarge,
+}
+
+// The systematic generator matrix and the parity-check matrix of a code, with what is known of its dual
+// Finding the dual enumerates its codewords, so this is worked out once per code and kept by the caller
+#[derive(Debug)]
+pub struct Matrices {
+    length: usize,
+    information_set: Vec<usize>,
+    generator_matrix: Vec<Vector>,
+    parity_check_matrix: Vec<Vector>,
+    dual: Dual,
+}
+
+impl Matrices {
+    pub fn new(code: &BinaryLinearCode) -> Self {
+        let dual = match code.dual() {
+            Ok(dual) if dual.codewords() == code.codewords() => Dual::Itself,
+            Ok(dual) => Dual::Other(dual.dimension(), dual.minimum_distance()),
+            Err(_) => Dual::TooLarge,
+        };
+        Self {
+            length: code.length(),
+            information_set: code.information_set(),
+            generator_matrix: code.generator_matrix(),
+            parity_check_matrix: code.parity_check_matrix(),
+            dual,
+        }
+    }
+}
+
+// The matrices with the syndrome of the selection
+// Shown inside a collapsing header, so the matrices need only be worked out while it is open
+pub fn show(ui: &mut Ui, matrices: &Matrices, selection: &Vector) {
+    let n = matrices.length;
+    let information_set = matrices
+        .information_set
+        .iter()
+        .map(|i| i.to_string())
+        .collect::<Vec<_>>()
+        .join(" ");
+    ui.label(format!(
+        "Generator matrix, the identity on the columns {information_set}"
+    ));
+    for row in &matrices.generator_matrix {
+        ui.label(row_text(row, n));
+    }
+
+    ui.label("Parity-check matrix");
+    for row in &matrices.parity_check_matrix {
+        ui.label(row_text(row, n));
+    }
+
+    let syndrome = linear_code::syndrome(&matrices.parity_check_matrix, selection);
+    ui.horizontal(|ui| {
+        ui.label("Syndrome of the selection");
+        ui.label(row_text(&syndrome, matrices.parity_check_matrix.len()));
+    });
+    if syndrome == Vector::zero() {
+        ui.label("It's zero, so the selection is a codeword");
+    }
+
+    match matrices.dual {
+        Dual::Itself => {
+            ui.label("The code is its own dual");
+        }
+        Dual::Other(dimension, minimum_distance) => {
+            ui.label(format!(
+                "The dual code is a [{n}, {dimension}, {minimum_distance}] code"
+            ));
+        }
+        Dual::TooLarge => {
+            ui.label(format!(
+                "The dual code has dimension {}, too large to list",
+                matrices.parity_check_matrix.len()
+            ));
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use crate::app::logic::linear_code::Code;
+use crate::app::logic::miracle_octad_generator::LinearCode;
+use crate::app::ui::palette::Palette;
+use eframe::egui::{Context, Grid, Window};
+use std::sync::OnceLock;
+
+// Numbers describing the Golay code, which take a pass over all 4096 codewords so are only computed once
+struct CodeStatistics {
+    length: usize,
+    dimension: usize,
+    minimum_distance: usize,
+    weight_distribution: Vec<usize>,
+    self_dual: bool,
+}
+
+static STATISTICS: OnceLock<CodeStatistics> = OnceLock::new();
+
+fn statistics() -> &'static CodeStatistics {
+    STATISTICS.get_or_init(|| {
+        let mog = super::mog::mog();
+        let weight_distribution = mog.weight_distribution();
+        CodeStatistics {
+            length: weight_distribution.len() - 1,
+            dimension: mog.dimension(),
+            minimum_distance: mog.minimum_distance(),
+            weight_distribution,
+            self_dual: mog.is_self_dual(),
+        }
+    })
+}
+
+// The weight enumerator as a polynomial in x, e.g. `1 + 759x^8 + x^24`
+fn weight_enumerator(weight_distribution: &[usize]) -> String {
+    weight_distribution
+        .iter()
+        .enumerate()
+        .filter(|(_, count)| **count != 0)
+        .map(|(weight, count)| match (weight, count) {
+            (0, count) => count.to_string(),
+            (weight, 1) => format!("x^{weight}"),
+            (weight, count) => format!("{count}x^{weight}"),
+        })
+        .collect::<Vec<_>>()
+        .join(" + ")
+}
+
+// A window of facts about the Golay code
+pub fn show(ctx: &Context, open: &mut bool) {
+    let statistics = statistics();
+    Window::new("Code Statistics")
+        .open(open)
+        .resizable(false)
+        .show(ctx, |ui| {
+            Grid::new("code_statistics_grid")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    ui.label("Length");
+                    ui.label(statistics.length.to_string());
+                    ui.end_row();
+
+                    ui.label("Dimension");
+                    ui.label(statistics.dimension.to_string());
+                    ui.end_row();
+
+                    ui.label("Minimum distance");
+                    ui.label(statistics.minimum_distance.to_string());
+                    ui.end_row();
+
+                    ui.label("Self-dual");
+                    if statistics.self_dual {
+                        ui.colored_label(Palette::get(ctx).good(), "Yes");
+                    } else {
+                        ui.colored_label(ui.visuals().error_fg_color, "No");
+                    }
+                    ui.end_row();
+                });
+
+            ui.heading("Weight Enumerator");
+            ui.label(weight_enumerator(&statistics.weight_distribution));
+            Grid::new("code_statistics_weights")
+                .num_columns(2)
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.strong("Weight");
+                    ui.strong("Codewords");
+                    ui.end_row();
+                    for (weight, count) in statistics.weight_distribution.iter().enumerate() {
+                        if *count != 0 {
+                            ui.label(weight.to_string());
+                            ui.label(count.to_string());
+                            ui.end_row();
+                        }
+                    }
+                });
+        });
+}
@@ -0,0 +1,211 @@
+use eframe::egui::{
+    Align2, Context, Id, Key, KeyboardShortcut, Modifiers, ScrollArea, TextEdit, Window,
+};
+
+pub const OPEN: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::P);
+
+#[derive(Debug, Clone, PartialEq)]
+struct Command {
+    name: String,
+    shortcut: Option<KeyboardShortcut>,
+}
+
+// The commands the screens offered during the current frame, and the one picked from the palette
+#[derive(Debug, Clone, Default)]
+struct Commands {
+    offered: Vec<Command>,
+    // Run the next time it is offered
+    chosen: Option<Command>,
+}
+
+fn id() -> Id {
+    Id::new("command_palette_commands")
+}
+
+fn with_commands<T>(ctx: &Context, f: impl FnOnce(&mut Commands) -> T) -> T {
+    ctx.data_mut(|data| f(data.get_temp_mut_or_default::<Commands>(id())))
+}
+
+// Forget what was offered in the previous frame
+pub fn begin_frame(ctx: &Context) {
+    with_commands(ctx, |commands| commands.offered.clear());
+}
+
+/*
+List a command in the palette for as long as it is offered every frame, usually while its button is shown
+Returns true on the frame after it is picked, so the caller runs it the same way as when its button is clicked
+Buttons made with `keyboard::button` offer themselves
+*/
+pub fn offer(ctx: &Context, name: &str, shortcut: Option<KeyboardShortcut>) -> bool {
+    let command = Command {
+        name: name.to_string(),
+        shortcut,
+    };
+    with_commands(ctx, |commands| {
+        if commands.chosen.as_ref() == Some(&command) {
+            commands.chosen = None;
+            return true;
+        }
+        if !commands.offered.contains(&command) {
+            commands.offered.push(command);
+        }
+        false
+    })
+}
+
+// How well the query matches the name, lower being better, or None if its letters are not all in the name in order
+// Letters matched at the start of a word or straight after the previous match count in its favour
+pub fn fuzzy_score(query: &str, name: &str) -> Option<usize> {
+    let name = name.to_lowercase().chars().collect::<Vec<_>>();
+    let mut score = 0;
+    let mut next = 0;
+    for q in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let i = next + name[next..].iter().position(|c| *c == q)?;
+        let word_start = i == 0 || !name[i - 1].is_alphanumeric();
+        if i != next && !word_start {
+            score += i - next;
+        }
+        next = i + 1;
+    }
+    Some(score)
+}
+
+// A search box over every command offered this frame, opened with ctrl + P
+#[derive(Debug, Default)]
+pub struct CommandPalette {
+    open: bool,
+    query: String,
+    // The position of the highlighted command among those matching the query
+    highlighted: usize,
+}
+
+impl CommandPalette {
+    pub fn open(&mut self) {
+        self.open = true;
+        self.query.clear();
+        self.highlighted = 0;
+    }
+
+    // Show the palette after the screen has drawn, so every command it offers is listed
+    pub fn show(&mut self, ctx: &Context) {
+        // A command picked last frame which was not offered again, e.g. because the screen changed, is dropped
+        with_commands(ctx, |commands| commands.chosen = None);
+        if ctx.input_mut(|i| i.consume_shortcut(&OPEN)) {
+            if self.open {
+                self.open = false;
+            } else {
+                self.open();
+            }
+        }
+        if !self.open {
+            return;
+        }
+
+        let mut matches = with_commands(ctx, |commands| {
+            commands
+                .offered
+                .iter()
+                .filter_map(|command| {
+                    Some((fuzzy_score(&self.query, &command.name)?, command.clone()))
+                })
+                .collect::<Vec<_>>()
+        });
+        matches.sort_by_key(|(score, _)| *score);
+        self.highlighted = self.highlighted.min(matches.len().saturating_sub(1));
+
+        let mut chosen = None;
+        let mut open = true;
+        Window::new("Commands")
+            .open(&mut open)
+            .collapsible(false)
+            .anchor(Align2::CENTER_TOP, [0.0, 40.0])
+            .show(ctx, |ui| {
+                let response = ui.add(
+                    TextEdit::singleline(&mut self.query)
+                        .hint_text("Type to search")
+                        .desired_width(300.0),
+                );
+                response.request_focus();
+                if response.changed() {
+                    self.highlighted = 0;
+                }
+                ui.input_mut(|i| {
+                    if i.consume_key(Modifiers::NONE, Key::ArrowDown) {
+                        self.highlighted =
+                            (self.highlighted + 1).min(matches.len().saturating_sub(1));
+                    }
+                    if i.consume_key(Modifiers::NONE, Key::ArrowUp) {
+                        self.highlighted = self.highlighted.saturating_sub(1);
+                    }
+                    if i.consume_key(Modifiers::NONE, Key::Enter) {
+                        chosen = matches
+                            .get(self.highlighted)
+                            .map(|(_, command)| command.clone());
+                    }
+                });
+
+                ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                    if matches.is_empty() {
+                        ui.label("No matching commands");
+                    }
+                    for (i, (_, command)) in matches.iter().enumerate() {
+                        ui.horizontal(|ui| {
+                            let label = ui.selectable_label(i == self.highlighted, &command.name);
+                            if i == self.highlighted {
+                                label.scroll_to_me(None);
+                            }
+                            if label.clicked() {
+                                chosen = Some(command.clone());
+                            }
+                            if let Some(shortcut) = &command.shortcut {
+                                ui.weak(ctx.format_shortcut(shortcut));
+                            }
+                        });
+                    }
+                });
+            });
+
+        if chosen.is_some()
+            || !open
+            || ctx.input_mut(|i| i.consume_key(Modifiers::NONE, Key::Escape))
+        {
+            self.open = false;
+        }
+        if let Some(command) = chosen {
+            with_commands(ctx, |commands| commands.chosen = Some(command));
+            ctx.request_repaint();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fuzzy_score;
+
+    #[test]
+    fn matches_letters_in_order() {
+        assert_eq!(fuzzy_score("", "Complete octad: Complete"), Some(0));
+        assert!(fuzzy_score("cmp oct", "Complete octad: Complete").is_some());
+        assert_eq!(fuzzy_score("octc", "Complete octad"), None);
+        assert_eq!(fuzzy_score("x", "Complete octad"), None);
+    }
+
+    #[test]
+    fn ignores_case_and_spaces() {
+        assert_eq!(
+            fuzzy_score("RANDOM auto", "Random automorphism"),
+            fuzzy_score("randomauto", "Random automorphism")
+        );
+    }
+
+    #[test]
+    fn prefers_word_starts_and_runs() {
+        // A prefix of the name and the initials of its words cost nothing
+        assert_eq!(fuzzy_score("comp", "Complete sextet: Select"), Some(0));
+        assert_eq!(fuzzy_score("css", "Complete sextet: Select"), Some(0));
+        assert_eq!(fuzzy_score("cse", "Complete sextet: Select"), Some(0));
+        // Skipping letters in the middle of a word does
+        assert_eq!(fuzzy_score("set", "Complete sextet: Select"), Some(1));
+        assert!(fuzzy_score("cete", "Complete sextet: Select").unwrap() > 0);
+    }
+}
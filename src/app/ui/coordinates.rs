@@ -0,0 +1,97 @@
+use crate::app::logic::miracle_octad_generator::Point;
+use crate::app::logic::psl2_23;
+use crate::app::logic::traits::Enumerated;
+use crate::app::ui::grid::GridShower;
+use crate::app::ui::layout::MogLayout;
+use crate::app::ui::mog::{draw_f4, row_to_f4};
+use eframe::egui::{Align2, Context, FontId, Id, Painter, Rect, Ui, Vec2};
+
+// Labels drawn in the corner of every point of the MOG, to match it up with the literature
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum CoordinateLabels {
+    #[default]
+    None,
+    // The numbering 0-23 used throughout this app, row by row
+    Mog,
+    // Curtis's labels by the projective line over F23, as printed with the MOG
+    Curtis,
+    // The points of the projective line over F23 the generators α, β, γ, δ are written in
+    Projective,
+    // The column 1-6 and the F4 row label of each point
+    Hexacode,
+}
+
+impl CoordinateLabels {
+    fn id() -> Id {
+        Id::new("coordinate_labels")
+    }
+
+    // The labels every screen should draw this frame
+    pub fn get(ctx: &Context) -> Self {
+        ctx.data(|data| data.get_temp(Self::id()).unwrap_or_default())
+    }
+
+    pub fn set(self, ctx: &Context) {
+        ctx.data_mut(|data| data.insert_temp(Self::id(), self));
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CoordinateLabels::None => "No Labels",
+            CoordinateLabels::Mog => "MOG Numbering 0-23",
+            CoordinateLabels::Curtis => "Curtis's Labels",
+            CoordinateLabels::Projective => "PSL(2, 23) Labels",
+            CoordinateLabels::Hexacode => "Hexacode Column and Row",
+        }
+    }
+
+    pub fn menu(&mut self, ui: &mut Ui) {
+        for labels in [
+            CoordinateLabels::None,
+            CoordinateLabels::Mog,
+            CoordinateLabels::Curtis,
+            CoordinateLabels::Projective,
+            CoordinateLabels::Hexacode,
+        ] {
+            ui.radio_value(self, labels, labels.name());
+        }
+    }
+
+    // Draw the label of every point, over whatever has been drawn in its cell
+    pub fn draw(&self, ui: &mut Ui, painter: &Painter, grid: &GridShower, layout: MogLayout) {
+        if *self == CoordinateLabels::None {
+            return;
+        }
+        let colour = ui.visuals().text_color();
+        for p in Point::points() {
+            let i = p.point_to_usize();
+            let rect = grid.cell_to_rect(layout.point_to_cell(p));
+            let size = 0.28 * rect.height();
+            let corner = rect.min + Vec2::splat(0.1 * rect.height());
+            let text = |text: String| {
+                painter.text(
+                    corner,
+                    Align2::LEFT_TOP,
+                    text,
+                    FontId::proportional(size),
+                    colour,
+                );
+            };
+            match self {
+                CoordinateLabels::None => {}
+                CoordinateLabels::Mog => text(i.to_string()),
+                CoordinateLabels::Curtis => text(psl2_23::curtis_label_string(p)),
+                CoordinateLabels::Projective => text(psl2_23::label_string(p)),
+                CoordinateLabels::Hexacode => {
+                    // The F4 element is drawn the same way as the row labels, beside the column
+                    text((i % 6 + 1).to_string());
+                    let row_rect = Rect::from_center_size(
+                        corner + Vec2::new(size, 0.5 * size),
+                        Vec2::splat(size / 0.7),
+                    );
+                    draw_f4(ui, painter, row_rect, colour, row_to_f4(i / 6));
+                }
+            }
+        }
+    }
+}
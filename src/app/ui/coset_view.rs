@@ -0,0 +1,134 @@
+use crate::app::logic::miracle_octad_generator::{Point, Vector};
+use crate::app::logic::traits::Labelled;
+use crate::app::ui::mog::sextet_idx_to_colour;
+use crate::app::ui::mog_selection::MogSelection;
+use crate::app::ui::panels::side_panel;
+use crate::app::ui::session::{PointToggleSession, Session};
+use crate::app::{AppState, Navigate};
+use eframe::{
+    Frame,
+    egui::{CentralPanel, Color32, Context, Grid, RichText},
+};
+
+// The coset of the Golay code containing a selection, found from its syndrome
+#[derive(Clone)]
+pub struct State {
+    parent: PointToggleSession,
+    vector: Vector,
+    // Computed once, since it takes a pass over all 4096 codewords
+    weight_distribution: Vec<usize>,
+}
+
+impl State {
+    pub fn new(parent: PointToggleSession) -> Self {
+        let vector = parent.selected_points.clone();
+        let weight_distribution = super::mog::mog().coset_weight_distribution(&vector);
+        Self {
+            parent,
+            vector,
+            weight_distribution,
+        }
+    }
+}
+
+impl AppState for State {
+    fn update(&mut self, ctx: &Context, _frame: &mut Frame) -> Option<Navigate> {
+        let mog = super::mog::mog();
+        let leaders = mog.coset_leaders(&self.vector);
+        let mut preview_leader = None;
+
+        if let Some(navigate) = side_panel(ctx, |ui| -> Option<Navigate> {
+            // Back
+            if ui.button("Back").clicked() {
+                return Some(Navigate::Back);
+            }
+
+            ui.heading("Coset");
+            ui.label("The selection plus every codeword of the Golay code");
+            // One bit for the parity of the selection on each row of the generator matrix
+            let syndrome = format!("{:012b}", mog.syndrome(&self.vector));
+            ui.horizontal(|ui| {
+                ui.label("Syndrome");
+                ui.label(RichText::new(syndrome).monospace());
+            });
+
+            // Every coset has a unique leader of weight at most 3, or six of weight 4 forming a sextet
+            ui.heading(if leaders.len() == 1 {
+                "Coset Leader"
+            } else {
+                "Coset Leaders"
+            });
+            ui.label(format!("Weight {}", leaders[0].weight()));
+            if leaders.len() == 6 {
+                ui.label("The six leaders are the foursomes of a sextet");
+            }
+            for (i, leader) in leaders.iter().enumerate() {
+                let selected = ui.horizontal(|ui| {
+                    let button = ui.button("Select");
+                    if button.hovered() {
+                        preview_leader = Some(i);
+                    }
+                    ui.colored_label(sextet_idx_to_colour(ctx, i), leader.to_point_list());
+                    if button.clicked() {
+                        return Some(Navigate::Pick(leader.clone()));
+                    }
+                    None
+                });
+                if let Some(navigate) = selected.inner {
+                    return Some(navigate);
+                }
+            }
+
+            ui.heading("Weight Distribution");
+            Grid::new("coset_weight_distribution")
+                .num_columns(2)
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.strong("Weight");
+                    ui.strong("Vectors");
+                    ui.end_row();
+                    for (weight, count) in self.weight_distribution.iter().enumerate() {
+                        if *count != 0 {
+                            ui.label(weight.to_string());
+                            ui.label(count.to_string());
+                            ui.end_row();
+                        }
+                    }
+                });
+
+            None
+        }) {
+            return Some(navigate);
+        }
+
+        // Outline the points of each leader in its own colour
+        let mut highlights = Labelled::<Point, Option<Color32>>::new_constant(None);
+        for (i, leader) in leaders.iter().enumerate() {
+            if preview_leader.is_none_or(|j| i == j) {
+                for p in leader.points() {
+                    highlights.set(p, Some(sextet_idx_to_colour(ctx, i)));
+                }
+            }
+        }
+
+        CentralPanel::default().show(ctx, |ui| {
+            MogSelection::new(&self.vector)
+                .highlights(&highlights)
+                .show(ui);
+        });
+
+        None
+    }
+
+    fn title(&self) -> String {
+        "Coset".to_string()
+    }
+
+    fn parent(&self) -> Option<Box<dyn AppState>> {
+        Some(Box::new(self.parent.clone().restore()))
+    }
+
+    fn session(&self) -> Option<Session> {
+        Some(Session::PointToggle(self.parent.clone()))
+    }
+}
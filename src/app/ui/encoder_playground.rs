@@ -0,0 +1,250 @@
+use crate::app::logic::miracle_octad_generator::{BinaryGolayCode, DecodeResult, Point, Vector};
+use crate::app::logic::traits::{Enumerated, Labelled};
+use crate::app::ui::mog_selection::MogSelection;
+use crate::app::ui::palette::Palette;
+use crate::app::ui::panels::side_panel;
+use crate::app::ui::session::{PointToggleSession, Session};
+use crate::app::{AppState, Navigate};
+use eframe::{
+    Frame,
+    egui::{CentralPanel, Color32, Context, DragValue, Grid, RichText},
+};
+
+// The most errors the Golay code is guaranteed to correct
+const MAX_ERRORS: usize = 3;
+
+// The 24 bits of a vector, with a dot for the deleted point of the perfect code
+fn bits(vector: &Vector, punctured: Option<Point>) -> RichText {
+    RichText::new(
+        Point::points()
+            .map(|p| {
+                if Some(p) == punctured {
+                    '·'
+                } else if vector.contains_point(p) {
+                    '1'
+                } else {
+                    '0'
+                }
+            })
+            .collect::<String>(),
+    )
+    .monospace()
+}
+
+// Encode 12 information bits as a codeword, flip some of its points as a noisy channel would, and watch the syndrome decoder put them back
+#[derive(Clone)]
+pub struct State {
+    parent: PointToggleSession,
+    information: usize,
+    // The points flipped by the channel
+    errors: Vector,
+    // The point deleted from every codeword to give the perfect [23, 12, 7] code, if any
+    punctured: Option<Point>,
+}
+
+impl State {
+    // Start from the selection if it is a codeword
+    pub fn new(parent: PointToggleSession) -> Self {
+        let information = super::mog::mog()
+            .information(&parent.selected_points)
+            .unwrap_or(0);
+        Self {
+            parent,
+            information,
+            errors: Vector::zero(),
+            punctured: None,
+        }
+    }
+}
+
+impl AppState for State {
+    fn update(&mut self, ctx: &Context, _frame: &mut Frame) -> Option<Navigate> {
+        let mog = super::mog::mog();
+        let palette = Palette::get(ctx);
+        let mut codeword = mog.encode(self.information);
+        if let Some(p) = self.punctured {
+            codeword.set(p, false);
+        }
+        let received = &codeword + &self.errors;
+        // The perfect code always decodes, by way of the Golay code with the deleted bit restored as a parity bit
+        let (syndrome, decoded) = match self.punctured {
+            None => (mog.syndrome(&received), mog.decode(&received)),
+            Some(p) => {
+                let (codeword, errors) = mog.decode_punctured(p, &received);
+                (
+                    mog.syndrome(&BinaryGolayCode::extend_punctured(p, &received)),
+                    DecodeResult::Corrected { codeword, errors },
+                )
+            }
+        };
+
+        if let Some(navigate) = side_panel(ctx, |ui| -> Option<Navigate> {
+            // Back
+            if ui.button("Back").clicked() {
+                return Some(Navigate::Back);
+            }
+
+            ui.heading("Information Bits");
+            ui.label("Bit i adds row i of the generator matrix, most significant first");
+            ui.horizontal(|ui| {
+                for i in (0..12).rev() {
+                    let bit = self.information & (1 << i) != 0;
+                    if ui
+                        .selectable_label(
+                            bit,
+                            RichText::new(if bit { "1" } else { "0" }).monospace(),
+                        )
+                        .clicked()
+                    {
+                        self.information ^= 1 << i;
+                    }
+                }
+            });
+            if ui.button("Clear").clicked() {
+                self.information = 0;
+            }
+
+            ui.heading("Code");
+            let mut perfect = self.punctured.is_some();
+            if ui
+                .checkbox(
+                    &mut perfect,
+                    "Delete a point, for the perfect [23, 12, 7] code",
+                )
+                .changed()
+            {
+                self.punctured = perfect.then(|| Point::usize_to_point(0).unwrap());
+            }
+            if let Some(p) = self.punctured {
+                let mut i = p.point_to_usize();
+                ui.horizontal(|ui| {
+                    ui.label("Deleted point");
+                    ui.add(DragValue::new(&mut i).range(0..=Point::N - 1));
+                });
+                let p = Point::usize_to_point(i).unwrap();
+                self.punctured = Some(p);
+                self.errors.set(p, false);
+                ui.label("Every word of length 23 is within distance 3 of exactly one codeword");
+            }
+
+            ui.heading("Codeword");
+            ui.label(bits(&codeword, self.punctured));
+            ui.label(format!("Weight {}", codeword.weight()));
+
+            ui.heading("Channel");
+            ui.label(format!(
+                "Click points on the grid to flip them, at most {MAX_ERRORS}"
+            ));
+            ui.label(format!(
+                "{} error{}: {}",
+                self.errors.weight(),
+                if self.errors.weight() == 1 { "" } else { "s" },
+                self.errors.to_point_list()
+            ));
+            if ui.button("Clear errors").clicked() {
+                self.errors = Vector::zero();
+            }
+            ui.label("Received");
+            ui.label(bits(&received, self.punctured));
+
+            ui.heading("Decoder");
+            Grid::new("encoder_playground_decoder")
+                .num_columns(2)
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label("Syndrome");
+                    ui.label(RichText::new(format!("{syndrome:012b}")).monospace());
+                    ui.end_row();
+
+                    match &decoded {
+                        DecodeResult::Corrected {
+                            codeword: corrected,
+                            errors,
+                        } => {
+                            ui.label("Errors found");
+                            ui.label(errors.to_point_list());
+                            ui.end_row();
+
+                            ui.label("Corrected");
+                            ui.label(bits(corrected, self.punctured));
+                            ui.end_row();
+
+                            ui.label("Information");
+                            ui.label(
+                                RichText::new(format!(
+                                    "{:012b}",
+                                    mog.information(&match self.punctured {
+                                        None => corrected.clone(),
+                                        Some(p) => BinaryGolayCode::extend_punctured(p, corrected),
+                                    })
+                                    .unwrap()
+                                ))
+                                .monospace(),
+                            );
+                            ui.end_row();
+                        }
+                        // Unreachable with at most 3 errors, but a cap of 4 would reach it
+                        DecodeResult::Detected { .. } => {
+                            ui.label("Result");
+                            ui.label("4 errors detected, which can't be corrected");
+                            ui.end_row();
+                        }
+                    }
+                });
+            if self.errors.weight() == 0 {
+                ui.label("The syndrome is zero, so nothing needs correcting");
+            } else if matches!(&decoded, DecodeResult::Corrected { codeword: corrected, .. } if corrected == &codeword)
+            {
+                ui.colored_label(palette.good(), "Every error was corrected");
+            } else {
+                ui.colored_label(palette.bad(), "The decoder got it wrong");
+            }
+
+            None
+        }) {
+            return Some(navigate);
+        }
+
+        // Tint the points flipped by the channel and outline those the decoder flips back
+        let mut tints = Labelled::<Point, Option<Color32>>::new_constant(None);
+        for p in self.errors.points() {
+            tints.set(p, Some(palette.bad()));
+        }
+        if let Some(p) = self.punctured {
+            tints.set(p, Some(Color32::GRAY));
+        }
+        let mut highlights = Labelled::<Point, Option<Color32>>::new_constant(None);
+        if let DecodeResult::Corrected { errors, .. } = &decoded {
+            for p in errors.points() {
+                highlights.set(p, Some(palette.good()));
+            }
+        }
+
+        CentralPanel::default().show(ctx, |ui| {
+            let response = MogSelection::new(&received)
+                .tints(&tints)
+                .highlights(&highlights)
+                .show(ui);
+            if let Some(p) = response.clicked
+                && Some(p) != self.punctured
+                && (self.errors.contains_point(p) || self.errors.weight() < MAX_ERRORS)
+            {
+                self.errors.toggle(p);
+            }
+        });
+
+        None
+    }
+
+    fn title(&self) -> String {
+        "Encoder and Decoder".to_string()
+    }
+
+    fn parent(&self) -> Option<Box<dyn AppState>> {
+        Some(Box::new(self.parent.clone().restore()))
+    }
+
+    fn session(&self) -> Option<Session> {
+        Some(Session::PointToggle(self.parent.clone()))
+    }
+}
@@ -0,0 +1,111 @@
+use crate::app::AppState;
+use crate::app::logic::linear_code::BinaryLinearCode;
+use crate::app::logic::miracle_octad_generator::{BinaryCode, Point, Vector};
+use crate::app::logic::permutation::Permutation;
+use crate::app::ui::session::Session;
+use eframe::egui::{Align2, Color32, Context, Id, LayerId, Order, TextStyle};
+
+// Darken the window and show a hint while files are being dragged over it
+pub fn preview_files_being_dropped(ctx: &Context) {
+    if ctx.input(|i| i.raw.hovered_files.is_empty()) {
+        return;
+    }
+
+    let painter = ctx.layer_painter(LayerId::new(Order::Foreground, Id::new("file_drop_target")));
+    let screen_rect = ctx.screen_rect();
+    painter.rect_filled(screen_rect, 0.0, Color32::from_black_alpha(192));
+    painter.text(
+        screen_rect.center(),
+        Align2::CENTER_CENTER,
+        "Drop a session, or a file of points or permutations, to open it",
+        TextStyle::Heading.resolve(&ctx.style()),
+        Color32::WHITE,
+    );
+}
+
+// If a file was dropped this frame, parse it into the state it should be opened in
+pub fn take_dropped_file(ctx: &Context) -> Option<Result<Box<dyn AppState>, String>> {
+    let file = ctx.input(|i| i.raw.dropped_files.first().cloned())?;
+
+    let name = file
+        .path
+        .as_ref()
+        .map(|path| path.display().to_string())
+        .unwrap_or(file.name.clone());
+
+    let text = if let Some(bytes) = &file.bytes {
+        String::from_utf8(bytes.to_vec()).map_err(|_| format!("{name} is not a text file"))
+    } else if let Some(path) = &file.path {
+        read_path(path).map_err(|err| format!("Could not read {name}: {err}"))
+    } else {
+        Err(format!("Could not read {name}"))
+    };
+
+    Some(
+        text.and_then(|text| parse_file(&text))
+            .map_err(|err| format!("{name}: {err}")),
+    )
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn read_path(path: &std::path::Path) -> std::io::Result<String> {
+    std::fs::read_to_string(path)
+}
+
+#[cfg(target_arch = "wasm32")]
+fn read_path(_path: &std::path::Path) -> std::io::Result<String> {
+    Err(std::io::ErrorKind::Unsupported.into())
+}
+
+/*
+A `.mog` session file reopens the screen it was saved from
+A file of 12 rows of 24 binary digits is the generator matrix of a code to use instead of the Golay code
+A file of rows of binary digits of another size is the generator matrix of a code to explore on its own, if it can't be read as below
+
+Otherwise the file is read line by line
+ - Blank lines and lines starting with `#` are ignored
+ - A line of cycles e.g. `(0 1 2)(3 4)` is a permutation. Several such lines are composed in order
+ - Any other line is a selection of points e.g. `0 3 7 12 19`. At most one is allowed
+ */
+fn parse_file(text: &str) -> Result<Box<dyn AppState>, String> {
+    if let Ok(session) = Session::from_file_string(text) {
+        return session
+            .restore()
+            .ok_or("The saved screen is no longer valid".to_string());
+    }
+    if let Ok(code) = BinaryCode::parse_generator_matrix(text) {
+        return Ok(super::code_import::open_code(code));
+    }
+
+    parse_points_and_permutations(text).or_else(|err| {
+        let code = BinaryLinearCode::parse_generator_matrix(text).map_err(|_| err)?;
+        Ok(Box::new(super::linear_code_view::State::new(code)))
+    })
+}
+
+fn parse_points_and_permutations(text: &str) -> Result<Box<dyn AppState>, String> {
+    let mut selection: Option<Vector> = None;
+    let mut permutation = Permutation::<Point>::identity();
+
+    for (line_idx, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line_err = |err: String| format!("line {}: {err}", line_idx + 1);
+        if line.starts_with('(') {
+            permutation = &permutation * &Permutation::parse_cycles(line).map_err(line_err)?;
+        } else if selection.is_none() {
+            selection = Some(Vector::parse(line).map_err(line_err)?);
+        } else {
+            return Err(line_err(
+                "Only one selection of points is allowed".to_string(),
+            ));
+        }
+    }
+
+    Ok(Box::new(super::point_toggle::State::new(
+        selection.unwrap_or_else(Vector::zero),
+        permutation,
+    )))
+}
@@ -0,0 +1,77 @@
+use crate::app::ui::grid::GridBuilder;
+use eframe::egui::{Context, DragValue, Grid, Id, Slider, Ui};
+
+// The sizes the MOG and permutations are drawn with, all measured as fractions of a cell
+#[derive(Debug, Clone, Copy, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Geometry {
+    // The gap between squares
+    pub pad: f32,
+    // The corner radius of squares
+    pub rounding: f32,
+    // The width of the lines permutations are drawn with
+    pub line_width: f32,
+    // How many straight segments each curved line is made from
+    pub segments: usize,
+    // The size of the arrow heads relative to the line width
+    pub arrowhead: f64,
+}
+
+impl Default for Geometry {
+    fn default() -> Self {
+        Self {
+            pad: 0.05,
+            rounding: 0.05,
+            line_width: 0.1,
+            segments: 12,
+            arrowhead: 1.5,
+        }
+    }
+}
+
+impl Geometry {
+    fn id() -> Id {
+        Id::new("geometry")
+    }
+
+    // The geometry every screen should use this frame
+    pub fn get(ctx: &Context) -> Self {
+        ctx.data(|data| data.get_temp(Self::id()).unwrap_or_default())
+    }
+
+    pub fn set(self, ctx: &Context) {
+        ctx.data_mut(|data| data.insert_temp(Self::id(), self));
+    }
+
+    pub fn apply(&self, grid_builder: &mut GridBuilder) {
+        grid_builder.set_pad(self.pad);
+        grid_builder.set_rounding(self.rounding);
+    }
+
+    pub fn menu(&mut self, ui: &mut Ui) {
+        Grid::new("geometry").num_columns(2).show(ui, |ui| {
+            ui.label("Padding");
+            ui.add(Slider::new(&mut self.pad, 0.0..=0.3));
+            ui.end_row();
+
+            ui.label("Corner radius");
+            ui.add(Slider::new(&mut self.rounding, 0.0..=0.5));
+            ui.end_row();
+
+            ui.label("Line width");
+            ui.add(Slider::new(&mut self.line_width, 0.02..=0.25));
+            ui.end_row();
+
+            ui.label("Curve segments");
+            ui.add(DragValue::new(&mut self.segments).range(1..=64));
+            ui.end_row();
+
+            ui.label("Arrow head size");
+            ui.add(Slider::new(&mut self.arrowhead, 0.5..=3.0));
+            ui.end_row();
+        });
+        if ui.button("Reset").clicked() {
+            *self = Self::default();
+        }
+    }
+}
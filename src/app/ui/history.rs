@@ -0,0 +1,62 @@
+use std::collections::VecDeque;
+
+// How many changes can be undone
+const MAX_UNDO: usize = 100;
+
+// Undo and redo for a screen whose editable parts are captured by a snapshot `T`
+// The screen calls `record` once per frame and any change since the previous frame becomes undoable
+#[derive(Debug, Clone)]
+pub struct History<T> {
+    current: Option<T>,
+    undo: VecDeque<T>,
+    redo: Vec<T>,
+}
+
+impl<T> Default for History<T> {
+    fn default() -> Self {
+        Self {
+            current: None,
+            undo: VecDeque::new(),
+            redo: vec![],
+        }
+    }
+}
+
+impl<T: Clone + PartialEq> History<T> {
+    pub fn record(&mut self, snapshot: T) {
+        if self.current.as_ref() == Some(&snapshot) {
+            return;
+        }
+        if let Some(previous) = self.current.replace(snapshot) {
+            self.undo.push_back(previous);
+            if self.undo.len() > MAX_UNDO {
+                self.undo.pop_front();
+            }
+            self.redo.clear();
+        }
+    }
+
+    // The snapshot to go back to, if any
+    pub fn undo(&mut self) -> Option<T> {
+        let previous = self.undo.pop_back()?;
+        let current = self.current.replace(previous.clone()).unwrap();
+        self.redo.push(current);
+        Some(previous)
+    }
+
+    // The snapshot to go forward to, if any
+    pub fn redo(&mut self) -> Option<T> {
+        let next = self.redo.pop()?;
+        let current = self.current.replace(next.clone()).unwrap();
+        self.undo.push_back(current);
+        Some(next)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+}
@@ -0,0 +1,116 @@
+use crate::app::logic::miracle_octad_generator::Point;
+use crate::app::logic::traits::Enumerated;
+use crate::app::ui::layout::MogLayout;
+use eframe::egui::{Button, Context, Key, KeyboardShortcut, Modifiers, Response, Ui, WidgetText};
+
+// Whether a key was pressed this frame, unless a text box wants the keyboard
+// The key press is used up so nothing else reacts to it
+pub fn pressed(ctx: &Context, shortcut: KeyboardShortcut) -> bool {
+    !ctx.wants_keyboard_input() && ctx.input_mut(|i| i.consume_shortcut(&shortcut))
+}
+
+pub fn key(key: Key) -> KeyboardShortcut {
+    KeyboardShortcut::new(Modifiers::NONE, key)
+}
+
+// A button showing its accelerator, which is also listed in the command palette and can be pointed at by the tutorial
+// The bool is whether it was clicked, its accelerator was pressed or it was picked from the palette
+pub fn button(
+    ui: &mut Ui,
+    text: impl Into<WidgetText>,
+    shortcut: KeyboardShortcut,
+) -> (Response, bool) {
+    let text = text.into();
+    let label = text.text().to_string();
+    labelled_button(ui, text, &label, shortcut)
+}
+
+// The same, listed in the palette as `section: text` for buttons whose text alone is ambiguous, like Select
+pub fn section_button(
+    ui: &mut Ui,
+    section: &str,
+    text: impl Into<WidgetText>,
+    shortcut: KeyboardShortcut,
+) -> (Response, bool) {
+    let text = text.into();
+    let label = format!("{section}: {}", text.text());
+    labelled_button(ui, text, &label, shortcut)
+}
+
+fn labelled_button(
+    ui: &mut Ui,
+    text: WidgetText,
+    label: &str,
+    shortcut: KeyboardShortcut,
+) -> (Response, bool) {
+    let offered = super::command_palette::offer(ui.ctx(), label, Some(shortcut));
+    let response = ui.add(Button::new(text).shortcut_text(ui.ctx().format_shortcut(&shortcut)));
+    if ui.is_visible() {
+        super::tutorial::spot(ui.ctx(), label, shortcut, response.rect);
+    }
+    let activated = pressed(ui.ctx(), shortcut) || response.clicked() || offered;
+    (response, activated)
+}
+
+// A checkbox toggled by clicking it or pressing its accelerator
+pub fn checkbox(ui: &mut Ui, checked: &mut bool, text: &str, shortcut: KeyboardShortcut) {
+    if pressed(ui.ctx(), shortcut) || super::command_palette::offer(ui.ctx(), text, Some(shortcut))
+    {
+        *checked = !*checked;
+    }
+    ui.checkbox(checked, text)
+        .on_hover_text(ui.ctx().format_shortcut(&shortcut));
+}
+
+// The number keys 1-6 and the function keys F1-F12, for picking from a list
+pub const NUMBER_KEYS: [Key; 6] = [
+    Key::Num1,
+    Key::Num2,
+    Key::Num3,
+    Key::Num4,
+    Key::Num5,
+    Key::Num6,
+];
+pub const FUNCTION_KEYS: [Key; 12] = [
+    Key::F1,
+    Key::F2,
+    Key::F3,
+    Key::F4,
+    Key::F5,
+    Key::F6,
+    Key::F7,
+    Key::F8,
+    Key::F9,
+    Key::F10,
+    Key::F11,
+    Key::F12,
+];
+
+// The point next to `point` in a direction on the screen, wrapping around the edges of the grid
+pub fn step(layout: MogLayout, point: Point, (dx, dy): (isize, isize)) -> Point {
+    let cells = Point::points()
+        .map(|p| layout.point_to_cell(p))
+        .collect::<Vec<_>>();
+    let (min_x, max_x) = (
+        cells.iter().map(|c| c.0).min().unwrap(),
+        cells.iter().map(|c| c.0).max().unwrap(),
+    );
+    let (min_y, max_y) = (
+        cells.iter().map(|c| c.1).min().unwrap(),
+        cells.iter().map(|c| c.1).max().unwrap(),
+    );
+    let (x, y) = layout.point_to_cell(point);
+    let cell = (
+        min_x + (x - min_x + dx).rem_euclid(max_x - min_x + 1),
+        min_y + (y - min_y + dy).rem_euclid(max_y - min_y + 1),
+    );
+    Point::points()
+        .find(|p| layout.point_to_cell(*p) == cell)
+        .unwrap_or(point)
+}
+
+// The point in the same row as `point` in column `c` of the MOG
+pub fn in_column(point: Point, c: usize) -> Point {
+    let row = point.point_to_usize() / 6;
+    Point::usize_to_point(6 * row + c).unwrap()
+}
@@ -0,0 +1,99 @@
+use crate::app::logic::miracle_octad_generator::Point;
+use crate::app::logic::traits::Enumerated;
+use crate::app::ui::grid::{GridBuilder, GridCell};
+use eframe::egui::{Context, Id, Ui};
+
+// How the 24 points of the MOG are arranged on the screen
+// Different books draw the MOG differently
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum MogLayout {
+    // 6 columns and 4 rows
+    #[default]
+    Standard,
+    // 4 columns and 6 rows
+    Transposed,
+    // Three 2x4 bricks separated by gaps
+    Bricks,
+}
+
+impl MogLayout {
+    fn id() -> Id {
+        Id::new("mog_layout")
+    }
+
+    // The layout every screen should use this frame
+    pub fn get(ctx: &Context) -> Self {
+        ctx.data(|data| data.get_temp(Self::id()).unwrap_or_default())
+    }
+
+    pub fn set(self, ctx: &Context) {
+        ctx.data_mut(|data| data.insert_temp(Self::id(), self));
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MogLayout::Standard => "Standard",
+            MogLayout::Transposed => "Transposed",
+            MogLayout::Bricks => "Bricks",
+        }
+    }
+
+    pub fn menu(&mut self, ui: &mut Ui) {
+        for layout in [
+            MogLayout::Standard,
+            MogLayout::Transposed,
+            MogLayout::Bricks,
+        ] {
+            ui.radio_value(self, layout, layout.name());
+        }
+    }
+
+    fn cell(&self, column: isize, row: isize) -> GridCell {
+        match self {
+            MogLayout::Standard | MogLayout::Bricks => (column, row),
+            MogLayout::Transposed => (row, column),
+        }
+    }
+
+    // Separate the row and column labels from the points, and the bricks from each other
+    pub fn add_gaps(&self, grid_builder: &mut GridBuilder) {
+        match self {
+            MogLayout::Standard | MogLayout::Bricks => {
+                grid_builder.add_column_gap(0);
+                grid_builder.add_row_gap(4);
+            }
+            MogLayout::Transposed => {
+                grid_builder.add_row_gap(0);
+                grid_builder.add_column_gap(4);
+            }
+        }
+        if *self == MogLayout::Bricks {
+            grid_builder.add_column_gap(2);
+            grid_builder.add_column_gap(4);
+        }
+    }
+
+    pub fn point_to_cell(&self, p: Point) -> GridCell {
+        let i = p.point_to_usize();
+        self.cell((i % 6) as isize, (i / 6) as isize)
+    }
+
+    pub fn cell_to_point(&self, cell: GridCell) -> Option<Point> {
+        Point::points().find(|p| self.point_to_cell(*p) == cell)
+    }
+
+    // The label for row `r` of the MOG, before the first column
+    pub fn row_label_to_cell(&self, r: usize) -> GridCell {
+        self.cell(-1, r as isize)
+    }
+
+    // The label for column `c` of the MOG, after the last row
+    pub fn col_label_to_cell(&self, c: usize) -> GridCell {
+        self.cell(c as isize, 4)
+    }
+
+    // The parity of column `c` of the MOG, after its label
+    pub fn col_parity_to_cell(&self, c: usize) -> GridCell {
+        self.cell(c as isize, 5)
+    }
+}
@@ -0,0 +1,191 @@
+use crate::app::logic::linear_code::{BinaryLinearCode, Code, Vector};
+use crate::app::ui::code_matrices;
+use crate::app::ui::geometry::Geometry;
+use crate::app::ui::grid::{GridBuilder, GridCell};
+use crate::app::ui::memo::Memo;
+use crate::app::ui::panels::side_panel;
+use crate::app::{AppState, Navigate};
+use eframe::{
+    Frame,
+    egui::{Align2, CentralPanel, CollapsingHeader, Context, FontId, Grid, StrokeKind},
+};
+use std::sync::Arc;
+
+// Toggle points of a code of any length, for codes loaded from a generator matrix which don't fit the MOG
+#[derive(Clone)]
+pub struct State {
+    code: Arc<BinaryLinearCode>,
+    // Worked out once, since each enumerates every codeword
+    minimum_distance: usize,
+    weight_distribution: Vec<usize>,
+    selected_points: Vector,
+    nearest_codewords: Memo<Vector, (usize, Vec<Vector>)>,
+    matrices: Memo<(), Arc<code_matrices::Matrices>>,
+}
+
+impl State {
+    pub fn new(code: BinaryLinearCode) -> Self {
+        Self {
+            minimum_distance: code.minimum_distance(),
+            weight_distribution: code.weight_distribution(),
+            code: Arc::new(code),
+            selected_points: Vector::zero(),
+            nearest_codewords: Memo::default(),
+            matrices: Memo::default(),
+        }
+    }
+}
+
+// The number of columns to lay out n points in
+// Roughly square, preferring a width which divides n so the last row is full, e.g. 6 x 4 for 24 and 8 x 4 for 32
+fn columns(n: usize) -> usize {
+    let c = n.isqrt() + usize::from(n.isqrt().pow(2) < n);
+    (c..=2 * c).find(|d| n.is_multiple_of(*d)).unwrap_or(c)
+}
+
+fn point_to_cell(columns: usize, i: usize) -> GridCell {
+    ((i % columns) as isize, (i / columns) as isize)
+}
+
+impl AppState for State {
+    fn update(&mut self, ctx: &Context, _frame: &mut Frame) -> Option<Navigate> {
+        let code = self.code.clone();
+        let n = code.length();
+        // The codeword the selection would become, while hovering its button
+        let mut preview = None;
+
+        side_panel(ctx, |ui| {
+            ui.heading("Binary Linear Code");
+            ui.label(format!(
+                "A [{}, {}, {}] code",
+                n,
+                code.dimension(),
+                self.minimum_distance
+            ));
+
+            if self.selected_points.weight() != 0 {
+                ui.heading("Clear selection");
+                if ui.button("Clear").clicked() {
+                    self.selected_points = Vector::zero();
+                }
+            }
+
+            let (distance, nearest) = self
+                .nearest_codewords
+                .get(self.selected_points, |selected_points| {
+                    code.nearest_codewords(selected_points)
+                });
+            if distance == 0 {
+                ui.heading("It's a Codeword");
+            } else {
+                ui.heading(if nearest.len() == 1 {
+                    "Nearest Codeword"
+                } else {
+                    "Nearest Codewords"
+                });
+                ui.label(format!("Distance = {}", distance));
+                const MAX_SHOWN: usize = 6;
+                for (num, codeword) in nearest.iter().take(MAX_SHOWN).enumerate() {
+                    let button = ui.button(format!("Select {}", num + 1));
+                    if button.hovered() {
+                        preview = Some(*codeword);
+                    }
+                    if button.clicked() {
+                        self.selected_points = *codeword;
+                    }
+                }
+                if nearest.len() > MAX_SHOWN {
+                    ui.label(format!("and {} more", nearest.len() - MAX_SHOWN));
+                }
+            }
+
+            CollapsingHeader::new("Matrices").show(ui, |ui| {
+                let matrices = self
+                    .matrices
+                    .get((), |()| Arc::new(code_matrices::Matrices::new(&code)));
+                code_matrices::show(ui, &matrices, &self.selected_points);
+            });
+
+            CollapsingHeader::new("Weight Distribution").show(ui, |ui| {
+                Grid::new("weight_distribution")
+                    .num_columns(2)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong("Weight");
+                        ui.strong("Codewords");
+                        ui.end_row();
+                        for (weight, count) in self.weight_distribution.iter().enumerate() {
+                            if *count != 0 {
+                                ui.label(weight.to_string());
+                                ui.label(count.to_string());
+                                ui.end_row();
+                            }
+                        }
+                    });
+            });
+        });
+
+        let columns = columns(n);
+        let mut grid_builder = GridBuilder::default();
+        Geometry::get(ctx).apply(&mut grid_builder);
+        grid_builder.set_align(Align2::CENTER_TOP);
+        for i in 0..n {
+            grid_builder.include_cell(point_to_cell(columns, i));
+        }
+
+        CentralPanel::default().show(ctx, |ui| {
+            let (response, painter, grid) = grid_builder.show(ui);
+
+            let shown = preview.unwrap_or(self.selected_points);
+            let hovered_cell = response.hover_pos().and_then(|pos| grid.pos_to_cell(pos));
+            let clicked_cell = response
+                .interact_pointer_pos()
+                .filter(|_| response.clicked())
+                .and_then(|pos| grid.pos_to_cell(pos));
+            for i in 0..n {
+                let cell = point_to_cell(columns, i);
+                let rect = grid.cell_to_rect(cell);
+                let hovered = hovered_cell == Some(cell);
+
+                painter.rect_filled(
+                    rect,
+                    grid.rounding(),
+                    if shown.contains_point(i) {
+                        ui.visuals().selection.bg_fill
+                    } else {
+                        ui.visuals().widgets.inactive.bg_fill
+                    },
+                );
+                // Points which the preview would change
+                if hovered
+                    || preview.is_some()
+                        && shown.contains_point(i) != self.selected_points.contains_point(i)
+                {
+                    painter.rect_stroke(
+                        rect,
+                        grid.rounding(),
+                        ui.visuals().widgets.hovered.fg_stroke,
+                        StrokeKind::Middle,
+                    );
+                }
+                painter.text(
+                    rect.center(),
+                    Align2::CENTER_CENTER,
+                    i.to_string(),
+                    FontId::proportional(0.35 * rect.height()),
+                    ui.visuals().text_color(),
+                );
+
+                if clicked_cell == Some(cell) {
+                    self.selected_points.toggle(i);
+                }
+            }
+        });
+
+        None
+    }
+
+    fn title(&self) -> String {
+        "Binary Linear Code".to_string()
+    }
+}
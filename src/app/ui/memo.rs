@@ -0,0 +1,55 @@
+// A value worked out from some inputs, kept until it is asked for with different inputs
+// Anything else the value depends on must be handled by calling `invalidate` when it changes
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    entry: Option<(K, V)>,
+    // How often `get` could reuse the value and how often it had to work it out again
+    hits: usize,
+    misses: usize,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self {
+            entry: None,
+            hits: 0,
+            misses: 0,
+        }
+    }
+}
+
+impl<K: PartialEq, V: Clone> Memo<K, V> {
+    pub fn get(&mut self, key: K, compute: impl FnOnce(&K) -> V) -> V {
+        match &self.entry {
+            Some((k, v)) if *k == key => {
+                self.hits += 1;
+                v.clone()
+            }
+            _ => {
+                self.misses += 1;
+                let v = compute(&key);
+                self.entry = Some((key, v.clone()));
+                v
+            }
+        }
+    }
+
+    // The value for these inputs if it has been worked out already, for values too slow to work out unasked
+    pub fn cached(&mut self, key: &K) -> Option<V> {
+        match &self.entry {
+            Some((k, v)) if k == key => {
+                self.hits += 1;
+                Some(v.clone())
+            }
+            _ => None,
+        }
+    }
+
+    pub fn invalidate(&mut self) {
+        self.entry = None;
+    }
+
+    pub fn stats(&self) -> (usize, usize) {
+        (self.hits, self.misses)
+    }
+}
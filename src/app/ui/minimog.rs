@@ -0,0 +1,178 @@
+use crate::app::logic::finite_field_3::Point as F3Point;
+use crate::app::logic::minimog::{Point, TernaryGolayCode, Vector};
+use crate::app::logic::traits::Enumerated;
+use crate::app::ui::geometry::Geometry;
+use crate::app::ui::grid::GridBuilder;
+use crate::app::ui::panels::side_panel;
+use crate::app::ui::session::{PointToggleSession, Session};
+use crate::app::{AppState, Navigate};
+use eframe::{
+    Frame,
+    egui::{Align2, CentralPanel, Context, FontId, StrokeKind},
+};
+use std::sync::OnceLock;
+
+static MINIMOG: OnceLock<TernaryGolayCode> = OnceLock::new();
+
+pub fn minimog() -> &'static TernaryGolayCode {
+    MINIMOG.get_or_init(TernaryGolayCode::default)
+}
+
+// The MINIMOG, the 4x3 grid of the 12 points of the ternary Golay code on which M12 acts
+// Works like the point toggle screen, with hexads in place of octads
+#[derive(Clone)]
+pub struct State {
+    parent: PointToggleSession,
+    selected_points: Vector,
+}
+
+impl State {
+    pub fn new(parent: PointToggleSession) -> Self {
+        Self {
+            parent,
+            selected_points: Vector::zero(),
+        }
+    }
+}
+
+fn row_label(row: F3Point) -> &'static str {
+    match row {
+        F3Point::Zero => "0",
+        F3Point::Plus => "+",
+        F3Point::Minus => "−",
+    }
+}
+
+impl AppState for State {
+    fn update(&mut self, ctx: &Context, _frame: &mut Frame) -> Option<Navigate> {
+        let minimog = minimog();
+        // The point the selection would gain by completing a hexad, while hovering the button
+        let mut preview_point = None;
+
+        if let Some(navigate) = side_panel(ctx, |ui| {
+            // Back
+            if ui.button("Back").clicked() {
+                return Some(Navigate::Back);
+            }
+
+            ui.heading("MINIMOG");
+            ui.label("The 12 points of the ternary Golay code, on which M12 acts");
+            ui.label("Any 5 points lie in exactly one of the 132 hexads");
+
+            if self.selected_points.weight() != 0 {
+                ui.heading("Clear selection");
+                if ui.button("Clear").clicked() {
+                    self.selected_points = Vector::zero();
+                }
+            }
+
+            if minimog.is_hexad(&self.selected_points) {
+                ui.heading("It's a Hexad");
+            } else if self.selected_points.weight() == 5 {
+                let hexad = minimog.complete_hexad(&self.selected_points).unwrap();
+                ui.heading("Complete Hexad");
+                ui.label("The unique hexad containing these 5 points");
+                let button = ui.button("Complete");
+                if button.hovered() {
+                    preview_point = hexad
+                        .points()
+                        .find(|p| !self.selected_points.contains_point(*p));
+                }
+                if button.clicked() {
+                    self.selected_points = hexad;
+                }
+            }
+
+            // Count the hexads through a few points, as blocks of the Steiner system S(5, 6, 12)
+            if (1..=5).contains(&self.selected_points.weight()) {
+                let blocks = minimog.blocks_through(&self.selected_points).len();
+                ui.label(format!(
+                    "{blocks} hexad{} contain{} these points",
+                    if blocks == 1 { "" } else { "s" },
+                    if blocks == 1 { "s" } else { "" }
+                ));
+            }
+
+            None
+        }) {
+            return Some(navigate);
+        }
+
+        let mut grid_builder = GridBuilder::default();
+        Geometry::get(ctx).apply(&mut grid_builder);
+        grid_builder.add_column_gap(0);
+        grid_builder.set_align(Align2::CENTER_TOP);
+
+        // The rows labelled by F3
+        for r in 0..3 {
+            grid_builder.include_cell((-1, r));
+        }
+        // The 4x3 MINIMOG grid
+        let point_to_cell = |p: Point| (p.col as isize, p.row.point_to_usize() as isize);
+        for p in Point::points() {
+            grid_builder.include_cell(point_to_cell(p));
+        }
+
+        CentralPanel::default().show(ctx, |ui| {
+            let (response, painter, grid) = grid_builder.show(ui);
+
+            for row in F3Point::points() {
+                let rect = grid.cell_to_rect((-1, row.point_to_usize() as isize));
+                painter.text(
+                    rect.center(),
+                    Align2::CENTER_CENTER,
+                    row_label(row),
+                    FontId::proportional(0.7 * rect.height()),
+                    ui.visuals().text_color(),
+                );
+            }
+
+            let hovered_cell = response.hover_pos().and_then(|pos| grid.pos_to_cell(pos));
+            let clicked_cell = response
+                .interact_pointer_pos()
+                .filter(|_| response.clicked())
+                .and_then(|pos| grid.pos_to_cell(pos));
+            for p in Point::points() {
+                let cell = point_to_cell(p);
+                let rect = grid.cell_to_rect(cell);
+                let hovered = hovered_cell == Some(cell);
+
+                painter.rect_filled(
+                    rect,
+                    grid.rounding(),
+                    if self.selected_points.contains_point(p) {
+                        ui.visuals().selection.bg_fill
+                    } else {
+                        ui.visuals().widgets.inactive.bg_fill
+                    },
+                );
+                if hovered || preview_point == Some(p) {
+                    painter.rect_stroke(
+                        rect,
+                        grid.rounding(),
+                        ui.visuals().widgets.hovered.fg_stroke,
+                        StrokeKind::Middle,
+                    );
+                }
+
+                if clicked_cell == Some(cell) {
+                    self.selected_points.toggle(p);
+                }
+            }
+        });
+
+        None
+    }
+
+    fn title(&self) -> String {
+        "MINIMOG".to_string()
+    }
+
+    fn parent(&self) -> Option<Box<dyn AppState>> {
+        Some(Box::new(self.parent.clone().restore()))
+    }
+
+    fn session(&self) -> Option<Session> {
+        Some(Session::PointToggle(self.parent.clone()))
+    }
+}
@@ -1,22 +1,77 @@
+pub mod action_log;
+pub mod animation;
+pub mod bookmarks;
+pub mod cas_export;
+pub mod code_import;
+pub mod code_matrices;
+pub mod code_statistics;
+pub mod command_palette;
+pub mod coordinates;
+pub mod coset_view;
+pub mod encoder_playground;
+pub mod file_drop;
+pub mod geometry;
+pub mod history;
+pub mod keyboard;
+pub mod layout;
+pub mod linear_code_view;
+pub mod memo;
+pub mod minimog;
 pub mod mog_permutation_shapes;
+pub mod mog_selection;
+pub mod navigation;
+pub mod octad_browser;
+pub mod octad_partitions;
+pub mod octad_stabilizer;
+pub mod palette;
+pub mod panels;
+pub mod perf_hud;
+pub mod permutation_import;
+pub mod permutation_verify;
+pub mod permutation_workspace;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod png_export;
 pub mod point_toggle;
+pub mod reference_card;
+pub mod script_console;
+pub mod selection_statistics;
+pub mod selection_target;
+pub mod session;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod session_file;
+pub mod settings;
 pub mod sextet_labelling;
 pub mod shape;
+pub mod share_string;
+pub mod split_view;
+pub mod svg_export;
+pub mod table_export;
+pub mod trio_stabilizer;
+pub mod trio_view;
+pub mod tutorial;
+pub mod view_capture;
+pub mod workspaces;
 
 mod mog {
     use eframe::egui::{Color32, Rect};
-    use std::collections::HashSet;
+    use std::collections::{HashMap, HashSet};
     use std::sync::OnceLock;
 
     use crate::app::logic::finite_field_4::Point as F4Point;
+    use crate::app::logic::m24::FactorizationTable;
     use crate::app::logic::miracle_octad_generator::BinaryGolayCode;
 
     static MOG: OnceLock<BinaryGolayCode> = OnceLock::new();
+    static FACTORIZATION_TABLE: OnceLock<FactorizationTable> = OnceLock::new();
 
     pub fn mog() -> &'static BinaryGolayCode {
         MOG.get_or_init(BinaryGolayCode::default)
     }
 
+    pub fn factorization_table() -> &'static FactorizationTable {
+        FACTORIZATION_TABLE.get_or_init(FactorizationTable::default)
+    }
+
     // Draw an F4 element
     pub fn draw_f4(
         _ui: &mut eframe::egui::Ui,
@@ -52,6 +107,16 @@ mod mog {
         );
     }
 
+    // An F4 element as text, for labels where it can't be drawn
+    pub fn f4_text(x: F4Point) -> &'static str {
+        match x {
+            F4Point::Zero => "0",
+            F4Point::One => "1",
+            F4Point::Alpha => "ω",
+            F4Point::Beta => "ω̄",
+        }
+    }
+
     pub fn row_to_f4(r: usize) -> F4Point {
         match r {
             0 => F4Point::Zero,
@@ -62,16 +127,14 @@ mod mog {
         }
     }
 
-    pub fn sextet_idx_to_colour(i: usize) -> Color32 {
-        match i {
-            0 => Color32::RED,
-            1 => Color32::BLUE,
-            2 => Color32::GREEN,
-            3 => Color32::BROWN,
-            4 => Color32::MAGENTA,
-            5 => Color32::ORANGE,
-            _ => unreachable!(),
-        }
+    // The colour of the i-th foursome of a sextet in the chosen palette
+    pub fn sextet_idx_to_colour(ctx: &eframe::egui::Context, i: usize) -> Color32 {
+        super::palette::themed(ctx, super::palette::Palette::get(ctx).sextet(i))
+    }
+
+    // The colour of the i-th of n things in the chosen palette
+    pub fn palette_colour(ctx: &eframe::egui::Context, i: usize, n: usize) -> Color32 {
+        super::palette::themed(ctx, super::palette::Palette::get(ctx).colour(i, n))
     }
 
     #[derive(Debug)]
@@ -87,6 +150,8 @@ mod mog {
         response: &eframe::egui::Response,
         rect: eframe::egui::Rect,
         include: impl Into<HashSet<F4Point>>,
+        // Labels drawn greyed out, with why they can't be chosen shown when pointed at
+        disallowed: &HashMap<F4Point, &'static str>,
         include_cross: bool,
     ) -> F4SelectionResult {
         let include = include.into();
@@ -97,22 +162,45 @@ mod mog {
         let bottom_right = Rect::from_min_max(rect.center(), rect.right_bottom());
         let middle = Rect::from_center_size(rect.center(), rect.size() / 3.0);
 
-        let point_rects = [
+        let quadrants = [
             (F4Point::Zero, top_left),
             (F4Point::One, top_right),
             (F4Point::Alpha, bottom_left),
             (F4Point::Beta, bottom_right),
-        ]
-        .into_iter()
-        .filter(|(point, _)| include.contains(point))
-        .collect::<Vec<_>>();
+        ];
+        let point_rects = quadrants
+            .into_iter()
+            .filter(|(point, _)| include.contains(point))
+            .collect::<Vec<_>>();
+
+        for (point, point_rect) in quadrants {
+            if let Some(reason) = disallowed.get(&point) {
+                draw_f4(
+                    ui,
+                    painter,
+                    point_rect,
+                    ui.visuals().weak_text_color(),
+                    point,
+                );
+                if response
+                    .interact_pointer_pos()
+                    .is_some_and(|pos| point_rect.contains(pos))
+                {
+                    eframe::egui::Tooltip::for_widget(response)
+                        .at_pointer()
+                        .show(|ui| ui.label(format!("Not allowed: {reason}")));
+                }
+            }
+        }
 
         let mut result = F4SelectionResult::None;
         for (point, point_rect) in &point_rects {
             if (response.is_pointer_button_down_on()
                 || response.drag_stopped()
                 || response.clicked())
-                && point_rect.contains(response.interact_pointer_pos().unwrap())
+                && response
+                    .interact_pointer_pos()
+                    .is_some_and(|pos| point_rect.contains(pos))
             {
                 result = F4SelectionResult::Point(*point);
             }
@@ -121,7 +209,9 @@ mod mog {
             && (response.is_pointer_button_down_on()
                 || response.drag_stopped()
                 || response.clicked())
-            && middle.contains(response.interact_pointer_pos().unwrap())
+            && response
+                .interact_pointer_pos()
+                .is_some_and(|pos| middle.contains(pos))
         {
             result = F4SelectionResult::Cross;
         }
@@ -157,14 +247,20 @@ mod mog {
 }
 
 mod grid {
-    use eframe::egui::{Painter, Pos2, Rect, Response, Sense, Vec2};
-    use std::collections::HashSet;
+    use eframe::egui::{Align2, Color32, Painter, Pos2, Rect, Response, Sense, Vec2};
+    use std::collections::{HashMap, HashSet};
 
     pub type GridCell = (isize, isize);
 
     pub struct GridBuilder {
-        pad: f32, // The gap between squares
+        pad: f32,      // The gap between squares
+        rounding: f32, // The corner radius of squares
+        gap: f32,      // The size of the gaps between groups of cells
+        column_gaps: HashSet<isize>,
+        row_gaps: HashSet<isize>,
+        align: Align2, // Where to put the grid within the available space
         elements: HashSet<GridCell>,
+        backgrounds: HashMap<GridCell, Color32>,
     }
 
     #[derive(Debug, Clone, PartialEq)]
@@ -172,15 +268,46 @@ mod grid {
         rect: Rect,
         unit: f32,
         pad: f32,
+        rounding: f32,
+        gap: f32,
+        column_gaps: Vec<isize>,
+        row_gaps: Vec<isize>,
         min_cell: GridCell,
+        // The top left corner of the grid in cell units
+        origin: Vec2,
+        cells: Vec<GridCell>,
+    }
+
+    // The number of gaps strictly after `min` and at or before `i`
+    fn gaps_before(gaps: &[isize], min: isize, i: isize) -> usize {
+        gaps.iter().filter(|g| min < **g && **g <= i).count()
+    }
+
+    // The center of a cell in cell units, before the grid is positioned on the screen
+    // Cells have their centers at their coordinates, shifted along by any gaps
+    fn layout_pos(
+        cell: GridCell,
+        column_gaps: &[isize],
+        row_gaps: &[isize],
+        min_cell: GridCell,
+        gap: f32,
+    ) -> Vec2 {
+        Vec2 {
+            x: cell.0 as f32 + gaps_before(column_gaps, min_cell.0, cell.0) as f32 * gap,
+            y: cell.1 as f32 + gaps_before(row_gaps, min_cell.1, cell.1) as f32 * gap,
+        }
     }
 
     impl GridShower {
         pub fn cell_to_pos(&self, cell: GridCell) -> Pos2 {
-            Pos2 {
-                x: self.rect.left() + ((cell.0 - self.min_cell.0) as f32 + 0.5) * self.unit,
-                y: self.rect.top() + ((cell.1 - self.min_cell.1) as f32 + 0.5) * self.unit,
-            }
+            let pos = layout_pos(
+                cell,
+                &self.column_gaps,
+                &self.row_gaps,
+                self.min_cell,
+                self.gap,
+            );
+            self.rect.left_top() + (pos - self.origin) * self.unit
         }
 
         pub fn cell_to_rect(&self, cell: GridCell) -> Rect {
@@ -193,16 +320,35 @@ mod grid {
             )
         }
 
+        // The cell whose square contains the given position, if any
+        pub fn pos_to_cell(&self, pos: Pos2) -> Option<GridCell> {
+            self.cells
+                .iter()
+                .find(|cell| self.cell_to_rect(**cell).contains(pos))
+                .copied()
+        }
+
         pub fn cell_scalar_to_pos_scalar(&self, lambda: f32) -> f32 {
             lambda * self.unit
         }
+
+        // The corner radius to draw squares with
+        pub fn rounding(&self) -> f32 {
+            self.cell_scalar_to_pos_scalar(self.rounding)
+        }
     }
 
     impl Default for GridBuilder {
         fn default() -> Self {
             Self {
                 pad: 0.05,
+                rounding: 0.05,
+                gap: 0.25,
+                column_gaps: HashSet::new(),
+                row_gaps: HashSet::new(),
+                align: Align2::LEFT_TOP,
                 elements: HashSet::new(),
+                backgrounds: HashMap::new(),
             }
         }
     }
@@ -212,42 +358,121 @@ mod grid {
             self.elements.insert(cell);
         }
 
+        // The gap between squares as a fraction of a cell
+        pub fn set_pad(&mut self, pad: f32) {
+            self.pad = pad;
+        }
+
+        // The corner radius of squares as a fraction of a cell
+        pub fn set_rounding(&mut self, rounding: f32) {
+            self.rounding = rounding;
+        }
+
+        // Leave a gap between column `i - 1` and column `i`
+        pub fn add_column_gap(&mut self, i: isize) {
+            self.column_gaps.insert(i);
+        }
+
+        // Leave a gap between row `j - 1` and row `j`
+        pub fn add_row_gap(&mut self, j: isize) {
+            self.row_gaps.insert(j);
+        }
+
+        pub fn set_align(&mut self, align: Align2) {
+            self.align = align;
+        }
+
+        // Fill the square of a cell with a colour before anything else is drawn
+        pub fn set_cell_background(&mut self, cell: GridCell, colour: Color32) {
+            self.include_cell(cell);
+            self.backgrounds.insert(cell, colour);
+        }
+
         pub fn show(self, ui: &mut eframe::egui::Ui) -> (Response, Painter, GridShower) {
-            let min_i = self.elements.iter().map(|(i, _)| *i).min().unwrap_or(0);
-            let max_i = self.elements.iter().map(|(i, _)| *i).max().unwrap_or(0);
-            let min_j = self.elements.iter().map(|(_, j)| *j).min().unwrap_or(0);
-            let max_j = self.elements.iter().map(|(_, j)| *j).max().unwrap_or(0);
-            let size_i = max_i - min_i + 1;
-            let size_j = max_j - min_j + 1;
+            // Gaps are counted from the first cell
+            let min_cell = (
+                self.elements.iter().map(|(i, _)| *i).min().unwrap_or(0),
+                self.elements.iter().map(|(_, j)| *j).min().unwrap_or(0),
+            );
+            // Sorted so that equal grids compare equal
+            let mut column_gaps = self.column_gaps.into_iter().collect::<Vec<_>>();
+            let mut row_gaps = self.row_gaps.into_iter().collect::<Vec<_>>();
+            column_gaps.sort();
+            row_gaps.sort();
 
+            let centers = self
+                .elements
+                .iter()
+                .map(|cell| layout_pos(*cell, &column_gaps, &row_gaps, min_cell, self.gap))
+                .collect::<Vec<_>>();
+            let origin = Vec2 {
+                x: centers.iter().map(|c| c.x).fold(f32::INFINITY, f32::min),
+                y: centers.iter().map(|c| c.y).fold(f32::INFINITY, f32::min),
+            } - Vec2::splat(0.5);
+            let end = Vec2 {
+                x: centers
+                    .iter()
+                    .map(|c| c.x)
+                    .fold(f32::NEG_INFINITY, f32::max),
+                y: centers
+                    .iter()
+                    .map(|c| c.y)
+                    .fold(f32::NEG_INFINITY, f32::max),
+            } + Vec2::splat(0.5);
+            let (origin, size_i, size_j) = if centers.is_empty() {
+                (Vec2::ZERO, 1.0, 1.0)
+            } else {
+                (origin, end.x - origin.x, end.y - origin.y)
+            };
+
+            let available = ui.available_size();
+            let available = Vec2 {
+                x: available.x,
+                y: f32::min(available.y, 600.0),
+            };
+
+            let mut size = Vec2 {
+                x: available.x,
+                y: (size_j / size_i) * available.x,
+            };
+            if size.y > available.y {
+                size = size * available.y / size.y;
+            }
+
+            // Take all the width so the grid can be aligned within it
             let (response, painter) = ui.allocate_painter(
-                {
-                    let available = ui.available_size();
-
-                    let available = Vec2 {
-                        x: available.x,
-                        y: f32::min(available.y, 600.0),
-                    };
-
-                    let mut size = Vec2 {
-                        x: available.x,
-                        y: (size_j as f32 / size_i as f32) * available.x,
-                    };
-                    if size.y > available.y {
-                        size = size * available.y / size.y;
-                    }
-                    size
+                Vec2 {
+                    x: available.x,
+                    y: size.y,
                 },
                 Sense::click_and_drag(),
             );
+            let rect = self.align.align_size_within_rect(size, response.rect);
+            super::view_capture::include_in_view(ui.ctx(), rect);
 
+            let mut cells = self.elements.into_iter().collect::<Vec<_>>();
+            cells.sort();
             let coordinates = GridShower {
-                rect: response.rect,
-                unit: response.rect.width() / (size_i as f32),
+                rect,
+                unit: rect.width() / size_i,
                 pad: self.pad,
-                min_cell: (min_i, min_j),
+                rounding: self.rounding,
+                gap: self.gap,
+                column_gaps,
+                row_gaps,
+                min_cell,
+                origin,
+                cells,
             };
 
+            for (cell, colour) in self.backgrounds {
+                painter.rect_filled(
+                    coordinates.cell_to_rect(cell),
+                    coordinates.rounding(),
+                    colour,
+                );
+            }
+
             (response, painter, coordinates)
         }
     }
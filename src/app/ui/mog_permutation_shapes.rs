@@ -1,30 +1,114 @@
 use crate::app::{
     logic::permutation::Permutation,
     ui::{
+        geometry::Geometry,
         grid::{GridCell, GridShower},
-        shape::{Shape, arrowhead_cap},
+        shape::{Shape, arc_points, arc_through, arrowhead_cap, distance_to_path},
     },
 };
-use eframe::egui::Vec2;
+use eframe::egui::Pos2;
 use i_overlay::mesh::style::LineCap;
 
+// The arc a line between two cells is drawn along as its center, radius, start angle and end angle, or None if it is drawn straight
+// Lines which would pass through other cells are curved around them
+// The cells must be in order so that a line is curved the same way whichever way round it goes
+fn line_arc(
+    coordinates: &GridShower,
+    start_cell: GridCell,
+    end_cell: GridCell,
+) -> Option<(Pos2, f64, f64, f64)> {
+    debug_assert!(start_cell <= end_cell);
+    let cell_vec = (end_cell.0 - start_cell.0, end_cell.1 - start_cell.1);
+    let mut slope_type = (
+        start_cell.0.abs_diff(end_cell.0),
+        start_cell.1.abs_diff(end_cell.1),
+    );
+    if slope_type.0 < slope_type.1 {
+        slope_type = (slope_type.1, slope_type.0);
+    }
+    let start_pos = coordinates.cell_to_pos(start_cell);
+    let end_pos = coordinates.cell_to_pos(end_cell);
+    match slope_type {
+        (2, 0) | (3, 0) | (4, 0) | (5, 0) | (2, 2) | (3, 3) | (4, 2) => {
+            let pos_vec = end_pos - start_pos;
+            // Which side of the line the curve bulges towards
+            let mut side = 1.0;
+            // Hand-picked curve directions
+            match (start_cell, cell_vec) {
+                ((_, 1), (0, 2))
+                | ((1, _), (2, 0))
+                | ((3, _), (2, 0))
+                | ((1, _), (3, 0))
+                | ((1, _), (4, 0))
+                | ((_, 1), (2, 2))
+                | ((_, 3), (2, -2)) => side = -side,
+                _ => {}
+            }
+
+            Some(arc_through(
+                start_pos,
+                end_pos,
+                side * 0.085 * pos_vec.length() as f64,
+            ))
+        }
+        _ => None,
+    }
+}
+
+// The path from one cell to another along the line a permutation is drawn with between them
+pub fn line_path(
+    coordinates: &GridShower,
+    start_cell: GridCell,
+    end_cell: GridCell,
+    segments: usize,
+) -> Vec<Pos2> {
+    let (first, second) = (start_cell.min(end_cell), start_cell.max(end_cell));
+    let mut path = match line_arc(coordinates, first, second) {
+        Some((center, radius, start_angle, end_angle)) => {
+            arc_points(center, radius, start_angle, end_angle, segments)
+        }
+        None => vec![
+            coordinates.cell_to_pos(first),
+            coordinates.cell_to_pos(second),
+        ],
+    };
+    if first != start_cell {
+        path.reverse();
+    }
+    path
+}
+
 #[derive(Debug, Clone)]
 pub struct MogPermutationShapeCache {
-    state: Option<(Permutation<GridCell>, GridShower)>,
+    state: Option<(Permutation<GridCell>, GridShower, bool, Geometry)>,
+    // Draw dashed lines and hollow circles to show the permutation is not committed yet
+    preview: bool,
+    geometry: Geometry,
     cycles_and_shapes: Vec<(Vec<GridCell>, Shape)>,
-    line_width: f32,
+    // For each cycle, a path along one of its lines suitable for placing a label
+    label_paths: Vec<Vec<Pos2>>,
+    // Every line drawn, from a cell to its image, with the path it is drawn along
+    lines: Vec<(GridCell, GridCell, Vec<Pos2>)>,
     small_radius: f32,
     large_radius: f32,
+    // How often `set_permutation` could reuse the shapes and how often it had to rebuild them
+    hits: usize,
+    misses: usize,
 }
 
 impl Default for MogPermutationShapeCache {
     fn default() -> Self {
         Self {
             state: Default::default(),
+            preview: false,
+            geometry: Geometry::default(),
             cycles_and_shapes: Default::default(),
-            line_width: 0.1,
+            label_paths: Default::default(),
+            lines: Default::default(),
             small_radius: 0.08,
             large_radius: 0.1,
+            hits: 0,
+            misses: 0,
         }
     }
 }
@@ -33,6 +117,18 @@ impl MogPermutationShapeCache {
     pub fn small_radius(&self) -> f32 {
         self.small_radius
     }
+
+    pub fn set_preview(&mut self, preview: bool) {
+        self.preview = preview;
+    }
+
+    pub fn set_geometry(&mut self, geometry: Geometry) {
+        self.geometry = geometry;
+    }
+
+    pub fn cache_stats(&self) -> (usize, usize) {
+        (self.hits, self.misses)
+    }
 }
 
 impl MogPermutationShapeCache {
@@ -41,79 +137,102 @@ impl MogPermutationShapeCache {
         permutation: Option<Permutation<GridCell>>,
         coordinates: GridShower,
     ) {
-        let new_state = permutation.map(|permutation| (permutation, coordinates));
+        let new_state =
+            permutation.map(|permutation| (permutation, coordinates, self.preview, self.geometry));
 
-        if new_state != self.state {
+        if new_state == self.state {
+            self.hits += 1;
+        } else {
+            self.misses += 1;
             self.state = new_state.clone();
-            if let Some((permutation, coordinates)) = new_state {
-                let line_width = coordinates.cell_scalar_to_pos_scalar(self.line_width) as f64;
+            if let Some((permutation, coordinates, preview, geometry)) = new_state {
+                let line_width = coordinates.cell_scalar_to_pos_scalar(geometry.line_width) as f64;
                 let small_radius = coordinates.cell_scalar_to_pos_scalar(self.small_radius) as f64;
                 let large_radius = coordinates.cell_scalar_to_pos_scalar(self.large_radius) as f64;
+                let (dash, gap) = (1.5 * line_width, line_width);
 
-                let draw_line =
-                    |shape: &mut Shape,
-                     mut start_cell: GridCell,
-                     mut end_cell: GridCell,
-                     width: f64,
-                     mut start_cap: LineCap<[f64; 2], f64>,
-                     mut end_cap: LineCap<[f64; 2], f64>| {
-                        if start_cell > end_cell {
-                            (start_cell, end_cell) = (end_cell, start_cell);
-                            (start_cap, end_cap) = (end_cap, start_cap);
-                        }
-                        let cell_vec = (end_cell.0 - start_cell.0, end_cell.1 - start_cell.1);
-                        let mut slope_type = (
-                            start_cell.0.abs_diff(end_cell.0),
-                            start_cell.1.abs_diff(end_cell.1),
-                        );
-                        if slope_type.0 < slope_type.1 {
-                            slope_type = (slope_type.1, slope_type.0);
-                        }
-                        let start_pos = coordinates.cell_to_pos(start_cell);
-                        let end_pos = coordinates.cell_to_pos(end_cell);
-                        match slope_type {
-                            (2, 0) | (3, 0) | (4, 0) | (5, 0) | (2, 2) | (3, 3) | (4, 2) => {
-                                let pos_vec = end_pos - start_pos;
-                                let mut perp = Vec2 {
-                                    x: pos_vec.y,
-                                    y: -pos_vec.x,
+                let circle = |center: GridCell, radius: f64| -> Shape {
+                    let circle =
+                        Shape::regular_polygon(coordinates.cell_to_pos(center), radius, 12, 0.0);
+                    if preview {
+                        circle.outline(0.5 * line_width)
+                    } else {
+                        circle
+                    }
+                };
+
+                let draw_line = |shape: &mut Shape,
+                                 mut start_cell: GridCell,
+                                 mut end_cell: GridCell,
+                                 width: f64,
+                                 mut start_cap: LineCap<[f64; 2], f64>,
+                                 mut end_cap: LineCap<[f64; 2], f64>|
+                 -> Vec<Pos2> {
+                    if start_cell > end_cell {
+                        (start_cell, end_cell) = (end_cell, start_cell);
+                        (start_cap, end_cap) = (end_cap, start_cap);
+                    }
+                    match line_arc(&coordinates, start_cell, end_cell) {
+                        Some((center, radius, start_angle, end_angle)) => {
+                            let points = arc_points(
+                                center,
+                                radius,
+                                start_angle,
+                                end_angle,
+                                geometry.segments,
+                            );
+                            *shape = &*shape
+                                | &if preview {
+                                    Shape::dashed_lines(
+                                        points.clone(),
+                                        width,
+                                        dash,
+                                        gap,
+                                        start_cap,
+                                        end_cap,
+                                    )
+                                } else {
+                                    Shape::arc(
+                                        center,
+                                        radius,
+                                        start_angle,
+                                        end_angle,
+                                        width,
+                                        geometry.segments,
+                                        start_cap,
+                                        end_cap,
+                                    )
                                 };
-                                // Hand-picked curve directions
-                                match (start_cell, cell_vec) {
-                                    ((_, 1), (0, 2))
-                                    | ((1, _), (2, 0))
-                                    | ((3, _), (2, 0))
-                                    | ((1, _), (3, 0))
-                                    | ((1, _), (4, 0))
-                                    | ((_, 1), (2, 2))
-                                    | ((_, 3), (2, -2)) => perp = -perp,
-                                    _ => {}
-                                }
-
-                                *shape = &*shape
-                                    | &Shape::bezier(
-                                        vec![
-                                            start_pos,
-                                            start_pos + 0.5 * pos_vec + 0.17 * perp,
-                                            end_pos,
-                                        ],
+                            points
+                        }
+                        None => {
+                            let start_pos = coordinates.cell_to_pos(start_cell);
+                            let end_pos = coordinates.cell_to_pos(end_cell);
+                            *shape = &*shape
+                                | &if preview {
+                                    Shape::dashed_lines(
+                                        vec![start_pos, end_pos],
                                         width,
-                                        12,
+                                        dash,
+                                        gap,
                                         start_cap,
                                         end_cap,
-                                    );
-                            }
-                            _ => {
-                                *shape = &*shape
-                                    | &Shape::line(start_pos, end_pos, width, start_cap, end_cap);
-                            }
+                                    )
+                                } else {
+                                    Shape::line(start_pos, end_pos, width, start_cap, end_cap)
+                                };
+                            vec![start_pos, end_pos]
                         }
-                    };
+                    }
+                };
 
                 self.cycles_and_shapes = vec![];
+                self.label_paths = vec![];
+                let mut lines = vec![];
 
                 for cycle in permutation.disjoint_cycles() {
                     let mut shape = Shape::empty();
+                    let label_path;
                     let n = cycle.len();
                     debug_assert!(n >= 2);
 
@@ -122,7 +241,7 @@ impl MogPermutationShapeCache {
                         let start = *cycle[0];
                         let end = *cycle[1];
 
-                        draw_line(
+                        label_path = draw_line(
                             &mut shape,
                             start,
                             end,
@@ -130,71 +249,60 @@ impl MogPermutationShapeCache {
                             LineCap::Round(0.1),
                             LineCap::Round(0.1),
                         );
-                        shape = &shape
-                            | &Shape::regular_polygon(
-                                coordinates.cell_to_pos(start),
-                                small_radius,
-                                12,
-                                0.0,
-                            );
-                        shape = &shape
-                            | &Shape::regular_polygon(
-                                coordinates.cell_to_pos(end),
-                                small_radius,
-                                12,
-                                0.0,
-                            );
+                        lines.push((start, end, label_path.clone()));
+                        shape = &shape | &circle(start, small_radius);
+                        shape = &shape | &circle(end, small_radius);
                     } else {
                         // Draw n-cycles for n >= 3 as o--o--o->o
                         // Omit the longest line
                         // If there are multiple equally longest lines, pick one to omit in a systematic way
-                        let mut lines = vec![];
+                        let mut cycle_lines = vec![];
                         for i in 0..n {
                             let start = *cycle[i];
                             let end = *cycle[(i + 1) % n];
                             debug_assert_ne!(start, end);
-                            lines.push((start, end));
+                            cycle_lines.push((start, end));
                         }
                         let dist_sq = |x: &GridCell, y: &GridCell| -> usize {
                             let d = (x.0.abs_diff(y.0), x.1.abs_diff(y.1));
                             d.0 * d.0 + d.1 * d.1
                         };
-                        let max_dist_sq = lines.iter().map(|(x, y)| dist_sq(x, y)).max().unwrap();
-                        let chosen_longest_line_idx = lines
+                        let max_dist_sq = cycle_lines
+                            .iter()
+                            .map(|(x, y)| dist_sq(x, y))
+                            .max()
+                            .unwrap();
+                        let chosen_longest_line_idx = cycle_lines
                             .iter()
                             .enumerate()
                             .filter(|(_, (x, y))| dist_sq(x, y) == max_dist_sq)
                             .max_by_key(|(_, (x, _))| x)
                             .map(|(i, _)| i)
                             .unwrap();
-                        lines.rotate_left(chosen_longest_line_idx + 1);
-                        lines.pop().unwrap();
+                        cycle_lines.rotate_left(chosen_longest_line_idx + 1);
+                        cycle_lines.pop().unwrap();
 
                         // Draw circles everywhere except the end
-                        for (i, (start, _)) in lines.iter().enumerate() {
+                        for (i, (start, _)) in cycle_lines.iter().enumerate() {
                             shape = &shape
-                                | &Shape::regular_polygon(
-                                    coordinates.cell_to_pos(*start),
-                                    if i == 0 { large_radius } else { small_radius },
-                                    12,
-                                    0.0,
-                                );
+                                | &circle(*start, if i == 0 { large_radius } else { small_radius });
                         }
 
                         // Draw the last line with the arrow head
-                        let (start, end) = lines.pop().unwrap();
-                        draw_line(
+                        let (start, end) = cycle_lines.pop().unwrap();
+                        label_path = draw_line(
                             &mut shape,
                             start,
                             end,
                             line_width,
                             LineCap::Round(0.1),
-                            arrowhead_cap(1.5),
+                            arrowhead_cap(geometry.arrowhead),
                         );
+                        lines.push((start, end, label_path.clone()));
 
                         // Draw all the other lines without arrow heads
-                        for (start, end) in lines {
-                            draw_line(
+                        for (start, end) in cycle_lines {
+                            let path = draw_line(
                                 &mut shape,
                                 start,
                                 end,
@@ -202,14 +310,19 @@ impl MogPermutationShapeCache {
                                 LineCap::Round(0.1),
                                 LineCap::Round(0.1),
                             );
+                            lines.push((start, end, path));
                         }
                     }
 
                     self.cycles_and_shapes
                         .push((cycle.into_iter().cloned().collect(), shape));
+                    self.label_paths.push(label_path);
                 }
+                self.lines = lines;
             } else {
                 self.cycles_and_shapes = vec![];
+                self.label_paths = vec![];
+                self.lines = vec![];
             }
         }
     }
@@ -217,4 +330,35 @@ impl MogPermutationShapeCache {
     pub fn shapes(&self) -> &Vec<(Vec<GridCell>, Shape)> {
         &self.cycles_and_shapes
     }
+
+    // Paths to place labels along, in the same order as `shapes`
+    pub fn label_paths(&self) -> &Vec<Vec<Pos2>> {
+        &self.label_paths
+    }
+
+    // The index in `shapes` of the cycle drawn under a position, if any
+    pub fn hit_test(&self, pos: Pos2) -> Option<usize> {
+        // Later cycles are drawn on top
+        self.cycles_and_shapes
+            .iter()
+            .rposition(|(_, shape)| shape.contains(pos))
+    }
+
+    // The line under a position as the cell it goes from and the cell it goes to
+    // The nearest line wins where several meet at a circle
+    pub fn line_at(&self, pos: Pos2) -> Option<(GridCell, GridCell)> {
+        let (_, coordinates, _, geometry) = self.state.as_ref()?;
+        let reach = coordinates.cell_scalar_to_pos_scalar(geometry.line_width);
+        self.lines
+            .iter()
+            .map(|(start, end, path)| (distance_to_path(path, pos), (*start, *end)))
+            .filter(|(distance, _)| *distance <= reach)
+            .min_by(|(a, _), (b, _)| a.total_cmp(b))
+            .map(|(_, line)| line)
+    }
+
+    // How far from a label path to place the text so it clears the line
+    pub fn label_offset(&self, coordinates: &GridShower) -> f32 {
+        coordinates.cell_scalar_to_pos_scalar(1.5 * self.geometry.line_width)
+    }
 }
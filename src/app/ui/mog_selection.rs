@@ -0,0 +1,364 @@
+use crate::app::logic::finite_field_4::Point as F4Point;
+use crate::app::logic::hexacode;
+use crate::app::logic::miracle_octad_generator::{Point, Vector};
+use crate::app::logic::traits::{Enumerated, Labelled};
+use crate::app::ui::coordinates::CoordinateLabels;
+use crate::app::ui::geometry::Geometry;
+use crate::app::ui::grid::{GridBuilder, GridShower};
+use crate::app::ui::keyboard;
+use crate::app::ui::layout::MogLayout;
+use crate::app::ui::mog::{draw_f4, f4_text, row_to_f4};
+use crate::app::ui::palette::Palette;
+use eframe::egui::{
+    Align2, Color32, Key, Painter, Rect, Response, Sense, Stroke, StrokeKind, Ui, WidgetInfo,
+    WidgetType, accesskit,
+};
+
+// The 6x4 MOG grid with its F4 row labels and column sums, showing a selection of points
+// Screens which let the user pick points embed this and react to the clicks it reports
+pub struct MogSelection<'a> {
+    selected: &'a Vector,
+    // Points to show as selected or unselected in place of `selected`, e.g. while hovering a button
+    preview: Option<&'a Labelled<Point, Option<bool>>>,
+    // Points to outline in a colour
+    highlights: Option<&'a Labelled<Point, Option<Color32>>>,
+    // Points to tint with a colour, e.g. to show which orbit each point is in
+    tints: Option<&'a Labelled<Point, Option<Color32>>>,
+    // Outline the point under the mouse
+    hover_highlight: bool,
+    // Add a row with the parity of each column and colour the column sums by whether they obey the MOG rules
+    verification: bool,
+    // Points marked with a ring because permutations must fix them
+    pinned: Option<&'a Vector>,
+}
+
+pub struct MogSelectionResponse {
+    pub response: Response,
+    pub painter: Painter,
+    pub grid: GridShower,
+    pub layout: MogLayout,
+    pub hovered: Option<Point>,
+    pub clicked: Option<Point>,
+    // Double-clicked anywhere on the grid
+    // The clicks are also reported through `clicked` so a point is toggled twice
+    pub double_clicked: bool,
+    // Held down on a touch screen, where a finger often moves far enough to turn a tap into a drag
+    pub long_pressed: Option<Point>,
+    // Two or more fingers are on the grid, so a drag in progress is a pinch rather than a move
+    pub pinching: bool,
+}
+
+impl<'a> MogSelection<'a> {
+    pub fn new(selected: &'a Vector) -> Self {
+        Self {
+            selected,
+            preview: None,
+            highlights: None,
+            tints: None,
+            hover_highlight: true,
+            verification: false,
+            pinned: None,
+        }
+    }
+
+    pub fn preview(self, preview: &'a Labelled<Point, Option<bool>>) -> Self {
+        Self {
+            preview: Some(preview),
+            ..self
+        }
+    }
+
+    pub fn highlights(self, highlights: &'a Labelled<Point, Option<Color32>>) -> Self {
+        Self {
+            highlights: Some(highlights),
+            ..self
+        }
+    }
+
+    pub fn tints(self, tints: &'a Labelled<Point, Option<Color32>>) -> Self {
+        Self {
+            tints: Some(tints),
+            ..self
+        }
+    }
+
+    pub fn hover_highlight(self, hover_highlight: bool) -> Self {
+        Self {
+            hover_highlight,
+            ..self
+        }
+    }
+
+    pub fn verification(self, verification: bool) -> Self {
+        Self {
+            verification,
+            ..self
+        }
+    }
+
+    pub fn pinned(self, pinned: &'a Vector) -> Self {
+        Self {
+            pinned: Some(pinned),
+            ..self
+        }
+    }
+
+    // Whether a point is shown as selected, taking the preview into account
+    fn is_selected(&self, p: Point) -> bool {
+        self.preview
+            .and_then(|preview| *preview.get(p))
+            .unwrap_or(self.selected.contains_point(p))
+    }
+
+    pub fn show(self, ui: &mut Ui) -> MogSelectionResponse {
+        let mut grid_builder = GridBuilder::default();
+        Geometry::get(ui.ctx()).apply(&mut grid_builder);
+
+        let layout = MogLayout::get(ui.ctx());
+        layout.add_gaps(&mut grid_builder);
+        grid_builder.set_align(Align2::CENTER_TOP);
+
+        // The rows labelled by F4
+        for r in 0usize..4 {
+            grid_builder.include_cell(layout.row_label_to_cell(r));
+        }
+
+        // The columns labelled by the sum of the F4 values in column
+        for c in 0..6 {
+            grid_builder.include_cell(layout.col_label_to_cell(c));
+        }
+
+        // The parity of each column
+        if self.verification {
+            for c in 0..6 {
+                grid_builder.include_cell(layout.col_parity_to_cell(c));
+            }
+        }
+
+        // The 6x4 MOG grid
+        for p in Point::points() {
+            grid_builder.include_cell(layout.point_to_cell(p));
+        }
+
+        let (response, painter, grid) = grid_builder.show(ui);
+
+        let hover_pos = response.hover_pos();
+        let is_hovered = |rect: Rect| hover_pos.is_some_and(|pos| rect.contains(pos));
+        let hovered =
+            Point::points().find(|p| is_hovered(grid.cell_to_rect(layout.point_to_cell(*p))));
+        let hovered_row_label =
+            (0usize..4).find(|r| is_hovered(grid.cell_to_rect(layout.row_label_to_cell(*r))));
+        let hovered_col_label =
+            (0usize..6).find(|c| is_hovered(grid.cell_to_rect(layout.col_label_to_cell(*c))));
+
+        // Link the point under the mouse with the labels of its row and column, and the other way around
+        let highlighted_row = hovered
+            .map(|p| p.point_to_usize() / 6)
+            .or(hovered_row_label);
+        let highlighted_col = hovered
+            .map(|p| p.point_to_usize() % 6)
+            .or(hovered_col_label);
+        let faint_highlight = ui.visuals().widgets.hovered.bg_fill.gamma_multiply(0.5);
+
+        // The rows labelled by F4
+        for r in 0usize..4 {
+            let rect = grid.cell_to_rect(layout.row_label_to_cell(r));
+            accessible_label(ui, &response, ("row", r), rect, || {
+                format!("Row {}", f4_text(row_to_f4(r)))
+            });
+            if highlighted_row == Some(r) {
+                painter.rect_filled(rect, grid.rounding(), faint_highlight);
+            }
+            draw_f4(ui, &painter, rect, ui.visuals().text_color(), row_to_f4(r))
+        }
+
+        // Colour the column sums and parities green when they obey the MOG rules and red otherwise
+        let shown = Vector::from_fn(|p| self.is_selected(p));
+        let rule_colour = |obeyed: bool| {
+            if !self.verification {
+                ui.visuals().text_color()
+            } else if obeyed {
+                Palette::get(ui.ctx()).good()
+            } else {
+                ui.visuals().error_fg_color
+            }
+        };
+        let score_colour = rule_colour(hexacode::is_hexacodeword(&shown.score()));
+        let parity_colour = rule_colour(shown.has_consistent_parity());
+
+        // The columns labelled by the sum of the F4 values in column
+        for c in 0..6 {
+            let mut t = F4Point::Zero;
+            for r in 0..4 {
+                let i = c + 6 * r;
+                let p = Point::usize_to_point(i).unwrap();
+                if self.is_selected(p) {
+                    t = t + row_to_f4(r);
+                }
+            }
+            let rect = grid.cell_to_rect(layout.col_label_to_cell(c));
+            accessible_label(ui, &response, ("column", c), rect, || {
+                format!("Column {}, sum {}", c + 1, f4_text(t))
+            });
+            if highlighted_col == Some(c) {
+                painter.rect_filled(rect, grid.rounding(), faint_highlight);
+            }
+            draw_f4(ui, &painter, rect, score_colour, t);
+        }
+
+        // The columns labelled by their parity, E for even and O for odd
+        if self.verification {
+            let parities = shown.column_parities();
+            for c in 0..6 {
+                let odd = *parities.get(hexacode::Point::usize_to_point(c).unwrap());
+                let rect = grid.cell_to_rect(layout.col_parity_to_cell(c));
+                painter.text(
+                    rect.center(),
+                    Align2::CENTER_CENTER,
+                    if odd { "O" } else { "E" },
+                    eframe::egui::FontId::proportional(0.5 * rect.height()),
+                    parity_colour,
+                );
+            }
+        }
+
+        let mut clicked = None;
+        let mut long_pressed = None;
+
+        // The 6x4 MOG grid
+        for p in Point::points() {
+            let rect = grid.cell_to_rect(layout.point_to_cell(p));
+
+            // Draw square
+            if self.is_selected(p) {
+                // Selected
+                painter.rect_filled(rect, grid.rounding(), ui.visuals().selection.bg_fill);
+            } else {
+                // Not selected
+                painter.rect_filled(rect, grid.rounding(), ui.visuals().widgets.inactive.bg_fill);
+            }
+
+            if let Some(colour) = self.tints.and_then(|tints| *tints.get(p)) {
+                painter.rect_filled(rect, grid.rounding(), colour.gamma_multiply(0.5));
+            }
+
+            // Faintly mark the whole row or column of a hovered label
+            if hovered_row_label == Some(p.point_to_usize() / 6)
+                || hovered_col_label == Some(p.point_to_usize() % 6)
+            {
+                painter.rect_filled(rect, grid.rounding(), faint_highlight);
+            }
+
+            // Highlight
+            let previewed = self.preview.is_some_and(|preview| preview.get(p).is_some());
+            if previewed || (self.hover_highlight && hovered == Some(p)) {
+                painter.rect_stroke(
+                    rect,
+                    grid.rounding(),
+                    ui.visuals().widgets.hovered.fg_stroke,
+                    StrokeKind::Middle,
+                );
+            }
+
+            // Coloured highlights
+            if let Some(colour) = self.highlights.and_then(|highlights| *highlights.get(p)) {
+                painter.rect_stroke(
+                    rect,
+                    grid.rounding(),
+                    Stroke::new(3.0, colour.lerp_to_gamma(ui.visuals().faint_bg_color, 0.4)),
+                    StrokeKind::Inside,
+                );
+            }
+
+            if self.pinned.is_some_and(|pinned| pinned.contains_point(p)) {
+                painter.circle_stroke(
+                    rect.center(),
+                    0.3 * rect.height(),
+                    Stroke::new(0.06 * rect.height(), ui.visuals().warn_fg_color),
+                );
+            }
+
+            if response.clicked()
+                && response
+                    .interact_pointer_pos()
+                    .is_some_and(|pos| rect.contains(pos))
+            {
+                clicked = Some(p);
+            }
+
+            // Each point is also a checkbox for the keyboard and screen readers, in reading order
+            // It only takes focus, so clicks and drags still go to the grid as a whole
+            let cell = ui.interact(
+                rect,
+                response.id.with(("point", p.point_to_usize())),
+                Sense::focusable_noninteractive(),
+            );
+            let selected = self.is_selected(p);
+            cell.widget_info(|| {
+                WidgetInfo::selected(
+                    WidgetType::Checkbox,
+                    true,
+                    selected,
+                    format!(
+                        "Point {}, row {}, column {}",
+                        p.point_to_usize(),
+                        f4_text(p.row),
+                        p.col.point_to_usize() + 1
+                    ),
+                )
+            });
+            if cell.has_focus() {
+                painter.rect_stroke(
+                    rect,
+                    grid.rounding(),
+                    ui.visuals().selection.stroke,
+                    StrokeKind::Outside,
+                );
+                if keyboard::pressed(ui.ctx(), keyboard::key(Key::Space))
+                    || keyboard::pressed(ui.ctx(), keyboard::key(Key::Enter))
+                {
+                    clicked = Some(p);
+                }
+            }
+            if ui.input(|i| i.has_accesskit_action_request(cell.id, accesskit::Action::Click)) {
+                clicked = Some(p);
+            }
+            if response.long_touched()
+                && response
+                    .interact_pointer_pos()
+                    .is_some_and(|pos| rect.contains(pos))
+            {
+                long_pressed = Some(p);
+            }
+        }
+
+        // The app zooms the whole interface on a pinch, since the grid already fills the space it is given
+        let pinching = response.contains_pointer() && ui.input(|i| i.multi_touch()).is_some();
+
+        CoordinateLabels::get(ui.ctx()).draw(ui, &painter, &grid, layout);
+
+        MogSelectionResponse {
+            double_clicked: response.double_clicked(),
+            response,
+            painter,
+            grid,
+            layout,
+            hovered,
+            clicked,
+            long_pressed,
+            pinching,
+        }
+    }
+}
+
+// A row or column label which the keyboard and screen readers can reach, named when it is
+fn accessible_label(
+    ui: &Ui,
+    grid: &Response,
+    key: (&str, usize),
+    rect: Rect,
+    name: impl Fn() -> String,
+) {
+    ui.interact(rect, grid.id.with(key), Sense::focusable_noninteractive())
+        .widget_info(|| WidgetInfo::labeled(WidgetType::Label, true, name()));
+}
@@ -0,0 +1,144 @@
+use crate::app::{AppState, Navigate};
+use eframe::egui::{Button, Key, KeyboardShortcut, Modifiers, Ui};
+
+pub const BACK: KeyboardShortcut = KeyboardShortcut::new(Modifiers::ALT, Key::ArrowLeft);
+pub const FORWARD: KeyboardShortcut = KeyboardShortcut::new(Modifiers::ALT, Key::ArrowRight);
+
+// How many screens are kept to go back to, beyond which the oldest are dropped
+// Each keeps its edits and undo history, so an endless chain of screens would otherwise keep growing
+const MAX_BACK: usize = 32;
+
+// The open screen, the screens it was opened from, and the screens gone back from
+// Going back returns to the screens as they were left, edits and undo history included
+pub struct Navigation {
+    // Oldest first
+    back: Vec<Box<dyn AppState>>,
+    current: Box<dyn AppState>,
+    // Most recently left last
+    forward: Vec<Box<dyn AppState>>,
+}
+
+impl Navigation {
+    // Start at a screen, with the screens it says it was opened from behind it
+    pub fn new(state: Box<dyn AppState>) -> Self {
+        let mut back = vec![];
+        let mut parent = state.parent();
+        while let Some(state) = parent {
+            parent = state.parent();
+            back.push(state);
+        }
+        back.reverse();
+        Self {
+            back,
+            current: state,
+            forward: vec![],
+        }
+    }
+
+    pub fn current(&self) -> &dyn AppState {
+        self.current.as_ref()
+    }
+
+    pub fn current_mut(&mut self) -> &mut dyn AppState {
+        self.current.as_mut()
+    }
+
+    fn push_back(&mut self, state: Box<dyn AppState>) {
+        self.back.push(state);
+        if self.back.len() > MAX_BACK {
+            self.back.remove(0);
+        }
+    }
+
+    pub fn open(&mut self, state: Box<dyn AppState>) {
+        let state = std::mem::replace(&mut self.current, state);
+        self.push_back(state);
+        self.forward.clear();
+    }
+
+    pub fn go_back(&mut self) {
+        if let Some(state) = self.back.pop() {
+            self.forward
+                .push(std::mem::replace(&mut self.current, state));
+        }
+    }
+
+    pub fn go_forward(&mut self) {
+        if let Some(state) = self.forward.pop() {
+            let state = std::mem::replace(&mut self.current, state);
+            self.push_back(state);
+        }
+    }
+
+    // Go back from a screen which has done its job, so is not kept to go forward to
+    fn go_back_done(&mut self) -> bool {
+        match self.back.pop() {
+            Some(state) => {
+                self.current = state;
+                self.forward.clear();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn navigate(&mut self, navigate: Navigate) {
+        match navigate {
+            Navigate::Open(state) => self.open(state),
+            Navigate::Back => self.go_back(),
+            Navigate::Pick(vector) => {
+                if self.go_back_done() {
+                    self.current.picked(vector);
+                }
+            }
+            Navigate::PickPermutation(permutation) => {
+                if self.go_back_done() {
+                    self.current.picked_permutation(permutation);
+                }
+            }
+        }
+    }
+
+    // Back and forward buttons, then the title of each screen from the first to the open one
+    // Returns whether the open screen changed
+    pub fn bar(&mut self, ui: &mut Ui) -> bool {
+        ui.horizontal(|ui| {
+            let mut changed = false;
+            if ui
+                .add_enabled(!self.back.is_empty(), Button::new("⏴"))
+                .on_hover_text(format!("Back ({})", ui.ctx().format_shortcut(&BACK)))
+                .clicked()
+            {
+                self.go_back();
+                changed = true;
+            }
+            if ui
+                .add_enabled(!self.forward.is_empty(), Button::new("⏵"))
+                .on_hover_text(format!("Forward ({})", ui.ctx().format_shortcut(&FORWARD)))
+                .clicked()
+            {
+                self.go_forward();
+                changed = true;
+            }
+            ui.separator();
+
+            // Clicking an earlier screen goes back to it
+            let mut target = None;
+            for (i, state) in self.back.iter().enumerate() {
+                if ui.link(state.title()).clicked() {
+                    target = Some(i);
+                }
+                ui.label("›");
+            }
+            ui.strong(self.current.title());
+            if let Some(i) = target {
+                while self.back.len() > i {
+                    self.go_back();
+                }
+                changed = true;
+            }
+            changed
+        })
+        .inner
+    }
+}
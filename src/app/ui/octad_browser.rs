@@ -0,0 +1,239 @@
+use crate::app::logic::linear_code::Code;
+use crate::app::logic::miracle_octad_generator::{Point, Vector};
+use crate::app::logic::traits::Labelled;
+use crate::app::ui::mog_selection::MogSelection;
+use crate::app::ui::panels::side_panel;
+use crate::app::ui::session::{PointToggleSession, Session};
+use crate::app::ui::table_export::{VectorTable, export_buttons};
+use crate::app::{AppState, Navigate};
+use eframe::{
+    Frame,
+    egui::{CentralPanel, Context, ScrollArea, Ui},
+};
+
+const PAGE_SIZE: usize = 40;
+
+// What a list is of, the octads or the codewords of one weight or of every weight
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Listing {
+    Octads,
+    Codewords(Option<usize>),
+}
+
+// The octads or codewords, optionally only those meeting a selection in some way, a page at a time
+// Shown by the octad and codeword browsers, and the octads are docked as a pane of the point toggle screen
+#[derive(Clone)]
+pub struct CodewordList {
+    listing: Listing,
+    // Only show codewords containing all the selected points
+    contains_selected: bool,
+    // Only show codewords containing none of the selected points
+    disjoint_from_selected: bool,
+    page: usize,
+}
+
+impl CodewordList {
+    fn new(listing: Listing) -> Self {
+        Self {
+            listing,
+            contains_selected: false,
+            disjoint_from_selected: false,
+            page: 0,
+        }
+    }
+
+    pub fn octads() -> Self {
+        Self::new(Listing::Octads)
+    }
+
+    pub fn codewords() -> Self {
+        Self::new(Listing::Codewords(None))
+    }
+
+    // The codewords passing the filters, numbered by their position in the list of all octads or all codewords
+    fn filtered(&self, selected_points: &Vector) -> Vec<(usize, Vector)> {
+        let mog = super::mog::mog();
+        let all = match self.listing {
+            Listing::Octads => mog.octads(),
+            Listing::Codewords(_) => mog.codewords().to_vec(),
+        };
+        all.into_iter()
+            .enumerate()
+            .map(|(i, codeword)| (i + 1, codeword))
+            .filter(|(_, codeword)| match self.listing {
+                Listing::Codewords(Some(weight)) => codeword.weight() == weight,
+                _ => true,
+            })
+            .filter(|(_, codeword)| !self.contains_selected || codeword.contains(selected_points))
+            .filter(|(_, codeword)| {
+                !self.disjoint_from_selected || (codeword & selected_points).weight() == 0
+            })
+            .collect()
+    }
+
+    pub fn first_page(&mut self) {
+        self.page = 0;
+    }
+
+    // Returns the codeword clicked, and previews the one hovered on the grid
+    pub fn show(
+        &mut self,
+        ui: &mut Ui,
+        selected_points: &Vector,
+        preview_select_points: &mut Labelled<Point, Option<bool>>,
+    ) -> Option<Vector> {
+        if let Listing::Codewords(weight) = &mut self.listing {
+            ui.horizontal(|ui| {
+                ui.label("Weight");
+                ui.radio_value(weight, None, "All");
+                for w in [0, 8, 12, 16, 24] {
+                    ui.radio_value(weight, Some(w), w.to_string());
+                }
+            });
+        }
+        let codewords = self.filtered(selected_points);
+        let pages = codewords.len().div_ceil(PAGE_SIZE).max(1);
+        self.page = self.page.min(pages - 1);
+
+        ui.checkbox(
+            &mut self.contains_selected,
+            "Containing the selected points",
+        );
+        ui.checkbox(
+            &mut self.disjoint_from_selected,
+            "Disjoint from the selected points",
+        );
+        ui.label(match self.listing {
+            Listing::Octads => format!("{} of 759 octads", codewords.len()),
+            Listing::Codewords(_) => format!("{} of 4096 codewords", codewords.len()),
+        });
+
+        // The codewords on the open page, or every codeword passing the filters
+        export_buttons(ui, "Export visible", || {
+            VectorTable::with_indices(
+                codewords
+                    .iter()
+                    .skip(self.page * PAGE_SIZE)
+                    .take(PAGE_SIZE)
+                    .cloned(),
+            )
+        });
+        export_buttons(ui, "Export all", || {
+            VectorTable::with_indices(codewords.clone())
+        });
+
+        ui.horizontal(|ui| {
+            if ui.button("<").clicked() && self.page > 0 {
+                self.page -= 1;
+            }
+            ui.label(format!("Page {} of {}", self.page + 1, pages));
+            if ui.button(">").clicked() && self.page + 1 < pages {
+                self.page += 1;
+            }
+        });
+
+        let mut picked = None;
+        ScrollArea::vertical()
+            .id_salt("codeword_list")
+            .show(ui, |ui| {
+                for (i, codeword) in codewords.iter().skip(self.page * PAGE_SIZE).take(PAGE_SIZE) {
+                    let button = ui.button(format!("{}: {}", i, codeword.to_point_list()));
+                    // Preview codeword when hovering on button
+                    if button.hovered() {
+                        for p in (selected_points + codeword).points() {
+                            preview_select_points.set(p, Some(codeword.contains_point(p)));
+                        }
+                    }
+                    if button.clicked() {
+                        picked = Some(codeword.clone());
+                    }
+                }
+            });
+        picked
+    }
+}
+
+// A list of all 759 octads or all 4096 codewords, optionally only those meeting the selected points in some way
+// Clicking one goes back to the point toggle screen with it selected
+#[derive(Clone)]
+pub struct State {
+    parent: PointToggleSession,
+    selected_points: Vector,
+    list: CodewordList,
+}
+
+impl State {
+    pub fn new(parent: PointToggleSession) -> Self {
+        Self {
+            selected_points: parent.selected_points.clone(),
+            parent,
+            list: CodewordList::octads(),
+        }
+    }
+
+    pub fn codewords(parent: PointToggleSession) -> Self {
+        Self {
+            list: CodewordList::codewords(),
+            ..Self::new(parent)
+        }
+    }
+
+    fn is_octads(&self) -> bool {
+        self.list.listing == Listing::Octads
+    }
+}
+
+impl AppState for State {
+    fn update(&mut self, ctx: &Context, _frame: &mut Frame) -> Option<Navigate> {
+        let mut preview_select_points = Labelled::<Point, Option<bool>>::new_constant(None);
+
+        if let Some(navigate) = side_panel(ctx, |ui| {
+            // Back
+            if ui.button("Back").clicked() {
+                return Some(Navigate::Back);
+            }
+
+            ui.heading(if self.is_octads() {
+                "Octads"
+            } else {
+                "Codewords"
+            });
+            // Go back with the codeword selected
+            self.list
+                .show(ui, &self.selected_points, &mut preview_select_points)
+                .map(Navigate::Pick)
+        }) {
+            return Some(navigate);
+        }
+
+        CentralPanel::default().show(ctx, |ui| {
+            MogSelection::new(&self.selected_points)
+                .preview(&preview_select_points)
+                .show(ui);
+        });
+
+        None
+    }
+
+    fn title(&self) -> String {
+        if self.is_octads() {
+            "Octad Browser".to_string()
+        } else {
+            "Codeword Browser".to_string()
+        }
+    }
+
+    fn apply_selection(&mut self, vector: &Vector) -> bool {
+        self.selected_points = vector.clone();
+        self.list.first_page();
+        true
+    }
+
+    fn parent(&self) -> Option<Box<dyn AppState>> {
+        Some(Box::new(self.parent.clone().restore()))
+    }
+
+    fn session(&self) -> Option<Session> {
+        Some(Session::PointToggle(self.parent.clone()))
+    }
+}
@@ -0,0 +1,131 @@
+use crate::app::logic::miracle_octad_generator::{Point, Vector};
+use crate::app::logic::traits::Labelled;
+use crate::app::ui::mog::sextet_idx_to_colour;
+use crate::app::ui::mog_selection::MogSelection;
+use crate::app::ui::panels::side_panel;
+use crate::app::ui::selection_target::SelectionTarget;
+use crate::app::ui::session::{PointToggleSession, Session};
+use crate::app::{AppState, Navigate};
+use eframe::{
+    Frame,
+    egui::{CentralPanel, Color32, Context, ScrollArea},
+};
+
+/*
+Curtis's construction of the MOG: the 35 ways to halve an octad each complete to a sextet,
+whose other four foursomes split the remaining 16 points into parallel planes of their affine space
+Shown one at a time with each foursome in its own colour, the halves of the octad first
+*/
+#[derive(Clone)]
+pub struct State {
+    parent: PointToggleSession,
+    octad: Vector,
+    sextets: Vec<[Vector; 6]>,
+    index: usize,
+}
+
+impl State {
+    pub fn new(parent: PointToggleSession, octad: Vector) -> Result<Self, ()> {
+        let sextets = super::mog::mog().octad_sextets(&octad)?;
+        Ok(Self {
+            parent,
+            octad,
+            sextets,
+            index: 0,
+        })
+    }
+}
+
+impl AppState for State {
+    fn update(&mut self, ctx: &Context, _frame: &mut Frame) -> Option<Navigate> {
+        let mut preview_index = None;
+
+        if let Some(navigate) = side_panel(ctx, |ui| {
+            // Back
+            if ui.button("Back").clicked() {
+                return Some(Navigate::Back);
+            }
+
+            ui.heading("Octad Partitions");
+            if ui.button("Pick another octad").clicked() {
+                return Some(Navigate::Open(Box::new(
+                    self.parent
+                        .clone()
+                        .restore()
+                        .with_selected_points(Vector::zero())
+                        .with_target(Some(SelectionTarget::Octad)),
+                )));
+            }
+            ui.label("The ways to halve the octad, and how the octads meeting each half split the other 16 points");
+
+            let n = self.sextets.len();
+            ui.horizontal(|ui| {
+                if ui.button("<").clicked() {
+                    self.index = (self.index + n - 1) % n;
+                }
+                ui.label(format!("Partition {} of {}", self.index + 1, n));
+                if ui.button(">").clicked() {
+                    self.index = (self.index + 1) % n;
+                }
+            });
+
+            ScrollArea::vertical().show(ui, |ui| {
+                for (i, sextet) in self.sextets.iter().enumerate() {
+                    let button = ui.selectable_label(
+                        i == self.index,
+                        format!(
+                            "{}: {} | {}",
+                            i + 1,
+                            sextet[0].to_point_list(),
+                            sextet[1].to_point_list()
+                        ),
+                    );
+                    // Preview the partition when hovering on its button
+                    if button.hovered() {
+                        preview_index = Some(i);
+                    }
+                    if button.clicked() {
+                        self.index = i;
+                    }
+                }
+            });
+            None
+        }) {
+            return Some(navigate);
+        }
+
+        let sextet = &self.sextets[preview_index.unwrap_or(self.index)];
+        let mut coloured_points = Labelled::<Point, Option<Color32>>::new_constant(None);
+        for (i, foursome) in sextet.iter().enumerate() {
+            for p in foursome.points() {
+                coloured_points.set(p, Some(sextet_idx_to_colour(ctx, i)));
+            }
+        }
+
+        CentralPanel::default().show(ctx, |ui| {
+            MogSelection::new(&self.octad)
+                .highlights(&coloured_points)
+                .show(ui);
+        });
+
+        None
+    }
+
+    fn title(&self) -> String {
+        "Octad Partitions".to_string()
+    }
+
+    fn parent(&self) -> Option<Box<dyn AppState>> {
+        Some(Box::new(self.parent.clone().restore()))
+    }
+
+    fn picked(&mut self, vector: Vector) {
+        if let Ok(state) = Self::new(self.parent.clone(), vector) {
+            *self = state;
+        }
+    }
+
+    fn session(&self) -> Option<Session> {
+        Some(Session::PointToggle(self.parent.clone()))
+    }
+}
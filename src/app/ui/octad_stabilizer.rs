@@ -0,0 +1,214 @@
+use crate::app::logic::miracle_octad_generator::{Point, Vector};
+use crate::app::logic::permutation::Permutation;
+use crate::app::logic::traits::{Enumerated, Labelled};
+use crate::app::ui::geometry::Geometry;
+use crate::app::ui::mog::sextet_idx_to_colour;
+use crate::app::ui::mog_permutation_shapes::MogPermutationShapeCache;
+use crate::app::ui::mog_selection::{MogSelection, MogSelectionResponse};
+use crate::app::ui::palette::Palette;
+use crate::app::ui::panels::side_panel;
+use crate::app::ui::session::{PointToggleSession, Session};
+use crate::app::{AppState, Navigate};
+use eframe::{
+    Frame,
+    egui::{CentralPanel, Color32, Context},
+};
+
+// The permutation of the octad and the image of the base point
+type ExtensionKey = (Permutation<Point>, Point);
+
+// Build elements of the octad stabilizer 2^4:A8 from an even permutation of the octad
+// Each one extends to 16 automorphisms, one for each image of the first point off the octad
+#[derive(Clone)]
+pub struct State {
+    parent: PointToggleSession,
+    octad: Vector,
+    octad_permutation: Permutation<Point>,
+    // The first point off the octad, and where the extension sends it
+    base_point: Point,
+    base_image: Point,
+    // The first of two points of the octad clicked to swap them
+    swap_start: Option<Point>,
+    // The extension of the last octad permutation and base image it was computed for, since it takes a search
+    extension: Option<(ExtensionKey, Option<Permutation<Point>>)>,
+    permutation_shapes: MogPermutationShapeCache,
+}
+
+impl State {
+    pub fn new(parent: PointToggleSession, octad: Vector) -> Result<Self, ()> {
+        if !super::mog::mog().is_octad(&octad) {
+            return Err(());
+        }
+        let base_point = Point::points().find(|p| !octad.contains_point(*p)).unwrap();
+        Ok(Self {
+            parent,
+            octad,
+            octad_permutation: Permutation::identity(),
+            base_point,
+            base_image: base_point,
+            swap_start: None,
+            extension: None,
+            permutation_shapes: MogPermutationShapeCache::default(),
+        })
+    }
+
+    // The automorphism agreeing with the octad permutation and sending the base point to its image
+    // None when the octad permutation is odd
+    fn extension(&mut self) -> Option<Permutation<Point>> {
+        let key = (self.octad_permutation.clone(), self.base_image);
+        if self.extension.as_ref().is_none_or(|(k, _)| *k != key) {
+            let mut images = self
+                .octad
+                .points()
+                .map(|p| (p, *self.octad_permutation.apply(&p)))
+                .collect::<Vec<_>>();
+            images.push((self.base_point, self.base_image));
+            let extension = super::mog::mog().extend_to_automorphism(&images);
+            self.extension = Some((key, extension));
+        }
+        self.extension.as_ref().unwrap().1.clone()
+    }
+
+    // Step the image of the base point through the 16 points off the octad
+    fn step_base_image(&mut self, forward: bool) {
+        let off_octad = Point::points()
+            .filter(|p| !self.octad.contains_point(*p))
+            .collect::<Vec<_>>();
+        let i = off_octad
+            .iter()
+            .position(|p| *p == self.base_image)
+            .unwrap();
+        let n = off_octad.len();
+        self.base_image = off_octad[if forward {
+            (i + 1) % n
+        } else {
+            (i + n - 1) % n
+        }];
+    }
+}
+
+impl AppState for State {
+    fn update(&mut self, ctx: &Context, _frame: &mut Frame) -> Option<Navigate> {
+        let extension = self.extension();
+
+        if let Some(navigate) = side_panel(ctx, |ui| -> Option<Navigate> {
+            // Back
+            if ui.button("Back").clicked() {
+                return Some(Navigate::Back);
+            }
+
+            ui.heading("Octad Stabilizer");
+            ui.label("Build an element of 2^4:A8 from a permutation of the octad");
+            ui.label("Click two points of the octad to swap them");
+            ui.label("Click a point off the octad to send the first point off the octad there");
+
+            ui.heading("Octad Permutation");
+            ui.label(self.octad_permutation.to_cycle_string());
+            if self.octad_permutation.sign() == 1 {
+                ui.label("Even, so it's in A8");
+            } else {
+                ui.colored_label(
+                    ui.visuals().error_fg_color,
+                    "Odd, so it doesn't extend to an automorphism",
+                );
+                ui.label("The octad stabilizer only induces A8 on the octad");
+            }
+            if self.octad_permutation != Permutation::identity() && ui.button("Reset").clicked() {
+                self.octad_permutation = Permutation::identity();
+                self.swap_start = None;
+            }
+
+            // The 16 extensions differ by the pointwise stabilizer 2^4, which moves the points off the octad regularly
+            ui.heading("Coset of 2^4");
+            ui.horizontal(|ui| {
+                if ui.button("<").clicked() {
+                    self.step_base_image(false);
+                }
+                ui.label(format!(
+                    "Send {} to {}",
+                    self.base_point.point_to_usize(),
+                    self.base_image.point_to_usize()
+                ));
+                if ui.button(">").clicked() {
+                    self.step_base_image(true);
+                }
+            });
+
+            if let Some(extension) = &extension {
+                ui.heading("Automorphism");
+                ui.label(extension.to_cycle_string());
+                ui.label(format!("Order {}", extension.order()));
+                if ui.button("Select").clicked() {
+                    return Some(Navigate::PickPermutation(extension.clone()));
+                }
+            }
+
+            None
+        }) {
+            return Some(navigate);
+        }
+
+        // The point waiting to be swapped, and the base point with its image
+        let mut highlights = Labelled::<Point, Option<Color32>>::new_constant(None);
+        highlights.set(self.base_point, Some(sextet_idx_to_colour(ctx, 1)));
+        highlights.set(self.base_image, Some(sextet_idx_to_colour(ctx, 2)));
+        if let Some(p) = self.swap_start {
+            highlights.set(p, Some(sextet_idx_to_colour(ctx, 0)));
+        }
+
+        CentralPanel::default().show(ctx, |ui| {
+            let MogSelectionResponse {
+                painter,
+                grid,
+                layout,
+                clicked,
+                ..
+            } = MogSelection::new(&self.octad)
+                .highlights(&highlights)
+                .show(ui);
+
+            if let Some(p) = clicked {
+                if self.octad.contains_point(p) {
+                    match self.swap_start.take() {
+                        None => self.swap_start = Some(p),
+                        Some(q) if q == p => {}
+                        Some(q) => {
+                            self.octad_permutation =
+                                &self.octad_permutation * &Permutation::new_swap(&q, &p);
+                        }
+                    }
+                } else {
+                    self.base_image = p;
+                }
+            }
+
+            // The automorphism when there is one, otherwise the odd permutation of the octad
+            let (shown, colour) = match extension {
+                Some(extension) => (extension, Palette::get(ctx).good()),
+                None => (self.octad_permutation.clone(), Palette::get(ctx).bad()),
+            };
+            self.permutation_shapes.set_geometry(Geometry::get(ctx));
+            self.permutation_shapes.set_permutation(
+                Some(shown.map_injective_unchecked(|p| layout.point_to_cell(p))),
+                grid,
+            );
+            for (_, shape) in self.permutation_shapes.shapes() {
+                painter.add(shape.to_egui_mesh(colour));
+            }
+        });
+
+        None
+    }
+
+    fn title(&self) -> String {
+        "Octad Stabilizer".to_string()
+    }
+
+    fn parent(&self) -> Option<Box<dyn AppState>> {
+        Some(Box::new(self.parent.clone().restore()))
+    }
+
+    fn session(&self) -> Option<Session> {
+        Some(Session::PointToggle(self.parent.clone()))
+    }
+}
@@ -0,0 +1,117 @@
+use eframe::egui::{Color32, Context, Id, Rgba, Ui};
+
+// The colours used to tell things apart, for the sextet foursomes, permutation arrows and highlights
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum Palette {
+    #[default]
+    Standard,
+    // The Okabe-Ito colours, which stay distinct with the common forms of colour blindness
+    ColourBlind,
+    // Fully saturated colours which stand out against both light and dark backgrounds
+    HighContrast,
+}
+
+impl Palette {
+    fn id() -> Id {
+        Id::new("palette")
+    }
+
+    // The palette every screen should use this frame
+    pub fn get(ctx: &Context) -> Self {
+        ctx.data(|data| data.get_temp(Self::id()).unwrap_or_default())
+    }
+
+    pub fn set(self, ctx: &Context) {
+        ctx.data_mut(|data| data.insert_temp(Self::id(), self));
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Palette::Standard => "Standard",
+            Palette::ColourBlind => "Colour-blind safe",
+            Palette::HighContrast => "High contrast",
+        }
+    }
+
+    pub fn menu(&mut self, ui: &mut Ui) {
+        for palette in [
+            Palette::Standard,
+            Palette::ColourBlind,
+            Palette::HighContrast,
+        ] {
+            ui.radio_value(self, palette, palette.name());
+        }
+    }
+
+    // The colour of the i-th foursome of a sextet, for i < 6
+    pub fn sextet(&self, i: usize) -> Color32 {
+        let colours = match self {
+            Palette::Standard => [
+                Color32::RED,
+                Color32::BLUE,
+                Color32::GREEN,
+                Color32::BROWN,
+                Color32::MAGENTA,
+                Color32::ORANGE,
+            ],
+            Palette::ColourBlind => [
+                Color32::from_rgb(213, 94, 0),
+                Color32::from_rgb(0, 114, 178),
+                Color32::from_rgb(0, 158, 115),
+                Color32::from_rgb(230, 159, 0),
+                Color32::from_rgb(204, 121, 167),
+                Color32::from_rgb(86, 180, 233),
+            ],
+            Palette::HighContrast => [
+                Color32::from_rgb(255, 0, 0),
+                Color32::from_rgb(0, 64, 255),
+                Color32::from_rgb(0, 200, 0),
+                Color32::from_rgb(255, 200, 0),
+                Color32::from_rgb(255, 0, 255),
+                Color32::from_rgb(0, 220, 220),
+            ],
+        };
+        colours[i]
+    }
+
+    // The colour of the i-th of n things, matching the sextet colours when there are at most 6
+    // and otherwise spreading the hues evenly around the colour wheel
+    pub fn colour(&self, i: usize, n: usize) -> Color32 {
+        if n <= 6 {
+            self.sextet(i)
+        } else {
+            let (saturation, value) = match self {
+                Palette::Standard | Palette::ColourBlind => (0.85, 0.9),
+                Palette::HighContrast => (1.0, 1.0),
+            };
+            eframe::egui::ecolor::Hsva::new(i as f32 / n as f32, saturation, value, 1.0).into()
+        }
+    }
+
+    // For automorphisms, codewords and anything else which checks out
+    pub fn good(&self) -> Color32 {
+        match self {
+            Palette::Standard => Color32::GREEN,
+            Palette::ColourBlind => Color32::from_rgb(0, 114, 178),
+            Palette::HighContrast => Color32::from_rgb(0, 220, 0),
+        }
+    }
+
+    // For permutations which are not automorphisms and anything else which fails a check
+    pub fn bad(&self) -> Color32 {
+        match self {
+            Palette::Standard => Color32::RED,
+            Palette::ColourBlind => Color32::from_rgb(213, 94, 0),
+            Palette::HighContrast => Color32::from_rgb(255, 0, 0),
+        }
+    }
+}
+
+// Lighten colours too dark to read against the dark theme, such as pure blue, leaving them as they are in the light theme
+pub fn themed(ctx: &Context, colour: Color32) -> Color32 {
+    if ctx.style().visuals.dark_mode && Rgba::from(colour).intensity() < 0.2 {
+        colour.lerp_to_gamma(Color32::WHITE, 0.35)
+    } else {
+        colour
+    }
+}
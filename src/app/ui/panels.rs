@@ -0,0 +1,307 @@
+use eframe::egui::{
+    Align2, Area, CentralPanel, Context, Id, Order, ScrollArea, SidePanel, TopBottomPanel, Ui,
+    UiBuilder,
+};
+
+// A part of a screen which can be docked beside the grid on either side, or hidden
+// Every screen has the controls, and the point toggle screen also has the rest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum Pane {
+    Controls,
+    Analysis,
+    Statistics,
+    Octads,
+    Grid,
+}
+
+impl Pane {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Pane::Controls => "Controls",
+            Pane::Analysis => "Analysis",
+            Pane::Statistics => "Statistics",
+            Pane::Octads => "Octads",
+            Pane::Grid => "Grid",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum Dock {
+    Left,
+    Right,
+    Hidden,
+}
+
+/*
+Where each pane goes and the order they are stacked in, saved with the other settings
+Panes docked on the same side are stacked top to bottom, split by edges which can be dragged to resize them,
+and egui remembers the sizes along with the widths of the sides
+The grid always fills the middle of the window, so it can only be shown or hidden
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct PanelLayout {
+    pub panes: [(Pane, Dock); 5],
+    // Leave the whole window to the grid
+    pub hidden: bool,
+}
+
+impl Default for PanelLayout {
+    fn default() -> Self {
+        Self {
+            panes: [
+                (Pane::Controls, Dock::Left),
+                (Pane::Analysis, Dock::Left),
+                (Pane::Statistics, Dock::Hidden),
+                (Pane::Octads, Dock::Hidden),
+                (Pane::Grid, Dock::Left),
+            ],
+            hidden: false,
+        }
+    }
+}
+
+impl PanelLayout {
+    fn id() -> Id {
+        Id::new("panel_layout")
+    }
+
+    // The panel layout every screen should use this frame
+    pub fn get(ctx: &Context) -> Self {
+        ctx.data(|data| data.get_temp(Self::id()).unwrap_or_default())
+    }
+
+    pub fn set(self, ctx: &Context) {
+        ctx.data_mut(|data| data.insert_temp(Self::id(), self));
+    }
+
+    pub fn dock(&self, pane: Pane) -> Dock {
+        self.panes
+            .iter()
+            .find(|(p, _)| *p == pane)
+            .map_or(Dock::Left, |(_, dock)| *dock)
+    }
+
+    fn set_dock(&mut self, pane: Pane, dock: Dock) {
+        if let Some(entry) = self.panes.iter_mut().find(|(p, _)| *p == pane) {
+            entry.1 = dock;
+        }
+    }
+
+    // Swap the pane with the next one up or down on the same side
+    fn shift(&mut self, pane: Pane, up: bool) {
+        let Some(i) = self.panes.iter().position(|(p, _)| *p == pane) else {
+            return;
+        };
+        let dock = self.panes[i].1;
+        let same_side = |j: &usize| self.panes[*j].1 == dock;
+        let other = if up {
+            (0..i).rev().find(same_side)
+        } else {
+            (i + 1..self.panes.len()).find(same_side)
+        };
+        if let Some(j) = other {
+            self.panes.swap(i, j);
+        }
+    }
+
+    pub fn menu(&mut self, ui: &mut Ui) {
+        for (pane, dock) in &mut self.panes {
+            ui.horizontal(|ui| {
+                ui.label(pane.name());
+                if *pane == Pane::Grid {
+                    let mut shown = *dock != Dock::Hidden;
+                    ui.checkbox(&mut shown, "Shown");
+                    *dock = if shown { Dock::Left } else { Dock::Hidden };
+                } else {
+                    ui.radio_value(dock, Dock::Left, "Left");
+                    ui.radio_value(dock, Dock::Right, "Right");
+                    ui.radio_value(dock, Dock::Hidden, "Hidden");
+                }
+            });
+        }
+        ui.checkbox(&mut self.hidden, "Hide every pane");
+    }
+
+    // While the panes or just the controls are hidden, a button in the corner of the window to show them again
+    // e.g. to finish picking points for another screen
+    pub fn show_button(&mut self, ctx: &Context) {
+        let controls_hidden = self.dock(Pane::Controls) == Dock::Hidden;
+        if !self.hidden && !controls_hidden {
+            return;
+        }
+        let align = match self.dock(Pane::Controls) {
+            Dock::Right => Align2::RIGHT_BOTTOM,
+            Dock::Left | Dock::Hidden => Align2::LEFT_BOTTOM,
+        };
+        let offset = if align == Align2::RIGHT_BOTTOM {
+            [-8.0, -8.0]
+        } else {
+            [8.0, -8.0]
+        };
+        Area::new(Id::new("show_side_panel"))
+            .order(Order::Foreground)
+            .anchor(align, offset)
+            .show(ctx, |ui| {
+                if ui.button("Show controls").clicked() {
+                    self.hidden = false;
+                    if controls_hidden {
+                        self.set_dock(Pane::Controls, Dock::Left);
+                    }
+                }
+            });
+    }
+}
+
+// The title of a docked pane, with buttons to move it up, down or across, or hide it
+fn pane_header(ui: &mut Ui, layout: &mut PanelLayout, pane: Pane) {
+    ui.horizontal(|ui| {
+        ui.strong(pane.name());
+        ui.with_layout(
+            eframe::egui::Layout::right_to_left(eframe::egui::Align::Center),
+            |ui| {
+                if ui.small_button("✕").on_hover_text("Hide").clicked() {
+                    layout.set_dock(pane, Dock::Hidden);
+                }
+                let across = match layout.dock(pane) {
+                    Dock::Left => Dock::Right,
+                    Dock::Right | Dock::Hidden => Dock::Left,
+                };
+                if ui
+                    .small_button("⇄")
+                    .on_hover_text("Move to the other side")
+                    .clicked()
+                {
+                    layout.set_dock(pane, across);
+                }
+                if ui.small_button("⏷").on_hover_text("Move down").clicked() {
+                    layout.shift(pane, false);
+                }
+                if ui.small_button("⏶").on_hover_text("Move up").clicked() {
+                    layout.shift(pane, true);
+                }
+            },
+        );
+    });
+    ui.separator();
+}
+
+/*
+Show the panes a screen has, docked where the layout says, before the screen's central panel
+The contents of hidden panes are still run without being drawn, so their accelerators and command palette entries work
+Returns the first value a pane returned
+*/
+pub fn dock<R>(
+    ctx: &Context,
+    panes: &[Pane],
+    mut show: impl FnMut(Pane, &mut Ui) -> Option<R>,
+) -> Option<R> {
+    let mut layout = PanelLayout::get(ctx);
+    let mut result = None;
+    let mut run_hidden = |pane: Pane, result: &mut Option<R>| {
+        let mut ui = Ui::new(
+            ctx.clone(),
+            Id::new(("hidden_pane", pane.name())),
+            UiBuilder::new().invisible().max_rect(ctx.screen_rect()),
+        );
+        let r = show(pane, &mut ui);
+        if result.is_none() {
+            *result = r;
+        }
+    };
+    let docked = |side: Dock| -> Vec<Pane> {
+        layout
+            .panes
+            .iter()
+            .filter(|(pane, dock)| {
+                *dock == side && *pane != Pane::Grid && panes.contains(pane) && !layout.hidden
+            })
+            .map(|(pane, _)| *pane)
+            .collect()
+    };
+    let (left, right) = (docked(Dock::Left), docked(Dock::Right));
+    for pane in panes {
+        if *pane != Pane::Grid && !left.contains(pane) && !right.contains(pane) {
+            run_hidden(*pane, &mut result);
+        }
+    }
+
+    // Stack the panes of one side, each but the last in a panel whose bottom edge can be dragged
+    let mut stack = |ui: &mut Ui, side: &[Pane], layout: &mut PanelLayout| {
+        let Some((last, rest)) = side.split_last() else {
+            return;
+        };
+        for pane in rest {
+            TopBottomPanel::top(Id::new(("pane", pane.name())))
+                .resizable(true)
+                .show_inside(ui, |ui| {
+                    pane_header(ui, layout, *pane);
+                    ScrollArea::vertical().show(ui, |ui| {
+                        let r = show(*pane, ui);
+                        if result.is_none() {
+                            result = r;
+                        }
+                    });
+                });
+        }
+        CentralPanel::default().show_inside(ui, |ui| {
+            pane_header(ui, layout, *last);
+            ScrollArea::vertical().show(ui, |ui| {
+                let r = show(*last, ui);
+                if result.is_none() {
+                    result = r;
+                }
+            });
+        });
+    };
+
+    // Without the grid the panes on the right take up the middle of the window instead
+    let grid_shown = !panes.contains(&Pane::Grid) || layout.dock(Pane::Grid) != Dock::Hidden;
+    if !left.is_empty() {
+        SidePanel::left("side_panel")
+            .resizable(true)
+            .min_width(200.0)
+            .show(ctx, |ui| stack(ui, &left, &mut layout));
+    }
+    if !right.is_empty() {
+        if grid_shown {
+            SidePanel::right("right_side_panel")
+                .resizable(true)
+                .min_width(200.0)
+                .show(ctx, |ui| stack(ui, &right, &mut layout));
+        } else {
+            CentralPanel::default().show(ctx, |ui| stack(ui, &right, &mut layout));
+        }
+    }
+    layout.set(ctx);
+    result
+}
+
+// The panel of controls beside the grid, which must be shown before the screen's central panel
+pub fn side_panel<R>(ctx: &Context, add_contents: impl FnOnce(&mut Ui) -> R) -> R {
+    let mut add_contents = Some(add_contents);
+    dock(ctx, &[Pane::Controls], |_, ui| {
+        add_contents.take().map(|add_contents| add_contents(ui))
+    })
+    .unwrap()
+}
+
+// The grid in the middle of the window, unless the layout hides it
+// Call after `dock`, which gives the middle of the window to the panes on the right when the grid is hidden
+pub fn grid_panel(ctx: &Context, add_contents: impl FnOnce(&mut Ui)) {
+    let layout = PanelLayout::get(ctx);
+    if layout.dock(Pane::Grid) == Dock::Hidden {
+        if layout
+            .panes
+            .iter()
+            .any(|(pane, dock)| *dock == Dock::Right && *pane != Pane::Grid && !layout.hidden)
+        {
+            return;
+        }
+        // Nothing else wants the middle, so leave it empty
+        CentralPanel::default().show(ctx, |_| {});
+        return;
+    }
+    CentralPanel::default().show(ctx, add_contents);
+}
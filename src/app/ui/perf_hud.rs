@@ -0,0 +1,83 @@
+use eframe::egui::{Context, Grid, Id, Window};
+use std::collections::BTreeMap;
+use web_time::{Duration, Instant};
+
+// What the screens measured during the current frame
+#[derive(Debug, Clone, Default)]
+struct FrameStats {
+    // Total time spent in each named piece of logic
+    timings: BTreeMap<&'static str, Duration>,
+    // The number of hits and misses of each named cache since it was created
+    caches: BTreeMap<&'static str, (usize, usize)>,
+}
+
+fn id() -> Id {
+    Id::new("perf_hud_stats")
+}
+
+fn with_stats<T>(ctx: &Context, f: impl FnOnce(&mut FrameStats) -> T) -> T {
+    ctx.data_mut(|data| f(data.get_temp_mut_or_default::<FrameStats>(id())))
+}
+
+// Forget what was measured in the previous frame
+pub fn begin_frame(ctx: &Context) {
+    with_stats(ctx, |stats| *stats = FrameStats::default());
+}
+
+// Run `f`, adding the time it takes to the total for `name` this frame
+pub fn time<T>(ctx: &Context, name: &'static str, f: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let result = f();
+    let elapsed = start.elapsed();
+    with_stats(ctx, |stats| {
+        *stats.timings.entry(name).or_default() += elapsed
+    });
+    result
+}
+
+pub fn report_cache(ctx: &Context, name: &'static str, (hits, misses): (usize, usize)) {
+    with_stats(ctx, |stats| {
+        stats.caches.insert(name, (hits, misses));
+    });
+}
+
+// A window showing the frame time and everything measured this frame
+pub fn show(ctx: &Context, open: &mut bool) {
+    let stats = with_stats(ctx, |stats| stats.clone());
+    let frame_time = ctx.input(|i| i.stable_dt);
+    Window::new("Performance")
+        .open(open)
+        .resizable(false)
+        .show(ctx, |ui| {
+            Grid::new("perf_hud_grid").num_columns(2).show(ui, |ui| {
+                ui.label("Frame time");
+                ui.label(format!(
+                    "{:.1} ms ({:.0} fps)",
+                    1000.0 * frame_time,
+                    1.0 / frame_time
+                ));
+                ui.end_row();
+
+                for (name, duration) in &stats.timings {
+                    ui.label(*name);
+                    ui.label(format!("{:.2} ms", 1000.0 * duration.as_secs_f64()));
+                    ui.end_row();
+                }
+
+                for (name, (hits, misses)) in &stats.caches {
+                    ui.label(format!("{} cache", name));
+                    let total = hits + misses;
+                    if total == 0 {
+                        ui.label("unused");
+                    } else {
+                        ui.label(format!(
+                            "{:.1}% hits of {}",
+                            100.0 * *hits as f64 / total as f64,
+                            total
+                        ));
+                    }
+                    ui.end_row();
+                }
+            });
+        });
+}
@@ -0,0 +1,60 @@
+use crate::app::logic::miracle_octad_generator::Point;
+use crate::app::logic::permutation::Permutation;
+use eframe::egui::{Context, TextEdit, Window};
+
+// A window for pasting a permutation from another program, in cycle notation, one-line notation or as a permutation matrix
+#[derive(Clone, Default)]
+pub struct PermutationImportWindow {
+    open: bool,
+    text: String,
+    // Number the points 1-24 as GAP and Magma do, instead of the MOG numbering 0-23
+    numbered_from_one: bool,
+    error: Option<String>,
+}
+
+impl PermutationImportWindow {
+    pub fn open(&mut self) {
+        self.open = true;
+    }
+
+    // The permutation once it has been loaded
+    pub fn show(&mut self, ctx: &Context) -> Option<Permutation<Point>> {
+        let mut open = self.open;
+        let permutation = Window::new("Import Permutation")
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.label("Paste a permutation as cycles e.g. (1,2,3)(4,5),");
+                ui.label("the list of images of the points e.g. [2,3,1,...],");
+                ui.label("or a 24x24 permutation matrix, one row per line.");
+                ui.horizontal(|ui| {
+                    ui.radio_value(&mut self.numbered_from_one, false, "Points 0-23");
+                    ui.radio_value(&mut self.numbered_from_one, true, "Points 1-24 (GAP)");
+                });
+                ui.add(
+                    TextEdit::multiline(&mut self.text)
+                        .code_editor()
+                        .desired_rows(6),
+                );
+                if let Some(error) = &self.error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+                if ui.button("Load").clicked() {
+                    let first = if self.numbered_from_one { 1 } else { 0 };
+                    match Permutation::parse_any(&self.text, first) {
+                        Ok(permutation) => {
+                            self.error = None;
+                            self.open = false;
+                            return Some(permutation);
+                        }
+                        Err(error) => {
+                            self.error = Some(error);
+                        }
+                    }
+                }
+                None
+            })
+            .and_then(|response| response.inner.flatten());
+        self.open &= open;
+        permutation
+    }
+}
@@ -0,0 +1,108 @@
+use crate::app::logic::miracle_octad_generator::{
+    AutomorphismReport, LinearCode, Point, verify_automorphisms,
+};
+use crate::app::logic::permutation::Permutation;
+use crate::app::ui::palette::Palette;
+use eframe::egui::{Context, ScrollArea, TextEdit, Window};
+
+// How many failures of each kind to list
+const MAX_SHOWN: usize = 5;
+
+// A window for checking a whole list of permutations at once, one per line, against the code in use
+#[derive(Clone, Default)]
+pub struct PermutationVerifyWindow {
+    open: bool,
+    text: String,
+    // A file to read the list from
+    #[cfg(not(target_arch = "wasm32"))]
+    path: String,
+    // Number the points 1-24 as GAP and Magma do, instead of the MOG numbering 0-23
+    numbered_from_one: bool,
+    report: Option<AutomorphismReport>,
+    error: Option<String>,
+}
+
+impl PermutationVerifyWindow {
+    pub fn open(&mut self) {
+        self.open = true;
+    }
+
+    // A permutation which failed, once the user chooses to look at it
+    pub fn show(&mut self, ctx: &Context, code: &dyn LinearCode) -> Option<Permutation<Point>> {
+        let mut open = self.open;
+        let permutation = Window::new("Verify Permutations")
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.label("Paste permutations, one per line, in cycle or one-line notation.");
+                ui.horizontal(|ui| {
+                    ui.radio_value(&mut self.numbered_from_one, false, "Points 0-23");
+                    ui.radio_value(&mut self.numbered_from_one, true, "Points 1-24 (GAP)");
+                });
+                #[cfg(not(target_arch = "wasm32"))]
+                ui.horizontal(|ui| {
+                    ui.add(TextEdit::singleline(&mut self.path).hint_text("path/to/file.txt"));
+                    if ui.button("Read file").clicked() {
+                        match std::fs::read_to_string(self.path.trim()) {
+                            Ok(text) => {
+                                self.text = text;
+                                self.error = None;
+                            }
+                            Err(err) => {
+                                self.error = Some(format!("Could not read {}: {err}", self.path));
+                            }
+                        }
+                    }
+                });
+                ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+                    ui.add(
+                        TextEdit::multiline(&mut self.text)
+                            .code_editor()
+                            .desired_rows(6),
+                    );
+                });
+                if let Some(error) = &self.error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+                if ui.button("Verify").clicked() {
+                    let first = if self.numbered_from_one { 1 } else { 0 };
+                    self.report = Some(verify_automorphisms(code, &self.text, first));
+                }
+
+                let report = self.report.as_ref()?;
+                ui.separator();
+                ui.label(format!("Read {} permutations", report.permutations()));
+                ui.colored_label(
+                    Palette::get(ctx).good(),
+                    format!("{} are automorphisms", report.automorphisms),
+                );
+                let mut shown = None;
+                if !report.failures.is_empty() {
+                    ui.colored_label(
+                        ui.visuals().error_fg_color,
+                        format!("{} are not automorphisms", report.failures.len()),
+                    );
+                    for (line, permutation) in report.failures.iter().take(MAX_SHOWN) {
+                        ui.horizontal(|ui| {
+                            ui.label(format!("line {line}: {}", permutation.to_cycle_string()));
+                            if ui.button("Show").clicked() {
+                                shown = Some(permutation.clone());
+                            }
+                        });
+                    }
+                }
+                if !report.parse_errors.is_empty() {
+                    ui.colored_label(
+                        ui.visuals().error_fg_color,
+                        format!("{} lines could not be read", report.parse_errors.len()),
+                    );
+                    for (line, err) in report.parse_errors.iter().take(MAX_SHOWN) {
+                        ui.label(format!("line {line}: {err}"));
+                    }
+                }
+                shown
+            })
+            .and_then(|response| response.inner.flatten());
+        self.open &= open;
+        permutation
+    }
+}
@@ -0,0 +1,224 @@
+use crate::app::logic::miracle_octad_generator::{LinearCode, Point};
+use crate::app::logic::permutation::Permutation;
+use crate::app::ui::geometry::Geometry;
+use crate::app::ui::mog_permutation_shapes::MogPermutationShapeCache;
+use crate::app::ui::mog_selection::{MogSelection, MogSelectionResponse};
+use crate::app::ui::palette::Palette;
+use crate::app::ui::panels::side_panel;
+use crate::app::ui::session::{PointToggleSession, Session};
+use crate::app::{AppState, Navigate};
+use eframe::{
+    Frame,
+    egui::{ComboBox, Context, Key, ScrollArea, TextEdit},
+};
+
+// A list of named permutations which can be composed, inverted, conjugated and compared
+// The result of the last operation is drawn on the grid and can be kept in the list or taken back to the point toggle screen
+#[derive(Clone)]
+pub struct State {
+    parent: PointToggleSession,
+    permutations: Vec<(String, Permutation<Point>)>,
+    // The operands, as indices into `permutations`
+    first: usize,
+    second: usize,
+    // The word giving the result in terms of the names, and its product
+    result: Option<(String, Permutation<Point>)>,
+    comparison: Option<String>,
+    permutation_text: String, // A permutation typed in cycle notation
+    permutation_error: Option<String>,
+    permutation_shapes: MogPermutationShapeCache,
+}
+
+impl State {
+    pub fn new(parent: PointToggleSession) -> Self {
+        let mut state = Self {
+            permutations: vec![],
+            parent,
+            first: 0,
+            second: 0,
+            result: None,
+            comparison: None,
+            permutation_text: String::new(),
+            permutation_error: None,
+            permutation_shapes: MogPermutationShapeCache::default(),
+        };
+        let permutation = state.parent.selected_permutation.clone();
+        if permutation != Permutation::identity() {
+            state.add(permutation);
+        }
+        state
+    }
+
+    // Add a permutation under the first unused capital letter
+    fn add(&mut self, permutation: Permutation<Point>) {
+        let name = ('A'..='Z')
+            .map(|c| c.to_string())
+            .find(|name| !self.permutations.iter().any(|(n, _)| n == name))
+            .unwrap_or_else(|| format!("P{}", self.permutations.len() + 1));
+        self.permutations.push((name, permutation));
+    }
+
+    fn name(&self, i: usize) -> &str {
+        &self.permutations[i].0
+    }
+
+    fn permutation(&self, i: usize) -> &Permutation<Point> {
+        &self.permutations[i].1
+    }
+}
+
+impl AppState for State {
+    fn update(&mut self, ctx: &Context, _frame: &mut Frame) -> Option<Navigate> {
+        let mog = super::mog::mog();
+
+        let n = self.permutations.len();
+        self.first = self.first.min(n.saturating_sub(1));
+        self.second = self.second.min(n.saturating_sub(1));
+
+        if let Some(navigate) = side_panel(ctx, |ui| -> Option<Navigate> {
+            // Back
+            if ui.button("Back").clicked() {
+                return Some(Navigate::Back);
+            }
+
+            ui.heading("Permutations");
+            let mut remove = None;
+            ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+                for (i, (name, permutation)) in self.permutations.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        if ui.small_button("x").on_hover_text("Remove").clicked() {
+                            remove = Some(i);
+                        }
+                        ui.label(format!("{name} = {}", permutation.to_cycle_string()));
+                    });
+                }
+            });
+            if let Some(i) = remove {
+                self.permutations.remove(i);
+                return None;
+            }
+
+            // Type a permutation in cycle notation
+            let response =
+                ui.add(TextEdit::singleline(&mut self.permutation_text).hint_text("(0 1 2)(3 4)"));
+            let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+            if ui.button("Add").clicked() || submitted {
+                match Permutation::parse_cycles(&self.permutation_text) {
+                    Ok(permutation) => {
+                        self.add(permutation);
+                        self.permutation_error = None;
+                    }
+                    Err(error) => {
+                        self.permutation_error = Some(error);
+                    }
+                }
+            }
+            if let Some(error) = &self.permutation_error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+
+            if n > 0 {
+                ui.heading("Operations");
+                for (label, operand) in [("First", &mut self.first), ("Second", &mut self.second)] {
+                    ComboBox::from_label(label)
+                        .selected_text(self.permutations[*operand].0.clone())
+                        .show_ui(ui, |ui| {
+                            for (i, (name, _)) in self.permutations.iter().enumerate() {
+                                ui.selectable_value(operand, i, name);
+                            }
+                        });
+                }
+
+                let (a, b) = (self.name(self.first), self.name(self.second));
+                let (x, y) = (self.permutation(self.first), self.permutation(self.second));
+                // Composition is right to left, so `A∘B` is B followed by A
+                let mut result = None;
+                if ui.button(format!("{a}∘{b}")).clicked() {
+                    result = Some((format!("{a}∘{b}"), y * x));
+                }
+                if ui.button(format!("{a}⁻¹")).clicked() {
+                    result = Some((format!("{a}⁻¹"), x.clone().inverse()));
+                }
+                if ui.button(format!("{b}⁻¹∘{a}∘{b}")).clicked() {
+                    result = Some((format!("{b}⁻¹∘{a}∘{b}"), &(y * x) * &y.clone().inverse()));
+                }
+                if ui.button(format!("Compare {a} and {b}")).clicked() {
+                    self.comparison = Some(if x == y {
+                        format!("{a} = {b}")
+                    } else if x * y == y * x {
+                        format!("{a} and {b} commute")
+                    } else {
+                        format!("{a} and {b} do not commute")
+                    });
+                }
+                if let Some(comparison) = &self.comparison {
+                    ui.label(comparison);
+                }
+                if result.is_some() {
+                    self.result = result;
+                }
+            }
+
+            if let Some((word, product)) = self.result.clone() {
+                ui.heading("Result");
+                ui.label(&word);
+                ui.label(product.to_cycle_string());
+                ui.label(format!("Order {}", product.order()));
+                if ui.button("Keep").clicked() {
+                    self.add(product.clone());
+                }
+                if ui.button("Open in point toggle").clicked() {
+                    return Some(Navigate::PickPermutation(product));
+                }
+            }
+
+            None
+        }) {
+            return Some(navigate);
+        }
+
+        eframe::egui::CentralPanel::default().show(ctx, |ui| {
+            let MogSelectionResponse {
+                painter,
+                grid,
+                layout,
+                ..
+            } = MogSelection::new(&self.parent.selected_points)
+                .hover_highlight(false)
+                .show(ui);
+
+            // Draw the result, or the first operand until there is one
+            let shown = match &self.result {
+                Some((_, product)) => Some(product.clone()),
+                None => self.permutations.get(self.first).map(|(_, p)| p.clone()),
+            };
+            let colour = if shown.as_ref().is_none_or(|p| mog.is_automorphism(p)) {
+                Palette::get(ctx).good()
+            } else {
+                Palette::get(ctx).bad()
+            };
+            self.permutation_shapes.set_geometry(Geometry::get(ctx));
+            self.permutation_shapes.set_permutation(
+                shown.map(|p| p.map_injective_unchecked(|p| layout.point_to_cell(p))),
+                grid,
+            );
+            for (_, shape) in self.permutation_shapes.shapes() {
+                painter.add(shape.to_egui_mesh(colour));
+            }
+        });
+
+        None
+    }
+
+    fn title(&self) -> String {
+        "Permutation Workspace".to_string()
+    }
+
+    fn parent(&self) -> Option<Box<dyn AppState>> {
+        Some(Box::new(self.parent.clone().restore()))
+    }
+
+    fn session(&self) -> Option<Session> {
+        Some(Session::PointToggle(self.parent.clone()))
+    }
+}
@@ -0,0 +1,220 @@
+use eframe::egui::{Color32, Context, Id, Pos2, Rect, Shape};
+use eframe::emath::TSTransform;
+use eframe::epaint::text::Fonts;
+use eframe::epaint::{ClippedPrimitive, ClippedShape, ColorImage, Mesh, Primitive, Tessellator};
+
+// Render the MOG view in software at a chosen width, so the image does not depend on the window size or zoom
+// Text is laid out again with fonts rasterized at the scale of the image, so it is as sharp as the lines
+
+fn request_id() -> Id {
+    Id::new("png_export_requested")
+}
+
+// Save the view with the given width in pixels once this frame has been painted
+pub fn request_export(ctx: &Context, width: u32) {
+    ctx.data_mut(|data| data.insert_temp(request_id(), width));
+}
+
+// Call after every screen has been painted
+// Asks where to save the image if an export was requested
+// Returns where it was saved, or why it could not be, or None if nothing was requested or the dialog was cancelled
+pub fn end_frame(ctx: &Context) -> Option<Result<String, String>> {
+    let width = ctx.data_mut(|data| data.remove_temp::<u32>(request_id()))?;
+    let Some((view, shapes)) = super::view_capture::view_shapes(ctx) else {
+        return Some(Err("There is nothing to export on this screen".to_string()));
+    };
+    let path = rfd::FileDialog::new()
+        .add_filter("PNG image", &["png"])
+        .set_file_name("mog.png")
+        .save_file()?;
+    let image = render(ctx, view, shapes, width);
+    Some(
+        save(&image, &path)
+            .map(|()| path.display().to_string())
+            .map_err(|err| format!("Could not write {}: {err}", path.display())),
+    )
+}
+
+// Lay out the text of a shape again with other fonts
+fn relayout(shape: &mut Shape, fonts: &Fonts) {
+    match shape {
+        Shape::Vec(shapes) => {
+            for shape in shapes {
+                relayout(shape, fonts);
+            }
+        }
+        Shape::Text(text) => text.galley = fonts.layout_job((*text.galley.job).clone()),
+        _ => {}
+    }
+}
+
+fn render(ctx: &Context, view: Rect, shapes: Vec<ClippedShape>, width: u32) -> ColorImage {
+    // Tessellate with as many pixels per point as the image has, after moving the view to the origin
+    let pixels_per_point = width as f32 / view.width();
+    let fonts = Fonts::new(
+        pixels_per_point,
+        ctx.fonts(|fonts| fonts.max_texture_side()),
+        ctx.style().visuals.text_alpha_from_coverage,
+        ctx.fonts(|fonts| fonts.lock().fonts.definitions().clone()),
+    );
+    let transform = TSTransform::from_translation(-view.min.to_vec2());
+    let shapes = shapes
+        .into_iter()
+        .map(
+            |ClippedShape {
+                 clip_rect,
+                 mut shape,
+             }| {
+                relayout(&mut shape, &fonts);
+                shape.transform(transform);
+                ClippedShape {
+                    clip_rect: transform * clip_rect,
+                    shape,
+                }
+            },
+        )
+        .collect();
+    let (font_image, font_tex_size, prepared_discs) = {
+        let atlas = fonts.texture_atlas();
+        let atlas = atlas.lock();
+        (atlas.image().clone(), atlas.size(), atlas.prepared_discs())
+    };
+    let options = ctx.tessellation_options(|options| *options);
+    let primitives = Tessellator::new(pixels_per_point, options, font_tex_size, prepared_discs)
+        .tessellate_shapes(shapes);
+
+    let height = (view.height() * width as f32 / view.width()).round() as usize;
+    let mut canvas = Canvas::new(width as usize, height);
+    for ClippedPrimitive {
+        clip_rect,
+        primitive,
+    } in primitives
+    {
+        if let Primitive::Mesh(mesh) = primitive {
+            canvas.draw_mesh(&mesh, clip_rect, pixels_per_point, &font_image);
+        }
+    }
+    canvas.to_image()
+}
+
+// Premultiplied colours in gamma space, blended the same way egui's renderers do
+struct Canvas {
+    width: usize,
+    height: usize,
+    pixels: Vec<[f32; 4]>,
+}
+
+impl Canvas {
+    fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![[0.0; 4]; width * height],
+        }
+    }
+
+    fn draw_mesh(
+        &mut self,
+        mesh: &Mesh,
+        clip_rect: Rect,
+        pixels_per_point: f32,
+        font_image: &ColorImage,
+    ) {
+        let x_range = (clip_rect.min.x * pixels_per_point).max(0.0) as usize
+            ..((clip_rect.max.x * pixels_per_point).ceil().max(0.0) as usize).min(self.width);
+        let y_range = (clip_rect.min.y * pixels_per_point).max(0.0) as usize
+            ..((clip_rect.max.y * pixels_per_point).ceil().max(0.0) as usize).min(self.height);
+        let to_pixels = |pos: Pos2| pos * pixels_per_point;
+        let cross = |a: Pos2, b: Pos2, c: Pos2| (b - a).x * (c - a).y - (b - a).y * (c - a).x;
+
+        for triangle in mesh.indices.chunks_exact(3) {
+            let vertices = [0, 1, 2].map(|i| mesh.vertices[triangle[i] as usize]);
+            let [a, b, c] = vertices.map(|v| to_pixels(v.pos));
+            let area = cross(a, b, c);
+            if area == 0.0 {
+                continue;
+            }
+            let min_x = a.x.min(b.x).min(c.x).floor().max(x_range.start as f32) as usize;
+            let max_x = (a.x.max(b.x).max(c.x).ceil().max(0.0) as usize).min(x_range.end);
+            let min_y = a.y.min(b.y).min(c.y).floor().max(y_range.start as f32) as usize;
+            let max_y = (a.y.max(b.y).max(c.y).ceil().max(0.0) as usize).min(y_range.end);
+            for y in min_y..max_y {
+                for x in min_x..max_x {
+                    // Sample at the centre of the pixel
+                    let p = Pos2::new(x as f32 + 0.5, y as f32 + 0.5);
+                    let weights =
+                        [cross(b, c, p), cross(c, a, p), cross(a, b, p)].map(|w| w / area);
+                    if weights.iter().any(|w| *w < 0.0) {
+                        continue;
+                    }
+                    let mut colour = [0.0; 4];
+                    let mut uv = Pos2::ZERO;
+                    for (vertex, weight) in vertices.iter().zip(weights) {
+                        for (channel, value) in colour.iter_mut().zip(vertex.color.to_array()) {
+                            *channel += weight * value as f32 / 255.0;
+                        }
+                        uv += weight * vertex.uv.to_vec2();
+                    }
+                    let texel = sample(font_image, uv);
+                    self.blend(x, y, [0, 1, 2, 3].map(|i| colour[i] * texel[i]));
+                }
+            }
+        }
+    }
+
+    fn blend(&mut self, x: usize, y: usize, colour: [f32; 4]) {
+        let pixel = &mut self.pixels[y * self.width + x];
+        for i in 0..4 {
+            pixel[i] = colour[i] + pixel[i] * (1.0 - colour[3]);
+        }
+    }
+
+    fn to_image(&self) -> ColorImage {
+        ColorImage::new(
+            [self.width, self.height],
+            self.pixels
+                .iter()
+                .map(|[r, g, b, a]| {
+                    let channel = |value: f32| (255.0 * value).round().clamp(0.0, 255.0) as u8;
+                    Color32::from_rgba_premultiplied(
+                        channel(*r),
+                        channel(*g),
+                        channel(*b),
+                        channel(*a),
+                    )
+                })
+                .collect(),
+        )
+    }
+}
+
+// The nearest texel of the font atlas, as premultiplied channels between 0 and 1
+fn sample(image: &ColorImage, uv: Pos2) -> [f32; 4] {
+    let [width, height] = image.size;
+    let x = ((uv.x * width as f32) as usize).min(width - 1);
+    let y = ((uv.y * height as f32) as usize).min(height - 1);
+    image.pixels[y * width + x]
+        .to_array()
+        .map(|value| value as f32 / 255.0)
+}
+
+fn save(image: &ColorImage, path: &std::path::Path) -> Result<(), String> {
+    let file = std::fs::File::create(path).map_err(|err| err.to_string())?;
+    let mut encoder = png::Encoder::new(
+        std::io::BufWriter::new(file),
+        image.size[0] as u32,
+        image.size[1] as u32,
+    );
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(|err| err.to_string())?;
+    // PNG stores colours without premultiplying by alpha
+    let data = image
+        .pixels
+        .iter()
+        .flat_map(|colour| colour.to_srgba_unmultiplied())
+        .collect::<Vec<_>>();
+    writer
+        .write_image_data(&data)
+        .map_err(|err| err.to_string())
+}
@@ -1,334 +1,1873 @@
-use crate::app::logic::finite_field_4::Point as F4Point;
+use crate::app::logic::group::PermutationGroup;
+use crate::app::logic::hexacode;
+use crate::app::logic::leech;
+use crate::app::logic::linear_code::{self, BinaryLinearCode, Code};
+use crate::app::logic::m24::{self, M24_ORDER};
 use crate::app::logic::miracle_octad_generator::*;
 use crate::app::logic::permutation::Permutation;
+use crate::app::logic::psl2_23;
+use crate::app::logic::script::Action;
 use crate::app::logic::traits::{Enumerated, Labelled};
-use crate::app::ui::grid::GridCell;
-use crate::app::ui::mog::sextet_idx_to_colour;
-use crate::app::ui::mog_permutation_shapes::MogPermutationShapeCache;
-use crate::app::{
-    AppState,
-    ui::mog::{draw_f4, row_to_f4},
-};
+use crate::app::ui::action_log::ActionLogWindow;
+use crate::app::ui::animation::{APPLY_DURATION, HOVER_FADE, ease_in_out, fade, lerp_colour};
+use crate::app::ui::cas_export::{CasFormat, CasObject, export_buttons};
+use crate::app::ui::code_matrices;
+use crate::app::ui::command_palette;
+use crate::app::ui::geometry::Geometry;
+use crate::app::ui::history::History;
+use crate::app::ui::keyboard;
+use crate::app::ui::memo::Memo;
+use crate::app::ui::mog::{factorization_table, palette_colour, sextet_idx_to_colour};
+use crate::app::ui::mog_permutation_shapes::{MogPermutationShapeCache, line_path};
+use crate::app::ui::mog_selection::{MogSelection, MogSelectionResponse};
+use crate::app::ui::octad_browser::CodewordList;
+use crate::app::ui::palette::Palette;
+use crate::app::ui::panels::{Pane, dock, grid_panel};
+use crate::app::ui::perf_hud;
+use crate::app::ui::permutation_import::PermutationImportWindow;
+use crate::app::ui::permutation_verify::PermutationVerifyWindow;
+use crate::app::ui::reference_card::ReferenceCardWindow;
+use crate::app::ui::script_console::ScriptConsoleWindow;
+use crate::app::ui::selection_statistics::SelectionStatisticsWindow;
+use crate::app::ui::selection_target::SelectionTarget;
+use crate::app::ui::session::{PointToggleSession, Session};
+use crate::app::ui::shape::{point_along_path, text_along_path};
+use crate::app::ui::split_view;
+use crate::app::{AppState, Navigate};
 use eframe::{
     Frame,
-    egui::{CentralPanel, Color32, Context, SidePanel},
+    egui::{
+        Button, CollapsingHeader, Color32, ComboBox, Context, DragValue, FontId, Id, Key,
+        KeyboardShortcut, Modifiers, RichText, ScrollArea, Slider, StrokeKind, TextEdit, UiBuilder,
+    },
 };
+use rand::{SeedableRng, rngs::SmallRng};
+use std::sync::Arc;
+use web_time::{SystemTime, UNIX_EPOCH};
+
+// Applying the permutation to the selection over and over
+#[derive(Clone)]
+struct Playback {
+    // The selection before the permutation was first applied
+    start: Vector,
+    permutation: Permutation<Point>,
+    // How many times the permutation has been applied to `start`
+    power: usize,
+    // When it was last applied while playing, or None when paused
+    playing: Option<f64>,
+}
+
+// What is worked out from the selection and permutation, kept until they change rather than redone every frame
+// Everything here also depends on the code, so it is all invalidated when the code is replaced
+#[derive(Clone, Default)]
+struct Analysis {
+    nearest_codeword: Memo<Vector, NearestCodewordsResult>,
+    nearest_custom_codewords: Memo<Vector, (usize, Vec<Vector>)>,
+    // The number of Golay codewords among the codewords of the custom code, its size and its minimum distance
+    custom_comparison: Memo<(), (usize, usize, usize)>,
+    // The matrices of the code in use, or None if it could not be rebuilt as a linear code
+    matrices: Memo<(), Option<Arc<code_matrices::Matrices>>>,
+    leech_classes: Memo<Vector, Vec<leech::VectorClass>>,
+    nearest_dodecads: Memo<Vector, (usize, Vec<Vector>)>,
+    is_automorphism: Memo<Permutation<Point>, bool>,
+    // The permutation being dragged out, which differs from the selected one only during a drag
+    drag_is_automorphism: Memo<Permutation<Point>, bool>,
+    automorphism_witness: Memo<Permutation<Point>, Option<Vector>>,
+    // The order of the stabilizer of the selection and the sizes of its orbits
+    set_stabilizer: Memo<Vector, (u128, Vec<usize>)>,
+}
+
+impl Analysis {
+    fn invalidate(&mut self) {
+        self.nearest_codeword.invalidate();
+        self.nearest_custom_codewords.invalidate();
+        self.custom_comparison.invalidate();
+        self.matrices.invalidate();
+        self.leech_classes.invalidate();
+        self.nearest_dodecads.invalidate();
+        self.is_automorphism.invalidate();
+        self.drag_is_automorphism.invalidate();
+        self.automorphism_witness.invalidate();
+        self.set_stabilizer.invalidate();
+    }
+
+    fn stats(&self) -> (usize, usize) {
+        [
+            self.nearest_codeword.stats(),
+            self.nearest_custom_codewords.stats(),
+            self.custom_comparison.stats(),
+            self.matrices.stats(),
+            self.leech_classes.stats(),
+            self.nearest_dodecads.stats(),
+            self.is_automorphism.stats(),
+            self.drag_is_automorphism.stats(),
+            self.automorphism_witness.stats(),
+            self.set_stabilizer.stats(),
+        ]
+        .into_iter()
+        .fold((0, 0), |(hits, misses), (h, m)| (hits + h, misses + m))
+    }
+}
 
 #[derive(Clone)]
 pub struct State {
-    selected_points: Labelled<Point, bool>,
+    // A user supplied code to use instead of the Golay code
+    code: Option<Arc<BinaryCode>>,
+    selected_points: Vector,
     selected_permutation: Permutation<Point>,
     permutation_shapes: MogPermutationShapeCache,
+    label_cycles: bool,        // Write the length of each cycle along it
+    show_orbits: bool,         // Tint each point by the orbit of the permutation it is in
+    split_view: bool, // Show the selection before and after the permutation side by side instead of drawing its cycles
     drag_start: Option<Point>, // Set as soon as mouse is pressed
     is_dragging: bool, // Set only once the mouse has moved far enough to be considered dragging
     drag_end: Option<Point>, // Set at the end of the drag
+    translating: bool, // Shift-dragging a selected point translates the whole selection instead
+    drawing_cycle: bool, // Shift-dragging from an unselected point makes a cycle of the points passed through
+    cycle_path: Vec<Point>, // The points passed through so far, in order
+    cursor: Option<Point>, // Moved with the arrow keys, hidden until they are first used
+    history: History<(Vector, Permutation<Point>)>,
+    permutation_text: String, // A permutation typed in cycle notation
+    permutation_error: Option<String>,
+    permutation_import: PermutationImportWindow,
+    permutation_verify: PermutationVerifyWindow,
+    selection_statistics: SelectionStatisticsWindow,
+    octad_list: CodewordList,
+    reference_card: ReferenceCardWindow,
+    action_log: ActionLogWindow<Action>,
+    script_console: ScriptConsoleWindow,
+    // The M24 conjugacy class of the last permutation it was computed for, since computing it is slow
+    m24_class: Option<(Permutation<Point>, Result<&'static str, ()>)>,
+    // The permutation as a word in the generators of M24
+    factorization: Memo<Permutation<Point>, Result<String, ()>>,
+    // Permutations collected to see what group they generate
+    generators: Vec<Permutation<Point>>,
+    generated_group: PermutationGroup<Point>,
+    // Show the column parities and whether the selection obeys the rules for reading codewords off the MOG
+    hexacode_check: bool,
+    // Outline a codeword which is not sent to a codeword, and its image, when the permutation is not an automorphism
+    show_witness: bool,
+    // Where the last export was saved, or why it could not be
+    export_message: Option<Result<String, String>>,
+    // One or two points every permutation must fix, to work in M23 or M22 instead of M24
+    pinned_points: Vector,
+    // The permutation as of the last check against the pinned points, which a permutation moving them is turned back to
+    accepted_permutation: Permutation<Point>,
+    // Why the last permutation was turned back, until another is accepted
+    pinned_error: Option<String>,
+    // Named sets of points kept alongside the selection, each tinted in its own colour
+    point_sets: Vec<(String, Vector)>,
+    // The operands of the set operations, as indices into `point_sets`
+    first_set: usize,
+    second_set: usize,
+    show_point_sets: bool,
+    // Which of the octads through the selection is outlined, when there are several
+    octad_step: usize,
+    // The set to find an automorphism from, kept while the set to map it to is selected
+    map_source: Option<Vector>,
+    // Why there is no automorphism between the last two sets tried
+    map_error: Option<&'static str>,
+    // The selection before the last "Apply", the permutation applied and when, while the points move to their images
+    applying: Option<(Vector, Permutation<Point>, f64)>,
+    // Dropped as soon as the selection or permutation is changed some other way
+    playback: Option<Playback>,
+    apply_times: usize,
+    playback_speed: f32, // Steps per second
+    analysis: Analysis,
+    // Set when another screen opened this one to pick something for it
+    target: Option<SelectionTarget>,
 }
 
 impl Default for State {
     fn default() -> Self {
-        Self::new(Labelled::new_constant(false), Permutation::identity())
+        Self::new(Vector::zero(), Permutation::identity())
     }
 }
 
 impl State {
-    pub fn new(
-        selected_points: Labelled<Point, bool>,
-        selected_permutation: Permutation<Point>,
-    ) -> Self {
+    pub fn new(selected_points: Vector, selected_permutation: Permutation<Point>) -> Self {
         Self {
+            code: None,
             selected_points,
             selected_permutation,
             permutation_shapes: MogPermutationShapeCache::default(),
+            label_cycles: false,
+            show_orbits: false,
+            split_view: false,
             drag_start: None,
             is_dragging: false,
             drag_end: None,
+            translating: false,
+            drawing_cycle: false,
+            cycle_path: vec![],
+            cursor: None,
+            history: History::default(),
+            permutation_text: String::new(),
+            permutation_error: None,
+            permutation_import: PermutationImportWindow::default(),
+            permutation_verify: PermutationVerifyWindow::default(),
+            selection_statistics: SelectionStatisticsWindow::default(),
+            octad_list: CodewordList::octads(),
+            reference_card: ReferenceCardWindow::default(),
+            action_log: ActionLogWindow::default(),
+            script_console: ScriptConsoleWindow::default(),
+            m24_class: None,
+            factorization: Memo::default(),
+            generators: vec![],
+            generated_group: PermutationGroup::generated_by(&[]),
+            hexacode_check: false,
+            show_witness: false,
+            export_message: None,
+            pinned_points: Vector::zero(),
+            accepted_permutation: Permutation::identity(),
+            pinned_error: None,
+            point_sets: vec![],
+            first_set: 0,
+            second_set: 0,
+            show_point_sets: true,
+            octad_step: 0,
+            map_source: None,
+            map_error: None,
+            applying: None,
+            playback: None,
+            apply_times: 2,
+            playback_speed: 2.0,
+            analysis: Analysis::default(),
+            target: None,
+        }
+    }
+
+    // The first unused capital letter, for the next point set
+    fn next_point_set_name(&self) -> String {
+        ('A'..='Z')
+            .map(|c| c.to_string())
+            .find(|name| !self.point_sets.iter().any(|(n, _)| n == name))
+            .unwrap_or_else(|| format!("S{}", self.point_sets.len() + 1))
+    }
+
+    fn add_point_set(&mut self, points: Vector) {
+        let name = self.next_point_set_name();
+        self.point_sets.push((name, points));
+    }
+
+    // Apply the permutation `k` times to the selection, animating the points to their images
+    fn apply_power(&mut self, k: usize, time: f64) {
+        let playback = self.playback.get_or_insert_with(|| Playback {
+            start: self.selected_points.clone(),
+            permutation: self.selected_permutation.clone(),
+            power: 0,
+            playing: None,
+        });
+        playback.power += k;
+        let permutation = self.selected_permutation.pow(k);
+        self.applying = Some((self.selected_points.clone(), permutation.clone(), time));
+        self.selected_points = self.selected_points.permute(&permutation);
+    }
+
+    pub fn with_code(self, code: Option<Arc<BinaryCode>>) -> Self {
+        let mut state = Self { code, ..self };
+        state.analysis.invalidate();
+        state
+    }
+
+    pub fn with_selected_points(self, selected_points: Vector) -> Self {
+        Self {
+            selected_points,
+            ..self
         }
     }
+
+    pub fn with_target(self, target: Option<SelectionTarget>) -> Self {
+        Self { target, ..self }
+    }
+
+    pub fn point_toggle_session(&self) -> PointToggleSession {
+        PointToggleSession {
+            selected_points: self.selected_points.clone(),
+            selected_permutation: self.selected_permutation.clone(),
+            code: PointToggleSession::code_basis(&self.code),
+        }
+    }
+
+    // The selection moved by the offset from the start to the end of the current drag
+    // Rows and columns wrap around, so this is a translation of the 6x4 torus
+    fn translated_selection(&self) -> Option<Vector> {
+        if !self.translating {
+            return None;
+        }
+        let start = self.drag_start?.point_to_usize();
+        let end = self.drag_end?.point_to_usize();
+        let dc = (end % 6 + 6 - start % 6) % 6;
+        let dr = (end / 6 + 4 - start / 6) % 4;
+        Some(Vector::from_points(self.selected_points.points().map(
+            |p| {
+                let i = p.point_to_usize();
+                Point::usize_to_point((i % 6 + dc) % 6 + 6 * ((i / 6 + dr) % 4)).unwrap()
+            },
+        )))
+    }
+
+    /*
+    Turn back a new permutation which moves a pinned point, however it was set
+    Runs at the start and end of every frame, so permutations from other screens, windows and drags are all caught
+    */
+    fn enforce_pinned_points(&mut self) {
+        if self.selected_permutation == self.accepted_permutation {
+            return;
+        }
+        if self
+            .pinned_points
+            .points()
+            .any(|p| *self.selected_permutation.apply(&p) != p)
+        {
+            self.selected_permutation = self.accepted_permutation.clone();
+            self.pinned_error = Some(format!(
+                "That permutation moves a pinned point, which can't be moved while working in {}",
+                pinned_group_name(&self.pinned_points)
+            ));
+        } else {
+            self.accepted_permutation = self.selected_permutation.clone();
+            self.pinned_error = None;
+        }
+    }
+}
+
+// M23 fixes one point of M24 and M22 fixes two
+fn pinned_group_name(pinned_points: &Vector) -> &'static str {
+    match pinned_points.weight() {
+        1 => "M23",
+        2 => "M22",
+        _ => "M24",
+    }
+}
+
+// The usual name of a set stabilizer in M24, for the orders which only turn up for one kind of set
+fn stabilizer_name(order: u128) -> Option<&'static str> {
+    match order {
+        M24_ORDER => Some("M24"),
+        10200960 => Some("M23"),
+        887040 => Some("M22:2"),
+        120960 => Some("PΓL(3, 4)"),
+        322560 => Some("2^4:A8"),
+        95040 => Some("M12"),
+        _ => None,
+    }
+}
+
+// Seeded from the clock, which is plenty for picking random permutations to look at
+pub fn rng() -> SmallRng {
+    SmallRng::seed_from_u64(
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos() as u64,
+    )
+}
+
+// Cycle type with exponents for repeated lengths e.g. `1^8 2^8`, as in the ATLAS names of conjugacy classes
+fn cycle_type_string(cycle_type: &[usize]) -> String {
+    let mut lengths = cycle_type.to_vec();
+    lengths.sort_unstable();
+    lengths.dedup();
+    lengths
+        .into_iter()
+        .map(|n| match cycle_type.iter().filter(|m| **m == n).count() {
+            1 => n.to_string(),
+            count => format!("{n}^{count}"),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
 }
 
 impl AppState for State {
-    fn update(&mut self, ctx: &Context, _frame: &mut Frame) -> Option<Box<dyn AppState>> {
+    fn update(&mut self, ctx: &Context, _frame: &mut Frame) -> Option<Navigate> {
         let mut preview_select_points = Labelled::<Point, Option<bool>>::new_constant(None);
         let mut coloured_highlight_points = Labelled::<Point, Option<Color32>>::new_constant(None);
 
+        self.enforce_pinned_points();
+
         let mog = super::mog::mog();
+        let custom_code = self.code.clone();
+        let code: &dyn LinearCode = match &custom_code {
+            Some(custom_code) => custom_code.as_ref(),
+            None => mog,
+        };
 
-        if let Some(new_state) = SidePanel::left("left_panel")
-            .min_width(200.0)
-            .show(ctx, |ui| {
-                // Clear selection
-                if self.selected_points.weight() != 0
-                    || self.selected_permutation != Permutation::identity()
+        // Playing applies the permutation a step at a time until the selection is back where it started
+        if self.playback.as_ref().is_some_and(|playback| {
+            playback.permutation != self.selected_permutation
+                || playback
+                    .start
+                    .permute(playback.permutation.pow(playback.power))
+                    != self.selected_points
+        }) {
+            self.playback = None;
+        }
+        if let Some(last_step) = self.playback.as_ref().and_then(|playback| playback.playing) {
+            let time = ctx.input(|i| i.time);
+            let interval = 1.0 / self.playback_speed as f64;
+            if time - last_step >= interval {
+                self.apply_power(1, time);
+                let playback = self.playback.as_mut().unwrap();
+                playback.playing = if playback
+                    .power
+                    .is_multiple_of(self.selected_permutation.order())
                 {
-                    ui.heading("Clear selection");
-                    let button = ui.button("Clear");
+                    None
+                } else {
+                    Some(time)
+                };
+            } else {
+                ctx.request_repaint_after_secs((last_step + interval - time) as f32);
+            }
+        }
+        // Outline every point the selection has passed through
+        if let Some(playback) = &self.playback {
+            let mut image = playback.start.clone();
+            for _ in 0..=playback.power.min(self.selected_permutation.order()) {
+                for p in image.points() {
+                    coloured_highlight_points
+                        .set(p, Some(ctx.style().visuals.selection.stroke.color));
+                }
+                image = image.permute(&self.selected_permutation);
+            }
+        }
+
+        if let Some(permutation) = self.permutation_import.show(ctx) {
+            self.selected_permutation = permutation;
+        }
+        if let Some(permutation) = self.permutation_verify.show(ctx, code) {
+            self.selected_permutation = permutation;
+        }
+        self.selection_statistics.show(ctx, &self.selected_points);
+        if let Some(foursome) = self.reference_card.show(ctx) {
+            self.selected_points = foursome;
+        }
+        if let Some((selected_points, selected_permutation)) =
+            self.script_console
+                .show(ctx, &self.selected_points, &self.selected_permutation)
+        {
+            self.selected_points = selected_points;
+            self.selected_permutation = selected_permutation;
+        }
+        if let Some((selected_points, selected_permutation)) = self.action_log.show(ctx, || {
+            (
+                self.selected_points.clone(),
+                self.selected_permutation.clone(),
+            )
+        }) {
+            self.selected_points = selected_points;
+            self.selected_permutation = selected_permutation;
+        }
 
-                    if button.hovered() {
-                        for p in self.selected_points.points() {
-                            preview_select_points.set(p, Some(false));
+        let panes = [
+            Pane::Controls,
+            Pane::Analysis,
+            Pane::Statistics,
+            Pane::Octads,
+            Pane::Grid,
+        ];
+        if let Some(navigate) = dock(ctx, &panes, |pane, ui| -> Option<Navigate> {
+            match pane {
+                Pane::Controls => {
+                    // Hand the selection back to the screen which asked for it
+                    if let Some(target) = self.target {
+                        ui.heading(format!("Pick {}", target.name()));
+                        let navigate = ui.horizontal(|ui| {
+                            if ui
+                                .add_enabled(
+                                    target.accepts(&self.selected_points),
+                                    Button::new("Use selection"),
+                                )
+                                .on_disabled_hover_text(format!("Select {} first", target.name()))
+                                .clicked()
+                            {
+                                return Some(Navigate::Pick(self.selected_points.clone()));
+                            }
+                            if ui.button("Cancel").clicked() {
+                                return Some(Navigate::Back);
+                            }
+                            None
+                        });
+                        if let Some(navigate) = navigate.inner {
+                            return Some(navigate);
                         }
+                        ui.separator();
                     }
 
-                    if button.clicked() {
-                        self.selected_points.set_all(false);
-                        self.selected_permutation = Permutation::identity();
+                    // Clear selection
+                    if self.selected_points.weight() != 0
+                        || self.selected_permutation != Permutation::identity()
+                    {
+                        ui.heading("Clear selection");
+                        let (button, clear) = keyboard::section_button(
+                            ui,
+                            "Selection",
+                            "Clear",
+                            keyboard::key(Key::Delete),
+                        );
+
+                        if button.hovered() {
+                            for p in self.selected_points.points() {
+                                preview_select_points.set(p, Some(false));
+                            }
+                        }
+
+                        if clear {
+                            self.selected_points = Vector::zero();
+                            self.selected_permutation = Permutation::identity();
+                        }
+                    }
+                    if custom_code.is_none()
+                        && (ui.button("Selection statistics").clicked()
+                            || command_palette::offer(ctx, "Selection statistics", None))
+                    {
+                        self.selection_statistics.open();
+                    }
+                    if custom_code.is_none()
+                        && (ui.button("MOG reference card").clicked()
+                            || command_palette::offer(ctx, "MOG reference card", None))
+                    {
+                        self.reference_card.open();
+                    }
+                    if ui.button("Action log").clicked()
+                        || command_palette::offer(ctx, "Action log", None)
+                    {
+                        self.action_log.open();
+                    }
+                    if custom_code.is_none()
+                        && (ui.button("Script console").clicked()
+                            || command_palette::offer(ctx, "Script console", None))
+                    {
+                        self.script_console.open();
                     }
-                }
 
-                if self.selected_permutation != Permutation::identity() {
-                    ui.heading("Permutation");
-                    if mog.is_automorphism(&self.selected_permutation) {
-                        ui.label("Automorphism");
-                    } else {
-                        ui.label("Not Automorphism");
+                    // Type a permutation in cycle notation
+                    ui.heading("Enter permutation");
+                    let response = ui.add(
+                        TextEdit::singleline(&mut self.permutation_text).hint_text("(0 1 2)(3 4)"),
+                    );
+                    let submitted =
+                        response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+                    if ui.button("Set").clicked() || submitted {
+                        match Permutation::parse_cycles(&self.permutation_text) {
+                            Ok(permutation) => {
+                                self.selected_permutation = permutation;
+                                self.permutation_error = None;
+                            }
+                            Err(error) => {
+                                self.permutation_error = Some(error);
+                            }
+                        }
+                    }
+                    if let Some(error) = &self.permutation_error {
+                        ui.colored_label(ui.visuals().error_fg_color, error);
+                    }
+                    if keyboard::button(ui, "Import permutation", keyboard::key(Key::P)).1 {
+                        self.permutation_import.open();
+                    }
+                    if keyboard::button(ui, "Verify permutations", keyboard::key(Key::V)).1 {
+                        self.permutation_verify.open();
+                    }
+                    if keyboard::button(ui, "Permutation workspace", keyboard::key(Key::W)).1 {
+                        return Some(Navigate::Open(Box::new(
+                            super::permutation_workspace::State::new(self.point_toggle_session()),
+                        )));
+                    }
+                    if keyboard::button(ui, "MINIMOG (M12)", keyboard::key(Key::M)).1 {
+                        return Some(Navigate::Open(Box::new(super::minimog::State::new(
+                            self.point_toggle_session(),
+                        ))));
+                    }
+                    if keyboard::button(ui, "Trio stabilizer (bricks)", keyboard::key(Key::K)).1 {
+                        return Some(Navigate::Open(Box::new(
+                            super::trio_stabilizer::State::new(
+                                self.point_toggle_session(),
+                                super::mog::mog().bricks(),
+                            )
+                            .unwrap(),
+                        )));
+                    }
+
+                    if self.selected_permutation != Permutation::identity() {
+                        ui.heading("Permutation");
+                        ui.label(self.selected_permutation.to_cycle_string());
+                        ui.label(format!("Order {}", self.selected_permutation.order()));
+                        ui.label(if self.selected_permutation.sign() == 1 {
+                            "Even"
+                        } else {
+                            "Odd"
+                        });
+                        ui.label(format!(
+                            "Cycle type {}",
+                            cycle_type_string(&self.selected_permutation.cycle_type())
+                        ));
+                        let fixed_points = self.selected_permutation.fixed_points();
+                        if fixed_points.is_empty() {
+                            ui.label("No fixed points");
+                        } else {
+                            ui.label(format!(
+                                "Fixed points {}",
+                                Vector::from_points(fixed_points.into_iter()).to_point_list()
+                            ));
+                        }
+                        let moves_pinned_point = self
+                            .pinned_points
+                            .points()
+                            .any(|p| *self.selected_permutation.apply(&p) != p);
+                        if self
+                            .analysis
+                            .is_automorphism
+                            .get(self.selected_permutation.clone(), |permutation| {
+                                code.is_automorphism(permutation)
+                            })
+                        {
+                            if self.pinned_points.weight() == 0 {
+                                ui.label("Automorphism");
+                            } else if moves_pinned_point {
+                                ui.label("Automorphism, but it moves a pinned point");
+                            } else {
+                                ui.label(format!(
+                                    "Automorphism in {}",
+                                    pinned_group_name(&self.pinned_points)
+                                ));
+                            }
+                            if custom_code.is_none() {
+                                if self.m24_class.as_ref().is_none_or(|(permutation, _)| {
+                                    permutation != &self.selected_permutation
+                                }) {
+                                    self.m24_class = Some((
+                                        self.selected_permutation.clone(),
+                                        m24::conjugacy_class(mog, &self.selected_permutation),
+                                    ));
+                                }
+                                if let Some((_, Ok(class))) = &self.m24_class {
+                                    ui.label(format!("M24 class {class}"));
+                                }
+                                if let Ok(word) = self.factorization.get(
+                                    self.selected_permutation.clone(),
+                                    |permutation| {
+                                        m24::factorize(factorization_table(), permutation)
+                                            .map(|word| m24::word_string(&word))
+                                    },
+                                ) {
+                                    ui.horizontal_wrapped(|ui| {
+                                    ui.label(format!("= {word}")).on_hover_text(
+                                        "As a word in α: x → x+1, β: x → 2x, γ: x → −1/x and δ: x → x³/9 or 9x³, on the projective labels",
+                                    );
+                                    if ui.button("Copy").clicked() {
+                                        ui.ctx().copy_text(word);
+                                    }
+                                });
+                                }
+                            }
+                        } else {
+                            ui.label("Not Automorphism");
+                            if let Some(witness) = self
+                                .analysis
+                                .automorphism_witness
+                                .get(self.selected_permutation.clone(), |permutation| {
+                                    code.automorphism_witness(permutation)
+                                })
+                            {
+                                let image = witness.permute(&self.selected_permutation);
+                                ui.label(format!(
+                                    "{} {} is sent to {}, which is not a codeword",
+                                    if custom_code.is_none() {
+                                        "The octad"
+                                    } else {
+                                        "The codeword"
+                                    },
+                                    witness.to_point_list(),
+                                    image.to_point_list()
+                                ));
+                                ui.checkbox(&mut self.show_witness, "Show where it fails");
+                                if self.show_witness {
+                                    for p in witness.points() {
+                                        coloured_highlight_points.set(
+                                            p,
+                                            Some(ctx.style().visuals.selection.stroke.color),
+                                        );
+                                    }
+                                    for p in image.points() {
+                                        coloured_highlight_points
+                                            .set(p, Some(Palette::get(ctx).bad()));
+                                    }
+                                }
+                            }
+                        }
+
+                        ui.label("Right-click a line to take its point out of the cycle");
+                        ui.label("Ctrl-click (⌘-click on Mac) a cycle to select its points");
+
+                        keyboard::checkbox(
+                            ui,
+                            &mut self.label_cycles,
+                            "Label cycle lengths",
+                            keyboard::key(Key::L),
+                        );
+
+                        keyboard::checkbox(
+                            ui,
+                            &mut self.show_orbits,
+                            "Orbits",
+                            keyboard::key(Key::Q),
+                        );
+                        ui.checkbox(&mut self.split_view, "Before and after side by side");
+                        if self.show_orbits {
+                            let orbits = self.selected_permutation.sorted_cycles();
+                            for (i, orbit) in orbits.iter().enumerate() {
+                                ui.colored_label(
+                                    palette_colour(ctx, i, orbits.len()),
+                                    format!(
+                                        "Size {}: {}",
+                                        orbit.len(),
+                                        orbit
+                                            .iter()
+                                            .map(|p| p.to_string())
+                                            .collect::<Vec<_>>()
+                                            .join(" ")
+                                    ),
+                                );
+                            }
+                            let fixed = self.selected_permutation.fixed_points().len();
+                            if fixed != 0 {
+                                ui.label(format!("and {fixed} orbits of size 1"));
+                            }
+                        }
+
+                        if keyboard::section_button(
+                            ui,
+                            "Permutation",
+                            "Invert",
+                            keyboard::key(Key::I),
+                        )
+                        .1
+                        {
+                            self.selected_permutation = self.selected_permutation.clone().inverse();
+                        }
+
+                        let time = ctx.input(|i| i.time);
+                        let order = self.selected_permutation.order();
+                        if keyboard::section_button(
+                            ui,
+                            "Permutation",
+                            "Apply",
+                            keyboard::key(Key::A),
+                        )
+                        .1
+                        {
+                            self.apply_power(1, time);
+                        }
+                        ui.horizontal(|ui| {
+                            if ui
+                                .button(format!("Apply {} times", self.apply_times))
+                                .clicked()
+                            {
+                                self.apply_power(self.apply_times, time);
+                            }
+                            ui.add(DragValue::new(&mut self.apply_times).range(2..=order.max(2)));
+                        });
+                        ui.horizontal(|ui| {
+                            let playing = self
+                                .playback
+                                .as_ref()
+                                .is_some_and(|playback| playback.playing.is_some());
+                            let play_text = if playing { "Pause" } else { "Play" };
+                            if keyboard::button(ui, play_text, keyboard::key(Key::Y)).1 {
+                                if playing {
+                                    self.playback.as_mut().unwrap().playing = None;
+                                } else {
+                                    self.apply_power(1, time);
+                                    self.playback.as_mut().unwrap().playing = Some(time);
+                                }
+                            }
+                            ui.add(
+                                Slider::new(&mut self.playback_speed, 0.5..=8.0)
+                                    .logarithmic(true)
+                                    .suffix(" steps/s"),
+                            );
+                        });
+                        if let Some(playback) = &self.playback {
+                            ui.label(format!(
+                                "Power {} of a permutation of order {}",
+                                playback.power, order
+                            ));
+                            // The number of distinct images of the starting selection
+                            let mut image = playback.start.permute(&self.selected_permutation);
+                            let mut orbit_size = 1;
+                            while image != playback.start {
+                                image = image.permute(&self.selected_permutation);
+                                orbit_size += 1;
+                            }
+                            ui.label(format!("The selection's orbit has {orbit_size} sets"));
+                        }
+
+                        if keyboard::button(ui, "Add to generators", keyboard::key(Key::G)).1 {
+                            self.generators.push(self.selected_permutation.clone());
+                            self.generated_group = PermutationGroup::generated_by(&self.generators);
+                        }
                     }
 
-                    if ui.button("Invert").clicked() {
-                        self.selected_permutation = self.selected_permutation.clone().inverse();
+                    // The group generated by the collected permutations
+                    if !self.generators.is_empty() {
+                        ui.heading("Generated Group");
+                        for generator in &self.generators {
+                            ui.label(generator.to_cycle_string());
+                        }
+                        let order = self.generated_group.order();
+                        ui.label(format!("Order {order}"));
+                        if custom_code.is_none()
+                            && self.generators.iter().all(|g| mog.is_automorphism(g))
+                        {
+                            ui.label(format!("Index {} in M24", M24_ORDER / order));
+                        }
+                        if self.selected_permutation != Permutation::identity() {
+                            ui.label(
+                                if self.generated_group.contains(&self.selected_permutation) {
+                                    "Contains the permutation"
+                                } else {
+                                    "Does not contain the permutation"
+                                },
+                            );
+                        }
+                        if keyboard::button(ui, "Clear generators", keyboard::key(Key::X)).1 {
+                            self.generators.clear();
+                            self.generated_group = PermutationGroup::generated_by(&[]);
+                        }
                     }
 
-                    if ui.button("Apply").clicked() {
-                        self.selected_points =
-                            self.selected_points.permute(&self.selected_permutation);
+                    // Named sets of points, and their symmetric differences, intersections and unions
+                    ui.heading("Point Sets");
+                    if ui
+                        .button(format!("Save selection as {}", self.next_point_set_name()))
+                        .clicked()
+                    {
+                        self.add_point_set(self.selected_points.clone());
                     }
-                }
+                    let n = self.point_sets.len();
+                    self.first_set = self.first_set.min(n.saturating_sub(1));
+                    self.second_set = self.second_set.min(n.saturating_sub(1));
+                    let mut remove = None;
+                    for (i, (name, points)) in self.point_sets.iter().enumerate() {
+                        ui.horizontal(|ui| {
+                            ui.colored_label(
+                                palette_colour(ctx, i, n),
+                                format!("{name}: {}", points.to_point_list()),
+                            );
+                            let select = ui.button("Select");
+                            if select.hovered() {
+                                preview_select_points =
+                                    Labelled::from_fn(|p| Some(points.contains_point(p)));
+                            }
+                            if select.clicked() {
+                                self.selected_points = points.clone();
+                            }
+                            if ui.button("🗑").clicked() {
+                                remove = Some(i);
+                            }
+                        });
+                    }
+                    if let Some(i) = remove {
+                        self.point_sets.remove(i);
+                    }
+                    if !self.point_sets.is_empty() {
+                        ui.checkbox(&mut self.show_point_sets, "Tint the points of each set");
+                        for (label, operand) in [
+                            ("First set", &mut self.first_set),
+                            ("Second set", &mut self.second_set),
+                        ] {
+                            ComboBox::from_label(label)
+                                .selected_text(self.point_sets[*operand].0.clone())
+                                .show_ui(ui, |ui| {
+                                    for (i, (name, _)) in self.point_sets.iter().enumerate() {
+                                        ui.selectable_value(operand, i, name);
+                                    }
+                                });
+                        }
+                        let (a, x) = &self.point_sets[self.first_set];
+                        let (b, y) = &self.point_sets[self.second_set];
+                        let mut result = None;
+                        ui.horizontal(|ui| {
+                            for (text, hover_text, points) in [
+                                (format!("{a} + {b}"), "Symmetric difference", x + y),
+                                (format!("{a} ∧ {b}"), "Intersection", x & y),
+                                (format!("{a} ∨ {b}"), "Union", x | y),
+                            ] {
+                                let button = ui.button(text).on_hover_text(hover_text);
+                                if button.hovered() {
+                                    preview_select_points =
+                                        Labelled::from_fn(|p| Some(points.contains_point(p)));
+                                }
+                                if button.clicked() {
+                                    result = Some(points);
+                                }
+                            }
+                        });
+                        if let Some(points) = result {
+                            self.add_point_set(points);
+                        }
+                    }
+
+                    // The matrices of the code in use, with the syndrome of the selection
+                    CollapsingHeader::new("Matrices").show(ui, |ui| {
+                        let matrices = self.analysis.matrices.get((), |()| {
+                            BinaryLinearCode::from_mog_code(code)
+                                .ok()
+                                .map(|linear_code| {
+                                    Arc::new(code_matrices::Matrices::new(&linear_code))
+                                })
+                        });
+                        if let Some(matrices) = matrices {
+                            let selection = linear_code::Vector::from_points(
+                                self.selected_points.points().map(|p| p.point_to_usize()),
+                            );
+                            code_matrices::show(ui, &matrices, &selection);
+                        }
+                    });
 
-                // The nearest codeword(s)
-                let nearest = mog.nearest_codeword(&self.selected_points);
-                match nearest {
-                    NearestCodewordsResult::Unique { codeword, distance } => {
+                    // Copy the selection into a computer algebra system
+                    ui.heading("Export");
+                    let mut saved = vec![];
+                    let formats = [CasFormat::Gap, CasFormat::Magma];
+                    saved.push(export_buttons(ui, "Selection", &formats, || {
+                        CasObject::Set(self.selected_points.clone())
+                    }));
+                    if custom_code.is_none() && mog.is_octad(&self.selected_points) {
+                        saved.push(export_buttons(ui, "Octad", &formats, || {
+                            CasObject::Octad(self.selected_points.clone())
+                        }));
+                    }
+                    if custom_code.is_none() && self.selected_points.weight() == 4 {
+                        saved.push(export_buttons(ui, "Sextet", &formats, || {
+                            let tetrads = mog.complete_sextet(&self.selected_points).unwrap();
+                            CasObject::Sextet(tetrads.to_vec())
+                        }));
+                    }
+                    if self.selected_permutation != Permutation::identity() {
+                        saved.push(export_buttons(
+                            ui,
+                            "Permutation",
+                            &[CasFormat::Gap, CasFormat::GapPermList, CasFormat::Magma],
+                            || CasObject::Permutation(self.selected_permutation.clone()),
+                        ));
+                    }
+                    if let Some(message) = saved.into_iter().flatten().next() {
+                        self.export_message = Some(message);
+                    }
+                    match &self.export_message {
+                        Some(Ok(path)) => {
+                            ui.label(format!("Saved to {path}"));
+                        }
+                        Some(Err(err)) => {
+                            ui.colored_label(ui.visuals().error_fg_color, err);
+                        }
+                        None => {}
+                    }
+                }
+                Pane::Analysis => {
+                    if let Some(custom_code) = &custom_code {
+                        // The nearest codeword(s) in the user supplied code
+                        let (distance, nearest) = perf_hud::time(ctx, "Decoding", || {
+                            self.analysis
+                                .nearest_custom_codewords
+                                .get(self.selected_points.clone(), |selected_points| {
+                                    custom_code.nearest_codewords(selected_points)
+                                })
+                        });
                         if distance == 0 {
                             ui.heading("It's a Codeword");
                         } else {
-                            ui.heading("Nearest Codeword");
+                            ui.heading(if nearest.len() == 1 {
+                                "Nearest Codeword"
+                            } else {
+                                "Nearest Codewords"
+                            });
                             ui.label(format!("Distance = {}", distance));
+                            for (num, codeword) in nearest.iter().enumerate() {
+                                let text = format!("Select {}", num + 1);
+                                let (button, select) = match keyboard::FUNCTION_KEYS.get(num) {
+                                    Some(key) => keyboard::section_button(
+                                        ui,
+                                        "Nearest codewords",
+                                        text,
+                                        keyboard::key(*key),
+                                    ),
+                                    None => {
+                                        let button = ui.button(text);
+                                        let clicked = button.clicked();
+                                        (button, clicked)
+                                    }
+                                };
+                                // Preview codeword when hovering on button
+                                if button.hovered() {
+                                    for p in (&self.selected_points + codeword).points() {
+                                        preview_select_points
+                                            .set(p, Some(codeword.contains_point(p)));
+                                    }
+                                }
+                                // Complete the selection
+                                if select {
+                                    for p in (&self.selected_points + codeword).points() {
+                                        self.selected_points.set(p, codeword.contains_point(p));
+                                    }
+                                }
+                            }
+                        }
 
-                            let button = ui.button("Select");
-                            // Preview octad when hovering on button
-                            if button.hovered() {
-                                for p in (&self.selected_points + &codeword).points() {
-                                    preview_select_points.set(p, Some(*codeword.get(p)));
+                        // How the user supplied code relates to the Golay code
+                        ui.heading("Compare with Golay Code");
+                        let (common, size, minimum_distance) =
+                            self.analysis.custom_comparison.get((), |()| {
+                                let codewords = custom_code.codewords();
+                                let common =
+                                    codewords.iter().filter(|c| mog.is_codeword(c)).count();
+                                (common, codewords.len(), custom_code.minimum_distance())
+                            });
+                        if common == size {
+                            ui.label("Identical to the Golay code");
+                        } else {
+                            ui.label(format!(
+                                "{} of {} codewords are Golay codewords",
+                                common, size
+                            ));
+                        }
+                        ui.label(format!("Minimum distance = {}", minimum_distance));
+                    } else {
+                        // The nearest codeword(s)
+                        let nearest = perf_hud::time(ctx, "Decoding", || {
+                            self.analysis
+                                .nearest_codeword
+                                .get(self.selected_points.clone(), |selected_points| {
+                                    mog.nearest_codeword(selected_points)
+                                })
+                        });
+                        match nearest {
+                            NearestCodewordsResult::Unique { codeword, distance } => {
+                                if distance == 0 {
+                                    ui.heading("It's a Codeword");
+
+                                    // The shortest Leech lattice vectors built on the codeword
+                                    ui.heading("Leech Lattice");
+                                    ui.label("This selection corresponds to");
+                                    for class in self
+                                        .analysis
+                                        .leech_classes
+                                        .get(codeword.clone(), |codeword| {
+                                            leech::vectors_from_codeword(mog, codeword)
+                                        })
+                                    {
+                                        let example = Point::points()
+                                            .map(|p| format!("{:3}", class.example.coordinate(p)))
+                                            .collect::<Vec<_>>()
+                                            .chunks(6)
+                                            .map(|row| row.join(""))
+                                            .collect::<Vec<_>>()
+                                            .join("\n");
+                                        ui.label(format!(
+                                            "{} Leech lattice vectors of shape {}, type {}",
+                                            class.count,
+                                            class.example.shape(),
+                                            class.example.vector_type()
+                                        ))
+                                        .on_hover_text(
+                                            RichText::new(format!("For example\n{example}"))
+                                                .monospace(),
+                                        );
+                                    }
+                                } else {
+                                    ui.heading("Nearest Codeword");
+                                    ui.label(format!("Distance = {}", distance));
+
+                                    let (button, select) = keyboard::section_button(
+                                        ui,
+                                        "Nearest codeword",
+                                        "Select",
+                                        keyboard::key(Key::N),
+                                    );
+                                    // Preview octad when hovering on button
+                                    if button.hovered() {
+                                        for p in (&self.selected_points + &codeword).points() {
+                                            preview_select_points
+                                                .set(p, Some(codeword.contains_point(p)));
+                                        }
+                                    }
+                                    // Complete the selection
+                                    if select {
+                                        for p in (&self.selected_points + &codeword).points() {
+                                            self.selected_points.set(p, codeword.contains_point(p));
+                                        }
+                                    }
                                 }
                             }
-                            // Complete the selection
-                            if button.clicked() {
-                                for p in (&self.selected_points + &codeword).points() {
-                                    self.selected_points.set(p, *codeword.get(p));
+                            NearestCodewordsResult::Six { codewords } => {
+                                ui.heading("Nearest Codewords");
+                                ui.label("Distance = 4");
+                                for (num, codeword) in codewords.iter().enumerate() {
+                                    let (button, select) = keyboard::section_button(
+                                        ui,
+                                        "Nearest codewords",
+                                        format!("Select {}", num + 1),
+                                        keyboard::key(keyboard::FUNCTION_KEYS[num]),
+                                    );
+                                    // Preview octad when hovering on button
+                                    if button.hovered() {
+                                        for p in (&self.selected_points + codeword).points() {
+                                            preview_select_points
+                                                .set(p, Some(codeword.contains_point(p)));
+                                        }
+                                    }
+                                    // Complete the selection
+                                    if select {
+                                        for p in (&self.selected_points + codeword).points() {
+                                            self.selected_points.set(p, codeword.contains_point(p));
+                                        }
+                                    }
+                                }
+
+                                // Complete a sextet from 4 points
+                                ui.heading("Complete Sextet");
+                                let mut sextet = codewords
+                                    .iter()
+                                    .map(|codeword| &self.selected_points + codeword)
+                                    .collect::<Vec<_>>();
+                                sextet.sort_unstable();
+                                sextet.reverse();
+                                let ordered_sextet = sextet;
+
+                                if self.selected_points.weight() == 4 {
+                                    ui.label("The unique sextet containing these 4 points");
+                                    let (complete_sextet_button, complete_sextet) =
+                                        keyboard::section_button(
+                                            ui,
+                                            "Complete sextet",
+                                            "Select",
+                                            keyboard::key(Key::E),
+                                        );
+
+                                    if complete_sextet_button.hovered() {
+                                        for (i, vector) in ordered_sextet.iter().enumerate() {
+                                            for p in vector.points() {
+                                                coloured_highlight_points
+                                                    .set(p, Some(sextet_idx_to_colour(ctx, i)));
+                                            }
+                                        }
+                                    }
+
+                                    if complete_sextet {
+                                        return Some(Navigate::Open(Box::new(
+                                            super::sextet_labelling::State::from_foursome(
+                                                self.point_toggle_session(),
+                                                &self.selected_points,
+                                            ),
+                                        )));
+                                    }
+                                } else {
+                                    ui.label(
+                                    "\
+    The sextet whose foursomes are the differences between these points and the nearest 6 codewords",
+                                );
+                                    ui.label("Pick the foursome to start from");
+                                    for (i, foursome) in ordered_sextet.iter().enumerate() {
+                                        let (button, select) = keyboard::button(
+                                            ui,
+                                            format!("Foursome {}", i + 1),
+                                            KeyboardShortcut::new(
+                                                Modifiers::SHIFT,
+                                                keyboard::NUMBER_KEYS[i],
+                                            ),
+                                        );
+                                        // Preview just this foursome when hovering on its button
+                                        if button.on_hover_text(foursome.to_point_list()).hovered()
+                                        {
+                                            for p in foursome.points() {
+                                                coloured_highlight_points
+                                                    .set(p, Some(sextet_idx_to_colour(ctx, i)));
+                                            }
+                                        }
+                                        if select {
+                                            return Some(Navigate::Open(Box::new(
+                                                super::sextet_labelling::State::from_foursome(
+                                                    self.point_toggle_session(),
+                                                    foursome,
+                                                ),
+                                            )));
+                                        }
+                                    }
                                 }
                             }
                         }
-                    }
-                    NearestCodewordsResult::Six { codewords } => {
-                        ui.heading("Nearest Codewords");
-                        ui.label("Distance = 4");
-                        for (num, codeword) in codewords.iter().enumerate() {
-                            let button = ui.button(format!("Select {}", num + 1));
-                            // Preview octad when hovering on button
-                            if button.hovered() {
-                                for p in (&self.selected_points + codeword).points() {
-                                    preview_select_points.set(p, Some(*codeword.get(p)));
+
+                        // Dodecads near a selection of about 12 points
+                        if (9..=15).contains(&self.selected_points.weight()) {
+                            let (distance, nearest) = self
+                                .analysis
+                                .nearest_dodecads
+                                .get(self.selected_points.clone(), |selected_points| {
+                                    mog.nearest_dodecads(selected_points)
+                                });
+                            ui.heading(if nearest.len() == 1 {
+                                "Nearest Dodecad"
+                            } else {
+                                "Nearest Dodecads"
+                            });
+                            ui.label(format!("Distance = {}", distance));
+                            const MAX_SHOWN: usize = 6;
+                            for (num, dodecad) in nearest.iter().take(MAX_SHOWN).enumerate() {
+                                let pattern =
+                                    dodecad.column_pattern().map(|n| n.to_string()).join(" ");
+                                // Whether it is special, umbral or transverse with respect to the columns
+                                let (button, select) = ui
+                                    .horizontal(|ui| {
+                                        let button = keyboard::section_button(
+                                            ui,
+                                            "Nearest dodecads",
+                                            format!("Select {}", num + 1),
+                                            KeyboardShortcut::new(
+                                                Modifiers::ALT,
+                                                keyboard::NUMBER_KEYS[num],
+                                            ),
+                                        );
+                                        if let Ok(class) = mog.classify_dodecad(dodecad) {
+                                            ui.label(class.name());
+                                        }
+                                        button
+                                    })
+                                    .inner;
+                                let button =
+                                    button.on_hover_text(format!("Meets the columns {}", pattern));
+                                // Preview dodecad when hovering on button
+                                if button.hovered() {
+                                    for p in (&self.selected_points + dodecad).points() {
+                                        preview_select_points
+                                            .set(p, Some(dodecad.contains_point(p)));
+                                    }
+                                }
+                                if select {
+                                    self.selected_points = dodecad.clone();
                                 }
                             }
-                            // Complete the selection
-                            if button.clicked() {
-                                for p in (&self.selected_points + codeword).points() {
-                                    self.selected_points.set(p, *codeword.get(p));
+                            if nearest.len() > MAX_SHOWN {
+                                ui.label(format!("and {} more", nearest.len() - MAX_SHOWN));
+                            }
+                        }
+
+                        if mog.syndrome(&self.selected_points) != 0
+                            && keyboard::button(ui, "Coset", keyboard::key(Key::U)).1
+                        {
+                            return Some(Navigate::Open(Box::new(super::coset_view::State::new(
+                                self.point_toggle_session(),
+                            ))));
+                        }
+
+                        if keyboard::button(ui, "Encoder and Decoder", keyboard::key(Key::D)).1 {
+                            return Some(Navigate::Open(Box::new(
+                                super::encoder_playground::State::new(self.point_toggle_session()),
+                            )));
+                        }
+
+                        if keyboard::button(ui, "Browse Octads", keyboard::key(Key::O)).1 {
+                            return Some(Navigate::Open(Box::new(
+                                super::octad_browser::State::new(self.point_toggle_session()),
+                            )));
+                        }
+
+                        if keyboard::button(ui, "Browse Codewords", keyboard::key(Key::J)).1 {
+                            return Some(Navigate::Open(Box::new(
+                                super::octad_browser::State::codewords(self.point_toggle_session()),
+                            )));
+                        }
+
+                        if mog.is_octad(&self.selected_points)
+                            && keyboard::button(ui, "Trios", keyboard::key(Key::T)).1
+                        {
+                            return Some(Navigate::Open(Box::new(
+                                super::trio_view::State::new(
+                                    self.point_toggle_session(),
+                                    self.selected_points.clone(),
+                                )
+                                .unwrap(),
+                            )));
+                        }
+                        if mog.is_octad(&self.selected_points)
+                            && keyboard::button(ui, "Octad partitions", keyboard::key(Key::F)).1
+                        {
+                            return Some(Navigate::Open(Box::new(
+                                super::octad_partitions::State::new(
+                                    self.point_toggle_session(),
+                                    self.selected_points.clone(),
+                                )
+                                .unwrap(),
+                            )));
+                        }
+                        if mog.is_octad(&self.selected_points)
+                            && keyboard::button(ui, "Octad stabilizer", keyboard::key(Key::B)).1
+                        {
+                            return Some(Navigate::Open(Box::new(
+                                super::octad_stabilizer::State::new(
+                                    self.point_toggle_session(),
+                                    self.selected_points.clone(),
+                                )
+                                .unwrap(),
+                            )));
+                        }
+
+                        // Check the selection by hand the way Curtis reads codewords off the MOG
+                        ui.heading("Hexacode Check");
+                        keyboard::checkbox(
+                            ui,
+                            &mut self.hexacode_check,
+                            "Show column parities",
+                            keyboard::key(Key::H),
+                        );
+                        if self.hexacode_check {
+                            let score = self.selected_points.score();
+                            let is_hexacodeword = hexacode::is_hexacodeword(&score);
+                            let consistent = self.selected_points.has_consistent_parity();
+                            let rule = |ui: &mut eframe::egui::Ui, obeyed: bool, text: &str| {
+                                if obeyed {
+                                    ui.colored_label(Palette::get(ctx).good(), text);
+                                } else {
+                                    ui.colored_label(ui.visuals().error_fg_color, text);
                                 }
+                            };
+                            rule(
+                                ui,
+                                is_hexacodeword,
+                                if is_hexacodeword {
+                                    "The score is a hexacodeword"
+                                } else {
+                                    "The score is not a hexacodeword"
+                                },
+                            );
+                            rule(
+                                ui,
+                                consistent,
+                                &format!(
+                                    "Top row is {}, columns are {}",
+                                    if self.selected_points.top_row_parity() {
+                                        "odd"
+                                    } else {
+                                        "even"
+                                    },
+                                    self.selected_points
+                                        .column_parities()
+                                        .iter()
+                                        .map(|(_, odd)| if *odd { "O" } else { "E" })
+                                        .collect::<Vec<_>>()
+                                        .join(" ")
+                                ),
+                            );
+                            if is_hexacodeword && consistent {
+                                ui.label(match self.selected_points.weight() {
+                                    0 => "So it's the zero codeword",
+                                    8 => "So it's an octad",
+                                    12 => "So it's a dodecad",
+                                    16 => "So it's the complement of an octad",
+                                    _ => "So it's the whole set",
+                                });
+                            } else {
+                                ui.label("So it's not a codeword");
                             }
                         }
 
-                        // Complete a sextet from 4 points
-                        ui.heading("Complete Sextet");
-                        if self.selected_points.weight() == 4 {
-                            ui.label("The unique sextet containing these 4 points");
+                        // Pin one or two points to work in their stabilizer
+                        ui.heading("Pinned Points");
+                        if self.pinned_points.weight() == 0 {
+                            ui.label("Pin 1 or 2 points to work in M23 or M22");
                         } else {
-                            ui.label(
-                                "\
-The sextet whose foursomes are the differences between these points and the nearest 6 codewords",
-                            );
+                            ui.label(format!(
+                                "Working in {}, fixing {}",
+                                pinned_group_name(&self.pinned_points),
+                                self.pinned_points.to_point_list()
+                            ));
+                        }
+                        if (1..=2).contains(&self.selected_points.weight())
+                            && self.selected_points != self.pinned_points
+                            && ui.button("Pin selected points").clicked()
+                        {
+                            self.pinned_points = self.selected_points.clone();
+                        }
+                        if self.pinned_points.weight() != 0 && ui.button("Unpin").clicked() {
+                            self.pinned_points = Vector::zero();
+                            self.pinned_error = None;
+                        }
+                        if let Some(error) = &self.pinned_error {
+                            ui.colored_label(ui.visuals().error_fg_color, error);
                         }
-                        let complete_sextet_button = ui.button("Select");
 
-                        let mut sextet = codewords
-                            .iter()
-                            .map(|codeword| &self.selected_points + codeword)
-                            .collect::<Vec<_>>();
-                        sextet.sort_unstable();
-                        sextet.reverse();
-                        let ordered_sextet = sextet;
+                        // Random elements of M24, of the stabilizer of the pinned points, or of the stabilizer of the selection
+                        ui.heading("Random Automorphism");
+                        if keyboard::button(ui, "Random automorphism", keyboard::key(Key::R)).1 {
+                            self.selected_permutation = if self.pinned_points.weight() == 0 {
+                                mog.random_automorphism(&mut rng())
+                            } else {
+                                mog.random_point_stabilizer(&self.pinned_points, &mut rng())
+                                    .unwrap()
+                            };
+                        }
+                        // The stabilizers of the selection would move the pinned points
+                        if self.pinned_points.weight() == 0
+                            && mog.is_octad(&self.selected_points)
+                            && keyboard::button(
+                                ui,
+                                "Random octad stabilizer",
+                                keyboard::key(Key::S),
+                            )
+                            .1
+                        {
+                            self.selected_permutation = mog
+                                .random_octad_stabilizer(&self.selected_points, &mut rng())
+                                .unwrap();
+                        }
+                        if self.pinned_points.weight() == 0
+                            && self.selected_points.weight() == 4
+                            && keyboard::button(
+                                ui,
+                                "Random sextet stabilizer",
+                                keyboard::key(Key::S),
+                            )
+                            .1
+                        {
+                            self.selected_permutation = mog
+                                .random_sextet_stabilizer(&self.selected_points, &mut rng())
+                                .unwrap();
+                        }
+                        if self.pinned_points.weight() == 0
+                            && self.selected_points.weight() == 2
+                            && keyboard::button(ui, "Random duad stabilizer", keyboard::key(Key::S))
+                                .1
+                        {
+                            self.selected_permutation = mog
+                                .random_duad_stabilizer(&self.selected_points, &mut rng())
+                                .unwrap();
+                        }
 
-                        if complete_sextet_button.hovered() {
-                            for (i, vector) in ordered_sextet.iter().enumerate() {
-                                for p in vector.points() {
-                                    coloured_highlight_points.set(p, Some(sextet_idx_to_colour(i)));
+                        // Follow the permutation by a generator of PSL(2, 23), acting on the labels in the View menu
+                        ui.heading("PSL(2, 23)");
+                        ui.horizontal(|ui| {
+                            for (text, generator) in [
+                                ("x → x+1", psl2_23::translation()),
+                                ("x → 2x", psl2_23::doubling()),
+                                ("x → −1/x", psl2_23::negative_reciprocal()),
+                            ] {
+                                if ui.button(text).clicked() {
+                                    self.selected_permutation =
+                                        &self.selected_permutation * &generator;
                                 }
                             }
-                        }
+                        });
 
-                        if complete_sextet_button.clicked() {
-                            return Some(Box::new(super::sextet_labelling::State::from_foursome(
-                                self.clone(),
-                                &(&self.selected_points + &codewords[0]),
-                            )));
+                        // Count the octads through a few points, as blocks of the Steiner system S(5, 8, 24)
+                        if (1..=5).contains(&self.selected_points.weight()) {
+                            let octads = mog.octads_through(&self.selected_points);
+                            let blocks = octads.len();
+                            ui.label(format!(
+                                "{blocks} octad{} contain{} these points",
+                                if blocks == 1 { "" } else { "s" },
+                                if blocks == 1 { "s" } else { "" }
+                            ));
+
+                            // Step through the octads one at a time, outlining the current one
+                            if blocks > 1 {
+                                CollapsingHeader::new("Octads through these points").show(
+                                    ui,
+                                    |ui| {
+                                        self.octad_step %= blocks;
+                                        ui.horizontal(|ui| {
+                                            if keyboard::section_button(
+                                                ui,
+                                                "Octads through these points",
+                                                "◀ Previous",
+                                                keyboard::key(Key::OpenBracket),
+                                            )
+                                            .1
+                                            {
+                                                self.octad_step =
+                                                    (self.octad_step + blocks - 1) % blocks;
+                                            }
+                                            ui.label(format!(
+                                                "{} of {blocks}",
+                                                self.octad_step + 1
+                                            ));
+                                            if keyboard::section_button(
+                                                ui,
+                                                "Octads through these points",
+                                                "Next ▶",
+                                                keyboard::key(Key::CloseBracket),
+                                            )
+                                            .1
+                                            {
+                                                self.octad_step = (self.octad_step + 1) % blocks;
+                                            }
+                                        });
+                                        for p in octads[self.octad_step].points() {
+                                            coloured_highlight_points.set(
+                                                p,
+                                                Some(ctx.style().visuals.selection.stroke.color),
+                                            );
+                                        }
+                                        if ui.button("Select this octad").clicked() {
+                                            self.selected_points = octads[self.octad_step].clone();
+                                        }
+                                        ui.label("Double-click an octad to select it");
+                                        ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+                                            for (i, octad) in octads.iter().enumerate() {
+                                                let label = ui.selectable_label(
+                                                    i == self.octad_step,
+                                                    octad.to_point_list(),
+                                                );
+                                                if label.hovered() {
+                                                    preview_select_points =
+                                                        Labelled::from_fn(|p| {
+                                                            Some(octad.contains_point(p))
+                                                        });
+                                                }
+                                                if label.clicked() {
+                                                    self.octad_step = i;
+                                                }
+                                                if label.double_clicked() {
+                                                    self.selected_points = octad.clone();
+                                                }
+                                            }
+                                        });
+                                    },
+                                );
+                            }
                         }
-                    }
-                }
 
-                // Complete and octad from 5 points
-                if self.selected_points.weight() == 5 {
-                    ui.heading("Complete Octad");
-                    ui.label("The unique octad containing these 5 points");
-                    let button = ui.button("Complete");
+                        // Complete and octad from 5 points
+                        if self.selected_points.weight() == 5 {
+                            ui.heading("Complete Octad");
+                            ui.label("The unique octad containing these 5 points");
+                            ui.label("Double-click the grid to complete it");
+                            let (button, complete) = keyboard::section_button(
+                                ui,
+                                "Complete octad",
+                                "Complete",
+                                keyboard::key(Key::C),
+                            );
 
-                    let octad = mog.complete_octad(&self.selected_points).unwrap();
+                            let octad = mog.complete_octad(&self.selected_points).unwrap();
 
-                    // Preview octad when hovering on button
-                    if button.hovered() {
-                        for p in (&self.selected_points + &octad).points() {
-                            preview_select_points.set(p, Some(true));
+                            // Preview octad when hovering on button
+                            if button.hovered() {
+                                for p in (&self.selected_points + &octad).points() {
+                                    preview_select_points.set(p, Some(true));
+                                }
+                            }
+                            // complete the selection
+                            if complete {
+                                for p in octad.points() {
+                                    self.selected_points.set(p, true);
+                                }
+                            }
                         }
-                    }
-                    // complete the selection
-                    if button.clicked() {
-                        for p in octad.points() {
-                            self.selected_points.set(p, true);
+
+                        // The automorphisms fixing the selection as a set
+                        // Working it out can take a few hundred milliseconds, so it waits to be asked for
+                        ui.heading("Set Stabilizer");
+                        match self.analysis.set_stabilizer.cached(&self.selected_points) {
+                            Some((order, orbits)) => {
+                                match stabilizer_name(order) {
+                                    Some(name) => ui.label(format!("{name}, of order {order}")),
+                                    None => ui.label(format!("Order {order}")),
+                                };
+                                ui.label(format!("Index {} in M24", M24_ORDER / order));
+                                ui.label(format!(
+                                    "Orbits of sizes {}",
+                                    orbits
+                                        .iter()
+                                        .map(|n| n.to_string())
+                                        .collect::<Vec<_>>()
+                                        .join(" + ")
+                                ));
+                            }
+                            None => {
+                                ui.label("Not worked out for this selection yet");
+                                if ui.button("Compute stabilizer").clicked()
+                                    || command_palette::offer(ctx, "Compute stabilizer", None)
+                                {
+                                    perf_hud::time(ctx, "Set stabilizer", || {
+                                        self.analysis.set_stabilizer.get(
+                                            self.selected_points.clone(),
+                                            |selected_points| {
+                                                let stabilizer =
+                                                    mog.set_stabilizer(selected_points);
+                                                (
+                                                    stabilizer.order(),
+                                                    stabilizer
+                                                        .orbits()
+                                                        .iter()
+                                                        .map(|orbit| orbit.len())
+                                                        .collect(),
+                                                )
+                                            },
+                                        )
+                                    });
+                                    ctx.request_repaint();
+                                }
+                            }
+                        }
+
+                        // Find an automorphism taking one set to another, by choosing the first and then selecting the second
+                        if self.selected_points.weight() != 0 || self.map_source.is_some() {
+                            ui.heading("Map Sets");
+                            match self.map_source.clone() {
+                                None => {
+                                    if ui.button("Map from this set").clicked() {
+                                        self.map_source = Some(self.selected_points.clone());
+                                        self.map_error = None;
+                                    }
+                                }
+                                Some(source) => {
+                                    ui.label(format!("From {}", source.to_point_list()));
+                                    for p in source.points() {
+                                        coloured_highlight_points.set(
+                                            p,
+                                            Some(ctx.style().visuals.selection.stroke.color),
+                                        );
+                                    }
+                                    ui.horizontal(|ui| {
+                                        if ui
+                                            .add_enabled(
+                                                self.selected_points.weight() == source.weight(),
+                                                Button::new("Find map"),
+                                            )
+                                            .on_disabled_hover_text(format!(
+                                                "Select the {} points to map them to",
+                                                source.weight()
+                                            ))
+                                            .clicked()
+                                        {
+                                            match m24::set_map(mog, &source, &self.selected_points)
+                                            {
+                                                Ok(permutation) => {
+                                                    self.selected_permutation = permutation;
+                                                    self.map_source = None;
+                                                }
+                                                Err(reason) => self.map_error = Some(reason),
+                                            }
+                                        }
+                                        if ui.button("Cancel").clicked() {
+                                            self.map_source = None;
+                                            self.map_error = None;
+                                        }
+                                    });
+                                    if let Some(reason) = self.map_error {
+                                        ui.colored_label(
+                                            ui.visuals().error_fg_color,
+                                            "No automorphism exists",
+                                        );
+                                        ui.label(reason);
+                                    }
+                                }
+                            }
                         }
                     }
                 }
-
-                None
-            })
-            .inner
-        {
-            return Some(new_state);
+                Pane::Statistics => {
+                    self.selection_statistics
+                        .contents(ui, &self.selected_points);
+                }
+                Pane::Octads => {
+                    // Clicking an octad selects it
+                    if let Some(octad) =
+                        self.octad_list
+                            .show(ui, &self.selected_points, &mut preview_select_points)
+                    {
+                        self.selected_points = octad;
+                    }
+                }
+                Pane::Grid => {}
+            }
+            None
+        }) {
+            return Some(navigate);
         }
 
-        let mut grid_builder = super::grid::GridBuilder::default();
-
-        let row_label_to_cell = |r: usize| -> GridCell { (-1, r as isize) };
-        let col_label_to_cell = |c: usize| -> GridCell { (c as isize, 4) };
-        let point_to_cell = |p: Point| -> GridCell {
-            let i = p.point_to_usize();
-            (i as isize % 6, i as isize / 6)
-        };
-
-        // The rows labelled by F4
-        for r in 0usize..4 {
-            grid_builder.include_cell(row_label_to_cell(r));
+        // Show where a translation would move the selection
+        let translated_selection = self.translated_selection();
+        if let Some(translated) = &translated_selection {
+            for p in (&self.selected_points + translated).points() {
+                preview_select_points.set(p, Some(translated.contains_point(p)));
+            }
         }
 
-        // The columns labelled by the sum of the F4 values in column
-        for c in 0..6 {
-            grid_builder.include_cell(col_label_to_cell(c));
+        // How far the points are along their way after "Apply", until they arrive or the selection is changed
+        let applying = self
+            .applying
+            .take()
+            .and_then(|(before, permutation, start)| {
+                let t = (ctx.input(|i| i.time) - start) as f32 / APPLY_DURATION;
+                (t < 1.0 && before.permute(&permutation) == self.selected_points).then_some((
+                    before,
+                    permutation,
+                    start,
+                    t,
+                ))
+            });
+        if let Some((before, permutation, start, _)) = &applying {
+            self.applying = Some((before.clone(), permutation.clone(), *start));
+            // Only the points which stay put are drawn in place
+            preview_select_points = Labelled::from_fn(|p| {
+                Some(before.contains_point(p) && *permutation.apply(&p) == p)
+            });
+            ctx.request_repaint();
         }
 
-        // The 6x4 MOG grid
-        for p in Point::points() {
-            grid_builder.include_cell(point_to_cell(p));
+        // The orbits of the permutation, leaving the fixed points as they are, or else the point sets
+        let mut tints = Labelled::<Point, Option<Color32>>::new_constant(None);
+        if self.show_orbits {
+            let orbits = self.selected_permutation.sorted_cycles();
+            for (i, orbit) in orbits.iter().enumerate() {
+                for p in orbit {
+                    tints.set(
+                        Point::usize_to_point(*p).unwrap(),
+                        Some(palette_colour(ctx, i, orbits.len())),
+                    );
+                }
+            }
+        } else if self.show_point_sets {
+            for (i, (_, points)) in self.point_sets.iter().enumerate() {
+                for p in points.points() {
+                    tints.set(p, Some(palette_colour(ctx, i, self.point_sets.len())));
+                }
+            }
         }
 
-        CentralPanel::default().show(ctx, |ui| {
-            let (response, painter, grid) = grid_builder.show(ui);
-
-            // The rows labelled by F4
-            for r in 0usize..4 {
-                let rect = grid.cell_to_rect(row_label_to_cell(r));
-                draw_f4(ui, &painter, rect, ui.visuals().text_color(), row_to_f4(r))
+        grid_panel(ctx, |ui| {
+            // The split view puts the grid in the left half, and the right half is drawn once the permutation is known
+            let (before_rect, after_rect) = split_view::halves(ui);
+            let mut before_ui = self
+                .split_view
+                .then(|| ui.new_child(UiBuilder::new().max_rect(before_rect)));
+            let grid_ui = match &mut before_ui {
+                Some(before_ui) => before_ui,
+                None => &mut *ui,
+            };
+            let MogSelectionResponse {
+                response,
+                painter,
+                grid,
+                layout,
+                hovered: hovered_point,
+                clicked,
+                double_clicked,
+                long_pressed,
+                pinching,
+            } = MogSelection::new(&self.selected_points)
+                .preview(&preview_select_points)
+                .highlights(&coloured_highlight_points)
+                .tints(&tints)
+                // Don't highlight when dragging
+                .hover_highlight(!self.is_dragging)
+                .verification(self.hexacode_check && custom_code.is_none())
+                .pinned(&self.pinned_points)
+                .show(grid_ui);
+            if self.split_view {
+                ui.advance_cursor_after_rect(
+                    response
+                        .rect
+                        .union(after_rect.with_max_y(response.rect.bottom())),
+                );
             }
 
-            // The columns labelled by the sum of the F4 values in column
-            for c in 0..6 {
-                let mut t = F4Point::Zero;
-                for r in 0..4 {
-                    let i = c + 6 * r;
-                    let p = Point::usize_to_point(i).unwrap();
-                    if preview_select_points
-                        .get(p)
-                        .unwrap_or(*self.selected_points.get(p))
-                    {
-                        t = t + row_to_f4(r);
+            // Slide the moving points along the lines of the permutation
+            if let Some((before, permutation, _, t)) = &applying {
+                for p in before.points() {
+                    let image = *permutation.apply(&p);
+                    if image != p {
+                        let path = line_path(
+                            &grid,
+                            layout.point_to_cell(p),
+                            layout.point_to_cell(image),
+                            Geometry::get(ctx).segments,
+                        );
+                        let rect = grid.cell_to_rect(layout.point_to_cell(p));
+                        painter.rect_filled(
+                            rect.translate(
+                                point_along_path(&path, ease_in_out(*t)) - rect.center(),
+                            ),
+                            grid.rounding(),
+                            ui.visuals().selection.bg_fill,
+                        );
                     }
                 }
-                let rect = grid.cell_to_rect(col_label_to_cell(c));
-                draw_f4(ui, &painter, rect, ui.visuals().text_color(), t);
             }
 
-            // The 6x4 MOG grid
-            for p in Point::points() {
-                let rect = grid.cell_to_rect(point_to_cell(p));
+            // Hovering a line or circle of the permutation picks out its cycle
+            // The shapes are the ones drawn last frame, which are where the user sees them
+            let hovered_cycle = response
+                .hover_pos()
+                .filter(|_| !self.split_view)
+                .and_then(|pos| self.permutation_shapes.hit_test(pos));
 
-                // Draw square
-                if preview_select_points
-                    .get(p)
-                    .unwrap_or(*self.selected_points.get(p))
+            // Toggle if clicked, or select a whole cycle if it is clicked with the command key held
+            if let Some(p) = clicked {
+                if ctx.input(|i| i.modifiers.command)
+                    && let Some(i) = hovered_cycle
                 {
-                    // Selected
-                    painter.rect_filled(
-                        rect,
-                        grid.cell_scalar_to_pos_scalar(0.05),
-                        ui.visuals().selection.bg_fill,
+                    let (cycle, _) = &self.permutation_shapes.shapes()[i];
+                    self.selected_points = Vector::from_points(
+                        cycle.iter().filter_map(|cell| layout.cell_to_point(*cell)),
                     );
                 } else {
-                    // Not selected
-                    painter.rect_filled(
-                        rect,
-                        grid.cell_scalar_to_pos_scalar(0.05),
-                        ui.visuals().widgets.inactive.bg_fill,
-                    );
+                    self.selected_points.toggle(p);
                 }
+            }
+            // On a touch screen a long press toggles, since a tap can easily become a drag
+            if let Some(p) = long_pressed {
+                self.selected_points.toggle(p);
+            }
 
-                // Highlight
-                if preview_select_points.get(p).is_some() || {
-                    if self.is_dragging {
-                        // Don't highlight when dragging
-                        false
-                    } else if let Some(pos) = response.hover_pos() {
-                        // Highlight when hovering on this pos
-                        rect.contains(pos)
-                    } else {
-                        // Don't highlight otherwise
-                        false
-                    }
-                } {
-                    painter.rect_stroke(
-                        rect,
-                        grid.cell_scalar_to_pos_scalar(0.05),
-                        ui.visuals().widgets.hovered.fg_stroke,
-                        eframe::egui::StrokeKind::Middle,
-                    );
+            // Move the keyboard cursor with the arrow keys or to a column with the number keys, and toggle with space
+            let start = Point::points().next().unwrap();
+            for (key, direction) in [
+                (Key::ArrowLeft, (-1, 0)),
+                (Key::ArrowRight, (1, 0)),
+                (Key::ArrowUp, (0, -1)),
+                (Key::ArrowDown, (0, 1)),
+            ] {
+                if keyboard::pressed(ctx, keyboard::key(key)) {
+                    self.cursor = Some(match self.cursor {
+                        Some(p) => keyboard::step(layout, p, direction),
+                        None => start,
+                    });
                 }
-
-                // Coloured highlihgts
-                if let Some(colour) = coloured_highlight_points.get(p) {
-                    painter.rect_stroke(
-                        rect,
-                        grid.cell_scalar_to_pos_scalar(0.05),
-                        eframe::egui::Stroke::new(
-                            3.0,
-                            colour.lerp_to_gamma(ui.visuals().faint_bg_color, 0.4),
-                        ),
-                        eframe::egui::StrokeKind::Inside,
-                    );
+            }
+            for (c, key) in keyboard::NUMBER_KEYS.into_iter().enumerate() {
+                if keyboard::pressed(ctx, keyboard::key(key)) {
+                    self.cursor = Some(keyboard::in_column(self.cursor.unwrap_or(start), c));
                 }
+            }
+            if let Some(p) = self.cursor
+                && keyboard::pressed(ctx, keyboard::key(Key::Space))
+            {
+                self.selected_points.toggle(p);
+            }
+            if keyboard::pressed(ctx, keyboard::key(Key::Escape)) {
+                self.cursor = None;
+            }
+            if let Some(p) = self.cursor {
+                painter.rect_stroke(
+                    grid.cell_to_rect(layout.point_to_cell(p)),
+                    grid.rounding(),
+                    ui.visuals().widgets.active.fg_stroke,
+                    StrokeKind::Outside,
+                );
+            }
 
-                // Toggle if clicked
-                if response.clicked() && rect.contains(response.interact_pointer_pos().unwrap()) {
-                    let b = self.selected_points.get_mut(p);
-                    *b = !*b;
+            // Complete the octad on double-click, the same as the button in the side panel
+            if double_clicked && custom_code.is_none() && self.selected_points.weight() == 5 {
+                let octad = mog.complete_octad(&self.selected_points).unwrap();
+                for p in octad.points() {
+                    self.selected_points.set(p, true);
                 }
             }
 
-            let mut hovered_point = None;
-
             for p in Point::points() {
-                let rect = grid.cell_to_rect(point_to_cell(p));
-
-                // Check if the mouse is over this point
-                if let Some(pos) = response.hover_pos()
-                    && rect.contains(pos)
-                {
-                    hovered_point = Some(p);
-                }
+                let rect = grid.cell_to_rect(layout.point_to_cell(p));
 
                 // Start dragging
                 if response.is_pointer_button_down_on()
@@ -346,35 +1885,100 @@ The sextet whose foursomes are the differences between these points and the near
                     && rect.contains(pos)
                 {
                     self.drag_end = Some(p);
+                    if self.drawing_cycle && !self.cycle_path.contains(&p) {
+                        self.cycle_path.push(p);
+                    }
                 }
             }
 
             if response.drag_started() {
                 self.is_dragging = true;
+                let shift = ctx.input(|i| i.modifiers.shift);
+                let from_selected = self
+                    .drag_start
+                    .is_some_and(|p| self.selected_points.contains_point(p));
+                self.translating = shift && from_selected;
+                self.drawing_cycle = shift && !from_selected;
+                self.cycle_path = self.drag_start.into_iter().collect();
+            }
+
+            if let Some(translated) = &translated_selection {
+                if code.is_codeword(translated) {
+                    ui.colored_label(Palette::get(ctx).good(), "The translated set is a codeword");
+                } else {
+                    ui.colored_label(
+                        Palette::get(ctx).bad(),
+                        "The translated set is not a codeword",
+                    );
+                }
+                // Every translation but the identity moves every point
+                if self.pinned_points.weight() != 0 && translated != &self.selected_points {
+                    ui.colored_label(
+                        ui.visuals().error_fg_color,
+                        format!(
+                            "Pinned points can't be moved while working in {}",
+                            pinned_group_name(&self.pinned_points)
+                        ),
+                    );
+                } else if response.drag_stopped() {
+                    self.selected_points = translated.clone();
+                }
             }
 
             let mut drag_permutation = self.selected_permutation.clone();
             if self.is_dragging
+                && !self.translating
                 && let Some(start_p) = self.drag_start
                 && let Some(end_p) = self.drag_end
                 && (response.dragged() || response.drag_stopped())
             {
-                drag_permutation = &Permutation::new_swap(&start_p, &end_p) * &drag_permutation;
+                // A plain drag swaps its ends, and a shift-drag cycles the points in the order it passed through them
+                let moved = if self.drawing_cycle {
+                    self.cycle_path.clone()
+                } else if start_p != end_p {
+                    vec![start_p, end_p]
+                } else {
+                    vec![]
+                };
+                // Moving a pinned point is not allowed
+                if moved.iter().any(|p| self.pinned_points.contains_point(*p)) {
+                    ui.colored_label(
+                        ui.visuals().error_fg_color,
+                        format!(
+                            "Pinned points can't be moved while working in {}",
+                            pinned_group_name(&self.pinned_points)
+                        ),
+                    );
+                } else if moved.len() >= 2 {
+                    drag_permutation =
+                        &Permutation::new_cycle(moved.iter().collect()) * &drag_permutation;
+                }
             }
 
-            let colour = if mog.is_automorphism(&drag_permutation) {
-                Color32::GREEN
+            let colour = if self
+                .analysis
+                .drag_is_automorphism
+                .get(drag_permutation.clone(), |permutation| {
+                    code.is_automorphism(permutation)
+                })
+                && self
+                    .pinned_points
+                    .points()
+                    .all(|p| *drag_permutation.apply(&p) == p)
+            {
+                Palette::get(ctx).good()
             } else {
-                Color32::RED
+                Palette::get(ctx).bad()
             };
 
             if self.is_dragging
+                && !self.translating
                 && let Some(start_p) = self.drag_start
                 && start_p == self.drag_end.unwrap_or(start_p)
                 && response.is_pointer_button_down_on()
             {
                 painter.circle_filled(
-                    grid.cell_to_pos(point_to_cell(start_p)),
+                    grid.cell_to_pos(layout.point_to_cell(start_p)),
                     grid.cell_scalar_to_pos_scalar(self.permutation_shapes.small_radius()),
                     colour,
                 );
@@ -384,31 +1988,165 @@ The sextet whose foursomes are the differences between these points and the near
             if response.drag_stopped() {
                 self.selected_permutation = drag_permutation.clone();
             }
-            if !response.is_pointer_button_down_on() {
+            // A second finger turns a drag into a pinch, which zooms instead of moving points
+            if !response.is_pointer_button_down_on() || pinching {
                 self.drag_start = None;
                 self.is_dragging = false;
                 self.drag_end = None;
+                self.translating = false;
+                self.drawing_cycle = false;
+                self.cycle_path.clear();
+            }
+
+            if self.split_view {
+                split_view::show_after(
+                    ui,
+                    after_rect,
+                    &grid,
+                    layout,
+                    &self.selected_points,
+                    &drag_permutation,
+                    colour,
+                );
             }
 
             let cell_permutation = drag_permutation
                 .clone()
-                .map_injective_unchecked(point_to_cell);
+                .map_injective_unchecked(|p| layout.point_to_cell(p));
+
+            let label_offset = self.permutation_shapes.label_offset(&grid);
+            let label_font = FontId::proportional(grid.cell_scalar_to_pos_scalar(0.25));
 
+            // The permutation is only a preview until the drag is released
+            self.permutation_shapes
+                .set_preview(drag_permutation != self.selected_permutation);
+            self.permutation_shapes.set_geometry(Geometry::get(ctx));
             self.permutation_shapes
                 .set_permutation(Some(cell_permutation), grid);
+            // Right-clicking a line removes the point it goes from out of its cycle
+            if response.secondary_clicked()
+                && !self.is_dragging
+                && !self.split_view
+                && let Some(pos) = response.interact_pointer_pos()
+                && let Some((from, to)) = self.permutation_shapes.line_at(pos)
+            {
+                self.selected_permutation = &self.selected_permutation
+                    * &Permutation::new_swap(
+                        &layout.cell_to_point(from).unwrap(),
+                        &layout.cell_to_point(to).unwrap(),
+                    );
+            }
+            perf_hud::report_cache(
+                ctx,
+                "Permutation shapes",
+                self.permutation_shapes.cache_stats(),
+            );
+            perf_hud::report_cache(ctx, "Selection analysis", self.analysis.stats());
 
-            for (cycle, shape) in self.permutation_shapes.shapes() {
-                let colour = if let Some(p) = hovered_point
-                    && cycle.contains(&point_to_cell(p))
-                {
-                    colour
-                } else {
-                    colour * Color32::from_white_alpha(128)
-                };
+            // The cycles are drawn in place unless the split view shows where the points go instead
+            if self.split_view {
+                return;
+            }
+
+            for (i, ((cycle, shape), label_path)) in self
+                .permutation_shapes
+                .shapes()
+                .iter()
+                .zip(self.permutation_shapes.label_paths())
+                .enumerate()
+            {
+                let hovered = fade(
+                    ctx,
+                    Id::new(("point_toggle_cycle_hover", cycle)),
+                    hovered_cycle == Some(i)
+                        || hovered_point.is_some_and(|p| cycle.contains(&layout.point_to_cell(p))),
+                    HOVER_FADE,
+                );
+                let colour = lerp_colour(colour * Color32::from_white_alpha(128), colour, hovered);
 
                 painter.add(shape.to_egui_mesh(colour));
+
+                if self.label_cycles {
+                    text_along_path(
+                        &painter,
+                        label_path,
+                        label_offset,
+                        &cycle.len().to_string(),
+                        label_font.clone(),
+                        colour,
+                    );
+                }
             }
         });
+
+        self.enforce_pinned_points();
+        self.history.record((
+            self.selected_points.clone(),
+            self.selected_permutation.clone(),
+        ));
+        self.action_log.record(|| {
+            (
+                self.selected_points.clone(),
+                self.selected_permutation.clone(),
+            )
+        });
+
         None
     }
+
+    fn title(&self) -> String {
+        match self.target {
+            Some(target) => format!("Pick {}", target.name()),
+            None => "Point Toggle".to_string(),
+        }
+    }
+
+    fn session(&self) -> Option<Session> {
+        Some(Session::PointToggle(self.point_toggle_session()))
+    }
+
+    // Screens opened from this one hand back what they found, e.g. a coset leader or a trio stabilizer
+    fn picked(&mut self, vector: Vector) {
+        self.selected_points = vector;
+    }
+
+    // Checked against the pinned points on the next frame
+    fn picked_permutation(&mut self, permutation: Permutation<Point>) {
+        self.selected_permutation = permutation;
+    }
+
+    fn apply_selection(&mut self, vector: &Vector) -> bool {
+        self.selected_points = vector.clone();
+        true
+    }
+
+    fn apply_permutation(&mut self, permutation: &Permutation<Point>) -> bool {
+        self.selected_permutation = permutation.clone();
+        true
+    }
+
+    fn can_undo(&self) -> bool {
+        self.history.can_undo()
+    }
+
+    fn can_redo(&self) -> bool {
+        self.history.can_redo()
+    }
+
+    fn undo(&mut self) {
+        if let Some((selected_points, selected_permutation)) = self.history.undo() {
+            self.selected_points = selected_points;
+            // Undo goes back to exactly what was there, even from before the points were pinned
+            self.accepted_permutation = selected_permutation.clone();
+            self.selected_permutation = selected_permutation;
+        }
+    }
+
+    fn redo(&mut self) {
+        if let Some((selected_points, selected_permutation)) = self.history.redo() {
+            self.selected_points = selected_points;
+            self.accepted_permutation = selected_permutation.clone();
+            self.selected_permutation = selected_permutation;
+        }
+    }
 }
@@ -0,0 +1,97 @@
+use crate::app::logic::miracle_octad_generator::{Point, Vector};
+use crate::app::logic::traits::Enumerated;
+use crate::app::ui::mog::{mog, sextet_idx_to_colour};
+use eframe::egui::{
+    Context, Grid, Pos2, Rect, ScrollArea, Sense, Stroke, StrokeKind, Ui, Vec2, Window,
+};
+
+const CELL: f32 = 9.0;
+// The extra space between bricks
+const BRICK_GAP: f32 = 4.0;
+
+fn cell_rect(origin: Pos2, p: Point) -> Rect {
+    let col = p.col.point_to_usize();
+    let row = p.row.point_to_usize();
+    Rect::from_min_size(
+        origin
+            + Vec2::new(
+                col as f32 * CELL + (col / 2) as f32 * BRICK_GAP,
+                row as f32 * CELL,
+            ),
+        Vec2::splat(CELL),
+    )
+    .shrink(0.5)
+}
+
+// One sextet drawn as a small MOG with each foursome in its own colour
+// Returns the foursome clicked on
+fn picture(ui: &mut Ui, sextet: &[Vector; 6]) -> Option<Vector> {
+    let size = Vec2::new(6.0 * CELL + 2.0 * BRICK_GAP, 4.0 * CELL);
+    let (response, painter) = ui.allocate_painter(size, Sense::click());
+    let origin = response.rect.min;
+    let pointed = response.hover_pos().and_then(|pos| {
+        sextet.iter().find(|foursome| {
+            foursome
+                .points()
+                .any(|p| cell_rect(origin, p).contains(pos))
+        })
+    });
+    for (i, foursome) in sextet.iter().enumerate() {
+        for p in foursome.points() {
+            painter.rect_filled(cell_rect(origin, p), 1.0, sextet_idx_to_colour(ui.ctx(), i));
+            if pointed == Some(foursome) {
+                painter.rect_stroke(
+                    cell_rect(origin, p),
+                    1.0,
+                    Stroke::new(1.5, ui.visuals().strong_text_color()),
+                    StrokeKind::Inside,
+                );
+            }
+        }
+    }
+    if response.clicked() {
+        pointed.cloned()
+    } else {
+        None
+    }
+}
+
+// The 35 sextets pictured in the MOG, got by halving the left brick into two foursomes in every way
+// Clicking a foursome selects it
+#[derive(Clone, Default)]
+pub struct ReferenceCardWindow {
+    open: bool,
+}
+
+impl ReferenceCardWindow {
+    pub fn open(&mut self) {
+        self.open = true;
+    }
+
+    pub fn show(&mut self, ctx: &Context) -> Option<Vector> {
+        if !self.open {
+            return None;
+        }
+        let mut clicked = None;
+        Window::new("MOG Reference Card")
+            .open(&mut self.open)
+            .show(ctx, |ui| {
+                ui.label("The 35 sextets with two foursomes in the left brick");
+                ScrollArea::vertical().show(ui, |ui| {
+                    Grid::new("reference_card_grid")
+                        .spacing(Vec2::splat(8.0))
+                        .show(ui, |ui| {
+                            for (i, sextet) in mog().brick_sextets().iter().enumerate() {
+                                if let Some(foursome) = picture(ui, sextet) {
+                                    clicked = Some(foursome);
+                                }
+                                if i % 5 == 4 {
+                                    ui.end_row();
+                                }
+                            }
+                        });
+                });
+            });
+        clicked
+    }
+}
@@ -0,0 +1,96 @@
+use crate::app::logic::console::{self, FUNCTIONS};
+use crate::app::logic::miracle_octad_generator::{Point, Vector};
+use crate::app::logic::permutation::Permutation;
+use crate::app::ui::mog::mog;
+use eframe::egui::{Context, Key, KeyboardShortcut, Modifiers, ScrollArea, TextEdit, Window};
+
+const RUN: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::Enter);
+
+const EXAMPLE: &str = "select([0, 1, 2, 3, 4]);
+complete_octad();
+set_permutation(\"(0 1)(2 3)\");
+print(is_automorphism());";
+
+// Run a rhai script against the selection and permutation, for automating constructions
+#[derive(Clone)]
+pub struct ScriptConsoleWindow {
+    open: bool,
+    script: String,
+    // What the last run printed, then its value or the error which stopped it
+    output: String,
+}
+
+impl Default for ScriptConsoleWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            script: EXAMPLE.to_string(),
+            output: String::new(),
+        }
+    }
+}
+
+impl ScriptConsoleWindow {
+    pub fn open(&mut self) {
+        self.open = true;
+    }
+
+    // Changes made by the script before any error are kept
+    fn run(&mut self, state: (Vector, Permutation<Point>)) -> (Vector, Permutation<Point>) {
+        let (state, output) = console::run(mog(), &self.script, state);
+        self.output = output;
+        state
+    }
+
+    // Returns the selection and permutation left by a script which was run
+    pub fn show(
+        &mut self,
+        ctx: &Context,
+        selected_points: &Vector,
+        selected_permutation: &Permutation<Point>,
+    ) -> Option<(Vector, Permutation<Point>)> {
+        if !self.open {
+            return None;
+        }
+        let mut ran = None;
+        let mut open = self.open;
+        Window::new("Script Console")
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.collapsing("Functions", |ui| {
+                    for function in FUNCTIONS {
+                        ui.monospace(function);
+                    }
+                });
+                ScrollArea::vertical()
+                    .id_salt("script_console_script")
+                    .max_height(250.0)
+                    .show(ui, |ui| {
+                        ui.add(
+                            TextEdit::multiline(&mut self.script)
+                                .code_editor()
+                                .desired_rows(8),
+                        );
+                    });
+                let run = ui
+                    .button("Run")
+                    .on_hover_text(ctx.format_shortcut(&RUN))
+                    .clicked()
+                    || ui.input_mut(|i| i.consume_shortcut(&RUN));
+                if run {
+                    ran = Some(self.run((selected_points.clone(), selected_permutation.clone())));
+                }
+                if !self.output.is_empty() {
+                    ui.separator();
+                    ScrollArea::vertical()
+                        .id_salt("script_console_output")
+                        .max_height(150.0)
+                        .show(ui, |ui| {
+                            ui.monospace(&self.output);
+                        });
+                }
+            });
+        self.open = open;
+        ran
+    }
+}
@@ -0,0 +1,127 @@
+use crate::app::logic::miracle_octad_generator::Vector;
+use crate::app::ui::memo::Memo;
+use crate::app::ui::mog::{f4_text, mog};
+use eframe::egui::{Context, Grid, RichText, Ui, Window};
+
+// Numbers describing a selection of points in terms of the Golay code
+#[derive(Debug, Clone)]
+struct Statistics {
+    weight: usize,
+    syndrome: usize,
+    distance: usize,
+    octads_containing: usize,
+    octads_contained: usize,
+    // The number of points in each of the three bricks
+    brick_intersections: [usize; 3],
+    column_sums: String,
+    // The number of octads meeting the selection in 0, 1, ..., 8 points
+    octad_intersections: [usize; 9],
+}
+
+impl Statistics {
+    fn new(selected: &Vector) -> Self {
+        let mog = mog();
+        Self {
+            weight: selected.weight(),
+            syndrome: mog.syndrome(selected),
+            distance: mog.coset_leaders(selected)[0].weight(),
+            octads_containing: mog.octads_through(selected).len(),
+            octads_contained: mog
+                .octads()
+                .iter()
+                .filter(|octad| &(*octad & selected) == *octad)
+                .count(),
+            brick_intersections: mog.bricks().map(|brick| (&brick & selected).weight()),
+            column_sums: selected
+                .score()
+                .iter()
+                .map(|(_, x)| f4_text(*x))
+                .collect::<Vec<_>>()
+                .join(" "),
+            octad_intersections: mog.octad_intersection_counts(selected),
+        }
+    }
+}
+
+// A window of statistics for the selection, kept up to date as it changes
+#[derive(Clone, Default)]
+pub struct SelectionStatisticsWindow {
+    open: bool,
+    statistics: Memo<Vector, Statistics>,
+}
+
+impl SelectionStatisticsWindow {
+    pub fn open(&mut self) {
+        self.open = true;
+    }
+
+    pub fn show(&mut self, ctx: &Context, selected: &Vector) {
+        if !self.open {
+            return;
+        }
+        let mut open = self.open;
+        Window::new("Selection Statistics")
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| self.contents(ui, selected));
+        self.open = open;
+    }
+
+    // The statistics on their own, for the window or the statistics pane
+    pub fn contents(&mut self, ui: &mut Ui, selected: &Vector) {
+        let statistics = self.statistics.get(selected.clone(), Statistics::new);
+        Grid::new("selection_statistics_grid")
+            .num_columns(2)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Weight");
+                ui.label(statistics.weight.to_string());
+                ui.end_row();
+
+                ui.label("Syndrome");
+                ui.label(RichText::new(format!("{:012b}", statistics.syndrome)).monospace());
+                ui.end_row();
+
+                ui.label("Distance to the code");
+                ui.label(statistics.distance.to_string());
+                ui.end_row();
+
+                ui.label("Octads containing it");
+                ui.label(statistics.octads_containing.to_string());
+                ui.end_row();
+
+                ui.label("Octads it contains");
+                ui.label(statistics.octads_contained.to_string());
+                ui.end_row();
+
+                ui.label("Points in each brick");
+                ui.label(
+                    statistics
+                        .brick_intersections
+                        .map(|n| n.to_string())
+                        .join(" + "),
+                );
+                ui.end_row();
+
+                ui.label("Column sums");
+                ui.label(&statistics.column_sums);
+                ui.end_row();
+            });
+
+        ui.heading("Octads Meeting the Selection");
+        Grid::new("selection_statistics_octads")
+            .striped(true)
+            .show(ui, |ui| {
+                ui.strong("Points");
+                for i in 0..=8 {
+                    ui.strong(i.to_string());
+                }
+                ui.end_row();
+                ui.strong("Octads");
+                for count in statistics.octad_intersections {
+                    ui.label(count.to_string());
+                }
+                ui.end_row();
+            });
+    }
+}
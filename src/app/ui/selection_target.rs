@@ -0,0 +1,29 @@
+use crate::app::logic::linear_code::Code;
+use crate::app::logic::miracle_octad_generator::Vector;
+
+// Something a screen can ask the point toggle screen to pick, so it does not need its own way of selecting points
+// The picked points are handed back with `Navigate::Pick`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectionTarget {
+    Foursome,
+    Octad,
+    Codeword,
+}
+
+impl SelectionTarget {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SelectionTarget::Foursome => "a foursome",
+            SelectionTarget::Octad => "an octad",
+            SelectionTarget::Codeword => "a codeword",
+        }
+    }
+
+    pub fn accepts(&self, vector: &Vector) -> bool {
+        match self {
+            SelectionTarget::Foursome => vector.weight() == 4,
+            SelectionTarget::Octad => super::mog::mog().is_octad(vector),
+            SelectionTarget::Codeword => super::mog::mog().is_codeword(vector),
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use crate::app::AppState;
+use crate::app::logic::finite_field_4::Point as F4Point;
+use crate::app::logic::linear_code::Code;
+use crate::app::logic::miracle_octad_generator::{BinaryCode, Point, Vector};
+use crate::app::logic::permutation::Permutation;
+use crate::app::logic::traits::Labelled;
+use std::sync::Arc;
+
+// What is saved of the screen that was open when the app closed, so it can be reopened as it was
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub enum Session {
+    PointToggle(PointToggleSession),
+    SextetLabelling(SextetLabellingSession),
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct PointToggleSession {
+    pub selected_points: Vector,
+    pub selected_permutation: Permutation<Point>,
+    // The generator matrix of a user supplied code
+    pub code: Option<Vec<Vector>>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct SextetLabellingSession {
+    // The screen to go back to
+    pub prev_state: PointToggleSession,
+    // A foursome of the sextet
+    pub foursome: Vector,
+    // The order of the foursomes as a permutation of 0..6
+    pub ordering: Vec<usize>,
+    pub labelling: Labelled<Point, Option<F4Point>>,
+}
+
+impl PointToggleSession {
+    pub fn restore(self) -> super::point_toggle::State {
+        let code = self
+            .code
+            .and_then(|basis| BinaryCode::from_generator_matrix(basis).ok())
+            .map(Arc::new);
+        super::point_toggle::State::new(self.selected_points, self.selected_permutation)
+            .with_code(code)
+    }
+
+    pub fn code_basis(code: &Option<Arc<BinaryCode>>) -> Option<Vec<Vector>> {
+        code.as_ref().map(|code| code.basis().to_vec())
+    }
+}
+
+impl Session {
+    // None if the saved session no longer makes sense
+    pub fn restore(self) -> Option<Box<dyn AppState>> {
+        match self {
+            Session::PointToggle(session) => Some(Box::new(session.restore())),
+            Session::SextetLabelling(session) => super::sextet_labelling::State::from_session(
+                session.prev_state,
+                &session.foursome,
+                &session.ordering,
+                session.labelling,
+            )
+            .map(|state| Box::new(state) as Box<dyn AppState>),
+        }
+    }
+}
+
+// The contents of a `.mog` session file, versioned so that old files can be recognised if the format changes
+#[derive(serde::Deserialize, serde::Serialize)]
+struct SessionFile {
+    version: u32,
+    session: Session,
+}
+
+const SESSION_FILE_VERSION: u32 = 1;
+
+impl Session {
+    pub fn to_file_string(&self) -> String {
+        ron::ser::to_string_pretty(
+            &SessionFile {
+                version: SESSION_FILE_VERSION,
+                session: self.clone(),
+            },
+            ron::ser::PrettyConfig::default().compact_arrays(true),
+        )
+        .unwrap()
+    }
+
+    pub fn from_file_string(text: &str) -> Result<Self, String> {
+        let file: SessionFile =
+            ron::from_str(text).map_err(|err| format!("Not a session file: {err}"))?;
+        if file.version != SESSION_FILE_VERSION {
+            return Err(format!(
+                "Session file version {} is not supported",
+                file.version
+            ));
+        }
+        Ok(file.session)
+    }
+
+    pub fn restore_file_string(text: &str) -> Result<Box<dyn AppState>, String> {
+        Self::from_file_string(text)?
+            .restore()
+            .ok_or("The saved screen is no longer valid".to_string())
+    }
+}
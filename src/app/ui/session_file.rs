@@ -0,0 +1,34 @@
+use crate::app::AppState;
+use crate::app::ui::session::Session;
+
+// Saving and opening `.mog` session files, so work can be moved between machines and shared
+
+fn dialog() -> rfd::FileDialog {
+    rfd::FileDialog::new().add_filter("MOG session", &["mog"])
+}
+
+// Ask where to save the open screen, giving the path it was saved to, or None if the dialog was cancelled
+pub fn save(state: &dyn AppState) -> Option<Result<String, String>> {
+    let Some(session) = state.session() else {
+        return Some(Err("This screen can't be saved".to_string()));
+    };
+    let path = dialog().set_file_name("session.mog").save_file()?;
+    Some(
+        std::fs::write(&path, session.to_file_string())
+            .map(|()| path.display().to_string())
+            .map_err(|err| format!("Could not save {}: {err}", path.display())),
+    )
+}
+
+// Ask for a session file and reopen it, or None if the dialog was cancelled
+pub fn open() -> Option<Result<Box<dyn AppState>, String>> {
+    let path = dialog().pick_file()?;
+    let name = path.display().to_string();
+    Some(
+        std::fs::read_to_string(&path)
+            .map_err(|err| format!("Could not read {name}: {err}"))
+            .and_then(|text| {
+                Session::restore_file_string(&text).map_err(|err| format!("{name}: {err}"))
+            }),
+    )
+}
@@ -0,0 +1,40 @@
+use crate::app::ui::geometry::Geometry;
+use crate::app::ui::palette::{Palette, themed};
+use crate::app::ui::panels::PanelLayout;
+use eframe::egui::{Context, Window};
+
+// Preferences for how everything is drawn, saved with the rest of the app
+#[derive(Debug, Default, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Settings {
+    pub palette: Palette,
+    pub geometry: Geometry,
+    pub panels: PanelLayout,
+}
+
+impl Settings {
+    // Make the settings available to every screen this frame
+    pub fn set(&self, ctx: &Context) {
+        self.palette.set(ctx);
+        self.geometry.set(ctx);
+        self.panels.set(ctx);
+    }
+
+    pub fn show(&mut self, ctx: &Context, open: &mut bool) {
+        Window::new("Settings").open(open).show(ctx, |ui| {
+            ui.heading("Colours");
+            self.palette.menu(ui);
+            ui.horizontal(|ui| {
+                for i in 0..6 {
+                    ui.colored_label(themed(ctx, self.palette.sextet(i)), "⏹");
+                }
+            });
+            ui.separator();
+            ui.heading("Geometry");
+            self.geometry.menu(ui);
+            ui.separator();
+            ui.heading("Panels");
+            self.panels.menu(ui);
+        });
+    }
+}
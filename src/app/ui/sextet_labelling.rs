@@ -1,16 +1,30 @@
 use crate::app::logic::permutation::Permutation;
+use crate::app::logic::presets::{PRESETS, Preset};
+use crate::app::logic::script::LabelAction;
 use crate::app::logic::traits::{Enumerated, Labelled};
 use crate::app::logic::{hexacode, miracle_octad_generator::*};
-use crate::app::ui::grid::GridCell;
+use crate::app::ui::action_log::ActionLogWindow;
+use crate::app::ui::animation::{HOVER_FADE, fade, lerp_colour};
+use crate::app::ui::coordinates::CoordinateLabels;
+use crate::app::ui::geometry::Geometry;
+use crate::app::ui::history::History;
+use crate::app::ui::layout::MogLayout;
+use crate::app::ui::memo::Memo;
 use crate::app::ui::mog::mog;
 use crate::app::ui::mog_permutation_shapes::MogPermutationShapeCache;
+use crate::app::ui::palette::Palette;
+use crate::app::ui::panels::side_panel;
+use crate::app::ui::perf_hud;
+use crate::app::ui::selection_target::SelectionTarget;
+use crate::app::ui::session::{PointToggleSession, Session, SextetLabellingSession};
 use crate::app::{
-    AppState,
+    AppState, Navigate,
     logic::finite_field_4::Point as F4Point,
-    ui::mog::{draw_f4, f4_selection, sextet_idx_to_colour},
+    ui::mog::{draw_f4, f4_selection, f4_text, sextet_idx_to_colour},
 };
-use eframe::egui::{Button, CentralPanel, Color32, SidePanel};
-use std::collections::HashSet;
+use eframe::egui::{Align2, Button, CentralPanel, Color32, DragValue, Id};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
 #[derive(Debug)]
 enum PartialLabellingState {
@@ -30,7 +44,166 @@ enum PartialLabellingState {
         // This is the other foursome with label z
         third: hexacode::Point,
     },
-    Overset,
+    // Why no labelling of the MOG extends it
+    Overset(&'static str),
+}
+
+impl PartialLabellingState {
+    // Classify a partial labelling by the set of labels used in each foursome
+    fn from_used_labels(used_labels: &Labelled<hexacode::Point, HashSet<F4Point>>) -> Self {
+        if used_labels.iter().any(|(_, labels)| labels.len() >= 3) {
+            // No foursomes with >= 3 labels
+            return PartialLabellingState::Overset("a foursome has more than two labels");
+        }
+
+        // Which foursomes have labels
+        let with_label = used_labels
+            .iter()
+            .filter_map(|(foursome, labels)| {
+                if !labels.is_empty() {
+                    Some(foursome)
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(two_labels_foursome) = used_labels
+            .iter()
+            .find_map(|(h, labels)| if labels.len() == 2 { Some(h) } else { None })
+        {
+            for foursome in hexacode::Point::points() {
+                if foursome != two_labels_foursome && used_labels.get(foursome).len() >= 2 {
+                    // At most one foursome with >= 2 labels
+                    return PartialLabellingState::Overset("more than one foursome has two labels");
+                }
+            }
+
+            if with_label.len() == 2 {
+                // There is exactly 1 foursome with 2 labels
+                // The rest have 1 or 0 labels
+                // So, in this case, there is exactly 1 other label somewhere
+
+                let one_label_foursome = used_labels
+                    .iter()
+                    .find_map(|(h, labels)| if labels.len() == 1 { Some(h) } else { None })
+                    .unwrap();
+
+                if one_label_foursome.pair == two_labels_foursome.pair {
+                    let one_label = used_labels.get(one_label_foursome).iter().next().unwrap();
+                    if !used_labels
+                        .get(two_labels_foursome)
+                        .iter()
+                        .any(|two_label| one_label == two_label)
+                    {
+                        // The label in the foursomes with one label must match one of the labels in the foursome with 2 labels
+                        return PartialLabellingState::Overset(NOT_IN_PAIR);
+                    }
+                }
+                // Need a fourth label given in one of the other 4 foursomes
+            } else if with_label.len() == 3 {
+                if let Some(one_label_adjacent_foursome) =
+                    used_labels.iter().find_map(|(foursome, labels)| {
+                        if labels.len() == 1 && foursome.pair == two_labels_foursome.pair {
+                            Some(foursome)
+                        } else {
+                            None
+                        }
+                    })
+                {
+                    let pair = two_labels_foursome.pair;
+                    debug_assert_eq!(pair, one_label_adjacent_foursome.pair);
+
+                    let one_label_nonadjacent_foursome = used_labels
+                        .iter()
+                        .find_map(|(foursome, labels)| {
+                            if labels.len() == 1 && foursome != one_label_adjacent_foursome {
+                                Some(foursome)
+                            } else {
+                                None
+                            }
+                        })
+                        .unwrap();
+
+                    let one_label_adjacent = used_labels
+                        .get(one_label_adjacent_foursome)
+                        .iter()
+                        .next()
+                        .unwrap();
+                    if !used_labels
+                        .get(two_labels_foursome)
+                        .iter()
+                        .any(|two_label| one_label_adjacent == two_label)
+                    {
+                        // The label in the foursomes with one label must match one of the labels in the foursome with 2 labels
+                        return PartialLabellingState::Overset(NOT_IN_PAIR);
+                    }
+
+                    let one_label_nonadjacent = used_labels
+                        .get(one_label_nonadjacent_foursome)
+                        .iter()
+                        .next()
+                        .unwrap();
+
+                    let x = *one_label_adjacent;
+                    let y = *used_labels
+                        .get(two_labels_foursome)
+                        .iter()
+                        .find(|two_label| **two_label != x)
+                        .unwrap();
+                    let z = *one_label_nonadjacent;
+                    return PartialLabellingState::Perfect {
+                        x,
+                        y,
+                        z,
+                        pair,
+                        side: one_label_adjacent_foursome.side,
+                        third: one_label_nonadjacent_foursome,
+                    };
+                } else {
+                    // One of the foursomes with 1 label must be adjacent to the foursome with 2 labels
+                    return PartialLabellingState::Overset(
+                        "neither foursome with one label is paired with the foursome with two labels",
+                    );
+                }
+            } else if with_label.len() >= 4 {
+                // Too many labels
+                return PartialLabellingState::Overset("more than three foursomes have labels");
+            }
+
+            PartialLabellingState::Underset
+        } else {
+            if with_label.len() >= 4 {
+                // At most 3 foursomes with 1 label
+                return PartialLabellingState::Overset("more than three foursomes have labels");
+            }
+
+            if with_label.len() == 3 {
+                // Two must make up one of the three pairs
+                if with_label[0].pair != with_label[1].pair
+                    && with_label[0].pair != with_label[2].pair
+                    && with_label[1].pair != with_label[2].pair
+                {
+                    return PartialLabellingState::Overset(
+                        "the three foursomes with labels don't include a pair",
+                    );
+                }
+            }
+
+            PartialLabellingState::Underset
+        }
+    }
+}
+
+const NOT_IN_PAIR: &str =
+    "the foursome paired with the one with two labels has a label which is not one of those two";
+const REPEATED: &str = "a foursome uses the same label twice";
+
+fn has_repeats(labels: &[F4Point]) -> bool {
+    labels
+        .iter()
+        .enumerate()
+        .any(|(i, label)| labels[i + 1..].contains(label))
 }
 
 mod foursome_index {
@@ -86,6 +259,36 @@ impl SextetStabilizer {
     }
 }
 
+// e.g. `0 1 ω ω̄ 1 0`
+fn hexacode_text(vector: &hexacode::Vector) -> String {
+    vector
+        .iter()
+        .map(|(_, x)| f4_text(*x))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// The permutation of the foursomes in cycle notation, numbering the foursomes from 1
+fn foursome_cycles_text(permutation: &Permutation<hexacode::Point>) -> String {
+    let cycles = permutation.disjoint_cycles();
+    if cycles.is_empty() {
+        return "()".to_string();
+    }
+    cycles
+        .into_iter()
+        .map(|cycle| {
+            format!(
+                "({})",
+                cycle
+                    .into_iter()
+                    .map(|h| (h.point_to_usize() + 1).to_string())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            )
+        })
+        .collect()
+}
+
 #[derive(Default, Clone, PartialEq, Eq)]
 enum PermutationType {
     #[default]
@@ -95,19 +298,58 @@ enum PermutationType {
     SextetStabilizer,
 }
 
+// The parts of the state which undo and redo restore
+#[derive(Clone, PartialEq)]
+struct Snapshot {
+    ordering: Vec<FoursomeIndex>,
+    labelling: Labelled<Point, Option<F4Point>>,
+    sextet_stabilizer_permutation: SextetStabilizer,
+}
+
+// The ordering of the foursomes and the labels set so far
+type LabelsKey = (Vec<usize>, Labelled<Point, Option<F4Point>>);
+
+// What is worked out from the ordering of the foursomes and the labels set so far
+#[derive(Clone)]
+struct Labels {
+    // The labels each point can be given
+    allowed: Labelled<Point, HashSet<F4Point>>,
+    // Why each of the other labels can't be
+    disallowed: Labelled<Point, HashMap<F4Point, &'static str>>,
+    // Why the labels set so far don't extend to any labelling, if they don't
+    overset: Option<&'static str>,
+    // The completed labelling, if there is a unique one
+    completed: Option<OrderedSextetLabelling>,
+}
+
 #[derive(Clone)]
-pub struct State<PrevState: AppState + Clone + 'static> {
-    prev_state: PrevState,
+pub struct State {
+    // The point toggle screen this was opened from, kept as saved so a picker can be reopened from it
+    parent: PointToggleSession,
     sextet: Vec<Vector>,
     ordering: Vec<FoursomeIndex>, // A permutation of 0..6
     labelling: Labelled<Point, Option<F4Point>>,
     permutation_shapes: MogPermutationShapeCache,
     selected_permutation_type: PermutationType,
     sextet_stabilizer_permutation: SextetStabilizer,
+    history: History<Snapshot>,
+    // The allowed labels for each point and the completed labelling, kept until the ordering or labelling changes
+    labels: Memo<LabelsKey, Labels>,
+    // Labels to fill in for a unique completion, shown until accepted or the ordering or labelling changes
+    suggestion: Option<(LabelsKey, Labelled<Point, Option<F4Point>>)>,
+    // Every labelling of the foursomes in the current order
+    all_labellings: Memo<Vec<usize>, Arc<Vec<OrderedSextetLabelling>>>,
+    // What was last asked of the point toggle screen, so the picked points are used for the right thing
+    picking: Option<SelectionTarget>,
+    // A codeword read through the completed labelling as a hexacodeword and parities
+    reading: Option<Vector>,
+    // An octad to count the points of in each foursome
+    intersecting: Option<Vector>,
+    action_log: ActionLogWindow<LabelAction>,
 }
 
-impl<PrevState: AppState + Clone> State<PrevState> {
-    pub fn from_foursome(prev_state: PrevState, vector: &Vector) -> Self {
+impl State {
+    pub fn from_foursome(parent: PointToggleSession, vector: &Vector) -> Self {
         let mog = super::mog::mog();
         let mut sextet = mog
             .complete_sextet(vector)
@@ -116,17 +358,92 @@ impl<PrevState: AppState + Clone> State<PrevState> {
             .collect::<Vec<_>>();
         sextet.sort_unstable();
         sextet.reverse();
+        // The foursome it was completed from comes first
+        let seed = sextet.iter().position(|f| f == vector).unwrap_or(0);
         Self {
-            prev_state,
+            parent,
             sextet,
-            ordering: (0..6).map(FoursomeIndex::new).collect(),
+            ordering: std::iter::once(seed)
+                .chain((0..6).filter(|i| *i != seed))
+                .map(FoursomeIndex::new)
+                .collect(),
             labelling: Labelled::new_constant(None),
             permutation_shapes: MogPermutationShapeCache::default(),
             selected_permutation_type: PermutationType::default(),
             sextet_stabilizer_permutation: SextetStabilizer::default(),
+            history: History::default(),
+            labels: Memo::default(),
+            suggestion: None,
+            all_labellings: Memo::default(),
+            picking: None,
+            reading: None,
+            intersecting: None,
+            action_log: ActionLogWindow::default(),
+        }
+    }
+
+    // Reopen a saved session, or None if it is not a valid sextet and ordering
+    pub fn from_session(
+        parent: PointToggleSession,
+        foursome: &Vector,
+        ordering: &[usize],
+        labelling: Labelled<Point, Option<F4Point>>,
+    ) -> Option<Self> {
+        if foursome.weight() != 4 {
+            return None;
+        }
+        let mut sorted = ordering.to_vec();
+        sorted.sort_unstable();
+        if sorted != (0..6).collect::<Vec<_>>() {
+            return None;
+        }
+        let mut state = Self::from_foursome(parent, foursome);
+        state.ordering = ordering.iter().map(|i| FoursomeIndex::new(*i)).collect();
+        state.labelling = labelling;
+        Some(state)
+    }
+
+    // Open one of the well known labellings
+    pub fn from_preset(parent: PointToggleSession, preset: &Preset) -> Self {
+        let foursomes = preset.foursomes();
+        let first = foursomes.get(hexacode::Point::usize_to_point(0).unwrap());
+        let mut state = Self::from_foursome(parent, first);
+        state.ordering = hexacode::Point::points()
+            .map(|h| {
+                FoursomeIndex::new(
+                    state
+                        .sextet
+                        .iter()
+                        .position(|f| f == foursomes.get(h))
+                        .unwrap(),
+                )
+            })
+            .collect();
+        state.labelling = state.minimal_labels(&preset.labels());
+        state
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            ordering: self.ordering.clone(),
+            labelling: self.labelling.clone(),
+            sextet_stabilizer_permutation: self.sextet_stabilizer_permutation.clone(),
         }
     }
 
+    fn restore(&mut self, snapshot: Snapshot) {
+        self.ordering = snapshot.ordering;
+        self.labelling = snapshot.labelling;
+        self.sextet_stabilizer_permutation = snapshot.sextet_stabilizer_permutation;
+    }
+
+    fn labels_key(&self) -> LabelsKey {
+        (
+            self.ordering.iter().map(|i| i.index()).collect(),
+            self.labelling.clone(),
+        )
+    }
+
     fn get_foursome(&self, foursome: hexacode::Point) -> &Vector {
         &self.sextet[self.ordering[foursome.point_to_usize()].index()]
     }
@@ -135,6 +452,11 @@ impl<PrevState: AppState + Clone> State<PrevState> {
         OrderedSextet::from_foursomes(Labelled::from_fn(|h| self.get_foursome(h).clone()))
     }
 
+    // Whether enough points are labelled for the labelling of the whole MOG to follow
+    pub fn is_labelled(&self) -> bool {
+        self.complete_labelling().is_some()
+    }
+
     /*
     A partial labelling of the following form can be extended uniquely to a labelling of the MOG
 
@@ -160,184 +482,148 @@ impl<PrevState: AppState + Clone> State<PrevState> {
     which, by standard theory, extends to a unique labelling.
      */
     fn partial_labelling_state(&self) -> PartialLabellingState {
-        let sextet: Labelled<hexacode::Point, Vector> =
-            Labelled::from_fn(|h: hexacode::Point| self.get_foursome(h).clone());
+        match self.used_labels() {
+            Some(used_labels) => PartialLabellingState::from_used_labels(&used_labels),
+            // No duplicate labels per foursome
+            None => PartialLabellingState::Overset(REPEATED),
+        }
+    }
 
-        let mut used_labels: Labelled<hexacode::Point, HashSet<F4Point>> =
-            Labelled::new_constant(HashSet::new());
+    // The labels in each foursome, with any repeats
+    fn labels_by_foursome(&self) -> Labelled<hexacode::Point, Vec<F4Point>> {
+        Labelled::from_fn(|h| {
+            self.get_foursome(h)
+                .points()
+                .filter_map(|p| *self.labelling.get(p))
+                .collect()
+        })
+    }
 
+    // The set of labels used in each foursome, or None if some foursome uses a label twice
+    fn used_labels(&self) -> Option<Labelled<hexacode::Point, HashSet<F4Point>>> {
+        let labels = self.labels_by_foursome();
+        if labels.iter().any(|(_, labels)| has_repeats(labels)) {
+            return None;
+        }
+        Some(Labelled::from_fn(|h| {
+            labels.get(h).iter().copied().collect()
+        }))
+    }
+
+    // Given the labels currently set in self.labelling, return why each label can't be given to each point
+    // Any label not in the result is allowed
+    // Only the foursome of the point being labelled changes, so the labels of the other foursomes are worked out once
+    fn disallowed_labels(&self) -> Labelled<Point, HashMap<F4Point, &'static str>> {
+        let mut result = Labelled::new_constant(HashMap::new());
+        let labels = self.labels_by_foursome();
         for foursome in hexacode::Point::points() {
-            for p in sextet.get(foursome).points() {
-                if let Some(label) = *self.labelling.get(p)
-                    && !used_labels.get_mut(foursome).insert(label)
-                {
-                    // No duplicate labels per foursome
-                    return PartialLabellingState::Overset;
+            if labels
+                .iter()
+                .any(|(h, labels)| h != foursome && has_repeats(labels))
+            {
+                // Another foursome is already overset whatever this one is labelled
+                for p in self.get_foursome(foursome).points() {
+                    *result.get_mut(p) = F4Point::points().map(|label| (label, REPEATED)).collect();
                 }
+                continue;
             }
-        }
-
-        if used_labels.iter().any(|(_, labels)| labels.len() >= 3) {
-            // No foursomes with >= 3 labels
-            return PartialLabellingState::Overset;
-        }
-
-        // Which foursomes have labels
-        let with_label = used_labels
-            .iter()
-            .filter_map(|(foursome, labels)| {
-                if !labels.is_empty() {
-                    Some(foursome)
-                } else {
-                    None
+            let mut used_labels: Labelled<hexacode::Point, HashSet<F4Point>> =
+                Labelled::from_fn(|h| labels.get(h).iter().copied().collect());
+            for p in self.get_foursome(foursome).points() {
+                // The labels of the rest of the foursome
+                let mut others = labels.get(foursome).clone();
+                if let Some(label) = self.labelling.get(p)
+                    && let Some(i) = others.iter().position(|other| other == label)
+                {
+                    others.remove(i);
                 }
-            })
-            .collect::<Vec<_>>();
-
-        if let Some(two_labels_foursome) = used_labels
-            .iter()
-            .find_map(|(h, labels)| if labels.len() == 2 { Some(h) } else { None })
-        {
-            for foursome in hexacode::Point::points() {
-                if foursome != two_labels_foursome && used_labels.get(foursome).len() >= 2 {
-                    // At most one foursome with >= 2 labels
-                    return PartialLabellingState::Overset;
+                if has_repeats(&others) {
+                    *result.get_mut(p) = F4Point::points().map(|label| (label, REPEATED)).collect();
+                    continue;
                 }
-            }
-
-            if with_label.len() == 2 {
-                // There is exactly 1 foursome with 2 labels
-                // The rest have 1 or 0 labels
-                // So, in this case, there is exactly 1 other label somewhere
-
-                let one_label_foursome = used_labels
-                    .iter()
-                    .find_map(|(h, labels)| if labels.len() == 1 { Some(h) } else { None })
-                    .unwrap();
-
-                if one_label_foursome.pair == two_labels_foursome.pair {
-                    let one_label = used_labels.get(one_label_foursome).iter().next().unwrap();
-                    if !used_labels
-                        .get(two_labels_foursome)
-                        .iter()
-                        .any(|two_label| one_label == two_label)
-                    {
-                        // The label in the foursomes with one label must match one of the labels in the foursome with 2 labels
-                        return PartialLabellingState::Overset;
+                for label in F4Point::points() {
+                    if others.contains(&label) {
+                        // No duplicate labels per foursome
+                        result
+                            .get_mut(p)
+                            .insert(label, "this foursome already uses this label");
+                        continue;
                     }
-                }
-                // Need a fourth label given in one of the other 4 foursomes
-            } else if with_label.len() == 3 {
-                if let Some(one_label_adjacent_foursome) =
-                    used_labels.iter().find_map(|(foursome, labels)| {
-                        if labels.len() == 1 && foursome.pair == two_labels_foursome.pair {
-                            Some(foursome)
-                        } else {
-                            None
-                        }
-                    })
-                {
-                    let pair = two_labels_foursome.pair;
-                    debug_assert_eq!(pair, one_label_adjacent_foursome.pair);
-
-                    let one_label_nonadjacent_foursome = used_labels
-                        .iter()
-                        .find_map(|(foursome, labels)| {
-                            if labels.len() == 1 && foursome != one_label_adjacent_foursome {
-                                Some(foursome)
-                            } else {
-                                None
-                            }
-                        })
-                        .unwrap();
-
-                    let one_label_adjacent = used_labels
-                        .get(one_label_adjacent_foursome)
+                    *used_labels.get_mut(foursome) = others
                         .iter()
-                        .next()
-                        .unwrap();
-                    if !used_labels
-                        .get(two_labels_foursome)
-                        .iter()
-                        .any(|two_label| one_label_adjacent == two_label)
+                        .copied()
+                        .chain(std::iter::once(label))
+                        .collect();
+                    if let PartialLabellingState::Overset(reason) =
+                        PartialLabellingState::from_used_labels(&used_labels)
                     {
-                        // The label in the foursomes with one label must match one of the labels in the foursome with 2 labels
-                        return PartialLabellingState::Overset;
+                        result.get_mut(p).insert(label, reason);
                     }
-
-                    let one_label_nonadjacent = used_labels
-                        .get(one_label_nonadjacent_foursome)
-                        .iter()
-                        .next()
-                        .unwrap();
-
-                    let x = *one_label_adjacent;
-                    let y = *used_labels
-                        .get(two_labels_foursome)
-                        .iter()
-                        .find(|two_label| **two_label != x)
-                        .unwrap();
-                    let z = *one_label_nonadjacent;
-                    return PartialLabellingState::Perfect {
-                        x,
-                        y,
-                        z,
-                        pair,
-                        side: one_label_adjacent_foursome.side,
-                        third: one_label_nonadjacent_foursome,
-                    };
-                } else {
-                    // One of the foursomes with 1 label must be adjacent to the foursome with 2 labels
-                    return PartialLabellingState::Overset;
                 }
-            } else if with_label.len() >= 4 {
-                // Too many labels
-                return PartialLabellingState::Overset;
             }
+        }
+        result
+    }
 
-            PartialLabellingState::Underset
-        } else {
-            if with_label.len() >= 4 {
-                // At most 3 foursomes with 1 label
-                return PartialLabellingState::Overset;
+    // The four labels the editor needs to pin down a labelling
+    // 0 in the first foursome, 0 and 1 in the second, and the label of the first point of the third
+    fn minimal_labels(
+        &self,
+        labels: &Labelled<Point, F4Point>,
+    ) -> Labelled<Point, Option<F4Point>> {
+        let t = |i: usize| self.get_foursome(hexacode::Point::usize_to_point(i).unwrap());
+        let mut result = Labelled::new_constant(None);
+        for p in t(0).points() {
+            if *labels.get(p) == F4Point::Zero {
+                result.set(p, Some(F4Point::Zero));
             }
-
-            if with_label.len() == 3 {
-                // Two must make up one of the three pairs
-                if with_label[0].pair != with_label[1].pair
-                    && with_label[0].pair != with_label[2].pair
-                    && with_label[1].pair != with_label[2].pair
-                {
-                    return PartialLabellingState::Overset;
-                }
+        }
+        for p in t(1).points() {
+            if matches!(labels.get(p), F4Point::Zero | F4Point::One) {
+                result.set(p, Some(*labels.get(p)));
             }
-
-            PartialLabellingState::Underset
         }
+        let w = t(2).points().next().unwrap();
+        result.set(w, Some(*labels.get(w)));
+        result
     }
 
-    // Given the labels currently set in self.labelling, return a list of allowable labels for each point
-    fn allowed_labels(&self) -> Labelled<Point, HashSet<F4Point>> {
-        let mut result = Labelled::new_constant(HashSet::new());
-        for p in Point::points() {
-            for label in [F4Point::Zero, F4Point::One, F4Point::Alpha, F4Point::Beta] {
-                let mut modified_self = self.clone();
-                modified_self.labelling.set(p, Some(label));
-                match modified_self.partial_labelling_state() {
-                    PartialLabellingState::Underset | PartialLabellingState::Perfect { .. } => {
-                        result.get_mut(p).insert(label);
-                    }
-                    PartialLabellingState::Overset => {}
+    // The labels set so far together with the fewest more needed for a unique completion, or None if there is no completion
+    // The first allowed label is tried at the first unlabelled point, foursome by foursome, backing up if that leads nowhere
+    // so the x x / y / z pattern is filled in from the first foursomes
+    fn suggest_labelling(&mut self) -> Option<Labelled<Point, Option<F4Point>>> {
+        match self.partial_labelling_state() {
+            PartialLabellingState::Perfect { .. } => return Some(self.labelling.clone()),
+            PartialLabellingState::Overset(_) => return None,
+            PartialLabellingState::Underset => {}
+        }
+        let disallowed = self.disallowed_labels();
+        let points = hexacode::Point::points()
+            .flat_map(|h| self.get_foursome(h).points().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        for p in points {
+            if self.labelling.get(p).is_some() {
+                continue;
+            }
+            for label in F4Point::points() {
+                if disallowed.get(p).contains_key(&label) {
+                    continue;
+                }
+                self.labelling.set(p, Some(label));
+                let suggestion = self.suggest_labelling();
+                self.labelling.set(p, None);
+                if suggestion.is_some() {
+                    return suggestion;
                 }
             }
         }
-        result
+        None
     }
 
     // Given the labels currently set in self.labelling return the completion to a labelling of the MOG if unique
     fn complete_labelling(&self) -> Option<OrderedSextetLabelling> {
         match self.partial_labelling_state() {
-            PartialLabellingState::Underset | PartialLabellingState::Overset => None,
+            PartialLabellingState::Underset | PartialLabellingState::Overset(_) => None,
             PartialLabellingState::Perfect {
                 x,
                 y,
@@ -499,16 +785,57 @@ impl<PrevState: AppState + Clone> State<PrevState> {
     }
 }
 
-impl<PrevState: AppState + Clone> AppState for State<PrevState> {
+impl AppState for State {
     fn update(
         &mut self,
         ctx: &eframe::egui::Context,
         _frame: &mut eframe::Frame,
-    ) -> Option<Box<dyn AppState>> {
-        let allowed_labels = self.allowed_labels();
-        let completed_labels = self.complete_labelling();
+    ) -> Option<Navigate> {
+        if self
+            .suggestion
+            .as_ref()
+            .is_some_and(|(key, _)| *key != self.labels_key())
+        {
+            self.suggestion = None;
+        }
+
+        let mut labels = std::mem::take(&mut self.labels);
+        let Labels {
+            allowed: allowed_labels,
+            disallowed: disallowed_labels,
+            overset,
+            completed: completed_labels,
+        } = labels.get(self.labels_key(), |_| {
+            perf_hud::time(ctx, "Labelling", || {
+                let disallowed = self.disallowed_labels();
+                Labels {
+                    allowed: Labelled::from_fn(|p| {
+                        F4Point::points()
+                            .filter(|label| !disallowed.get(p).contains_key(label))
+                            .collect()
+                    }),
+                    disallowed,
+                    overset: match self.partial_labelling_state() {
+                        PartialLabellingState::Overset(reason) => Some(reason),
+                        _ => None,
+                    },
+                    completed: self.complete_labelling(),
+                }
+            })
+        });
+        self.labels = labels;
         let mut hovered_point = None;
 
+        // The codeword being read, moved so that each foursome is a column and each label a row
+        let reading = completed_labels.as_ref().zip(self.reading.as_ref()).map(
+            |(completed_labels, reading)| {
+                reading.permute(Permutation::from_fn(|p| Point {
+                    col: *completed_labels.foursomes().get(p),
+                    row: *completed_labels.labels().get(p),
+                }))
+            },
+        );
+
         let permutation = if let Some(completed_labels) = &completed_labels {
             let standard_labelling_to_completed_labelling = Permutation::from_fn(|p| Point {
                 col: *completed_labels.foursomes().get(p),
@@ -535,229 +862,481 @@ impl<PrevState: AppState + Clone> AppState for State<PrevState> {
             None
         };
 
-        if let Some(new_state) = SidePanel::left("left_panel")
-            .min_width(200.0)
-            .show(ctx, |ui| {
-                // Back
-                if ui.button("Back").clicked() {
-                    return Some(Box::<dyn AppState>::from(Box::new(self.prev_state.clone())));
+        if let Some((ordering, labelling)) = self.action_log.show(ctx, || {
+            (
+                self.ordering.iter().map(|i| i.index()).collect(),
+                self.labelling.clone(),
+            )
+        }) {
+            self.ordering = ordering.into_iter().map(FoursomeIndex::new).collect();
+            self.labelling = labelling;
+        }
+
+        if let Some(navigate) = side_panel(ctx, |ui| {
+            // Back
+            if ui.button("Back").clicked() {
+                return Some(Navigate::Back);
+            }
+
+            ui.heading("Labelling Editor");
+            if ui
+                .button("Change sextet")
+                .on_hover_text("Pick a foursome of another sextet to label")
+                .clicked()
+            {
+                self.picking = Some(SelectionTarget::Foursome);
+                return Some(Navigate::Open(Box::new(
+                    self.parent
+                        .clone()
+                        .restore()
+                        .with_selected_points(Vector::zero())
+                        .with_target(Some(SelectionTarget::Foursome)),
+                )));
+            }
+
+            let mut preset = PRESETS.iter().find(|p| {
+                super::command_palette::offer(ui.ctx(), &format!("Preset: {}", p.name), None)
+            });
+            ui.menu_button("Presets", |ui| {
+                for p in &PRESETS {
+                    if ui.button(p.name).on_hover_text(p.description).clicked() {
+                        preset = Some(p);
+                    }
                 }
+            });
+            if let Some(preset) = preset {
+                return Some(Navigate::Open(Box::new(Self::from_preset(
+                    self.parent.clone(),
+                    preset,
+                ))));
+            }
+            if ui.button("Action log").clicked()
+                || super::command_palette::offer(ui.ctx(), "Action log", None)
+            {
+                self.action_log.open();
+            }
 
-                ui.heading("Labelling Editor");
-
-                // Reorder the sextets
-                ui.label("Reorder Foursomes");
-                egui_dnd::dnd(ui, "foursome_ordering").show_vec(
-                    &mut self.ordering,
-                    |ui, item: &mut FoursomeIndex, handle, state| {
-                        handle.ui(ui, |ui| {
-                            ui.add_enabled(
-                                true,
-                                Button::new(format!("Foursome {}", state.index + 1)).fill(
-                                    sextet_idx_to_colour(item.index())
-                                        .lerp_to_gamma(ui.visuals().panel_fill, 0.6),
-                                ),
-                            );
-                        });
-                        if state.index == 1 || state.index == 3 {
-                            ui.add_space(4.0);
+            // Reorder the sextets
+            ui.label("Reorder Foursomes");
+            egui_dnd::dnd(ui, "foursome_ordering").show_vec(
+                &mut self.ordering,
+                |ui, item: &mut FoursomeIndex, handle, state| {
+                    handle.ui(ui, |ui| {
+                        ui.add_enabled(
+                            true,
+                            Button::new(format!("Foursome {}", state.index + 1)).fill(
+                                sextet_idx_to_colour(ctx, item.index())
+                                    .lerp_to_gamma(ui.visuals().panel_fill, 0.6),
+                            ),
+                        );
+                    });
+                    if state.index == 1 || state.index == 3 {
+                        ui.add_space(4.0);
+                    }
+                },
+            );
+
+            if let Some(reason) = overset {
+                ui.colored_label(
+                    ui.visuals().error_fg_color,
+                    format!("No labelling fits these labels: {reason}."),
+                );
+            } else if completed_labels.is_none() {
+                ui.label("Select labels until there is a unique completion to a full labelling.");
+                if let Some((_, suggestion)) = self.suggestion.clone() {
+                    ui.horizontal(|ui| {
+                        if ui.button("Accept suggestion").clicked() {
+                            self.labelling = suggestion;
+                            self.suggestion = None;
+                        }
+                        if ui.button("Dismiss").clicked() {
+                            self.suggestion = None;
                         }
+                    });
+                } else if ui
+                    .button("Suggest labelling")
+                    .on_hover_text("Fill in the fewest labels needed for a unique completion")
+                    .clicked()
+                {
+                    let key = self.labels_key();
+                    self.suggestion = self.suggest_labelling().map(|suggestion| (key, suggestion));
+                }
+            }
+
+            // Step through every labelling of the foursomes in this order
+            if let Some(completed_labels) = &completed_labels {
+                ui.heading("All Labellings");
+                let sextet = self.ordered_sextet();
+                let labellings = self.all_labellings.get(
+                    self.ordering.iter().map(|i| i.index()).collect(),
+                    |_| {
+                        perf_hud::time(ctx, "All labellings", || {
+                            Arc::new(sextet.all_labellings(mog()))
+                        })
                     },
                 );
+                let n = labellings.len();
+                let index = labellings
+                    .iter()
+                    .position(|labelling| labelling.labels() == completed_labels.labels())
+                    .unwrap_or(0);
+                let mut step = index + 1;
+                ui.horizontal(|ui| {
+                    if ui.button("<").clicked() {
+                        step = (index + n - 1) % n + 1;
+                    }
+                    ui.add(
+                        DragValue::new(&mut step)
+                            .range(1..=n)
+                            .prefix("Labelling ")
+                            .suffix(format!(" of {n}")),
+                    );
+                    if ui.button(">").clicked() {
+                        step = (index + 1) % n + 1;
+                    }
+                });
+                if step != index + 1 {
+                    self.labelling = self.minimal_labels(labellings[step - 1].labels());
+                }
+            }
 
-                if completed_labels.is_none() {
-                    ui.label(
-                        "Select labels until there is a unique completion to a full labelling.",
+            // Check an octad meets the foursomes in one of the ways an octad can meet a sextet
+            ui.heading("Octad Intersection");
+            let mut pick = false;
+            ui.horizontal(|ui| {
+                pick = ui.button("Pick octad").clicked();
+                if self.intersecting.is_some() && ui.button("Clear").clicked() {
+                    self.intersecting = None;
+                }
+            });
+            if pick {
+                self.picking = Some(SelectionTarget::Octad);
+                return Some(Navigate::Open(Box::new(
+                    self.parent
+                        .clone()
+                        .restore()
+                        .with_selected_points(self.intersecting.clone().unwrap_or(Vector::zero()))
+                        .with_target(Some(SelectionTarget::Octad)),
+                )));
+            }
+            if let Some(octad) = &self.intersecting {
+                let sizes = Vec::from(self.ordered_sextet().intersection_sizes(octad));
+                let text = |sizes: &[usize]| {
+                    sizes
+                        .iter()
+                        .map(|n| n.to_string())
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                };
+                ui.label(format!("Points in each foursome {}", text(&sizes)));
+                let mut pattern = sizes;
+                pattern.sort_unstable_by(|a, b| b.cmp(a));
+                if OCTAD_SEXTET_INTERSECTIONS
+                    .iter()
+                    .any(|allowed| allowed[..] == pattern[..])
+                {
+                    ui.label(format!("Pattern {}", text(&pattern)));
+                } else {
+                    ui.colored_label(
+                        ui.visuals().error_fg_color,
+                        format!(
+                            "Pattern {} is not one an octad can have, which is a bug",
+                            text(&pattern)
+                        ),
                     );
                 }
+            }
 
-                // Permutations
-                if completed_labels.is_some() {
-                    ui.heading("Permutation");
-                    ui.label(
-                        "\
+            // Read a codeword the way Curtis reads the MOG, with the foursomes as columns and the labels as rows
+            if completed_labels.is_some() {
+                ui.heading("Hexacode Reading");
+                let mut pick = false;
+                ui.horizontal(|ui| {
+                    pick = ui.button("Pick codeword").clicked();
+                    if self.reading.is_some() && ui.button("Clear").clicked() {
+                        self.reading = None;
+                    }
+                });
+                if pick {
+                    self.picking = Some(SelectionTarget::Codeword);
+                    return Some(Navigate::Open(Box::new(
+                        self.parent
+                            .clone()
+                            .restore()
+                            .with_selected_points(self.reading.clone().unwrap_or(Vector::zero()))
+                            .with_target(Some(SelectionTarget::Codeword)),
+                    )));
+                }
+                if let Some(reading) = &reading {
+                    ui.label(format!("Score {}", hexacode_text(&reading.score())));
+                    ui.label(format!(
+                        "Label 0 is {}, foursomes are {}",
+                        if reading.top_row_parity() {
+                            "odd"
+                        } else {
+                            "even"
+                        },
+                        reading
+                            .column_parities()
+                            .iter()
+                            .map(|(_, odd)| if *odd { "O" } else { "E" })
+                            .collect::<Vec<_>>()
+                            .join(" ")
+                    ));
+                }
+            }
+
+            // Permutations
+            if completed_labels.is_some() {
+                ui.heading("Permutation");
+                ui.label(
+                    "\
 Construct permutations induced by this labelling",
-                    );
-                    ui.radio_value(
-                        &mut self.selected_permutation_type,
-                        PermutationType::None,
-                        "None",
-                    )
-                    .on_hover_text("Don't show a permutation");
-                    ui.radio_value(
-                        &mut self.selected_permutation_type,
-                        PermutationType::StandardToLabellingAut,
-                        "To Standard Labelling",
-                    )
-                    .on_hover_text(
-                        "\
+                );
+                ui.radio_value(
+                    &mut self.selected_permutation_type,
+                    PermutationType::None,
+                    "None",
+                )
+                .on_hover_text("Don't show a permutation");
+                ui.radio_value(
+                    &mut self.selected_permutation_type,
+                    PermutationType::StandardToLabellingAut,
+                    "To Standard Labelling",
+                )
+                .on_hover_text(
+                    "\
 The permutation taking the ordered foursomes to the ordered columns \
 and the labelling labels to the row labels",
-                    );
-                    ui.radio_value(
-                        &mut self.selected_permutation_type,
-                        PermutationType::LabellingToStandardAut,
-                        "From Standard Labelling",
-                    )
-                    .on_hover_text(
-                        "\
+                );
+                ui.radio_value(
+                    &mut self.selected_permutation_type,
+                    PermutationType::LabellingToStandardAut,
+                    "From Standard Labelling",
+                )
+                .on_hover_text(
+                    "\
 The permutation taking ordered columns to the ordered foursomes \
 and the row labels to the labelling labels",
-                    );
-                    ui.radio_value(
-                        &mut self.selected_permutation_type,
-                        PermutationType::SextetStabilizer,
-                        "Sextet Stabilizer",
-                    )
-                    .on_hover_text(
-                        "\
+                );
+                ui.radio_value(
+                    &mut self.selected_permutation_type,
+                    PermutationType::SextetStabilizer,
+                    "Sextet Stabilizer",
+                )
+                .on_hover_text(
+                    "\
 Configure permutations which preserve the unordered sextet",
-                    );
+                );
+
+                if self.selected_permutation_type == PermutationType::SextetStabilizer {
+                    ui.heading("Sextet Stabilizer Configuration");
 
-                    if self.selected_permutation_type == PermutationType::SextetStabilizer {
-                        ui.heading("Sextet Stabilizer Configuration");
+                    ui.horizontal(|ui| {
+                        if ui.button("Reset").clicked() {
+                            self.sextet_stabilizer_permutation = SextetStabilizer::default();
+                        }
 
-                        ui.horizontal(|ui| {
-                            if ui.button("Reset").clicked() {
-                                self.sextet_stabilizer_permutation = SextetStabilizer::default();
+                        if ui.button("×ω").clicked() {
+                            for foursome_perm in
+                                &mut self.sextet_stabilizer_permutation.inner_permutations
+                            {
+                                *foursome_perm = &Permutation::new_cycle(vec![
+                                    &F4Point::One,
+                                    &F4Point::Alpha,
+                                    &F4Point::Beta,
+                                ]) * &*foursome_perm;
+                            }
+                        }
+                        if ui.button("Conjugate").clicked() {
+                            for foursome_perm in
+                                &mut self.sextet_stabilizer_permutation.inner_permutations
+                            {
+                                *foursome_perm =
+                                    &Permutation::new_swap(&F4Point::Alpha, &F4Point::Beta)
+                                        * &*foursome_perm;
                             }
+                        }
+                    });
+
+                    egui_dnd::dnd(ui, "foursome_permutation").show_vec(
+                        &mut self.sextet_stabilizer_permutation.foursome_permutation,
+                        |ui, item, handle, state| {
+                            ui.horizontal(|ui| {
+                                handle.ui(ui, |ui| {
+                                    ui.horizontal(|ui| {
+                                        ui.add_enabled(
+                                            true,
+                                            Button::new(format!(
+                                                "Foursome {}",
+                                                self.ordering
+                                                    .iter()
+                                                    .find(|index| { item.index() == index.index() })
+                                                    .unwrap()
+                                                    .index()
+                                                    + 1
+                                            ))
+                                            .fill(
+                                                sextet_idx_to_colour(
+                                                    ctx,
+                                                    self.ordering[item.index()].index(),
+                                                )
+                                                .lerp_to_gamma(ui.visuals().panel_fill, 0.6),
+                                            ),
+                                        );
+                                    });
+                                });
 
-                            if ui.button("×ω").clicked() {
-                                for foursome_perm in
-                                    &mut self.sextet_stabilizer_permutation.inner_permutations
-                                {
+                                let foursome_perm = &mut self
+                                    .sextet_stabilizer_permutation
+                                    .inner_permutations[item.index()];
+
+                                if ui.button("+1").clicked() {
+                                    *foursome_perm =
+                                        &Permutation::new_swap(&F4Point::Zero, &F4Point::One)
+                                            * &*foursome_perm;
+                                    *foursome_perm =
+                                        &Permutation::new_swap(&F4Point::Alpha, &F4Point::Beta)
+                                            * &*foursome_perm;
+                                }
+                                if ui.button("+ω").clicked() {
+                                    *foursome_perm =
+                                        &Permutation::new_swap(&F4Point::Zero, &F4Point::Alpha)
+                                            * &*foursome_perm;
+                                    *foursome_perm =
+                                        &Permutation::new_swap(&F4Point::One, &F4Point::Beta)
+                                            * &*foursome_perm;
+                                }
+                                if ui.button("×ω").clicked() {
                                     *foursome_perm = &Permutation::new_cycle(vec![
                                         &F4Point::One,
                                         &F4Point::Alpha,
                                         &F4Point::Beta,
                                     ]) * &*foursome_perm;
                                 }
-                            }
-                            if ui.button("Conjugate").clicked() {
-                                for foursome_perm in
-                                    &mut self.sextet_stabilizer_permutation.inner_permutations
-                                {
+                                if ui.button("Conjugate").clicked() {
                                     *foursome_perm =
                                         &Permutation::new_swap(&F4Point::Alpha, &F4Point::Beta)
                                             * &*foursome_perm;
                                 }
+                            });
+                            if state.index == 1 || state.index == 3 {
+                                ui.add_space(4.0);
                             }
-                        });
-
-                        egui_dnd::dnd(ui, "foursome_permutation").show_vec(
-                            &mut self.sextet_stabilizer_permutation.foursome_permutation,
-                            |ui, item, handle, state| {
-                                ui.horizontal(|ui| {
-                                    handle.ui(ui, |ui| {
-                                        ui.horizontal(|ui| {
-                                            ui.add_enabled(
-                                                true,
-                                                Button::new(format!(
-                                                    "Foursome {}",
-                                                    self.ordering
-                                                        .iter()
-                                                        .find(|index| {
-                                                            item.index() == index.index()
-                                                        })
-                                                        .unwrap()
-                                                        .index()
-                                                        + 1
-                                                ))
-                                                .fill(
-                                                    sextet_idx_to_colour(
-                                                        self.ordering[item.index()].index(),
-                                                    )
-                                                    .lerp_to_gamma(ui.visuals().panel_fill, 0.6),
-                                                ),
-                                            );
-                                        });
-                                    });
-
-                                    let foursome_perm = &mut self
-                                        .sextet_stabilizer_permutation
-                                        .inner_permutations[item.index()];
-
-                                    if ui.button("+1").clicked() {
-                                        *foursome_perm =
-                                            &Permutation::new_swap(&F4Point::Zero, &F4Point::One)
-                                                * &*foursome_perm;
-                                        *foursome_perm =
-                                            &Permutation::new_swap(&F4Point::Alpha, &F4Point::Beta)
-                                                * &*foursome_perm;
-                                    }
-                                    if ui.button("+ω").clicked() {
-                                        *foursome_perm =
-                                            &Permutation::new_swap(&F4Point::Zero, &F4Point::Alpha)
-                                                * &*foursome_perm;
-                                        *foursome_perm =
-                                            &Permutation::new_swap(&F4Point::One, &F4Point::Beta)
-                                                * &*foursome_perm;
-                                    }
-                                    if ui.button("×ω").clicked() {
-                                        *foursome_perm = &Permutation::new_cycle(vec![
-                                            &F4Point::One,
-                                            &F4Point::Alpha,
-                                            &F4Point::Beta,
-                                        ]) * &*foursome_perm;
-                                    }
-                                    if ui.button("Conjugate").clicked() {
-                                        *foursome_perm =
-                                            &Permutation::new_swap(&F4Point::Alpha, &F4Point::Beta)
-                                                * &*foursome_perm;
-                                    }
-                                });
-                                if state.index == 1 || state.index == 3 {
-                                    ui.add_space(4.0);
-                                }
-                            },
-                        );
-                    }
+                        },
+                    );
 
-                    if let Some(permutation) = permutation.as_ref()
-                        && let Some(new_state) = ui
-                            .horizontal(|ui| {
-                                let mut is_aut = mog().is_automorphism(permutation);
-                                let text = if is_aut {
-                                    "This permutation is an automorphism"
-                                } else {
-                                    "This permutation is not an automorphism"
-                                };
-                                ui.checkbox(&mut is_aut, "Automorphism").on_hover_text(text);
-
-                                if ui.button("Select").clicked() {
-                                    return Some(Box::<dyn AppState>::from(Box::new(
-                                        crate::app::ui::point_toggle::State::new(
-                                            Labelled::zero(),
-                                            permutation.clone(),
+                    // Where the configured element sits in 2^6:3.S6, numbering the foursomes as above
+                    ui.heading("Structure");
+                    match ColumnSextetElement::decompose(
+                        &self
+                            .sextet_stabilizer_permutation
+                            .standard_ordered_sextet_permutation(),
+                    ) {
+                        Ok(element) => {
+                            ui.label(format!("In {}", element.subgroup()));
+                            ui.label(format!(
+                                "Hexacode part (2^6): add {}",
+                                hexacode_text(&element.translation)
+                            ));
+                            ui.label(format!(
+                                "S6 image: {}",
+                                foursome_cycles_text(&element.columns)
+                            ));
+                            ui.label(match element.diagonal_scalar() {
+                                Some(mu) => format!("Diagonal part: ×{}", f4_text(mu)),
+                                None => format!("Scalars: ×{}", hexacode_text(&element.scalars)),
+                            });
+                            if element.conjugate {
+                                ui.label("Conjugates the labels");
+                            }
+                        }
+                        Err(err) => {
+                            ui.colored_label(
+                                    ui.visuals().error_fg_color,
+                                    match err {
+                                        ColumnSextetError::SplitsColumn(c) => format!(
+                                            "Foursome {} is split up",
+                                            c.point_to_usize() + 1
                                         ),
-                                    )));
-                                }
-                                None
-                            })
-                            .inner
-                    {
-                        return Some(new_state);
-                    };
+                                        ColumnSextetError::NotSemilinear(c) => format!(
+                                            "Foursome {} is not permuted by x → λx + t or x → λx̄ + t",
+                                            c.point_to_usize() + 1
+                                        ),
+                                        ColumnSextetError::MixedConjugation => {
+                                            "Only some of the foursomes are conjugated".to_string()
+                                        }
+                                        ColumnSextetError::NotHexacodeAutomorphism => {
+                                            "Moving and scaling the foursomes this way does not preserve the hexacode".to_string()
+                                        }
+                                        ColumnSextetError::TranslationNotHexacodeword(t) => {
+                                            format!(
+                                                "The additions {} are not a hexacodeword",
+                                                hexacode_text(&t)
+                                            )
+                                        }
+                                    },
+                                );
+                            ui.label("So this is not an automorphism");
+                        }
+                    }
                 }
 
-                None
-            })
-            .inner
-        {
-            return Some(new_state);
+                if let Some(permutation) = permutation.as_ref()
+                    && let Some(navigate) = ui
+                        .horizontal(|ui| {
+                            let mut is_aut = mog().is_automorphism(permutation);
+                            let text = if is_aut {
+                                "This permutation is an automorphism"
+                            } else {
+                                "This permutation is not an automorphism"
+                            };
+                            ui.checkbox(&mut is_aut, "Automorphism").on_hover_text(text);
+
+                            if ui.button("Select").clicked() {
+                                return Some(Navigate::Open(Box::new(
+                                    crate::app::ui::point_toggle::State::new(
+                                        Vector::zero(),
+                                        permutation.clone(),
+                                    ),
+                                )));
+                            }
+                            None
+                        })
+                        .inner
+                {
+                    return Some(navigate);
+                };
+            }
+
+            None
+        }) {
+            return Some(navigate);
         }
 
-        let point_to_cell = |p: Point| -> GridCell {
-            let i = p.point_to_usize();
-            (i as isize % 6, i as isize / 6)
-        };
+        let layout = MogLayout::get(ctx);
 
         let mut grid_builder = super::grid::GridBuilder::default();
-
-        // The 6x4 MOG grid
-        for foursome in &self.sextet {
+        Geometry::get(ctx).apply(&mut grid_builder);
+        layout.add_gaps(&mut grid_builder);
+        grid_builder.set_align(Align2::CENTER_TOP);
+
+        // The 6x4 MOG grid with a coloured box for each point of the MOG
+        let faint_bg_color = ctx.style().visuals.faint_bg_color;
+        for (foursome_idx, foursome) in self.sextet.iter().enumerate() {
+            let colour = sextet_idx_to_colour(ctx, foursome_idx).lerp_to_gamma(faint_bg_color, 0.6);
             for p in foursome.points() {
-                grid_builder.include_cell(point_to_cell(p));
+                grid_builder.set_cell_background(layout.point_to_cell(p), colour);
+            }
+        }
+
+        // The score and parity of each foursome of the codeword being read, under the grid in the colour of the foursome
+        if reading.is_some() {
+            for (c, foursome_idx) in self.ordering.iter().enumerate() {
+                let colour = sextet_idx_to_colour(ctx, foursome_idx.index())
+                    .lerp_to_gamma(faint_bg_color, 0.6);
+                grid_builder.set_cell_background(layout.col_label_to_cell(c), colour);
+                grid_builder.set_cell_background(layout.col_parity_to_cell(c), colour);
             }
         }
 
@@ -765,18 +1344,9 @@ Configure permutations which preserve the unordered sextet",
             let (response, painter, grid) = grid_builder.show(ui);
 
             // The 6x4 MOG grid
-            for (foursome_idx, foursome) in self.sextet.iter().enumerate() {
+            for foursome in &self.sextet {
                 for p in foursome.points() {
-                    let rect = grid.cell_to_rect(point_to_cell(p));
-
-                    let colour = sextet_idx_to_colour(foursome_idx);
-
-                    // Draw the coloured box for the point of the MOG
-                    painter.rect_filled(
-                        rect,
-                        grid.cell_scalar_to_pos_scalar(0.05),
-                        colour.lerp_to_gamma(ui.visuals().faint_bg_color, 0.6),
-                    );
+                    let rect = grid.cell_to_rect(layout.point_to_cell(p));
 
                     // Check if the mouse is over this point
                     if let Some(pos) = response.hover_pos()
@@ -791,7 +1361,7 @@ Configure permutations which preserve the unordered sextet",
                     {
                         painter.rect_stroke(
                             rect,
-                            grid.cell_scalar_to_pos_scalar(0.05),
+                            grid.rounding(),
                             ui.visuals().widgets.hovered.fg_stroke,
                             eframe::egui::StrokeKind::Middle,
                         );
@@ -800,7 +1370,9 @@ Configure permutations which preserve the unordered sextet",
                     if (response.is_pointer_button_down_on()
                         || response.drag_stopped()
                         || response.clicked())
-                        && rect.contains(response.interact_pointer_pos().unwrap())
+                        && response
+                            .interact_pointer_pos()
+                            .is_some_and(|pos| rect.contains(pos))
                     {
                         // Label selection
                         let result = f4_selection(
@@ -809,6 +1381,7 @@ Configure permutations which preserve the unordered sextet",
                             &response,
                             rect,
                             allowed_labels.get(p).clone(),
+                            disallowed_labels.get(p),
                             self.labelling.get(p).is_some(),
                         );
                         if response.drag_stopped() || response.clicked() {
@@ -825,6 +1398,13 @@ Configure permutations which preserve the unordered sextet",
                     } else if let Some(label) = *self.labelling.get(p) {
                         // Draw labels
                         draw_f4(ui, &painter, rect, ui.visuals().strong_text_color(), label);
+                    } else if let Some(label) = self
+                        .suggestion
+                        .as_ref()
+                        .and_then(|(_, suggestion)| *suggestion.get(p))
+                    {
+                        // Suggested labels waiting to be accepted
+                        draw_f4(ui, &painter, rect, ui.visuals().hyperlink_color, label);
                     } else if let Some(completed_labels) = completed_labels.clone() {
                         draw_f4(
                             ui,
@@ -837,29 +1417,141 @@ Configure permutations which preserve the unordered sextet",
                 }
             }
 
+            CoordinateLabels::get(ctx).draw(ui, &painter, &grid, layout);
+
+            if let Some(octad) = &self.intersecting {
+                for p in octad.points() {
+                    painter.rect_stroke(
+                        grid.cell_to_rect(layout.point_to_cell(p)),
+                        grid.rounding(),
+                        ui.visuals().selection.stroke,
+                        eframe::egui::StrokeKind::Middle,
+                    );
+                }
+            }
+
+            if let (Some(reading), Some(codeword)) = (&reading, &self.reading) {
+                for p in codeword.points() {
+                    painter.rect_stroke(
+                        grid.cell_to_rect(layout.point_to_cell(p)),
+                        grid.rounding(),
+                        ui.visuals().selection.stroke,
+                        eframe::egui::StrokeKind::Middle,
+                    );
+                }
+                let rule_colour = |obeyed: bool| {
+                    if obeyed {
+                        Palette::get(ctx).good()
+                    } else {
+                        ui.visuals().error_fg_color
+                    }
+                };
+                let score = reading.score();
+                let score_colour = rule_colour(hexacode::is_hexacodeword(&score));
+                let parity_colour = rule_colour(reading.has_consistent_parity());
+                let parities = reading.column_parities();
+                for c in 0..6 {
+                    let h = hexacode::Point::usize_to_point(c).unwrap();
+                    let rect = grid.cell_to_rect(layout.col_label_to_cell(c));
+                    draw_f4(ui, &painter, rect, score_colour, *score.get(h));
+                    let rect = grid.cell_to_rect(layout.col_parity_to_cell(c));
+                    painter.text(
+                        rect.center(),
+                        Align2::CENTER_CENTER,
+                        if *parities.get(h) { "O" } else { "E" },
+                        eframe::egui::FontId::proportional(0.5 * rect.height()),
+                        parity_colour,
+                    );
+                }
+            }
+
             // Draw the selected permutation
-            let cell_permutation = permutation
-                .clone()
-                .map(|permutation| permutation.map_injective_unchecked(point_to_cell));
+            let cell_permutation = permutation.clone().map(|permutation| {
+                permutation.map_injective_unchecked(|p| layout.point_to_cell(p))
+            });
 
+            self.permutation_shapes.set_geometry(Geometry::get(ctx));
             self.permutation_shapes
                 .set_permutation(cell_permutation, grid);
+            perf_hud::report_cache(
+                ctx,
+                "Permutation shapes",
+                self.permutation_shapes.cache_stats(),
+            );
+            perf_hud::report_cache(ctx, "Labelling", self.labels.stats());
 
             let colour = ui.visuals().strong_text_color();
 
             for (cycle, shape) in self.permutation_shapes.shapes() {
-                let colour = if let Some(p) = hovered_point
-                    && cycle.contains(&point_to_cell(p))
-                {
-                    colour
-                } else {
-                    colour * Color32::from_white_alpha(96)
-                };
+                let hovered = fade(
+                    ctx,
+                    Id::new(("sextet_labelling_cycle_hover", cycle)),
+                    hovered_point.is_some_and(|p| cycle.contains(&layout.point_to_cell(p))),
+                    HOVER_FADE,
+                );
+                let colour = lerp_colour(colour * Color32::from_white_alpha(96), colour, hovered);
 
                 painter.add(shape.to_egui_mesh(colour));
             }
         });
 
+        self.history.record(self.snapshot());
+        self.action_log.record(|| {
+            (
+                self.ordering.iter().map(|i| i.index()).collect(),
+                self.labelling.clone(),
+            )
+        });
+
         None
     }
+
+    fn title(&self) -> String {
+        "Labelling Editor".to_string()
+    }
+
+    fn parent(&self) -> Option<Box<dyn AppState>> {
+        Some(Box::new(self.parent.clone().restore()))
+    }
+
+    fn picked(&mut self, vector: Vector) {
+        match self.picking.take() {
+            // Start again on the sextet of the picked foursome
+            Some(SelectionTarget::Foursome) => {
+                *self = Self::from_foursome(self.parent.clone(), &vector);
+            }
+            Some(SelectionTarget::Codeword) => self.reading = Some(vector),
+            Some(SelectionTarget::Octad) => self.intersecting = Some(vector),
+            _ => {}
+        }
+    }
+
+    fn session(&self) -> Option<Session> {
+        Some(Session::SextetLabelling(SextetLabellingSession {
+            prev_state: self.parent.clone(),
+            foursome: self.sextet[0].clone(),
+            ordering: self.ordering.iter().map(|i| i.index()).collect(),
+            labelling: self.labelling.clone(),
+        }))
+    }
+
+    fn can_undo(&self) -> bool {
+        self.history.can_undo()
+    }
+
+    fn can_redo(&self) -> bool {
+        self.history.can_redo()
+    }
+
+    fn undo(&mut self) {
+        if let Some(snapshot) = self.history.undo() {
+            self.restore(snapshot);
+        }
+    }
+
+    fn redo(&mut self) {
+        if let Some(snapshot) = self.history.redo() {
+            self.restore(snapshot);
+        }
+    }
 }
@@ -1,4 +1,5 @@
-use eframe::egui::{Color32, Mesh, Pos2, TextureId};
+use eframe::egui::{Align2, Color32, FontId, Mesh, Painter, Pos2, TextureId, Vec2};
+use eframe::epaint::TextShape;
 use i_overlay::{
     core::{fill_rule::FillRule, overlay_rule::OverlayRule},
     float::single::SingleFloatOverlay,
@@ -82,27 +83,309 @@ impl Shape {
         start_cap: LineCap<[f64; 2], f64>,
         end_cap: LineCap<[f64; 2], f64>,
     ) -> Self {
-        assert!(points.len() >= 2);
-        fn compute_bezier(points: &[Pos2], t: f32) -> Pos2 {
-            debug_assert!(!points.is_empty());
-            if points.len() == 1 {
-                return points[0];
+        Self::lines(bezier_points(&points, segments), width, start_cap, end_cap)
+    }
+
+    // Part of the circle of the given radius about the center, from `start_angle` to `end_angle`
+    // Angles are measured the same way as in `regular_polygon` and the arc may go either way round
+    #[allow(clippy::too_many_arguments)]
+    pub fn arc(
+        center: Pos2,
+        radius: f64,
+        start_angle: f64,
+        end_angle: f64,
+        width: f64,
+        segments: usize,
+        start_cap: LineCap<[f64; 2], f64>,
+        end_cap: LineCap<[f64; 2], f64>,
+    ) -> Self {
+        Self::lines(
+            arc_points(center, radius, start_angle, end_angle, segments),
+            width,
+            start_cap,
+            end_cap,
+        )
+    }
+
+    // Like `lines` but broken into dashes of length `dash` separated by gaps of length `gap`
+    // The caps are only applied to the ends of the whole path
+    // If the path ends in a gap, a stub as long as the line is wide is drawn at the very end to carry the end cap
+    pub fn dashed_lines(
+        points: Vec<Pos2>,
+        width: f64,
+        dash: f64,
+        gap: f64,
+        start_cap: LineCap<[f64; 2], f64>,
+        end_cap: LineCap<[f64; 2], f64>,
+    ) -> Self {
+        let (mut dashes, ends_in_dash) = dash_path(&points, dash, gap);
+        if !ends_in_dash {
+            let reversed = points.iter().rev().copied().collect::<Vec<_>>();
+            if let Some(mut stub) = dash_path(&reversed, width.min(dash), f64::INFINITY)
+                .0
+                .into_iter()
+                .next()
+            {
+                stub.reverse();
+                dashes.push(stub);
+            }
+        }
+        let n = dashes.len();
+        let (mut start_cap, mut end_cap) = (Some(start_cap), Some(end_cap));
+        let mut shape = Self::empty();
+        for (i, dash_points) in dashes.into_iter().enumerate() {
+            let dash_start_cap = if i == 0 { start_cap.take() } else { None };
+            let dash_end_cap = if i + 1 == n { end_cap.take() } else { None };
+            let (dash_start_cap, dash_end_cap) = (
+                dash_start_cap.unwrap_or(LineCap::Butt),
+                dash_end_cap.unwrap_or(LineCap::Butt),
+            );
+            shape = &shape | &Self::lines(dash_points, width, dash_start_cap, dash_end_cap);
+        }
+        shape
+    }
+
+    pub fn dashed_bezier(
+        points: Vec<Pos2>,
+        width: f64,
+        segments: usize,
+        dash: f64,
+        gap: f64,
+        start_cap: LineCap<[f64; 2], f64>,
+        end_cap: LineCap<[f64; 2], f64>,
+    ) -> Self {
+        Self::dashed_lines(
+            bezier_points(&points, segments),
+            width,
+            dash,
+            gap,
+            start_cap,
+            end_cap,
+        )
+    }
+
+    // Whether a position is inside the shape, counting crossings of the boundary so holes are left out
+    pub fn contains(&self, pos: Pos2) -> bool {
+        let (x, y) = (pos.x as f64, pos.y as f64);
+        self.shapes.iter().any(|polygon| {
+            let mut inside = false;
+            for contour in polygon {
+                for (i, a) in contour.iter().enumerate() {
+                    let b = contour[(i + 1) % contour.len()];
+                    if (a[1] > y) != (b[1] > y)
+                        && x < a[0] + (y - a[1]) / (b[1] - a[1]) * (b[0] - a[0])
+                    {
+                        inside = !inside;
+                    }
+                }
+            }
+            inside
+        })
+    }
+
+    // The boundary of this shape stroked with the given width, without the inside filled
+    pub fn outline(&self, width: f64) -> Self {
+        let style = StrokeStyle::new(width).line_join(LineJoin::Round(0.1));
+        Self {
+            shapes: self.shapes.stroke(style, true),
+        }
+    }
+}
+
+// Sample `segments + 1` evenly spaced points along the bezier curve with the given control points
+pub fn bezier_points(points: &[Pos2], segments: usize) -> Vec<Pos2> {
+    assert!(points.len() >= 2);
+    fn compute_bezier(points: &[Pos2], t: f32) -> Pos2 {
+        debug_assert!(!points.is_empty());
+        if points.len() == 1 {
+            return points[0];
+        }
+        let mut new_points = Vec::with_capacity(points.len() - 1);
+        for i in 0..points.len() - 1 {
+            let p = points[i] + t * (points[i + 1] - points[i]);
+            new_points.push(p);
+        }
+        compute_bezier(&new_points, t)
+    }
+    assert!(segments >= 1);
+    let mut interpolate_points = vec![];
+    for i in 0..=segments {
+        let t = i as f32 / segments as f32;
+        interpolate_points.push(compute_bezier(points, t));
+    }
+    interpolate_points
+}
+
+// Sample `segments + 1` evenly spaced points along a circular arc
+pub fn arc_points(
+    center: Pos2,
+    radius: f64,
+    start_angle: f64,
+    end_angle: f64,
+    segments: usize,
+) -> Vec<Pos2> {
+    assert!(segments >= 1);
+    (0..=segments)
+        .map(|i| {
+            let t = start_angle + (end_angle - start_angle) * (i as f64) / (segments as f64);
+            Pos2 {
+                x: ((center.x as f64) + radius * t.cos()) as f32,
+                y: ((center.y as f64) + radius * t.sin()) as f32,
             }
-            let mut new_points = Vec::with_capacity(points.len() - 1);
-            for i in 0..points.len() - 1 {
-                let p = points[i] + t * (points[i + 1] - points[i]);
-                new_points.push(p);
+        })
+        .collect()
+}
+
+// The center, radius, start angle and end angle of the circular arc from `start` to `end`
+// which bulges `sagitta` to the left of the straight line between them, or to the right if `sagitta` is negative
+// Suitable for passing to `Shape::arc` or `arc_points`
+pub fn arc_through(start: Pos2, end: Pos2, sagitta: f64) -> (Pos2, f64, f64, f64) {
+    let chord = end - start;
+    let c = chord.length() as f64;
+    assert!(c > 0.0 && sagitta != 0.0);
+    let side = Vec2 {
+        x: chord.y,
+        y: -chord.x,
+    }
+    .normalized()
+        * (sagitta.signum() as f32);
+    let sagitta = sagitta.abs();
+    let radius = (0.25 * c * c + sagitta * sagitta) / (2.0 * sagitta);
+    let center = start + 0.5 * chord - ((radius - sagitta) as f32) * side;
+    let angle = |p: Pos2| ((p.y - center.y) as f64).atan2((p.x - center.x) as f64);
+    let start_angle = angle(start);
+    // The short way round passes through the bulge exactly when the arc is at most a semicircle
+    let mut sweep = (angle(end) - start_angle + std::f64::consts::PI)
+        .rem_euclid(std::f64::consts::TAU)
+        - std::f64::consts::PI;
+    if 2.0 * sagitta > c {
+        sweep -= sweep.signum() * std::f64::consts::TAU;
+    }
+    (center, radius, start_angle, start_angle + sweep)
+}
+
+// Draw text centred along a path, each character rotated to follow it
+// The text is shifted by `offset` to the left of the path, after orienting the path to read left to right
+pub fn text_along_path(
+    painter: &Painter,
+    path: &[Pos2],
+    offset: f32,
+    text: &str,
+    font: FontId,
+    colour: Color32,
+) {
+    if path.len() < 2 {
+        return;
+    }
+    let mut path = path.to_vec();
+    if path.last().unwrap().x < path[0].x {
+        path.reverse();
+    }
+
+    // The distance along the path to each point
+    let mut distances = vec![0.0];
+    for pair in path.windows(2) {
+        distances.push(distances.last().unwrap() + (pair[1] - pair[0]).length());
+    }
+    let length = *distances.last().unwrap();
+
+    // The point and unit tangent at a given distance along the path
+    let position = |s: f32| -> (Pos2, Vec2) {
+        let i = distances
+            .windows(2)
+            .position(|d| s <= d[1])
+            .unwrap_or(distances.len() - 2);
+        let tangent = (path[i + 1] - path[i]).normalized();
+        (path[i] + tangent * (s - distances[i]), tangent)
+    };
+
+    let galleys = text
+        .chars()
+        .map(|c| painter.layout_no_wrap(c.to_string(), font.clone(), colour))
+        .collect::<Vec<_>>();
+    let width = galleys.iter().map(|galley| galley.size().x).sum::<f32>();
+
+    let mut s = f32::max(0.0, 0.5 * (length - width));
+    for galley in galleys {
+        let (point, tangent) = position(s + 0.5 * galley.size().x);
+        let normal = Vec2 {
+            x: tangent.y,
+            y: -tangent.x,
+        };
+        let centre = point + offset * normal;
+        painter.add(
+            TextShape::new(centre - 0.5 * galley.size(), galley.clone(), colour)
+                .with_angle_and_anchor(tangent.angle(), Align2::CENTER_CENTER),
+        );
+        s += galley.size().x;
+    }
+}
+
+// The point a fraction `t` of the way along a path, measured by length
+pub fn point_along_path(path: &[Pos2], t: f32) -> Pos2 {
+    let length = path
+        .windows(2)
+        .map(|pair| (pair[1] - pair[0]).length())
+        .sum::<f32>();
+    let mut s = t.clamp(0.0, 1.0) * length;
+    for pair in path.windows(2) {
+        let segment = (pair[1] - pair[0]).length();
+        if s <= segment && segment > 0.0 {
+            return pair[0] + (s / segment) * (pair[1] - pair[0]);
+        }
+        s -= segment;
+    }
+    *path.last().unwrap()
+}
+
+// The distance from a position to the nearest point of a path
+pub fn distance_to_path(path: &[Pos2], pos: Pos2) -> f32 {
+    path.windows(2)
+        .map(|pair| {
+            let segment = pair[1] - pair[0];
+            let t = if segment.length_sq() > 0.0 {
+                ((pos - pair[0]).dot(segment) / segment.length_sq()).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            (pair[0] + t * segment).distance(pos)
+        })
+        .fold(f32::INFINITY, f32::min)
+}
+
+// Split a path into pieces of length `dash` separated by gaps of length `gap`
+// Also whether the path ends part way through a dash rather than in a gap
+fn dash_path(points: &[Pos2], dash: f64, gap: f64) -> (Vec<Vec<Pos2>>, bool) {
+    assert!(dash > 0.0 && gap >= 0.0);
+    let mut dashes = vec![];
+    let mut current = points.first().into_iter().copied().collect::<Vec<_>>();
+    let mut drawing = true;
+    // How far to go before switching between a dash and a gap
+    let mut remaining = dash;
+    for pair in points.windows(2) {
+        let (mut start, end) = (pair[0], pair[1]);
+        while (end - start).length() as f64 > remaining {
+            start += (end - start).normalized() * remaining as f32;
+            // Either the end of the current dash or the start of the next one
+            current.push(start);
+            if drawing {
+                dashes.push(std::mem::take(&mut current));
+                remaining = gap;
+            } else {
+                remaining = dash;
             }
-            compute_bezier(&new_points, t)
+            drawing = !drawing;
         }
-        assert!(segments >= 1);
-        let mut interpolate_points = vec![];
-        for i in 0..=segments {
-            let t = i as f32 / segments as f32;
-            interpolate_points.push(compute_bezier(&points, t));
+        remaining -= (end - start).length() as f64;
+        if drawing {
+            current.push(end);
         }
-        Self::lines(interpolate_points, width, start_cap, end_cap)
     }
+    let ends_in_dash = drawing && current.len() >= 2;
+    if ends_in_dash {
+        dashes.push(current);
+    }
+    (dashes, ends_in_dash)
 }
 
 impl BitOr<&Shape> for &Shape {
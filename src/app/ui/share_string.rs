@@ -0,0 +1,223 @@
+use crate::app::AppState;
+use crate::app::logic::finite_field_4::Point as F4Point;
+use crate::app::logic::miracle_octad_generator::{Point, Vector};
+use crate::app::logic::permutation::Permutation;
+use crate::app::logic::traits::{Enumerated, Labelled};
+use crate::app::ui::session::{PointToggleSession, Session, SextetLabellingSession};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use eframe::egui::{Context, TextEdit, Ui, Window};
+
+/*
+A session packed into bytes and written in URL safe base64, short enough to paste into a chat or a link
+
+A point toggle screen is
+ - 3 bytes for the selection, one bit per point
+ - 24 bytes for the images of the points under the permutation
+ - 1 byte for the number of rows of an imported generator matrix, then 3 bytes for each row
+A sextet labelling is the point toggle screen it goes back to, then
+ - 3 bytes for a foursome of the sextet
+ - 6 bytes for the ordering of the foursomes
+ - 12 bytes for the labels, half a byte per point, 0 if unlabelled and otherwise 1 more than the label
+The whole is preceded by a byte, 0 for a point toggle screen and 1 for a sextet labelling
+ */
+
+fn push_vector(bytes: &mut Vec<u8>, vector: &Vector) {
+    let bits = vector
+        .points()
+        .fold(0u32, |bits, p| bits | (1 << p.point_to_usize()));
+    bytes.extend_from_slice(&bits.to_le_bytes()[..3]);
+}
+
+fn push_point_toggle(bytes: &mut Vec<u8>, session: &PointToggleSession) {
+    push_vector(bytes, &session.selected_points);
+    for p in Point::points() {
+        bytes.push(session.selected_permutation.apply(&p).point_to_usize() as u8);
+    }
+    let rows = session.code.as_deref().unwrap_or_default();
+    bytes.push(rows.len() as u8);
+    for row in rows {
+        push_vector(bytes, row);
+    }
+}
+
+pub fn encode(session: &Session) -> String {
+    let mut bytes = vec![];
+    match session {
+        Session::PointToggle(session) => {
+            bytes.push(0);
+            push_point_toggle(&mut bytes, session);
+        }
+        Session::SextetLabelling(session) => {
+            bytes.push(1);
+            push_point_toggle(&mut bytes, &session.prev_state);
+            push_vector(&mut bytes, &session.foursome);
+            bytes.extend(session.ordering.iter().map(|i| *i as u8));
+            let labels = session
+                .labelling
+                .iter()
+                .map(|(_, label)| label.map_or(0, |x| 1 + x.point_to_usize() as u8))
+                .collect::<Vec<_>>();
+            bytes.extend(labels.chunks(2).map(|pair| pair[0] | (pair[1] << 4)));
+        }
+    }
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl Reader<'_> {
+    fn byte(&mut self) -> Result<u8, String> {
+        let (first, rest) = self
+            .bytes
+            .split_first()
+            .ok_or("The share string is too short".to_string())?;
+        self.bytes = rest;
+        Ok(*first)
+    }
+
+    fn point(&mut self) -> Result<Point, String> {
+        Point::usize_to_point(self.byte()? as usize)
+            .map_err(|()| "The share string has a point out of range".to_string())
+    }
+
+    fn vector(&mut self) -> Result<Vector, String> {
+        let bits = u32::from_le_bytes([self.byte()?, self.byte()?, self.byte()?, 0]);
+        Ok(Vector::from_fn(|p| bits & (1 << p.point_to_usize()) != 0))
+    }
+
+    fn point_toggle(&mut self) -> Result<PointToggleSession, String> {
+        let selected_points = self.vector()?;
+        let mut perm = vec![];
+        for p in Point::points() {
+            let image = self.point()?;
+            if image != p {
+                perm.push((p, image));
+            }
+        }
+        let selected_permutation = Permutation::try_from(perm)?;
+        let rows = self.byte()?;
+        let code = if rows == 0 {
+            None
+        } else {
+            Some((0..rows).map(|_| self.vector()).collect::<Result<_, _>>()?)
+        };
+        Ok(PointToggleSession {
+            selected_points,
+            selected_permutation,
+            code,
+        })
+    }
+
+    fn label(nibble: u8) -> Result<Option<F4Point>, String> {
+        match nibble {
+            0 => Ok(None),
+            _ => F4Point::usize_to_point(nibble as usize - 1)
+                .map(Some)
+                .map_err(|()| "The share string has a label out of range".to_string()),
+        }
+    }
+
+    fn labelling(&mut self) -> Result<Labelled<Point, Option<F4Point>>, String> {
+        let mut labels = vec![];
+        for _ in 0..12 {
+            let byte = self.byte()?;
+            labels.push(Self::label(byte & 15)?);
+            labels.push(Self::label(byte >> 4)?);
+        }
+        Ok(Labelled::from_fn(|p: Point| labels[p.point_to_usize()]))
+    }
+}
+
+pub fn decode(text: &str) -> Result<Session, String> {
+    let bytes = URL_SAFE_NO_PAD
+        .decode(text.trim())
+        .map_err(|_| "Not a share string".to_string())?;
+    let mut reader = Reader { bytes: &bytes };
+    let session = match reader.byte()? {
+        0 => Session::PointToggle(reader.point_toggle()?),
+        1 => {
+            let prev_state = reader.point_toggle()?;
+            let foursome = reader.vector()?;
+            let ordering = (0..6)
+                .map(|_| reader.byte().map(usize::from))
+                .collect::<Result<_, _>>()?;
+            let labelling = reader.labelling()?;
+            Session::SextetLabelling(SextetLabellingSession {
+                prev_state,
+                foursome,
+                ordering,
+                labelling,
+            })
+        }
+        _ => return Err("Not a share string".to_string()),
+    };
+    if !reader.bytes.is_empty() {
+        return Err("The share string is too long".to_string());
+    }
+    Ok(session)
+}
+
+// The "Copy share string" and "Paste share string" commands
+#[derive(Default)]
+pub struct ShareStringWindow {
+    open: bool,
+    text: String,
+    error: Option<String>,
+}
+
+impl ShareStringWindow {
+    pub fn menu(&mut self, ui: &mut Ui, state: &dyn AppState) {
+        let session = state.session();
+        if ui
+            .add_enabled(
+                session.is_some(),
+                eframe::egui::Button::new("Copy share string"),
+            )
+            .clicked()
+        {
+            ui.ctx().copy_text(encode(&session.unwrap()));
+        }
+        if ui.button("Paste share string").clicked() {
+            self.open = true;
+        }
+    }
+
+    pub fn show(&mut self, ctx: &Context) -> Option<Box<dyn AppState>> {
+        let mut open = self.open;
+        let new_state = Window::new("Paste Share String")
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.add(
+                    TextEdit::singleline(&mut self.text)
+                        .code_editor()
+                        .hint_text("Share string"),
+                );
+                if let Some(error) = &self.error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+                if ui.button("Open").clicked() {
+                    match decode(&self.text).and_then(|session| {
+                        session
+                            .restore()
+                            .ok_or("The shared screen is not valid".to_string())
+                    }) {
+                        Ok(state) => {
+                            self.error = None;
+                            self.open = false;
+                            self.text.clear();
+                            return Some(state);
+                        }
+                        Err(error) => {
+                            self.error = Some(error);
+                        }
+                    }
+                }
+                None
+            })
+            .and_then(|response| response.inner.flatten());
+        self.open &= open;
+        new_state
+    }
+}
@@ -0,0 +1,51 @@
+use crate::app::logic::miracle_octad_generator::{Point, Vector};
+use crate::app::logic::permutation::Permutation;
+use crate::app::logic::traits::Enumerated;
+use crate::app::ui::grid::GridShower;
+use crate::app::ui::layout::MogLayout;
+use crate::app::ui::mog_selection::MogSelection;
+use eframe::egui::{Color32, Rect, Stroke, Ui, UiBuilder};
+
+// The halves of the central panel for the grid before and after applying a permutation
+pub fn halves(ui: &Ui) -> (Rect, Rect) {
+    ui.available_rect_before_wrap()
+        .split_left_right_at_fraction(0.5)
+}
+
+// Show the image of the selection in `rect`, with a line from each selected point on the grid before to where it goes
+// With nothing selected there is a line from every point moved, so a dense permutation can be followed point by point
+pub fn show_after(
+    ui: &mut Ui,
+    rect: Rect,
+    before: &GridShower,
+    layout: MogLayout,
+    selected: &Vector,
+    permutation: &Permutation<Point>,
+    colour: Color32,
+) {
+    let image = selected.permute(permutation);
+    let mut after_ui = ui.new_child(UiBuilder::new().max_rect(rect));
+    let after = MogSelection::new(&image)
+        .hover_highlight(false)
+        .show(&mut after_ui);
+
+    let stroke = Stroke::new(1.5, colour * Color32::from_white_alpha(160));
+    let painter = ui.painter();
+    for p in Point::points() {
+        let q = *permutation.apply(&p);
+        let shown = if selected.weight() == 0 {
+            p != q
+        } else {
+            selected.contains_point(p)
+        };
+        if shown {
+            painter.line_segment(
+                [
+                    before.cell_to_pos(layout.point_to_cell(p)),
+                    after.grid.cell_to_pos(layout.point_to_cell(q)),
+                ],
+                stroke,
+            );
+        }
+    }
+}
@@ -0,0 +1,266 @@
+use eframe::egui::{Color32, Context, Id, Pos2, Rect, Stroke, StrokeKind, Vec2};
+use eframe::epaint::{ColorMode, Mesh, Shape, TextShape};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+fn request_id() -> Id {
+    Id::new("svg_export_requested")
+}
+
+// Copy the view to the clipboard once this frame has been painted
+pub fn request_copy(ctx: &Context) {
+    ctx.data_mut(|data| data.insert_temp(request_id(), true));
+}
+
+// Call after every screen has been painted
+pub fn end_frame(ctx: &Context) {
+    if !ctx.data_mut(|data| data.remove_temp::<bool>(request_id()).unwrap_or(false)) {
+        return;
+    }
+    let Some((view, shapes)) = super::view_capture::view_shapes(ctx) else {
+        return;
+    };
+    let svg = to_svg(shapes.iter().map(|clipped| &clipped.shape), view);
+    ctx.copy_text(svg);
+}
+
+// An SVG image of the part of the shapes within `view`, in the same units as egui points
+pub fn to_svg<'a>(shapes: impl Iterator<Item = &'a Shape>, view: Rect) -> String {
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\" width=\"{}\" height=\"{}\">\n",
+        view.min.x,
+        view.min.y,
+        view.width(),
+        view.height(),
+        view.width(),
+        view.height()
+    );
+    for shape in shapes {
+        write_shape(&mut svg, shape);
+    }
+    svg += "</svg>\n";
+    svg
+}
+
+// e.g. `fill="#ff0000" fill-opacity="0.5"`, or `fill="none"` for transparent colours
+fn paint(attribute: &str, colour: Color32) -> String {
+    let [r, g, b, a] = colour.to_srgba_unmultiplied();
+    if a == 0 {
+        format!("{attribute}=\"none\"")
+    } else if a == 255 {
+        format!("{attribute}=\"#{r:02x}{g:02x}{b:02x}\"")
+    } else {
+        format!(
+            "{attribute}=\"#{r:02x}{g:02x}{b:02x}\" {attribute}-opacity=\"{:.3}\"",
+            a as f32 / 255.0
+        )
+    }
+}
+
+fn stroke(stroke: Stroke) -> String {
+    if stroke.is_empty() {
+        "stroke=\"none\"".to_string()
+    } else {
+        format!(
+            "{} stroke-width=\"{}\"",
+            paint("stroke", stroke.color),
+            stroke.width
+        )
+    }
+}
+
+fn points(points: &[Pos2]) -> String {
+    points
+        .iter()
+        .map(|p| format!("{},{}", p.x, p.y))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn write_shape(svg: &mut String, shape: &Shape) {
+    match shape {
+        Shape::Vec(shapes) => {
+            for shape in shapes {
+                write_shape(svg, shape);
+            }
+        }
+        Shape::Circle(circle) => {
+            let _ = writeln!(
+                svg,
+                "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" {} {}/>",
+                circle.center.x,
+                circle.center.y,
+                circle.radius,
+                paint("fill", circle.fill),
+                stroke(circle.stroke)
+            );
+        }
+        Shape::LineSegment {
+            points: [a, b],
+            stroke: s,
+        } => {
+            let _ = writeln!(
+                svg,
+                "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" {}/>",
+                a.x,
+                a.y,
+                b.x,
+                b.y,
+                stroke(*s)
+            );
+        }
+        Shape::Path(path) => {
+            let s = match path.stroke.color {
+                ColorMode::Solid(colour) => Stroke::new(path.stroke.width, colour),
+                ColorMode::UV(_) => Stroke::NONE,
+            };
+            let _ = writeln!(
+                svg,
+                "<{} points=\"{}\" {} {}/>",
+                if path.closed { "polygon" } else { "polyline" },
+                points(&path.points),
+                paint("fill", path.fill),
+                stroke(s)
+            );
+        }
+        Shape::Rect(rect) => {
+            // Move the outline to where egui draws it
+            let rect_for_stroke = match rect.stroke_kind {
+                StrokeKind::Inside => rect.rect.shrink(0.5 * rect.stroke.width),
+                StrokeKind::Middle => rect.rect,
+                StrokeKind::Outside => rect.rect.expand(0.5 * rect.stroke.width),
+            };
+            let radius = rect.corner_radius.average();
+            if rect.fill != Color32::TRANSPARENT {
+                let _ = writeln!(
+                    svg,
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"{}\" {}/>",
+                    rect.rect.min.x,
+                    rect.rect.min.y,
+                    rect.rect.width(),
+                    rect.rect.height(),
+                    radius,
+                    paint("fill", rect.fill)
+                );
+            }
+            if !rect.stroke.is_empty() {
+                let _ = writeln!(
+                    svg,
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"{}\" fill=\"none\" {}/>",
+                    rect_for_stroke.min.x,
+                    rect_for_stroke.min.y,
+                    rect_for_stroke.width(),
+                    rect_for_stroke.height(),
+                    radius,
+                    stroke(rect.stroke)
+                );
+            }
+        }
+        Shape::Text(text) => write_text(svg, text),
+        Shape::Mesh(mesh) => {
+            // Meshes are single coloured regions, so draw their outlines rather than every triangle
+            let Some(vertex) = mesh.vertices.first() else {
+                return;
+            };
+            let d = mesh_outline(mesh)
+                .into_iter()
+                .map(|outline| format!("M {} Z", points(&outline)))
+                .collect::<Vec<_>>()
+                .join(" ");
+            let _ = writeln!(
+                svg,
+                "<path d=\"{d}\" fill-rule=\"evenodd\" {}/>",
+                paint("fill", vertex.color)
+            );
+        }
+        // Nothing else is drawn on the MOG
+        _ => {}
+    }
+}
+
+fn write_text(svg: &mut String, text: &TextShape) {
+    let Some(section) = text.galley.job.sections.first() else {
+        return;
+    };
+    let mut colour = text.override_text_color.unwrap_or(section.format.color);
+    if colour == Color32::PLACEHOLDER {
+        colour = text.fallback_color;
+    }
+    // egui rotates text about `pos`, the top left corner of the galley
+    let rotate = |v: Vec2| Vec2 {
+        x: v.x * text.angle.cos() - v.y * text.angle.sin(),
+        y: v.x * text.angle.sin() + v.y * text.angle.cos(),
+    };
+    let centre = text.pos + rotate(text.galley.rect.center().to_vec2());
+    let _ = writeln!(
+        svg,
+        "<text x=\"{}\" y=\"{}\" transform=\"rotate({} {} {})\" font-family=\"CMU Serif, serif\" font-size=\"{}\" text-anchor=\"middle\" dominant-baseline=\"central\" {}>{}</text>",
+        centre.x,
+        centre.y,
+        text.angle.to_degrees(),
+        centre.x,
+        centre.y,
+        section.format.font_id.size,
+        paint("fill", colour),
+        escape(text.galley.text())
+    );
+}
+
+// The closed loops of edges belonging to only one triangle of a mesh
+fn mesh_outline(mesh: &Mesh) -> Vec<Vec<Pos2>> {
+    let mut edge_counts = HashMap::<(u32, u32), usize>::new();
+    for triangle in mesh.indices.chunks_exact(3) {
+        for (a, b) in [
+            (triangle[0], triangle[1]),
+            (triangle[1], triangle[2]),
+            (triangle[2], triangle[0]),
+        ] {
+            *edge_counts.entry((a.min(b), a.max(b))).or_default() += 1;
+        }
+    }
+    let mut neighbours = HashMap::<u32, Vec<u32>>::new();
+    let mut edges = edge_counts
+        .into_iter()
+        .filter(|(_, count)| *count == 1)
+        .map(|(edge, _)| edge)
+        .collect::<Vec<_>>();
+    edges.sort_unstable();
+    for (a, b) in &edges {
+        neighbours.entry(*a).or_default().push(*b);
+        neighbours.entry(*b).or_default().push(*a);
+    }
+
+    // Walk around each loop, using up its edges
+    let mut outlines = vec![];
+    for (a, b) in edges {
+        if !neighbours[&a].contains(&b) {
+            continue;
+        }
+        let mut outline = vec![a];
+        let mut current = b;
+        neighbours.get_mut(&a).unwrap().retain(|v| *v != b);
+        neighbours.get_mut(&b).unwrap().retain(|v| *v != a);
+        while current != a {
+            outline.push(current);
+            let Some(next) = neighbours[&current].first().copied() else {
+                break;
+            };
+            neighbours.get_mut(&current).unwrap().retain(|v| *v != next);
+            neighbours.get_mut(&next).unwrap().retain(|v| *v != current);
+            current = next;
+        }
+        outlines.push(
+            outline
+                .into_iter()
+                .map(|i| mesh.vertices[i as usize].pos)
+                .collect(),
+        );
+    }
+    outlines
+}
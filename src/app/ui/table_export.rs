@@ -0,0 +1,104 @@
+use crate::app::logic::linear_code::Code;
+use crate::app::logic::miracle_octad_generator::Vector;
+use eframe::egui::{Context, Ui};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Text,
+}
+
+impl ExportFormat {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "CSV",
+            ExportFormat::Text => "Text",
+        }
+    }
+}
+
+// A table of vectors for use in other software
+// Each row has the index of the vector in the list, its points in the MOG numbering and its bitstring
+pub struct VectorTable {
+    vectors: Vec<(usize, Vector)>,
+}
+
+impl VectorTable {
+    // Rows are numbered from 1 in the order given
+    pub fn new(vectors: impl IntoIterator<Item = Vector>) -> Self {
+        Self {
+            vectors: vectors
+                .into_iter()
+                .enumerate()
+                .map(|(i, v)| (i + 1, v))
+                .collect(),
+        }
+    }
+
+    // Rows numbered by the given indices, e.g. positions in a longer list which has been filtered
+    pub fn with_indices(vectors: impl IntoIterator<Item = (usize, Vector)>) -> Self {
+        Self {
+            vectors: vectors.into_iter().collect(),
+        }
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("index,points,bitstring\n");
+        for (i, vector) in &self.vectors {
+            out += &format!(
+                "{},{},{}\n",
+                i,
+                vector.to_point_list(),
+                vector.to_bitstring()
+            );
+        }
+        out
+    }
+
+    // One vector per line as a list of points, readable by `Vector::parse`
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        for (_, vector) in &self.vectors {
+            out += &vector.to_point_list();
+            out += "\n";
+        }
+        out
+    }
+
+    pub fn export(&self, format: ExportFormat) -> String {
+        match format {
+            ExportFormat::Csv => self.to_csv(),
+            ExportFormat::Text => self.to_text(),
+        }
+    }
+
+    pub fn copy_to_clipboard(&self, ctx: &Context, format: ExportFormat) {
+        ctx.copy_text(self.export(format));
+    }
+}
+
+// A row of buttons, one per format, copying the table produced by `table` to the clipboard
+pub fn export_buttons(ui: &mut Ui, label: &str, table: impl Fn() -> VectorTable) {
+    ui.horizontal(|ui| {
+        ui.label(label);
+        for format in [ExportFormat::Csv, ExportFormat::Text] {
+            if ui
+                .button(format.name())
+                .on_hover_text("Copy to the clipboard")
+                .clicked()
+            {
+                table().copy_to_clipboard(ui.ctx(), format);
+            }
+        }
+    });
+}
+
+// Menu entries for exporting whole tables of the Golay code
+// The octad and codeword browsers export a page or a filtered list instead
+pub fn export_menu(ui: &mut Ui) {
+    let mog = super::mog::mog();
+    export_buttons(ui, "All octads", || VectorTable::new(mog.octads()));
+    export_buttons(ui, "All codewords", || {
+        VectorTable::new(mog.codewords().to_vec())
+    });
+}
@@ -0,0 +1,291 @@
+use crate::app::logic::finite_field_4::Point as F4Point;
+use crate::app::logic::hexacode::{Pair, Side};
+use crate::app::logic::miracle_octad_generator::{
+    BrickCoordinate, BrickLinearMap, BrickTrioElement, Point, Vector,
+};
+use crate::app::logic::permutation::Permutation;
+use crate::app::logic::traits::{Enumerated, Labelled};
+use crate::app::ui::geometry::Geometry;
+use crate::app::ui::layout::MogLayout;
+use crate::app::ui::mog::{f4_text, sextet_idx_to_colour};
+use crate::app::ui::mog_permutation_shapes::MogPermutationShapeCache;
+use crate::app::ui::mog_selection::{MogSelection, MogSelectionResponse};
+use crate::app::ui::palette::Palette;
+use crate::app::ui::panels::side_panel;
+use crate::app::ui::session::{PointToggleSession, Session};
+use crate::app::{AppState, Navigate};
+use eframe::{
+    Frame,
+    egui::{CentralPanel, Color32, Context, RichText},
+};
+
+// Build elements of the stabilizer 2^6:(S3 x L3(2)) of a trio of octads
+// Dragging one octad onto another swaps them, and the side panel moves the points within the octads
+// Any trio is worked with through an automorphism carrying the bricks onto it, so each octad gets the coordinates of a brick
+#[derive(Clone)]
+pub struct State {
+    parent: PointToggleSession,
+    trio: [Vector; 3],
+    // An automorphism taking brick i to octad i of the trio
+    frame: Permutation<Point>,
+    // The element as it acts on the bricks, before moving it onto the trio
+    element: BrickTrioElement,
+    // The brick the current drag started in
+    drag_start: Option<Pair>,
+    permutation_shapes: MogPermutationShapeCache,
+}
+
+impl State {
+    pub fn new(parent: PointToggleSession, trio: [Vector; 3]) -> Result<Self, ()> {
+        let frame = super::mog::mog().trio_map(&super::mog::mog().bricks(), &trio)?;
+        Ok(Self {
+            parent,
+            trio,
+            frame,
+            element: BrickTrioElement::decompose(&Permutation::identity()).unwrap(),
+            drag_start: None,
+            permutation_shapes: MogPermutationShapeCache::default(),
+        })
+    }
+
+    fn is_bricks(&self) -> bool {
+        self.trio == super::mog::mog().bricks()
+    }
+
+    fn octad_name(&self, b: Pair) -> String {
+        let kind = if self.is_bricks() { "Brick" } else { "Octad" };
+        format!("{kind} {}", b.point_to_usize() + 1)
+    }
+
+    // The brick whose points the frame takes onto the octad of the trio containing the point
+    fn brick_of(&self, p: Point) -> Pair {
+        self.frame.apply_inverse(&p).brick()
+    }
+
+    // The element as a permutation of the points of the trio
+    fn permutation(&self) -> Permutation<Point> {
+        &(&self.frame.clone().inverse() * &self.element.to_permutation()) * &self.frame
+    }
+
+    // Follow the element by another element of the trio stabilizer
+    fn then(&mut self, other: &Permutation<Point>) {
+        self.element =
+            BrickTrioElement::decompose(&(&self.element.to_permutation() * other)).unwrap();
+    }
+
+    // The automorphism permuting the bricks and acting on every brick by a linear map
+    // The translation forced on the last brick is there to make it an automorphism
+    fn then_linear(&mut self, bricks: &Permutation<Pair>, linear: &BrickLinearMap) {
+        let generator = super::mog::mog()
+            .brick_trio_element(
+                bricks,
+                linear,
+                BrickCoordinate::zero(),
+                BrickCoordinate::zero(),
+            )
+            .unwrap();
+        self.then(&generator.to_permutation());
+    }
+
+    // Fix one brick and translate the other two by the same vector, an element of the 2^6
+    fn then_translation(&mut self, fixed: Pair, translation: BrickCoordinate) {
+        let generator = BrickTrioElement {
+            bricks: Permutation::identity(),
+            linear: BrickLinearMap::identity(),
+            translations: Labelled::from_fn(|b| {
+                if b == fixed {
+                    BrickCoordinate::zero()
+                } else {
+                    translation
+                }
+            }),
+        };
+        self.then(&generator.to_permutation());
+    }
+}
+
+fn coordinate_text(x: BrickCoordinate) -> String {
+    let side = match x.side {
+        Side::Left => "L",
+        Side::Right => "R",
+    };
+    format!("({side}, {})", f4_text(x.row))
+}
+
+impl AppState for State {
+    fn update(&mut self, ctx: &Context, _frame: &mut Frame) -> Option<Navigate> {
+        // The bricks are always shown separated, whatever the chosen layout
+        if self.is_bricks() {
+            MogLayout::Bricks.set(ctx);
+        }
+
+        if let Some(navigate) = side_panel(ctx, |ui| -> Option<Navigate> {
+            // Back
+            if ui.button("Back").clicked() {
+                return Some(Navigate::Back);
+            }
+
+            ui.heading("Trio Stabilizer");
+            if self.is_bricks() {
+                ui.label("The three bricks of two columns are a trio of octads");
+            } else {
+                for (b, octad) in Pair::points().zip(&self.trio) {
+                    ui.label(format!("{}: {}", self.octad_name(b), octad.to_point_list()));
+                }
+            }
+            ui.label(
+                "Its stabilizer 2^6:(S3 x L3(2)) permutes the octads and moves each one affinely",
+            );
+            ui.label("Drag an octad onto another octad to swap them");
+
+            ui.heading("Octads");
+            for b in Pair::points() {
+                ui.colored_label(
+                    sextet_idx_to_colour(ctx, self.element.bricks.apply(&b).point_to_usize()),
+                    format!(
+                        "{} → {}",
+                        self.octad_name(b),
+                        self.octad_name(*self.element.bricks.apply(&b))
+                    ),
+                );
+            }
+
+            // Every octad is moved by the same linear map, shown by where it sends the basis
+            ui.heading("Linear Part");
+            ui.label("A point at (side, row) of a brick has coordinates in F2 x F4");
+            if !self.is_bricks() {
+                ui.label(
+                    "Each point of the trio has the coordinates of the brick point carried onto it",
+                );
+            }
+            for (x, image) in BrickCoordinate::basis()
+                .into_iter()
+                .zip(self.element.linear.images())
+            {
+                ui.label(
+                    RichText::new(format!(
+                        "{} → {}",
+                        coordinate_text(x),
+                        coordinate_text(image)
+                    ))
+                    .monospace(),
+                );
+            }
+            ui.horizontal(|ui| {
+                if ui.button("Rows × ω").clicked() {
+                    self.then_linear(
+                        &Permutation::identity(),
+                        &BrickLinearMap::row_scaling(F4Point::Alpha),
+                    );
+                }
+                if ui.button("Side ↔ Row 1").clicked() {
+                    self.then_linear(
+                        &Permutation::identity(),
+                        &BrickLinearMap::side_row_exchange(),
+                    );
+                }
+            });
+
+            // The translation of each octad, indexed by where it ends up
+            ui.heading("Translations");
+            for b in Pair::points() {
+                ui.label(format!(
+                    "Onto {}: + {}",
+                    self.octad_name(b),
+                    coordinate_text(*self.element.translations.get(b))
+                ));
+            }
+            ui.label("Fix an octad and translate the other two");
+            for b in Pair::points() {
+                let name = self.octad_name(b);
+                ui.horizontal(|ui| {
+                    ui.label(format!("Fix {name}:"));
+                    for t in BrickCoordinate::basis() {
+                        if ui.button(format!("+ {}", coordinate_text(t))).clicked() {
+                            self.then_translation(b, t);
+                        }
+                    }
+                });
+            }
+
+            let permutation = self.permutation();
+            ui.heading("Automorphism");
+            ui.label(format!("In {}", self.element.subgroup()));
+            ui.label(permutation.to_cycle_string());
+            ui.label(format!("Order {}", permutation.order()));
+            if permutation != Permutation::identity() {
+                if ui.button("Reset").clicked() {
+                    self.element = BrickTrioElement::decompose(&Permutation::identity()).unwrap();
+                }
+                if ui.button("Select").clicked() {
+                    return Some(Navigate::PickPermutation(permutation));
+                }
+            }
+
+            None
+        }) {
+            return Some(navigate);
+        }
+
+        // Colour each point by the octad it is sent to
+        let mut highlights = Labelled::<Point, Option<Color32>>::new_constant(None);
+        for p in Point::points() {
+            let d = *self.element.bricks.apply(&self.brick_of(p));
+            highlights.set(p, Some(sextet_idx_to_colour(ctx, d.point_to_usize())));
+        }
+
+        let no_points = Vector::zero();
+        CentralPanel::default().show(ctx, |ui| {
+            let MogSelectionResponse {
+                response,
+                painter,
+                grid,
+                layout,
+                hovered,
+                ..
+            } = MogSelection::new(&no_points)
+                .highlights(&highlights)
+                .show(ui);
+
+            if response.drag_started() {
+                self.drag_start = hovered.map(|p| self.brick_of(p));
+            }
+            if response.drag_stopped()
+                && let Some(start) = self.drag_start.take()
+                && let Some(end) = hovered.map(|p| self.brick_of(p))
+                && start != end
+            {
+                self.then_linear(
+                    &Permutation::new_swap(&start, &end),
+                    &BrickLinearMap::identity(),
+                );
+            }
+
+            self.permutation_shapes.set_geometry(Geometry::get(ctx));
+            self.permutation_shapes.set_permutation(
+                Some(
+                    self.permutation()
+                        .map_injective_unchecked(|p| layout.point_to_cell(p)),
+                ),
+                grid,
+            );
+            for (_, shape) in self.permutation_shapes.shapes() {
+                painter.add(shape.to_egui_mesh(Palette::get(ctx).good()));
+            }
+        });
+
+        None
+    }
+
+    fn title(&self) -> String {
+        "Trio Stabilizer".to_string()
+    }
+
+    fn parent(&self) -> Option<Box<dyn AppState>> {
+        Some(Box::new(self.parent.clone().restore()))
+    }
+
+    fn session(&self) -> Option<Session> {
+        Some(Session::PointToggle(self.parent.clone()))
+    }
+}
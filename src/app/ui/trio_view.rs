@@ -0,0 +1,146 @@
+use crate::app::logic::miracle_octad_generator::{Point, Vector};
+use crate::app::logic::traits::Labelled;
+use crate::app::ui::mog::sextet_idx_to_colour;
+use crate::app::ui::mog_selection::MogSelection;
+use crate::app::ui::panels::side_panel;
+use crate::app::ui::selection_target::SelectionTarget;
+use crate::app::ui::session::{PointToggleSession, Session};
+use crate::app::{AppState, Navigate};
+use eframe::{
+    Frame,
+    egui::{CentralPanel, Color32, Context, ScrollArea},
+};
+
+// The 15 trios of disjoint octads containing a chosen octad, shown one at a time with each octad in its own colour
+#[derive(Clone)]
+pub struct State {
+    parent: PointToggleSession,
+    octad: Vector,
+    // The ways to split the complement of the octad into two more octads
+    trios: Vec<(Vector, Vector)>,
+    index: usize,
+}
+
+impl State {
+    pub fn new(parent: PointToggleSession, octad: Vector) -> Result<Self, ()> {
+        let trios = super::mog::mog().complete_trio(&octad)?;
+        Ok(Self {
+            parent,
+            octad,
+            trios,
+            index: 0,
+        })
+    }
+}
+
+impl AppState for State {
+    fn update(&mut self, ctx: &Context, _frame: &mut Frame) -> Option<Navigate> {
+        let mut preview_index = None;
+
+        if let Some(navigate) = side_panel(ctx, |ui| {
+            // Back
+            if ui.button("Back").clicked() {
+                return Some(Navigate::Back);
+            }
+
+            ui.heading("Trios");
+            if ui.button("Pick another octad").clicked() {
+                return Some(Navigate::Open(Box::new(
+                    self.parent
+                        .clone()
+                        .restore()
+                        .with_selected_points(Vector::zero())
+                        .with_target(Some(SelectionTarget::Octad)),
+                )));
+            }
+            ui.label("The ways to split the other 16 points into two octads");
+
+            let n = self.trios.len();
+            ui.horizontal(|ui| {
+                if ui.button("<").clicked() {
+                    self.index = (self.index + n - 1) % n;
+                }
+                ui.label(format!("Trio {} of {}", self.index + 1, n));
+                if ui.button(">").clicked() {
+                    self.index = (self.index + 1) % n;
+                }
+            });
+
+            let (a, b) = &self.trios[self.index];
+            if ui
+                .button("Trio stabilizer")
+                .on_hover_text("Build automorphisms moving these octads, with the same coordinates as the bricks")
+                .clicked()
+            {
+                let trio = [self.octad.clone(), a.clone(), b.clone()];
+                return Some(Navigate::Open(Box::new(
+                    super::trio_stabilizer::State::new(self.parent.clone(), trio).unwrap(),
+                )));
+            }
+            if ui
+                .button("Random trio stabilizer")
+                .on_hover_text("Select a random automorphism sending these octads to each other")
+                .clicked()
+            {
+                let trio = [self.octad.clone(), a.clone(), b.clone()];
+                let permutation = super::mog::mog()
+                    .random_trio_stabilizer(&trio, &mut super::point_toggle::rng())
+                    .unwrap();
+                return Some(Navigate::PickPermutation(permutation));
+            }
+
+            ScrollArea::vertical().show(ui, |ui| {
+                for (i, (a, b)) in self.trios.iter().enumerate() {
+                    let button = ui.selectable_label(
+                        i == self.index,
+                        format!("{}: {} | {}", i + 1, a.to_point_list(), b.to_point_list()),
+                    );
+                    // Preview the trio when hovering on its button
+                    if button.hovered() {
+                        preview_index = Some(i);
+                    }
+                    if button.clicked() {
+                        self.index = i;
+                    }
+                }
+            });
+            None
+        }) {
+            return Some(navigate);
+        }
+
+        let (a, b) = &self.trios[preview_index.unwrap_or(self.index)];
+        let mut coloured_points = Labelled::<Point, Option<Color32>>::new_constant(None);
+        for (i, octad) in [&self.octad, a, b].into_iter().enumerate() {
+            for p in octad.points() {
+                coloured_points.set(p, Some(sextet_idx_to_colour(ctx, i)));
+            }
+        }
+
+        CentralPanel::default().show(ctx, |ui| {
+            MogSelection::new(&self.octad)
+                .highlights(&coloured_points)
+                .show(ui);
+        });
+
+        None
+    }
+
+    fn title(&self) -> String {
+        "Trios".to_string()
+    }
+
+    fn parent(&self) -> Option<Box<dyn AppState>> {
+        Some(Box::new(self.parent.clone().restore()))
+    }
+
+    fn picked(&mut self, vector: Vector) {
+        if let Ok(state) = Self::new(self.parent.clone(), vector) {
+            *self = state;
+        }
+    }
+
+    fn session(&self) -> Option<Session> {
+        Some(Session::PointToggle(self.parent.clone()))
+    }
+}
@@ -0,0 +1,255 @@
+use crate::app::logic::finite_field_4::Point as F4Point;
+use crate::app::logic::miracle_octad_generator::{LinearCode, Point, Vector};
+use crate::app::logic::permutation::Permutation;
+use crate::app::logic::traits::Labelled;
+use crate::app::ui::keyboard;
+use crate::app::ui::memo::Memo;
+use crate::app::ui::mog::mog;
+use crate::app::ui::session::{Session, SextetLabellingSession};
+use eframe::egui::{
+    Align2, Button, Context, Id, Key, KeyboardShortcut, LayerId, Order, ProgressBar, Rect, Stroke,
+    StrokeKind, Window,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    SelectPentad,
+    CompleteOctad,
+    SelectFoursome,
+    OpenSextet,
+    Label,
+    Automorphism,
+}
+
+const STEPS: [Step; 6] = [
+    Step::SelectPentad,
+    Step::CompleteOctad,
+    Step::SelectFoursome,
+    Step::OpenSextet,
+    Step::Label,
+    Step::Automorphism,
+];
+
+impl Step {
+    fn title(self) -> &'static str {
+        match self {
+            Step::SelectPentad => "Select 5 points",
+            Step::CompleteOctad => "Complete an octad",
+            Step::SelectFoursome => "Select a foursome",
+            Step::OpenSextet => "Build its sextet",
+            Step::Label => "Label the sextet",
+            Step::Automorphism => "Generate an automorphism",
+        }
+    }
+
+    fn instructions(self) -> &'static str {
+        match self {
+            Step::SelectPentad => {
+                "Click points of the grid to select them. Any 5 points lie in exactly one octad, a codeword of weight 8."
+            }
+            Step::CompleteOctad => {
+                "Press Complete in the Complete Octad section to add the 3 points which finish the octad."
+            }
+            Step::SelectFoursome => {
+                "Press Clear, then select any 4 points. Every foursome belongs to a sextet of 6 foursomes, any two of which make an octad."
+            }
+            Step::OpenSextet => {
+                "Press Select in the Sextet section to open the labelling editor for it."
+            }
+            Step::Label => {
+                "Click points and choose labels from 0, 1, ω and ω̄ until the rest of the labelling is filled in. Suggest labelling does it for you."
+            }
+            Step::Automorphism => {
+                "Go back to the point toggle screen and press Random automorphism, or build a permutation from the labelling and press Select."
+            }
+        }
+    }
+
+    // The button to point at, by its command palette name and accelerator
+    fn target(self) -> Option<(&'static str, KeyboardShortcut)> {
+        match self {
+            Step::CompleteOctad => Some(("Complete octad: Complete", keyboard::key(Key::C))),
+            Step::OpenSextet => Some(("Complete sextet: Select", keyboard::key(Key::E))),
+            Step::Automorphism => Some(("Random automorphism", keyboard::key(Key::R))),
+            _ => None,
+        }
+    }
+}
+
+// The button the open step points at, and where it was drawn this frame if it was
+#[derive(Debug, Clone, Default)]
+struct Spotlight {
+    target: Option<(&'static str, KeyboardShortcut)>,
+    rect: Option<Rect>,
+}
+
+fn id() -> Id {
+    Id::new("tutorial_spotlight")
+}
+
+fn with_spotlight<T>(ctx: &Context, f: impl FnOnce(&mut Spotlight) -> T) -> T {
+    ctx.data_mut(|data| f(data.get_temp_mut_or_default::<Spotlight>(id())))
+}
+
+// Note where a button was drawn, in case the tutorial is pointing at it
+// Buttons made with `keyboard::button` call this themselves
+pub fn spot(ctx: &Context, name: &str, shortcut: KeyboardShortcut, rect: Rect) {
+    with_spotlight(ctx, |spotlight| {
+        if spotlight
+            .target
+            .is_some_and(|target| target == (name, shortcut))
+        {
+            spotlight.rect = Some(rect);
+        }
+    });
+}
+
+type LabellingKey = (Vector, Vec<usize>, Labelled<Point, Option<F4Point>>);
+
+// A walk through the main constructions, moving on as soon as an edit on the open screen does each step
+#[derive(Default)]
+pub struct Tutorial {
+    open: bool,
+    step: usize,
+    // Whether the open screen showed the step done last frame
+    // None after going back or skipping, so a step which is already done waits for the screen to change
+    was_done: Option<bool>,
+    // Whether the labelling on the open screen extends to the whole MOG, kept until it changes
+    labelled: Memo<LabellingKey, bool>,
+}
+
+impl Tutorial {
+    pub fn open(&mut self) {
+        self.open = true;
+        self.go_to(0);
+        self.was_done = Some(false);
+    }
+
+    fn go_to(&mut self, step: usize) {
+        self.step = step;
+        self.was_done = None;
+    }
+
+    fn is_labelled(&mut self, session: &SextetLabellingSession) -> bool {
+        self.labelled.get(
+            (
+                session.foursome.clone(),
+                session.ordering.clone(),
+                session.labelling.clone(),
+            ),
+            |(foursome, ordering, labelling)| {
+                super::sextet_labelling::State::from_session(
+                    session.prev_state.clone(),
+                    foursome,
+                    ordering,
+                    labelling.clone(),
+                )
+                .is_some_and(|state| state.is_labelled())
+            },
+        )
+    }
+
+    // Whether the open screen shows the step was done
+    fn done(&mut self, step: Step, session: Option<&Session>) -> bool {
+        match (step, session) {
+            (Step::SelectPentad, Some(Session::PointToggle(session))) => {
+                session.selected_points.weight() == 5
+            }
+            (Step::CompleteOctad, Some(Session::PointToggle(session))) => {
+                mog().is_octad(&session.selected_points)
+            }
+            (Step::SelectFoursome, Some(Session::PointToggle(session))) => {
+                session.selected_points.weight() == 4
+            }
+            (Step::OpenSextet, Some(Session::SextetLabelling(_))) => true,
+            (Step::Label, Some(Session::SextetLabelling(session))) => self.is_labelled(session),
+            (Step::Automorphism, Some(Session::PointToggle(session))) => {
+                session.selected_permutation != Permutation::identity()
+                    && mog().is_automorphism(&session.selected_permutation)
+            }
+            _ => false,
+        }
+    }
+
+    // Say which button to look out for, before the screen is drawn
+    pub fn begin_frame(&self, ctx: &Context) {
+        let target = STEPS
+            .get(self.step)
+            .filter(|_| self.open)
+            .and_then(|step| step.target());
+        with_spotlight(ctx, |spotlight| {
+            *spotlight = Spotlight { target, rect: None }
+        });
+    }
+
+    // Show the open step after the screen has drawn, with a ring around the button it needs
+    pub fn show(&mut self, ctx: &Context, session: impl FnOnce() -> Option<Session>) {
+        if !self.open {
+            return;
+        }
+        if let Some(step) = STEPS.get(self.step).copied() {
+            let done = self.done(step, session().as_ref());
+            if done && self.was_done == Some(false) {
+                // The next step may already be done too
+                self.step += 1;
+            } else {
+                self.was_done = Some(done);
+            }
+        }
+
+        let mut open = true;
+        Window::new("Tutorial")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::RIGHT_BOTTOM, [-16.0, -16.0])
+            .default_width(280.0)
+            .show(ctx, |ui| {
+                ui.add(
+                    ProgressBar::new(self.step as f32 / STEPS.len() as f32)
+                        .text(format!("{} of {} done", self.step, STEPS.len())),
+                );
+                match STEPS.get(self.step) {
+                    Some(step) => {
+                        ui.heading(step.title());
+                        ui.label(step.instructions());
+                    }
+                    None => {
+                        ui.heading("Finished");
+                        ui.label("That's the tour. The command palette, ctrl + P, lists everything else there is to try.");
+                    }
+                }
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(self.step > 0, Button::new("Back"))
+                        .clicked()
+                    {
+                        self.go_to(self.step - 1);
+                    }
+                    if self.step < STEPS.len() && ui.button("Skip").clicked() {
+                        self.go_to(self.step + 1);
+                    }
+                    if ui.button("Restart").clicked() {
+                        self.go_to(0);
+                    }
+                });
+            });
+        if !open {
+            self.open = false;
+        }
+
+        if let Some(rect) = with_spotlight(ctx, |spotlight| spotlight.rect) {
+            // A slow pulse, so the ring catches the eye without flickering
+            let time = ctx.input(|i| i.time);
+            let width = 2.0 + 1.5 * (time * 4.0).sin().abs() as f32;
+            ctx.layer_painter(LayerId::new(Order::Foreground, id()))
+                .rect_stroke(
+                    rect.expand(3.0),
+                    4.0,
+                    Stroke::new(width, ctx.style().visuals.selection.stroke.color),
+                    StrokeKind::Outside,
+                );
+            ctx.request_repaint();
+        }
+    }
+}
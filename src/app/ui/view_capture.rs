@@ -0,0 +1,39 @@
+use eframe::egui::{Context, Id, LayerId, Rect};
+use eframe::epaint::ClippedShape;
+
+// The MOG view is whatever is painted over the grids drawn this frame
+// Every `GridBuilder` adds its rect, so exporting the view works the same on every screen
+fn view_id() -> Id {
+    Id::new("mog_view")
+}
+
+pub fn include_in_view(ctx: &Context, rect: Rect) {
+    let pass = ctx.cumulative_pass_nr();
+    ctx.data_mut(|data| {
+        let view = data.get_temp_mut_or(view_id(), (pass, rect));
+        *view = if view.0 == pass {
+            (pass, view.1.union(rect))
+        } else {
+            (pass, rect)
+        };
+    });
+}
+
+// The view and the shapes painted over it so far this frame, or None if no grid has been drawn
+pub fn view_shapes(ctx: &Context) -> Option<(Rect, Vec<ClippedShape>)> {
+    let pass = ctx.cumulative_pass_nr();
+    let view = ctx
+        .data(|data| data.get_temp::<(u64, Rect)>(view_id()))
+        .filter(|(view_pass, _)| *view_pass == pass)
+        .map(|(_, view)| view)?;
+    let shapes = ctx.graphics(|graphics| {
+        graphics
+            .get(LayerId::background())
+            .into_iter()
+            .flat_map(|paint_list| paint_list.all_entries())
+            .filter(|clipped| clipped.clip_rect.intersects(view))
+            .cloned()
+            .collect()
+    });
+    Some((view, shapes))
+}
@@ -0,0 +1,153 @@
+use crate::app::logic::miracle_octad_generator::Vector;
+use crate::app::logic::permutation::Permutation;
+use crate::app::ui::navigation::Navigation;
+use crate::app::ui::session::{PointToggleSession, Session};
+use eframe::egui::Ui;
+
+fn new_tab() -> Navigation {
+    Navigation::new(Box::new(super::point_toggle::State::default()))
+}
+
+// Independent workspaces, each with its own screens and history, shown one at a time as tabs
+// The selection and permutation of one can be copied and pasted into another
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Workspaces {
+    #[serde(skip)]
+    tabs: Vec<Navigation>,
+    current: usize,
+    // The saved form of the open screen of each tab, only filled in when saving and loading
+    sessions: Vec<Option<Session>>,
+    #[serde(skip)]
+    clipboard: Option<PointToggleSession>,
+}
+
+impl Default for Workspaces {
+    fn default() -> Self {
+        Self {
+            tabs: vec![new_tab()],
+            current: 0,
+            sessions: vec![],
+            clipboard: None,
+        }
+    }
+}
+
+impl Workspaces {
+    pub fn navigation(&self) -> &Navigation {
+        &self.tabs[self.current]
+    }
+
+    pub fn navigation_mut(&mut self) -> &mut Navigation {
+        &mut self.tabs[self.current]
+    }
+
+    pub fn save(&mut self) {
+        self.sessions = self
+            .tabs
+            .iter()
+            .map(|tab| tab.current().session())
+            .collect();
+    }
+
+    pub fn forget_saved(&mut self) {
+        self.sessions.clear();
+    }
+
+    // Reopen the tabs saved last time, returning false if there were none
+    pub fn restore(&mut self) -> bool {
+        if self.sessions.is_empty() {
+            return false;
+        }
+        self.tabs = self
+            .sessions
+            .drain(..)
+            .map(|session| {
+                session
+                    .and_then(Session::restore)
+                    .map_or_else(new_tab, Navigation::new)
+            })
+            .collect();
+        self.current = self.current.min(self.tabs.len() - 1);
+        true
+    }
+
+    // The point toggle screen of the open tab, or a fresh one if its screen has nothing to save
+    fn current_point_toggle(&self) -> PointToggleSession {
+        match self.navigation().current().session() {
+            Some(Session::PointToggle(session)) => session,
+            Some(Session::SextetLabelling(session)) => session.prev_state,
+            None => PointToggleSession {
+                selected_points: Vector::zero(),
+                selected_permutation: Permutation::identity(),
+                code: None,
+            },
+        }
+    }
+
+    // The tabs, then buttons to copy between them
+    // Returns whether the open screen changed
+    pub fn bar(&mut self, ui: &mut Ui) -> bool {
+        ui.horizontal(|ui| {
+            let mut changed = false;
+            let mut closed = None;
+            for i in 0..self.tabs.len() {
+                if ui
+                    .selectable_label(i == self.current, format!("Workspace {}", i + 1))
+                    .on_hover_text(self.tabs[i].current().title())
+                    .clicked()
+                    && i != self.current
+                {
+                    self.current = i;
+                    changed = true;
+                }
+                if self.tabs.len() > 1 && ui.small_button("×").clicked() {
+                    closed = Some(i);
+                }
+            }
+            if let Some(i) = closed {
+                self.tabs.remove(i);
+                if self.current > i || self.current == self.tabs.len() {
+                    self.current -= 1;
+                }
+                changed = true;
+            }
+            if ui.button("+").on_hover_text("New workspace").clicked() {
+                self.tabs.push(new_tab());
+                self.current = self.tabs.len() - 1;
+                changed = true;
+            }
+            ui.separator();
+
+            if ui
+                .button("Copy")
+                .on_hover_text("Copy the selection and permutation of this workspace")
+                .clicked()
+            {
+                self.clipboard = Some(self.current_point_toggle());
+            }
+            // Pasting opens the point toggle screen of this workspace with the copied part replaced, so it can be undone with back
+            if let Some(copied) = self.clipboard.clone() {
+                let mut pasted = None;
+                if ui.button("Paste selection").clicked() {
+                    pasted = Some(PointToggleSession {
+                        selected_points: copied.selected_points,
+                        ..self.current_point_toggle()
+                    });
+                }
+                if ui.button("Paste permutation").clicked() {
+                    pasted = Some(PointToggleSession {
+                        selected_permutation: copied.selected_permutation,
+                        ..self.current_point_toggle()
+                    });
+                }
+                if let Some(session) = pasted {
+                    self.navigation_mut().open(Box::new(session.restore()));
+                    changed = true;
+                }
+            }
+            changed
+        })
+        .inner
+    }
+}